@@ -1,2 +1,3 @@
 pub mod pack;
+pub mod patch;
 pub mod script;
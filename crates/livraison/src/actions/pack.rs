@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
-use crate::{LivraisonResult, common::FileRef, deb::DebLivraisonPacker, msi::MsiLivraisonPacker, rpm::RpmLivraisonPacker};
+use crate::{
+    LivraisonResult, common::FileRef, deb::DebLivraisonPacker, msi::MsiLivraisonPacker,
+    rpm::RpmLivraisonPacker,
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct CommonOptions {
@@ -19,8 +22,29 @@ pub struct CommonOptions {
     /// Product author
     pub author: Option<User>,
 
+    /// Home page of the product
+    pub homepage: Option<String>,
+
+    /// Technical support page of the product
+    pub support_url: Option<String>,
+
+    /// Icon of the product
+    pub icon: Option<PathBuf>,
+
     /// Binary files
     pub bin_files: Vec<FileRef>,
+
+    /// Languages of the installer, e.g. `fr-FR`, only used by the MSI target
+    pub languages: Vec<String>,
+
+    /// Certificate used to sign the installer, only used by the MSI target
+    pub sign_certificate: Option<PathBuf>,
+
+    /// Private key of the signing certificate, only used by the MSI target
+    pub sign_key: Option<PathBuf>,
+
+    /// Merge modules (.msm) merged into the installer, only used by the MSI target
+    pub merge_modules: Vec<PathBuf>,
 }
 
 #[derive(Debug, Default, Clone)]
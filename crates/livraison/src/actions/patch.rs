@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use color::cyan;
+
+use crate::{
+    LivraisonResult,
+    msi::patch::{MsiPatchOptions, create_patch},
+};
+
+#[derive(Debug, Args)]
+pub struct PatchArgs {
+    /// Installer (.msi) of the installed version
+    #[arg(long)]
+    pub from: String,
+
+    /// Installer (.msi) of the new version
+    #[arg(long)]
+    pub to: String,
+
+    /// Output file path, defaults to the new installer with the .msp extension
+    #[arg(short, long)]
+    pub out: Option<String>,
+
+    /// Patch family, patches of the same family supersede each other. Defaults to the product name
+    #[arg(long)]
+    pub family: Option<String>,
+
+    /// Name of the patch in Add or Remove Programs
+    #[arg(long)]
+    pub display_name: Option<String>,
+
+    /// Description of the patch
+    #[arg(long)]
+    pub description: Option<String>,
+
+    /// Kind of patch, e.g. `Hotfix`, `Security Rollup` or `Update`
+    #[arg(long)]
+    pub classification: Option<String>,
+
+    /// Prevent uninstalling the patch without uninstalling the product
+    #[arg(long, default_value_t = false)]
+    pub no_removal: bool,
+}
+
+pub fn patch(args: PatchArgs) -> LivraisonResult<()> {
+    let to = PathBuf::from(&args.to);
+    let out = match args.out {
+        Some(out) => PathBuf::from(out),
+        None => to.with_extension("msp"),
+    };
+    let options = MsiPatchOptions {
+        family: args.family,
+        display_name: args.display_name,
+        description: args.description,
+        classification: args.classification,
+        no_removal: args.no_removal,
+        ..Default::default()
+    };
+    create_patch(&options, &PathBuf::from(&args.from), &to, &out)?;
+    println!("Patch created at: {}", cyan(out.display().to_string()));
+    Ok(())
+}
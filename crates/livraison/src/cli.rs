@@ -11,6 +11,7 @@ use crate::{
     LivraisonResult,
     actions::{
         pack::{CommonOptions, pack_for_target},
+        patch::{PatchArgs, patch},
         script::{ScriptArgs, create_script},
     },
     common::FileRef,
@@ -40,6 +41,8 @@ enum Command {
     Pack(PackArgs),
     /// Create an installer script
     Script(ScriptArgs),
+    /// Create a patch (.msp) updating the installs of an MSI to a newer MSI
+    Patch(PatchArgs),
 }
 
 #[derive(Debug, Args)]
@@ -60,6 +63,18 @@ struct PackArgs {
     #[arg(long)]
     description: Option<String>,
 
+    /// Home page of the product
+    #[arg(long)]
+    homepage: Option<String>,
+
+    /// Technical support page of the product
+    #[arg(long)]
+    support_url: Option<String>,
+
+    /// Icon of the product
+    #[arg(long)]
+    icon: Option<String>,
+
     /// Output file path
     #[arg(short, long)]
     out: Option<String>,
@@ -67,6 +82,22 @@ struct PackArgs {
     /// Binary files
     #[arg(long)]
     bin_file: Vec<String>,
+
+    /// Language of the installer, e.g. `fr-FR`, one package is created per language
+    #[arg(long)]
+    language: Vec<String>,
+
+    /// Certificate (PEM or DER) to sign the installer with, followed by any intermediate certificates
+    #[arg(long, requires = "sign_key")]
+    sign_cert: Option<String>,
+
+    /// Private key (PEM or DER) of the signing certificate
+    #[arg(long, requires = "sign_cert")]
+    sign_key: Option<String>,
+
+    /// Merge module (.msm) to merge into the installer, e.g. the VC++ runtime
+    #[arg(long)]
+    merge_module: Vec<String>,
 }
 
 pub fn run_cli<I, T>(args: I) -> LivraisonResult<()>
@@ -84,11 +115,18 @@ where
                 name: pack_args.name,
                 version: pack_args.version,
                 description: pack_args.description,
+                homepage: pack_args.homepage,
+                support_url: pack_args.support_url,
+                icon: pack_args.icon.map(PathBuf::from),
                 bin_files: pack_args
                     .bin_file
                     .iter()
                     .map(FileRef::from_local)
                     .collect::<Vec<FileRef>>(),
+                languages: pack_args.language,
+                sign_certificate: pack_args.sign_cert.map(PathBuf::from),
+                sign_key: pack_args.sign_key.map(PathBuf::from),
+                merge_modules: pack_args.merge_module.iter().map(PathBuf::from).collect(),
                 out: match pack_args.out {
                     Some(out) => PathBuf::from(out),
                     None => cwd.join("dist"),
@@ -97,6 +135,7 @@ where
             },
         )?,
         Command::Script(args) => create_script(args)?,
+        Command::Patch(args) => patch(args)?,
     }
 
     Ok(())
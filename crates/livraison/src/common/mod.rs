@@ -1,3 +1,5 @@
 mod file_ref;
+mod version;
 
 pub use file_ref::*;
+pub use version::*;
@@ -0,0 +1,281 @@
+use std::{fmt, str::FromStr};
+
+use crate::{LivraisonError, LivraisonResult};
+
+/// Product version shared by the packers, parsed from a semantic version.
+/// https://semver.org
+///
+/// Missing minor and patch numbers default to 0, e.g. `1.2` is `1.2.0`.
+/// The build metadata doesn't change the precedence of versions, packers which can't hold it reject
+/// it rather than making different builds the same version.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release identifiers, e.g. `beta.1` in `1.2.3-beta.1`
+    pub pre: Option<String>,
+    /// Build metadata, e.g. `build5` in `1.2.3+build5`
+    pub build: Option<String>,
+}
+
+/// Version of a debian package: `[epoch:]upstream[-revision]`
+/// https://www.debian.org/doc/debian-policy/ch-controlfields.html#s-f-version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebVersion {
+    pub epoch: Option<u32>,
+    pub upstream: String,
+    pub revision: Option<String>,
+}
+
+/// Version and release of a RPM package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpmVersion {
+    pub version: String,
+    pub release: String,
+}
+
+impl Version {
+    pub fn parse(version: &str) -> LivraisonResult<Version> {
+        let invalid =
+            |reason: &str| LivraisonError::InvalidVersion(version.to_string(), reason.to_string());
+
+        let (version_pre, build) = match version.trim().split_once('+') {
+            Some((version_pre, build)) => (version_pre, Some(build)),
+            None => (version.trim(), None),
+        };
+        let (core, pre) = match version_pre.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (version_pre, None),
+        };
+
+        let numbers = core
+            .split('.')
+            .map(|number| {
+                if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid("major, minor and patch must be numbers"));
+                }
+                if number.len() > 1 && number.starts_with('0') {
+                    return Err(invalid("numbers must not have leading zeros"));
+                }
+                number
+                    .parse::<u64>()
+                    .map_err(|_| invalid("number is too large"))
+            })
+            .collect::<LivraisonResult<Vec<u64>>>()?;
+        if numbers.len() > 3 {
+            return Err(invalid("expected at most major.minor.patch"));
+        }
+
+        for (name, identifiers) in [("pre-release", pre), ("build metadata", build)] {
+            let Some(identifiers) = identifiers else {
+                continue;
+            };
+            let valid = identifiers.split('.').all(|identifier| {
+                !identifier.is_empty()
+                    && identifier
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            });
+            if !valid {
+                return Err(invalid(&format!(
+                    "{name} must be dot separated identifiers of [0-9A-Za-z-]"
+                )));
+            }
+        }
+
+        Ok(Version {
+            major: numbers[0],
+            minor: numbers.get(1).copied().unwrap_or(0),
+            patch: numbers.get(2).copied().unwrap_or(0),
+            pre: pre.map(str::to_string),
+            build: build.map(str::to_string),
+        })
+    }
+
+    /// MSI ProductVersion: `major.minor.build` where major and minor are at most 255 and build at most 65535.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/productversion
+    ///
+    /// Pre-releases and build metadata are rejected as Windows Installer would consider them equal to the release.
+    pub fn to_msi(&self) -> LivraisonResult<String> {
+        let invalid = |reason: String| LivraisonError::InvalidVersion(self.to_string(), reason);
+        if self.major > 255 {
+            return Err(invalid(format!(
+                "MSI major version {} is above 255",
+                self.major
+            )));
+        }
+        if self.minor > 255 {
+            return Err(invalid(format!(
+                "MSI minor version {} is above 255",
+                self.minor
+            )));
+        }
+        if self.patch > 65535 {
+            return Err(invalid(format!(
+                "MSI build version {} is above 65535",
+                self.patch
+            )));
+        }
+        if let Some(pre) = &self.pre {
+            return Err(invalid(format!(
+                "MSI versions can't hold the pre-release {pre}, it would be installed as {}.{}.{}",
+                self.major, self.minor, self.patch
+            )));
+        }
+        if let Some(build) = &self.build {
+            return Err(invalid(format!(
+                "MSI versions can't hold the build metadata {build}, it would be installed as {}.{}.{}",
+                self.major, self.minor, self.patch
+            )));
+        }
+        Ok(format!("{}.{}.{}", self.major, self.minor, self.patch))
+    }
+
+    /// Debian version, the pre-release is separated with `~` to sort before the release.
+    pub fn to_deb(
+        &self,
+        epoch: Option<u32>,
+        revision: Option<String>,
+    ) -> LivraisonResult<DebVersion> {
+        let mut upstream = format!("{}.{}.{}", self.major, self.minor, self.patch);
+        if let Some(pre) = &self.pre {
+            // Hyphens are only allowed in the upstream version when there is a revision
+            if revision.is_none() && pre.contains('-') {
+                return Err(LivraisonError::InvalidVersion(
+                    self.to_string(),
+                    "debian versions without revision can't contain a hyphen".to_string(),
+                ));
+            }
+            upstream = format!("{upstream}~{pre}");
+        }
+        if let Some(build) = &self.build {
+            upstream = format!("{upstream}+{build}");
+        }
+        Ok(DebVersion {
+            epoch,
+            upstream,
+            revision,
+        })
+    }
+
+    /// RPM version and release, the pre-release is separated with `~` to sort before the release.
+    /// The build metadata is rejected as `+` isn't allowed in RPM versions.
+    pub fn to_rpm(&self, release: Option<String>) -> LivraisonResult<RpmVersion> {
+        if let Some(build) = &self.build {
+            return Err(LivraisonError::InvalidVersion(
+                self.to_string(),
+                format!("RPM versions can't hold the build metadata {build}"),
+            ));
+        }
+        let mut version = format!("{}.{}.{}", self.major, self.minor, self.patch);
+        if let Some(pre) = &self.pre {
+            if pre.contains('-') {
+                return Err(LivraisonError::InvalidVersion(
+                    self.to_string(),
+                    "RPM versions can't contain a hyphen".to_string(),
+                ));
+            }
+            version = format!("{version}~{pre}");
+        }
+        Ok(RpmVersion {
+            version,
+            release: release.unwrap_or_else(|| "1".to_string()),
+        })
+    }
+}
+
+impl FromStr for Version {
+    type Err = LivraisonError;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        Version::parse(version)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{pre}")?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{build}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DebVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(epoch) = self.epoch {
+            write!(f, "{epoch}:")?;
+        }
+        write!(f, "{}", self.upstream)?;
+        if let Some(revision) = &self.revision {
+            write!(f, "-{revision}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let version = Version::parse("1.2.3-beta.1+build5").unwrap();
+        assert_eq!(version.major, 1);
+        assert_eq!(version.minor, 2);
+        assert_eq!(version.patch, 3);
+        assert_eq!(version.pre.as_deref(), Some("beta.1"));
+        assert_eq!(version.build.as_deref(), Some("build5"));
+        assert_eq!(version.to_string(), "1.2.3-beta.1+build5");
+        assert_eq!(Version::parse("2").unwrap().to_string(), "2.0.0");
+        assert!(Version::parse("1.2.3.4").is_err());
+        assert!(Version::parse("1.02").is_err());
+        assert!(Version::parse("1.x").is_err());
+        assert!(Version::parse("1.2.3-beta..1").is_err());
+    }
+
+    #[test]
+    fn msi() {
+        assert_eq!(Version::parse("1.2.3").unwrap().to_msi().unwrap(), "1.2.3");
+        let err = Version::parse("1.2.3+build5")
+            .unwrap()
+            .to_msi()
+            .unwrap_err();
+        assert!(err.to_string().contains("build metadata build5"));
+        assert!(Version::parse("256.0.0").unwrap().to_msi().is_err());
+        assert!(Version::parse("1.256.0").unwrap().to_msi().is_err());
+        assert!(Version::parse("1.2.65536").unwrap().to_msi().is_err());
+        assert!(Version::parse("1.2.3-beta.1").unwrap().to_msi().is_err());
+    }
+
+    #[test]
+    fn deb() {
+        let version = Version::parse("1.2.3-beta.1+build5").unwrap();
+        let deb = version.to_deb(Some(1), Some("2".to_string())).unwrap();
+        assert_eq!(deb.upstream, "1.2.3~beta.1+build5");
+        assert_eq!(deb.to_string(), "1:1.2.3~beta.1+build5-2");
+        let version = Version::parse("1.2.3-rc-1").unwrap();
+        assert!(version.to_deb(None, None).is_err());
+    }
+
+    #[test]
+    fn rpm() {
+        let rpm = Version::parse("1.2.3-beta.1")
+            .unwrap()
+            .to_rpm(None)
+            .unwrap();
+        assert_eq!(rpm.version, "1.2.3~beta.1");
+        assert_eq!(rpm.release, "1");
+        let err = Version::parse("1.2.3-beta.1+build5")
+            .unwrap()
+            .to_rpm(None)
+            .unwrap_err();
+        assert!(err.to_string().contains("build metadata build5"));
+        assert!(Version::parse("1.2.3-rc-1").unwrap().to_rpm(None).is_err());
+    }
+}
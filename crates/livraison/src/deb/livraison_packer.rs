@@ -3,6 +3,7 @@ use std::fs;
 use crate::{
     LivraisonResult,
     actions::pack::{CommonOptions, LivraisonPacker},
+    common::Version,
     deb::{
         control::{Control, User},
         package::{DataFile, DebPackage},
@@ -14,10 +15,13 @@ pub struct DebLivraisonPacker {}
 
 impl LivraisonPacker for DebLivraisonPacker {
     fn pack(&self, options: CommonOptions) -> LivraisonResult<()> {
+        let version = Version::parse(options.version.as_deref().unwrap_or("1.0.0"))?
+            .to_deb(None, Some("12".to_string()))?;
         let control = Control {
             package: options.name.clone(),
-            version: options.version.unwrap_or("1.0.0".to_string()),
-            revision: Some("12".to_string()),
+            version: version.upstream,
+            epoch: version.epoch,
+            revision: version.revision,
             description: options.description.unwrap_or("No description.".to_string()),
             architecture: "all".to_string(),
             maintainer: match options.author {
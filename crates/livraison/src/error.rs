@@ -12,6 +12,12 @@ quick_error! {
         Utf8(err: std::str::Utf8Error) {
             display("utf8 error")
         }
+        InvalidOption(msg: String) {
+            display("Invalid option: {}", msg)
+        }
+        InvalidVersion(version: String, reason: String) {
+            display("Invalid version {}: {}", version, reason)
+        }
     }
 }
 
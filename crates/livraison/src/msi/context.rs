@@ -1,3 +1,4 @@
+use msi_installer::InstallScope;
 use uuid::Uuid;
 
 pub struct Context {
@@ -5,4 +6,6 @@ pub struct Context {
     pub product_code: Uuid,
     /// The UUID that uniquely identifies this installer package even if the name changes.
     pub upgrade_code: Uuid,
+    /// Context in which the package is installed.
+    pub scope: InstallScope,
 }
@@ -0,0 +1,68 @@
+use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
+
+#[allow(dead_code)]
+pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::classic())
+        .add(
+            ui::control::text("Title", "{\\TitleFont}Change destination folder")
+                .pos((20, 15))
+                .size((330, 15)),
+        )
+        .add(
+            ui::control::text("Description", "Browse to the destination folder.")
+                .pos((20, 28))
+                .size((330, 15)),
+        )
+        .add(ui::control::line("BannerLine").pos((0, 44)).width(374))
+        .add(
+            ui::control::text("ComboLabel", "Look in:")
+                .pos((20, 60))
+                .size((44, 10)),
+        )
+        .add(
+            ui::control::directory_combo("DirectoryCombo", "INSTALLDIR")
+                .pos((70, 57))
+                .size((220, 80)),
+        )
+        .add(
+            ui::control::button("Up", "Up")
+                .pos((296, 57))
+                .size((30, 17))
+                .trigger(ui::event::directory_list_up()),
+        )
+        .add(
+            ui::control::button("NewFolder", "New")
+                .pos((330, 57))
+                .size((30, 17))
+                .trigger(ui::event::directory_list_new()),
+        )
+        .add(
+            ui::control::directory_list("DirectoryList", "INSTALLDIR")
+                .pos((20, 83))
+                .size((340, 98)),
+        )
+        .add(
+            ui::control::text("PathLabel", "Folder name:")
+                .pos((20, 192))
+                .size((50, 10)),
+        )
+        .add(
+            ui::control::path_edit("PathEdit", "INSTALLDIR")
+                .pos((75, 189))
+                .width(285),
+        )
+        .add(ui::control::line("BottomLine").pos((0, 234)).width(374))
+        .add(
+            ui::control::button("OK", "OK")
+                .pos((236, 243))
+                .trigger(ui::event::set_target_path("INSTALLDIR"))
+                .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+        )
+        .add(
+            ui::control::button("Cancel", "Cancel")
+                .pos((304, 243))
+                .trigger(ui::event::reset())
+                .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+        )
+}
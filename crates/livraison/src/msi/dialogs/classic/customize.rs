@@ -0,0 +1,59 @@
+use msi_installer::ui::{self, dialog::DialogSize};
+
+use crate::msi::dialogs::next_events;
+
+#[allow(dead_code)]
+pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::classic())
+        .add(
+            ui::control::text("Title", "{\\TitleFont}Custom setup")
+                .pos((20, 15))
+                .size((330, 15)),
+        )
+        .add(
+            ui::control::text(
+                "Description",
+                "Select the way you want features to be installed.",
+            )
+            .pos((20, 28))
+            .size((330, 15)),
+        )
+        .add(ui::control::line("BannerLine").pos((0, 44)).width(374))
+        .add(
+            ui::control::selection_tree("Tree", "INSTALLDIR")
+                .pos((20, 55))
+                .size((175, 170)),
+        )
+        .add(
+            ui::control::dyn_text("ItemDescription", "SelectionDescription")
+                .pos((205, 55))
+                .size((145, 80)),
+        )
+        .add(
+            ui::control::dyn_text("ItemSize", "SelectionSize")
+                .pos((205, 140))
+                .size((145, 50)),
+        )
+        .add(ui::control::line("BottomLine").pos((0, 234)).width(374))
+        .add(
+            ui::control::button("Reset", "Reset")
+                .pos((20, 243))
+                .trigger(ui::event::reset()),
+        )
+        .add(
+            ui::control::button("Next", "Install")
+                .pos((236, 243))
+                .triggers(next_events("CustomizeDlg")),
+        )
+        .add(
+            ui::control::button("Cancel", "Cancel")
+                .pos((304, 243))
+                .trigger(ui::event::spawn_dialog("CancelDlg")),
+        )
+        .add(
+            ui::control::button("Back", "Back")
+                .pos((180, 243))
+                .trigger(ui::event::new_dialog("WelcomeDlg")),
+        )
+}
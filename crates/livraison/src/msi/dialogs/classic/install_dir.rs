@@ -0,0 +1,55 @@
+use msi_installer::ui::{self, dialog::DialogSize};
+
+use crate::msi::dialogs::next_events;
+
+#[allow(dead_code)]
+pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::classic())
+        .add(
+            ui::control::text("Title", "{\\TitleFont}Destination folder")
+                .pos((20, 15))
+                .size((330, 15)),
+        )
+        .add(
+            ui::control::text(
+                "Description",
+                "Click Install to install to the default folder or click Change to choose another.",
+            )
+            .pos((20, 28))
+            .size((330, 15)),
+        )
+        .add(ui::control::line("BannerLine").pos((0, 44)).width(374))
+        .add(
+            ui::control::text("FolderLabel", "Install [ProductName] to:")
+                .pos((20, 60))
+                .size((290, 10)),
+        )
+        .add(
+            ui::control::path_edit("Folder", "INSTALLDIR")
+                .pos((20, 75))
+                .width(330),
+        )
+        .add(
+            ui::control::button("ChangeFolder", "Change...")
+                .pos((20, 97))
+                .trigger(ui::event::spawn_dialog("BrowseDlg")),
+        )
+        .add(ui::control::line("BottomLine").pos((0, 234)).width(374))
+        .add(
+            ui::control::button("Next", "Install")
+                .pos((236, 243))
+                .trigger(ui::event::set_target_path("INSTALLDIR"))
+                .triggers(next_events("InstallDirDlg")),
+        )
+        .add(
+            ui::control::button("Cancel", "Cancel")
+                .pos((304, 243))
+                .trigger(ui::event::spawn_dialog("CancelDlg")),
+        )
+        .add(
+            ui::control::button("Back", "Back")
+                .pos((180, 243))
+                .trigger(ui::event::new_dialog("WelcomeDlg")),
+        )
+}
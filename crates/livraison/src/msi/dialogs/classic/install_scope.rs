@@ -0,0 +1,62 @@
+use msi_installer::ui::{self, dialog::DialogSize};
+
+use crate::msi::dialogs::install_scope_events;
+
+#[allow(dead_code)]
+pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::classic())
+        .add(
+            ui::control::text("Title", "{\\TitleFont}Installation scope")
+                .pos((20, 15))
+                .size((330, 15)),
+        )
+        .add(
+            ui::control::text(
+                "Description",
+                "Choose whether to install [ProductName] just for you or for all users of this computer.",
+            )
+            .pos((20, 28))
+            .size((330, 15)),
+        )
+        .add(ui::control::line("BannerLine").pos((0, 44)).width(374))
+        .add(
+            ui::control::button("PerUser", "Install just for me")
+                .pos((20, 65))
+                .size((160, 17))
+                .triggers(install_scope_events(false)),
+        )
+        .add(
+            ui::control::text(
+                "PerUserDescription",
+                "[ProductName] will be installed in your user folder and will only be available to you.",
+            )
+            .pos((20, 85))
+            .size((330, 20)),
+        )
+        .add(
+            ui::control::button("PerMachine", "Install for all users")
+                .pos((20, 115))
+                .size((160, 17))
+                .triggers(install_scope_events(true)),
+        )
+        .add(
+            ui::control::text(
+                "PerMachineDescription",
+                "[ProductName] will be installed in Program Files for all users. Administrator privileges are required.",
+            )
+            .pos((20, 135))
+            .size((330, 20)),
+        )
+        .add(ui::control::line("BottomLine").pos((0, 234)).width(374))
+        .add(
+            ui::control::button("Cancel", "Cancel")
+                .pos((304, 243))
+                .trigger(ui::event::spawn_dialog("CancelDlg")),
+        )
+        .add(
+            ui::control::button("Back", "Back")
+                .pos((180, 243))
+                .trigger(ui::event::new_dialog("WelcomeDlg")),
+        )
+}
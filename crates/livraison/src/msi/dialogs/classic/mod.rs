@@ -1,9 +1,13 @@
 use msi_installer::ui;
 
+mod browse;
 mod cancel;
 mod common;
+mod customize;
 mod exit;
 mod fatal_error;
+mod install_dir;
+mod install_scope;
 mod progress;
 mod remove;
 mod welcome;
@@ -12,6 +16,10 @@ mod welcome;
 pub fn create() -> ui::UiBuilder {
     ui::new()
         .new_dialog("WelcomeDlg", welcome::create)
+        .new_dialog("InstallScopeDlg", install_scope::create)
+        .new_dialog("InstallDirDlg", install_dir::create)
+        .new_dialog("BrowseDlg", browse::create)
+        .new_dialog("CustomizeDlg", customize::create)
         .new_dialog("RemoveDlg", remove::create)
         .new_dialog("FatalErrorDlg", fatal_error::create)
         .new_dialog("ProgressDlg", progress::create)
@@ -1,6 +1,7 @@
-use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
+use msi_installer::ui::{self, dialog::DialogSize};
 
 use super::common;
+use crate::msi::dialogs::welcome_next_events;
 
 #[allow(dead_code)]
 pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
@@ -34,7 +35,7 @@ pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
                 "Install",
             )
             .pos(( 236, 243))
-            .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+            .triggers(welcome_next_events()),
         )
         .add(
             ui::control::button(
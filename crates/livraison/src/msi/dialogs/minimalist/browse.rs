@@ -0,0 +1,52 @@
+use msi_installer::ui::{self, event::EndDialogAction};
+
+use crate::msi::localization::Strings;
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
+    builder
+        .size((260, 180))
+        .add(
+            ui::control::text("ComboLabel", strings.browse_look_in)
+                .pos((20, 12))
+                .size((40, 10)),
+        )
+        .add(
+            ui::control::directory_combo("DirectoryCombo", "INSTALLDIR")
+                .pos((60, 10))
+                .size((124, 80)),
+        )
+        .add(
+            ui::control::button("Up", strings.up)
+                .pos((188, 10))
+                .size((24, 17))
+                .trigger(ui::event::directory_list_up()),
+        )
+        .add(
+            ui::control::button("NewFolder", strings.new_folder)
+                .pos((216, 10))
+                .size((24, 17))
+                .trigger(ui::event::directory_list_new()),
+        )
+        .add(
+            ui::control::directory_list("DirectoryList", "INSTALLDIR")
+                .pos((20, 33))
+                .size((220, 90)),
+        )
+        .add(
+            ui::control::path_edit("PathEdit", "INSTALLDIR")
+                .pos((20, 129))
+                .width(220),
+        )
+        .add(
+            ui::control::button("OK", strings.ok)
+                .pos((66, 155))
+                .trigger(ui::event::set_target_path("INSTALLDIR"))
+                .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+        )
+        .add(
+            ui::control::button("Cancel", strings.cancel)
+                .pos((134, 155))
+                .trigger(ui::event::reset())
+                .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+        )
+}
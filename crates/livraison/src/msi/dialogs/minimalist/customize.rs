@@ -0,0 +1,46 @@
+use msi_installer::ui::{self, event::EndDialogAction};
+
+use crate::msi::{dialogs::next_events, localization::Strings};
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
+    builder
+        .size((260, 180))
+        .add(
+            ui::control::text(
+                "Title",
+                &format!("{{\\TitleFont}}{}", strings.customize_title),
+            )
+            .pos((20, 10))
+            .size((220, 15)),
+        )
+        .add(
+            ui::control::text("Text", strings.customize_description)
+                .pos((20, 28))
+                .size((220, 10)),
+        )
+        .add(
+            ui::control::selection_tree("Tree", "INSTALLDIR")
+                .pos((20, 42))
+                .size((130, 108)),
+        )
+        .add(
+            ui::control::dyn_text("ItemDescription", "SelectionDescription")
+                .pos((158, 42))
+                .size((82, 80)),
+        )
+        .add(
+            ui::control::button("Reset", strings.reset)
+                .pos((20, 155))
+                .trigger(ui::event::reset()),
+        )
+        .add(
+            ui::control::button("Next", strings.install)
+                .pos((118, 155))
+                .triggers(next_events("CustomizeDlg")),
+        )
+        .add(
+            ui::control::button("Cancel", strings.cancel)
+                .pos((186, 155))
+                .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
+        )
+}
@@ -1,20 +1,22 @@
 use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
 
-pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+use crate::msi::localization::Strings;
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
     builder
         .size(DialogSize::minimal())
         .add(
-            ui::control::text("Title", "{\\TitleFont}[ProductName] [Text_action] complete")
+            ui::control::text("Title", &format!("{{\\TitleFont}}{}", strings.exit_title))
                 .pos((20, 20))
                 .size((220, 60)),
         )
         .add(
-            ui::control::button("Finish", "Finish")
+            ui::control::button("Finish", strings.finish)
                 .pos((66, 75))
                 .trigger(ui::event::end_dialog(EndDialogAction::Return)),
         )
         .add(
-            ui::control::button("Cancel", "Cancel")
+            ui::control::button("Cancel", strings.cancel)
                 .pos((134, 75))
                 .trigger(ui::event::end_dialog(EndDialogAction::Exit))
                 .disable(),
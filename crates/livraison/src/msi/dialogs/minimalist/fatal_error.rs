@@ -1,23 +1,28 @@
 use msi_installer::ui::{self, dialog::DialogSize};
 
-pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
-    builder.size(DialogSize::classic())
-        .add(
-            ui::control::text("Title", "{\\TitleFont}[ProductName] [Text_agent] ended prematurely")
-                .pos((20, 20))
-                .size((220, 60)),
-        )
+use crate::msi::localization::Strings;
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::classic())
         .add(
             ui::control::text(
-                "Description1",
-                "[ProductName] [Text_action] ended because of an error. The program has not been installed. This installer can be run again at a later time.",
+                "Title",
+                &format!("{{\\TitleFont}}{}", strings.fatal_error_title),
             )
-            .pos((20, 30))
-            .size((220, 40)),
+            .pos((20, 20))
+            .size((220, 60)),
+        )
+        .add(
+            ui::control::text("Description1", strings.fatal_error_description)
+                .pos((20, 30))
+                .size((220, 40)),
         )
         .add(
-            ui::control::button("Finish", "Finish")
+            ui::control::button("Finish", strings.finish)
                 .pos((66, 75))
-                .trigger(ui::event::end_dialog(msi_installer::ui::event::EndDialogAction::Exit)),
+                .trigger(ui::event::end_dialog(
+                    msi_installer::ui::event::EndDialogAction::Exit,
+                )),
         )
 }
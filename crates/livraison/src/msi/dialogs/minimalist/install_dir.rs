@@ -0,0 +1,42 @@
+use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
+
+use crate::msi::{dialogs::next_events, localization::Strings};
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::minimal())
+        .add(
+            ui::control::text(
+                "Title",
+                &format!("{{\\TitleFont}}{}", strings.install_dir_title),
+            )
+            .pos((20, 10))
+            .size((220, 15)),
+        )
+        .add(
+            ui::control::button("Next", strings.install)
+                .pos((66, 75))
+                .trigger(ui::event::set_target_path("INSTALLDIR"))
+                .triggers(next_events("InstallDirDlg")),
+        )
+        .add(
+            ui::control::button("Cancel", strings.cancel)
+                .pos((134, 75))
+                .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
+        )
+        .add(
+            ui::control::text("FolderLabel", strings.install_dir_label)
+                .pos((20, 30))
+                .size((220, 10)),
+        )
+        .add(
+            ui::control::path_edit("Folder", "INSTALLDIR")
+                .pos((20, 43))
+                .width(160),
+        )
+        .add(
+            ui::control::button("ChangeFolder", strings.change)
+                .pos((184, 43))
+                .trigger(ui::event::spawn_dialog("BrowseDlg")),
+        )
+}
@@ -0,0 +1,38 @@
+use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
+
+use crate::msi::{dialogs::install_scope_events, localization::Strings};
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
+    builder
+        .size(DialogSize::minimal())
+        .add(
+            ui::control::text(
+                "Title",
+                &format!("{{\\TitleFont}}{}", strings.install_scope_title),
+            )
+            .pos((20, 10))
+            .size((220, 15)),
+        )
+        .add(
+            ui::control::text("Description", strings.install_scope_description)
+                .pos((20, 30))
+                .size((220, 20)),
+        )
+        .add(
+            ui::control::button("PerUser", strings.per_user)
+                .pos((20, 55))
+                .size((106, 17))
+                .triggers(install_scope_events(false)),
+        )
+        .add(
+            ui::control::button("PerMachine", strings.per_machine)
+                .pos((134, 55))
+                .size((106, 17))
+                .triggers(install_scope_events(true)),
+        )
+        .add(
+            ui::control::button("Cancel", strings.cancel)
+                .pos((134, 75))
+                .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
+        )
+}
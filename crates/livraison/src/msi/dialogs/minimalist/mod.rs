@@ -1,17 +1,35 @@
 use msi_installer::ui;
 
+use crate::msi::localization::Strings;
+
+mod browse;
 pub mod common;
+mod customize;
 mod exit;
 mod fatal_error;
+mod install_dir;
+mod install_scope;
 mod progress;
 mod remove;
 mod welcome;
 
-pub fn create() -> ui::UiBuilder {
+pub fn create(strings: &Strings) -> ui::UiBuilder {
     ui::new()
-        .new_dialog("WelcomeDlg", welcome::create)
-        .new_dialog("RemoveDlg", remove::create)
-        .new_dialog("FatalErrorDlg", fatal_error::create)
-        .new_dialog("ProgressDlg", progress::create)
-        .new_dialog("ExitDlg", exit::create)
+        .title(strings.setup_title)
+        .font(strings.font)
+        .new_dialog("WelcomeDlg", |dialog| welcome::create(dialog, strings))
+        .new_dialog("InstallScopeDlg", |dialog| {
+            install_scope::create(dialog, strings)
+        })
+        .new_dialog("InstallDirDlg", |dialog| {
+            install_dir::create(dialog, strings)
+        })
+        .new_dialog("BrowseDlg", |dialog| browse::create(dialog, strings))
+        .new_dialog("CustomizeDlg", |dialog| customize::create(dialog, strings))
+        .new_dialog("RemoveDlg", |dialog| remove::create(dialog, strings))
+        .new_dialog("FatalErrorDlg", |dialog| {
+            fatal_error::create(dialog, strings)
+        })
+        .new_dialog("ProgressDlg", |dialog| progress::create(dialog, strings))
+        .new_dialog("ExitDlg", |dialog| exit::create(dialog, strings))
 }
@@ -1,21 +1,29 @@
 use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
 
-pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+use crate::msi::localization::Strings;
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
     builder
         .size(DialogSize::minimal())
         .modeless()
-        .add(
-            ui::control::text("Title", "{\\TitleFont}Installing [ProductName]")
-                .pos((20, 10))
-                .size((220, 20)),
-        )
         .add(
             ui::control::text(
-                "Text",
-                "Please wait while [ProductName] is [Text_done]. This may take several minutes.",
+                "Title",
+                &format!("{{\\TitleFont}}{}", strings.progress_title),
             )
-            .pos((20, 30))
-            .size((220, 40)),
+            .pos((20, 10))
+            .size((220, 20)),
+        )
+        .add(
+            ui::control::text("Text", strings.progress_description)
+                .pos((20, 30))
+                .size((220, 18)),
+        )
+        .add(
+            // Description of the running action, from the ActionText table
+            ui::control::dyn_text("ActionText", "ActionText")
+                .pos((20, 48))
+                .size((220, 10)),
         )
         .add(
             ui::control::progress_bar("ProgressBar")
@@ -23,9 +31,13 @@ pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
                 .width(220),
         )
         .add(
-            ui::control::button("Cancel", "Cancel")
+            ui::control::button("Cancel", strings.cancel)
                 .pos((134, 75))
                 .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
         )
-        .add(ui::control::button("Next", "Next").pos((66, 75)).disable())
+        .add(
+            ui::control::button("Next", strings.next)
+                .pos((66, 75))
+                .disable(),
+        )
 }
@@ -1,38 +1,28 @@
 use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
 
-pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+use crate::msi::localization::Strings;
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
     builder
         .size(DialogSize::minimal())
         .add(
-            ui::control::text(
-                "Title",
-                "{\\TitleFont}Uninstall [ProductName]",
-            )
-            .pos((20, 10))
-            .size((220, 20)),
+            ui::control::text("Title", &format!("{{\\TitleFont}}{}", strings.remove_title))
+                .pos((20, 10))
+                .size((220, 20)),
         )
         .add(
-            ui::control::text(
-                "Description",
-                "This will remove [ProductName] from your computer. Click Remove to continue or Cancel to exit the uninstaller.",
-            )
-            .pos((20, 30))
-            .size((220, 40)),
+            ui::control::text("Description", strings.remove_description)
+                .pos((20, 30))
+                .size((220, 40)),
         )
         .add(
-            ui::control::button(
-                "Remove",
-                "Remove",
-            )
-            .pos((66, 75))
-            .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+            ui::control::button("Remove", strings.remove)
+                .pos((66, 75))
+                .trigger(ui::event::end_dialog(EndDialogAction::Return)),
         )
         .add(
-            ui::control::button(
-                "Cancel",
-                "Cancel",
-            )
-            .pos((134, 75))
-            .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
+            ui::control::button("Cancel", strings.cancel)
+                .pos((134, 75))
+                .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
         )
 }
@@ -1,12 +1,14 @@
 use msi_installer::ui::{self, dialog::DialogSize, event::EndDialogAction};
 
-pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
+use crate::msi::{dialogs::welcome_next_events, localization::Strings};
+
+pub fn create(builder: ui::dialog::DialogBuilder, strings: &Strings) -> ui::dialog::DialogBuilder {
     builder
         .size(DialogSize::minimal())
         .add(
             ui::control::text(
                 "Title",
-                "{\\TitleFont}Welcome to the [ProductName] installer",
+                &format!("{{\\TitleFont}}{}", strings.welcome_title),
             )
             .pos((20, 10))
             .size((220, 20)),
@@ -14,25 +16,19 @@ pub fn create(builder: ui::dialog::DialogBuilder) -> ui::dialog::DialogBuilder {
         .add(
             ui::control::text(
                 "Description",
-                "{\\DefaultFont}This will install [ProductName] on your computer. Click Install to continue or Cancel to exit the installer.",
+                &format!("{{\\DefaultFont}}{}", strings.welcome_description),
             )
             .pos((20, 30))
             .size((220, 40)),
         )
         .add(
-            ui::control::button(
-                "Next",
-                "Install",
-            )
-            .pos((66, 75))
-            .trigger(ui::event::end_dialog(EndDialogAction::Return)),
+            ui::control::button("Next", strings.install)
+                .pos((66, 75))
+                .triggers(welcome_next_events()),
         )
         .add(
-            ui::control::button(
-                "Cancel",
-                "Cancel",
-            )
-            .pos((134, 75))
-            .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
+            ui::control::button("Cancel", strings.cancel)
+                .pos((134, 75))
+                .trigger(ui::event::end_dialog(EndDialogAction::Exit)),
         )
 }
@@ -1,2 +1,81 @@
+use msi_installer::ui::{self, event::EndDialogAction};
+
 pub mod classic;
 pub mod minimalist;
+
+/// Property enabling the install directory dialog after the welcome dialog.
+pub const SHOW_INSTALLDIR_DLG: &str = "SHOW_INSTALLDIR_DLG";
+
+/// Property enabling the install scope dialog after the welcome dialog.
+pub const SHOW_INSTALLSCOPE_DLG: &str = "SHOW_INSTALLSCOPE_DLG";
+
+/// Install location when choosing to install for the current user.
+const PER_USER_INSTALLDIR: &str = "[LocalAppDataFolder]Programs\\[ProductName]\\";
+/// Install location when choosing to install for everyone.
+/// ProgramFiles64Folder is already redirected to the per user location at this point.
+const PER_MACHINE_INSTALLDIR: &str = "[%ProgramW6432]\\[ProductName]\\";
+
+/// Property enabling the feature selection dialog after the install directory dialog.
+pub const SHOW_CUSTOMIZE_DLG: &str = "SHOW_CUSTOMIZE_DLG";
+
+/// Dialogs shown after the welcome dialog, in order, with the property enabling them.
+const OPTIONAL_DIALOGS: &[(&str, &str)] = &[
+    ("InstallScopeDlg", SHOW_INSTALLSCOPE_DLG),
+    ("InstallDirDlg", SHOW_INSTALLDIR_DLG),
+    ("CustomizeDlg", SHOW_CUSTOMIZE_DLG),
+];
+
+/// Events to trigger when leaving the welcome dialog, going to the next enabled dialog.
+pub fn welcome_next_events() -> Vec<ui::event::Event> {
+    next_dialog_events(OPTIONAL_DIALOGS)
+}
+
+/// Events to trigger when leaving the given optional dialog, going to the next enabled dialog.
+pub fn next_events(dialog: &str) -> Vec<ui::event::Event> {
+    let index = OPTIONAL_DIALOGS
+        .iter()
+        .position(|(id, _)| *id == dialog)
+        .map_or(OPTIONAL_DIALOGS.len(), |index| index + 1);
+    next_dialog_events(&OPTIONAL_DIALOGS[index..])
+}
+
+// Open the first enabled dialog, end the dialog sequence when none is enabled.
+fn next_dialog_events(dialogs: &[(&str, &str)]) -> Vec<ui::event::Event> {
+    let mut events = Vec::new();
+    let mut skipped: Vec<String> = Vec::new();
+    for (dialog, property) in dialogs {
+        let condition = skipped
+            .iter()
+            .cloned()
+            .chain([format!("{property} = \"1\"")])
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        events.push(ui::event::new_dialog(dialog).when(&condition));
+        skipped.push(format!("{property} <> \"1\""));
+    }
+    let end = ui::event::end_dialog(EndDialogAction::Return);
+    if skipped.is_empty() {
+        events.push(end);
+    } else {
+        events.push(end.when(&skipped.join(" AND ")));
+    }
+    events
+}
+
+/// Events to trigger when the user picks the install scope.
+pub fn install_scope_events(per_machine: bool) -> Vec<ui::event::Event> {
+    let (all_users, per_user, install_dir) = if per_machine {
+        ("1", "{}", PER_MACHINE_INSTALLDIR)
+    } else {
+        ("{}", "1", PER_USER_INSTALLDIR)
+    };
+    vec![
+        ui::event::set_property("ALLUSERS", all_users),
+        ui::event::set_property("MSIINSTALLPERUSER", per_user),
+        ui::event::set_property("INSTALLDIR", install_dir),
+        ui::event::set_target_path("INSTALLDIR"),
+    ]
+    .into_iter()
+    .chain(next_events("InstallScopeDlg"))
+    .collect()
+}
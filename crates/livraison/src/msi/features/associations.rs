@@ -0,0 +1,247 @@
+use std::{
+    io::{Read, Seek, Write},
+    path::Path,
+};
+
+use msi::Package;
+use msi_installer::{
+    identifier::identifier,
+    tables::{Entity, Extension, Mime, ProgId, RegistryRoot, Verb},
+};
+
+use super::registry::{RegistryKeyOptions, RegistryValue, RegistryValueOptions};
+use crate::LivraisonError;
+
+/// Config to open files with the given extension with one of the installed binaries.
+#[derive(Debug, Clone, Default)]
+pub struct FileAssociationOptions {
+    /// Extension of the files without the leading dot, e.g. `foo`
+    pub extension: String,
+
+    /// Name of the binary file, as declared in `binaries`, which opens the files.
+    pub binary: String,
+
+    /// Programmatic identifier of the file type, defaults to `<binary file stem>.<extension>` with
+    /// the characters other than letters, digits, `_` and `.` replaced by `_`.
+    pub prog_id: Option<String>,
+
+    /// Name of the file type shown in the explorer, e.g. `Foo document`
+    pub description: Option<String>,
+
+    /// MIME type of the files, e.g. `application/x-foo`
+    pub content_type: Option<String>,
+}
+
+/// Config to open links of an URL scheme, e.g. `foo://`, with one of the installed binaries.
+#[derive(Debug, Clone, Default)]
+pub struct UrlProtocolOptions {
+    /// Scheme of the URL without `://`, e.g. `foo`
+    pub scheme: String,
+
+    /// Name of the binary file, as declared in `binaries`, which receives the URL as first argument.
+    pub binary: String,
+
+    pub description: Option<String>,
+}
+
+impl FileAssociationOptions {
+    fn extension(&self) -> &str {
+        self.extension.trim_start_matches('.')
+    }
+
+    fn prog_id(&self) -> String {
+        self.prog_id.clone().unwrap_or_else(|| {
+            let binary = Path::new(&self.binary);
+            let stem = binary.file_stem().unwrap_or(binary.as_os_str());
+            identifier(&format!("{}.{}", stem.to_string_lossy(), self.extension()))
+        })
+    }
+
+    /// Registry keys of the association, used instead of the Extension, ProgId, Verb and MIME
+    /// tables in per user installs: these tables build the open command from the key path of the
+    /// binary's component, which must be a HKCU registry value in the user profile (ICE38).
+    /// `file_key` is the key of the binary in the File table and `feature` the feature installing it.
+    pub fn registry_keys(&self, file_key: &str, feature: &str) -> Vec<RegistryKeyOptions> {
+        let extension = format!(".{}", self.extension());
+        let prog_id = self.prog_id();
+        let key = |key: String, values: Vec<RegistryValueOptions>| RegistryKeyOptions {
+            root: RegistryRoot::ClassesRoot,
+            key,
+            values,
+            remove_on_uninstall: false,
+            feature: Some(feature.to_string()),
+        };
+
+        let mut extension_values = vec![RegistryValueOptions::new(
+            None,
+            RegistryValue::String(prog_id.clone()),
+        )];
+        if let Some(content_type) = &self.content_type {
+            extension_values.push(RegistryValueOptions::new(
+                Some("Content Type"),
+                RegistryValue::String(content_type.clone()),
+            ));
+        }
+        let mut keys = vec![
+            key(extension.clone(), extension_values),
+            key(
+                format!("{prog_id}\\DefaultIcon"),
+                vec![RegistryValueOptions::new(
+                    None,
+                    RegistryValue::String(format!("[#{file_key}],0")),
+                )],
+            ),
+            key(
+                format!("{prog_id}\\shell\\open\\command"),
+                vec![RegistryValueOptions::new(
+                    None,
+                    RegistryValue::String(format!("\"[#{file_key}]\" \"%1\"")),
+                )],
+            ),
+        ];
+        if let Some(description) = &self.description {
+            keys.push(key(
+                prog_id.clone(),
+                vec![RegistryValueOptions::new(
+                    None,
+                    RegistryValue::String(description.clone()),
+                )],
+            ));
+        }
+        if let Some(content_type) = &self.content_type {
+            keys.push(key(
+                format!("MIME\\Database\\Content Type\\{content_type}"),
+                vec![RegistryValueOptions::new(
+                    Some("Extension"),
+                    RegistryValue::String(extension),
+                )],
+            ));
+        }
+        keys
+    }
+}
+
+impl UrlProtocolOptions {
+    /// Registry keys of the protocol, written under HKCU or HKLM `Software\Classes` depending on the install scope.
+    /// `file_key` is the key of the binary in the File table and `feature` the feature installing it.
+    pub fn registry_keys(&self, file_key: &str, feature: &str) -> Vec<RegistryKeyOptions> {
+        let description = self
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("{} Protocol", self.scheme));
+        vec![
+            RegistryKeyOptions {
+                root: RegistryRoot::ClassesRoot,
+                key: self.scheme.clone(),
+                values: vec![
+                    RegistryValueOptions::new(
+                        None,
+                        RegistryValue::String(format!("URL:{description}")),
+                    ),
+                    RegistryValueOptions::new(
+                        Some("URL Protocol"),
+                        RegistryValue::String(String::new()),
+                    ),
+                ],
+                remove_on_uninstall: true,
+                feature: Some(feature.to_string()),
+            },
+            RegistryKeyOptions {
+                root: RegistryRoot::ClassesRoot,
+                key: format!("{}\\shell\\open\\command", self.scheme),
+                values: vec![RegistryValueOptions::new(
+                    None,
+                    RegistryValue::String(format!("\"[#{file_key}]\" \"%1\"")),
+                )],
+                remove_on_uninstall: false,
+                feature: Some(feature.to_string()),
+            },
+        ]
+    }
+}
+
+/// Register the file associations in the Extension, ProgId, Verb and MIME tables.
+/// The extensions are attached to the component and the feature of their binary, found with `component_of`.
+/// Files are shown with the `icon` of the Icon table when given.
+pub fn register_file_associations<F: Read + Seek + Write>(
+    package: &mut Package<F>,
+    associations: &[FileAssociationOptions],
+    icon: Option<&str>,
+    component_of: impl Fn(&str) -> Option<(String, String)>,
+) -> Result<(), LivraisonError> {
+    ProgId::create_table(package)?;
+    Extension::create_table(package)?;
+    Verb::create_table(package)?;
+    Mime::create_table(package)?;
+
+    let mut prog_ids: Vec<ProgId> = Vec::new();
+    let mut extensions: Vec<Extension> = Vec::new();
+    let mut verbs: Vec<Verb> = Vec::new();
+    let mut mimes: Vec<Mime> = Vec::new();
+    for association in associations {
+        let extension = association.extension().to_string();
+        let (component, feature) = component_of(&association.binary).ok_or_else(|| {
+            LivraisonError::InvalidOption(format!(
+                "File association .{extension} uses the unknown binary {}",
+                association.binary
+            ))
+        })?;
+        let prog_id = association.prog_id();
+
+        prog_ids.push(ProgId {
+            prog_id: prog_id.clone(),
+            description: association.description.clone(),
+            icon: icon.map(str::to_string),
+            icon_index: icon.map(|_| 0),
+            ..Default::default()
+        });
+        extensions.push(Extension {
+            extension: extension.clone(),
+            component,
+            prog_id: Some(prog_id),
+            mime: association.content_type.clone(),
+            feature,
+        });
+        verbs.push(Verb {
+            extension: extension.clone(),
+            verb: "open".to_string(),
+            sequence: Some(1),
+            command: Some("Open".to_string()),
+            argument: Some("\"%1\"".to_string()),
+        });
+        if let Some(content_type) = &association.content_type {
+            mimes.push(Mime {
+                content_type: content_type.clone(),
+                extension,
+                clsid: None,
+            });
+        }
+    }
+
+    ProgId::insert(package, &prog_ids)?;
+    Extension::insert(package, &extensions)?;
+    Verb::insert(package, &verbs)?;
+    Mime::insert(package, &mimes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileAssociationOptions;
+
+    #[test]
+    fn default_prog_id() {
+        let prog_id = |binary: &str| {
+            FileAssociationOptions {
+                extension: ".foo".to_string(),
+                binary: binary.to_string(),
+                ..Default::default()
+            }
+            .prog_id()
+        };
+        assert_eq!(prog_id("app.exe"), "app.foo");
+        assert_eq!(prog_id("bin/app.exe"), "app.foo");
+        assert_eq!(prog_id("bin/my app.v2.exe"), "my_app.v2.foo");
+        assert_eq!(prog_id("2app"), "_2app.foo");
+    }
+}
@@ -1,11 +1,13 @@
 use std::io::{Read, Seek, Write};
 
 use msi::Package;
-use msi_installer::tables::{
-    Component, ComponentAttributes, Entity, Environment, FeatureComponents, Registry,
+use msi_installer::{
+    InstallScope,
+    tables::{Component, ComponentAttributes, Entity, Environment, FeatureComponents, Registry},
 };
 use uuid::Uuid;
 
+use super::key_path::registry_key_path;
 use crate::{LivraisonError, msi::Context};
 
 /// Config to register an environment variable
@@ -34,57 +36,94 @@ pub fn register_environment_vars<F: Read + Seek + Write>(
     actions: &Vec<EnvironmentAction>,
 ) -> Result<(), LivraisonError> {
     Environment::create_table(package)?;
-    Registry::create_table(package)?;
 
     let mut environments: Vec<Environment> = Vec::new();
     let mut components: Vec<Component> = Vec::new();
     let mut feature_components: Vec<FeatureComponents> = Vec::new();
-    // let mut registry_items: Vec<Registry> = Vec::new();
+    let mut registry_items: Vec<Registry> = Vec::new();
     for action in actions {
-        let component_id = format!("env_{}", action.id.to_lowercase());
-        // let component_id = "reg384648C2D0DE5577014AD3CB66D5A086".to_string();
-        let uuid = Uuid::new_v5(&context.upgrade_code, component_id.as_bytes());
-        components.push(Component {
-            component: component_id.clone(),
-            id: Some(uuid),
-            directory: "INSTALLDIR".to_string(),
-            attributes: ComponentAttributes::Bit64, // | ComponentAttributes::RegistryKeyPath,
-            condition: None,
-            key_path: None,
-            // key_path: Some(component_id.clone()),
-        });
-
-        environments.push(Environment {
-            environment: action.name.clone(),
-            name: get_environment_name(action),
-            value: Some(get_environment_value(action)),
-            component: component_id.clone(),
-        });
-
-        // registry_items.push(Registry {
-        //     registry: component_id.to_string(),
-        //     name: "InstallDir".to_string(),
-        //     key: "SOFTWARE\\Microsoft\\test".to_string(),
-        //     value: action.value.to_string(),
-        //     component: component_id.clone(),
-        //     root: RegistryRoot::CurrentUser,
-        // });
-
-        feature_components.push(FeatureComponents {
-            feature: "MainFeature".to_string(),
-            component: component_id.clone(),
-        });
+        for target in get_environment_targets(context, action) {
+            let uuid = Uuid::new_v5(&context.upgrade_code, target.component_id.as_bytes());
+            let key_path = registry_key_path(context, &target.component_id);
+            components.push(Component {
+                component: target.component_id.clone(),
+                id: Some(uuid),
+                directory: "INSTALLDIR".to_string(),
+                attributes: ComponentAttributes::Bit64 | ComponentAttributes::RegistryKeyPath,
+                condition: target.condition,
+                key_path: Some(key_path.registry.clone()),
+            });
+
+            environments.push(Environment {
+                environment: target.component_id.clone(),
+                name: get_environment_name(action, target.system),
+                value: Some(get_environment_value(action)),
+                component: target.component_id.clone(),
+            });
+
+            registry_items.push(key_path);
+
+            feature_components.push(FeatureComponents {
+                feature: "MainFeature".to_string(),
+                component: target.component_id.clone(),
+            });
+        }
     }
 
     Component::insert(package, &components)?;
     Environment::insert(package, &environments)?;
-    // Registry::insert(package, &registry_items)?;
+    Registry::insert(package, &registry_items)?;
     FeatureComponents::insert(package, &feature_components)?;
     Ok(())
 }
 
-fn get_environment_name(action: &EnvironmentAction) -> String {
-    format!("=-{}", action.name)
+/// Where an environment variable is written.
+struct EnvironmentTarget {
+    component_id: String,
+    /// Write the system environment variable instead of the user one.
+    system: bool,
+    condition: Option<String>,
+}
+
+// A dual purpose package only knows if it is installed per user or per machine at install time.
+// Both variants are declared in separate components conditioned on ALLUSERS.
+fn get_environment_targets(
+    context: &Context,
+    action: &EnvironmentAction,
+) -> Vec<EnvironmentTarget> {
+    let component_id = format!("env_{}", action.id.to_lowercase());
+    match context.scope {
+        InstallScope::PerUser => vec![EnvironmentTarget {
+            component_id,
+            system: false,
+            condition: None,
+        }],
+        InstallScope::PerMachine => vec![EnvironmentTarget {
+            component_id,
+            system: true,
+            condition: None,
+        }],
+        InstallScope::DualPurpose => vec![
+            EnvironmentTarget {
+                component_id: format!("{component_id}_user"),
+                system: false,
+                condition: Some("ALLUSERS <> \"1\"".to_string()),
+            },
+            EnvironmentTarget {
+                component_id: format!("{component_id}_machine"),
+                system: true,
+                condition: Some("ALLUSERS = \"1\"".to_string()),
+            },
+        ],
+    }
+}
+
+fn get_environment_name(action: &EnvironmentAction, system: bool) -> String {
+    if system {
+        format!("=-*{}", action.name)
+    } else {
+        format!("=-{}", action.name)
+    }
 }
 
 fn get_environment_value(action: &EnvironmentAction) -> String {
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use msi_installer::{
+    identifier::identifier,
+    tables::{Feature, FeatureAttributes},
+};
+
+use crate::LivraisonError;
+
+// Maximum length of the key column of the Feature table:
+const MAX_FEATURE_ID_LENGTH: usize = 38;
+
+// Level of the features not installed by default, above the default INSTALLLEVEL of 1:
+const ABSENT_LEVEL: i32 = 1000;
+
+/// Config of a feature of the product, shown in the selection tree of the customize dialog.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureOptions {
+    /// Identifier used to assign binaries to the feature, at most 38 characters.
+    /// Made of letters, digits, `_` and `.`, starting with a letter or `_`.
+    pub id: String,
+
+    pub title: String,
+
+    /// Shown in the customize dialog when the feature is selected.
+    pub description: Option<String>,
+
+    /// Whether the feature is selected when the installer starts.
+    pub default_state: FeatureState,
+
+    /// Let the user opt out of the feature.
+    pub optional: bool,
+
+    pub children: Vec<FeatureOptions>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeatureState {
+    #[default]
+    Installed,
+    /// Only installed when the user selects it, the feature must be optional.
+    Absent,
+}
+
+/// Returns true if the user can choose to install some of the features.
+pub fn has_optional_features(features: &[FeatureOptions]) -> bool {
+    features
+        .iter()
+        .any(|feature| feature.optional || has_optional_features(&feature.children))
+}
+
+/// Check the ids of the features are valid keys of the Feature table.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/identifier
+pub fn validate_feature_ids(features: &[FeatureOptions]) -> Result<(), LivraisonError> {
+    for feature in features {
+        if feature.id.is_empty() || feature.id.len() > MAX_FEATURE_ID_LENGTH {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Feature id {:?} must be between 1 and {MAX_FEATURE_ID_LENGTH} characters",
+                feature.id
+            )));
+        }
+        if identifier(&feature.id) != feature.id {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Feature id {:?} must only contain letters, digits, `_` and `.`, and start with \
+                 a letter or `_`",
+                feature.id
+            )));
+        }
+        validate_feature_ids(&feature.children)?;
+    }
+    Ok(())
+}
+
+/// Flatten the feature tree into rows of the Feature table, under the given root feature.
+pub fn feature_rows(
+    root: &str,
+    directory: &str,
+    features: &[FeatureOptions],
+) -> Result<Vec<Feature>, LivraisonError> {
+    let mut rows = Vec::new();
+    let mut ids = HashSet::from([root.to_string()]);
+    let mut display = 1;
+    add_feature_rows(&mut rows, &mut ids, &mut display, root, directory, features)?;
+    Ok(rows)
+}
+
+fn add_feature_rows(
+    rows: &mut Vec<Feature>,
+    ids: &mut HashSet<String>,
+    display: &mut i32,
+    parent: &str,
+    directory: &str,
+    features: &[FeatureOptions],
+) -> Result<(), LivraisonError> {
+    for feature in features {
+        if !ids.insert(feature.id.clone()) {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Feature {} is declared twice",
+                feature.id
+            )));
+        }
+        if feature.default_state == FeatureState::Absent && !feature.optional {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Feature {} is absent by default but not optional",
+                feature.id
+            )));
+        }
+
+        let mut attributes = FeatureAttributes::DisallowAdvertise;
+        if !feature.optional {
+            attributes |= FeatureAttributes::UIDisallowAbsent;
+        }
+        // Odd numbers display the feature expanded in the selection tree
+        *display += 2;
+        rows.push(Feature {
+            feature: feature.id.clone(),
+            parent: Some(parent.to_string()),
+            title: Some(feature.title.clone()),
+            description: feature.description.clone(),
+            display: Some(*display),
+            level: match feature.default_state {
+                FeatureState::Installed => 1,
+                FeatureState::Absent => ABSENT_LEVEL,
+            },
+            directory: Some(directory.to_string()),
+            attributes,
+        });
+        add_feature_rows(
+            rows,
+            ids,
+            display,
+            &feature.id,
+            directory,
+            &feature.children,
+        )?;
+    }
+    Ok(())
+}
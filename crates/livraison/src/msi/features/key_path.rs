@@ -0,0 +1,26 @@
+use msi_installer::{
+    InstallScope, identifier,
+    tables::{Registry, RegistryRoot},
+};
+
+use crate::msi::Context;
+
+/// Registry key under which the key paths of the components are written.
+const KEY_PATH_REGISTRY_KEY: &str = "Software\\[Manufacturer]\\[ProductName]";
+
+/// Registry value used as the key path of the given component.
+/// Components installed in the user profile must use a HKCU registry value as key path (ICE38).
+pub fn registry_key_path(context: &Context, component: &str) -> Registry {
+    Registry {
+        registry: identifier(&format!("reg_{component}")),
+        root: match context.scope {
+            InstallScope::PerUser => RegistryRoot::CurrentUser,
+            InstallScope::PerMachine => RegistryRoot::LocalMachine,
+            InstallScope::DualPurpose => RegistryRoot::Auto,
+        },
+        key: KEY_PATH_REGISTRY_KEY.to_string(),
+        name: Some(component.to_string()),
+        value: Some("#1".to_string()),
+        component: component.to_string(),
+    }
+}
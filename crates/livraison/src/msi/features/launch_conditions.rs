@@ -0,0 +1,288 @@
+use std::io::{Read, Seek, Write};
+
+use msi::Package;
+use msi_installer::{
+    identifier::identifier,
+    tables::{
+        AppSearch, DrLocator, Entity, LaunchCondition, RegLocator, RegLocatorType, RegistryRoot,
+        Signature,
+    },
+};
+
+use crate::{LivraisonError, msi::localization::Strings};
+
+// Property holding the build number of Windows, VersionNT stops at 603 on Windows 10 and later
+const WINDOWS_BUILD_PROPERTY: &str = "WINDOWS_BUILD";
+const WINDOWS_BUILD_KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion";
+
+/// Checks done before installing, the install is blocked with a message when one fails.
+/// Nothing is checked on uninstall or repair.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchConditionsOptions {
+    /// Oldest version of Windows the product runs on.
+    pub minimum_windows: Option<WindowsVersion>,
+
+    /// Block the install on 32-bit Windows.
+    pub require_64bit: bool,
+
+    /// Software which must be installed first, found in the registry or the file system.
+    pub prerequisites: Vec<PrerequisiteOptions>,
+
+    /// Any other condition, with the message shown when it is false.
+    pub conditions: Vec<LaunchConditionOptions>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WindowsVersion {
+    Windows7,
+    Windows8,
+    Windows8_1,
+    Windows10,
+    Windows11,
+}
+
+/// Config of a prerequisite, e.g. a runtime, found by a search.
+#[derive(Debug, Clone)]
+pub struct PrerequisiteOptions {
+    /// Public property set with the result of the search, in upper case, e.g. `DOTNET_RUNTIME`
+    pub property: String,
+
+    pub search: PrerequisiteSearch,
+
+    /// Shown when the prerequisite isn't found, e.g. `Install the .NET runtime first`
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum PrerequisiteSearch {
+    /// Found when the registry value exists, the property is set to its content.
+    Registry {
+        /// `Auto` isn't allowed in searches.
+        root: RegistryRoot,
+        key: String,
+        /// Name of the value, `None` for the default value of the key.
+        name: Option<String>,
+        /// Read the 64-bit view of the registry instead of the 32-bit one.
+        win64: bool,
+    },
+    /// Found when the file is in the directory or one of its subdirectories, the property is set to its path.
+    File {
+        /// Directory searched, may contain properties, e.g. `[ProgramFiles64Folder]dotnet`
+        directory: String,
+        file_name: String,
+        /// Oldest accepted version of the file, e.g. `8.0.0`
+        min_version: Option<String>,
+        /// Number of levels of subdirectories searched.
+        depth: i32,
+    },
+    /// Found when the directory exists, the property is set to its path.
+    Directory { path: String },
+}
+
+/// Condition blocking the install when false, e.g. `VersionNT >= 603`
+/// https://learn.microsoft.com/en-us/windows/win32/msi/conditional-statement-syntax
+#[derive(Debug, Clone, Default)]
+pub struct LaunchConditionOptions {
+    pub condition: String,
+    pub message: String,
+}
+
+impl LaunchConditionsOptions {
+    /// Returns true if the AppSearch action must run before the conditions are evaluated.
+    pub fn has_searches(&self) -> bool {
+        !self.prerequisites.is_empty()
+            || self
+                .minimum_windows
+                .is_some_and(|version| version.min_build().is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.minimum_windows.is_none()
+            && !self.require_64bit
+            && self.prerequisites.is_empty()
+            && self.conditions.is_empty()
+    }
+}
+
+impl WindowsVersion {
+    fn name(self) -> &'static str {
+        match self {
+            WindowsVersion::Windows7 => "Windows 7",
+            WindowsVersion::Windows8 => "Windows 8",
+            WindowsVersion::Windows8_1 => "Windows 8.1",
+            WindowsVersion::Windows10 => "Windows 10",
+            WindowsVersion::Windows11 => "Windows 11",
+        }
+    }
+
+    // Value of the VersionNT property
+    // https://learn.microsoft.com/en-us/windows/win32/msi/operating-system-property-values
+    fn version_nt(self) -> i32 {
+        match self {
+            WindowsVersion::Windows7 => 601,
+            WindowsVersion::Windows8 => 602,
+            WindowsVersion::Windows8_1 | WindowsVersion::Windows10 | WindowsVersion::Windows11 => {
+                603
+            }
+        }
+    }
+
+    // Versions sharing the same VersionNT are told apart by their build number
+    fn min_build(self) -> Option<i32> {
+        match self {
+            WindowsVersion::Windows10 => Some(10240),
+            WindowsVersion::Windows11 => Some(22000),
+            _ => None,
+        }
+    }
+
+    fn condition(self) -> String {
+        match self.min_build() {
+            Some(build) => format!(
+                "VersionNT >= {} AND {WINDOWS_BUILD_PROPERTY} >= {build}",
+                self.version_nt()
+            ),
+            None => format!("VersionNT >= {}", self.version_nt()),
+        }
+    }
+}
+
+/// Fill the LaunchCondition table, and the AppSearch, Signature, RegLocator and DrLocator tables
+/// with the searches of the prerequisites.
+pub fn register_launch_conditions<F: Read + Seek + Write>(
+    package: &mut Package<F>,
+    options: &LaunchConditionsOptions,
+    strings: &Strings,
+) -> Result<(), LivraisonError> {
+    let mut conditions: Vec<LaunchCondition> = Vec::new();
+    let mut searches: Vec<AppSearch> = Vec::new();
+    let mut signatures: Vec<Signature> = Vec::new();
+    let mut reg_locators: Vec<RegLocator> = Vec::new();
+    let mut dr_locators: Vec<DrLocator> = Vec::new();
+
+    if let Some(version) = options.minimum_windows {
+        if version.min_build().is_some() {
+            let signature = "WindowsBuildSearch".to_string();
+            searches.push(AppSearch {
+                property: WINDOWS_BUILD_PROPERTY.to_string(),
+                signature: signature.clone(),
+            });
+            reg_locators.push(RegLocator {
+                signature,
+                root: RegistryRoot::LocalMachine,
+                key: WINDOWS_BUILD_KEY.to_string(),
+                name: Some("CurrentBuildNumber".to_string()),
+                locator_type: RegLocatorType::Raw | RegLocatorType::Win64,
+            });
+        }
+        conditions.push(LaunchCondition {
+            condition: install_condition(&version.condition()),
+            description: strings.requires_windows(version.name()),
+        });
+    }
+    if options.require_64bit {
+        conditions.push(LaunchCondition {
+            condition: install_condition("VersionNT64"),
+            description: strings.requires_64bit.to_string(),
+        });
+    }
+
+    for prerequisite in options.prerequisites.iter() {
+        let property = &prerequisite.property;
+        let is_public = property
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '.');
+        if !is_public || identifier(property) != *property {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Prerequisite property {property:?} must be an upper case identifier"
+            )));
+        }
+        let signature = identifier(&format!("{property}_search"));
+        match &prerequisite.search {
+            PrerequisiteSearch::Registry {
+                root,
+                key,
+                name,
+                win64,
+            } => {
+                if *root == RegistryRoot::Auto {
+                    return Err(LivraisonError::InvalidOption(format!(
+                        "Prerequisite {property} must search a registry root other than Auto"
+                    )));
+                }
+                let mut locator_type = RegLocatorType::Raw;
+                if *win64 {
+                    locator_type |= RegLocatorType::Win64;
+                }
+                reg_locators.push(RegLocator {
+                    signature: signature.clone(),
+                    root: *root,
+                    key: key.clone(),
+                    name: name.clone(),
+                    locator_type,
+                });
+            }
+            PrerequisiteSearch::File {
+                directory,
+                file_name,
+                min_version,
+                depth,
+            } => {
+                signatures.push(Signature {
+                    signature: signature.clone(),
+                    file_name: file_name.clone(),
+                    min_version: min_version.clone(),
+                    ..Default::default()
+                });
+                dr_locators.push(DrLocator {
+                    signature: signature.clone(),
+                    parent: None,
+                    path: Some(directory.clone()),
+                    depth: Some(*depth),
+                });
+            }
+            PrerequisiteSearch::Directory { path } => {
+                dr_locators.push(DrLocator {
+                    signature: signature.clone(),
+                    parent: None,
+                    path: Some(path.clone()),
+                    depth: Some(0),
+                });
+            }
+        }
+        searches.push(AppSearch {
+            property: property.clone(),
+            signature,
+        });
+        conditions.push(LaunchCondition {
+            condition: install_condition(property),
+            description: prerequisite.message.clone(),
+        });
+    }
+
+    for condition in options.conditions.iter() {
+        conditions.push(LaunchCondition {
+            condition: install_condition(&condition.condition),
+            description: condition.message.clone(),
+        });
+    }
+
+    LaunchCondition::create_table(package)?;
+    LaunchCondition::insert(package, &conditions)?;
+    if options.has_searches() {
+        AppSearch::create_table(package)?;
+        Signature::create_table(package)?;
+        RegLocator::create_table(package)?;
+        DrLocator::create_table(package)?;
+        AppSearch::insert(package, &searches)?;
+        Signature::insert(package, &signatures)?;
+        RegLocator::insert(package, &reg_locators)?;
+        DrLocator::insert(package, &dr_locators)?;
+    }
+    Ok(())
+}
+
+// Only check the condition on install so the product can still be removed or repaired
+fn install_condition(condition: &str) -> String {
+    format!("Installed OR ({condition})")
+}
@@ -1 +1,7 @@
+pub mod associations;
 pub mod environment_vars;
+pub mod feature_tree;
+pub mod key_path;
+pub mod launch_conditions;
+pub mod registry;
+pub mod services;
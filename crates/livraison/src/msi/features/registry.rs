@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, Write},
+};
+
+use msi::Package;
+use msi_installer::{
+    identifier::{identifier, unique_identifier},
+    tables::{Component, ComponentAttributes, Entity, FeatureComponents, Registry, RegistryRoot},
+};
+use uuid::Uuid;
+
+use crate::{LivraisonError, msi::Context};
+
+/// Registry key under which applications are registered to be found by `ShellExecute` and the Run dialog.
+const APP_PATHS_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\App Paths";
+
+/// Config to write a registry key and its values.
+/// Each key is installed by its own component, values are removed on uninstall.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryKeyOptions {
+    /// `Auto` writes under HKCU for per user installs and under HKLM for per machine installs.
+    pub root: RegistryRoot,
+
+    /// Path of the key relative to the root, e.g. `Software\[Manufacturer]\[ProductName]`
+    pub key: String,
+
+    pub values: Vec<RegistryValueOptions>,
+
+    /// Delete the key and all its subkeys on uninstall, including values not written by the installer.
+    pub remove_on_uninstall: bool,
+
+    /// Id of the feature installing the key, the product feature when `None`.
+    pub feature: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryValueOptions {
+    /// Name of the value, `None` for the default value of the key.
+    pub name: Option<String>,
+    pub value: RegistryValue,
+}
+
+/// Typed registry value. Strings are formatted, e.g. `[INSTALLDIR]` is replaced by the install directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryValue {
+    /// REG_SZ
+    String(String),
+    /// REG_EXPAND_SZ, environment variables like `%PATH%` are expanded when the value is read.
+    Expandable(String),
+    /// REG_DWORD
+    Dword(u32),
+    /// REG_MULTI_SZ
+    MultiString(Vec<String>),
+    /// REG_BINARY
+    Binary(Vec<u8>),
+}
+
+impl RegistryKeyOptions {
+    /// Register an executable in "App Paths" so it can be started by name from the Run dialog or `start`.
+    /// `exe` is the name of the binary file as declared in `binaries`.
+    pub fn app_path(exe: &str) -> Self {
+        RegistryKeyOptions {
+            root: RegistryRoot::Auto,
+            key: format!("{APP_PATHS_KEY}\\{exe}"),
+            values: vec![
+                RegistryValueOptions::new(
+                    None,
+                    RegistryValue::String(format!("[INSTALLDIR]{exe}")),
+                ),
+                RegistryValueOptions::new(
+                    Some("Path"),
+                    RegistryValue::String("[INSTALLDIR]".to_string()),
+                ),
+            ],
+            remove_on_uninstall: true,
+            feature: None,
+        }
+    }
+}
+
+impl RegistryValueOptions {
+    pub fn new(name: Option<&str>, value: RegistryValue) -> Self {
+        RegistryValueOptions {
+            name: name.map(str::to_string),
+            value,
+        }
+    }
+}
+
+impl RegistryValue {
+    /// Encode the value in the format of the Value column of the Registry table.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/registry-table#value
+    pub fn encode(&self) -> String {
+        match self {
+            // A leading # would be read as a type prefix
+            RegistryValue::String(value) if value.starts_with('#') => format!("#{value}"),
+            RegistryValue::String(value) => value.clone(),
+            RegistryValue::Expandable(value) => format!("#%{value}"),
+            // Read back as a signed 32 bits integer, keep the bits of values above i32::MAX
+            RegistryValue::Dword(value) => format!("#{}", *value as i32),
+            // A single string needs a delimiter on both sides to replace the existing value
+            // instead of being appended or prepended to it.
+            RegistryValue::MultiString(values) => match values.as_slice() {
+                [] => "[~]".to_string(),
+                [value] => format!("[~]{value}[~]"),
+                values => values.join("[~]"),
+            },
+            RegistryValue::Binary(value) => encode_binary(value),
+        }
+    }
+}
+
+fn encode_binary(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02X}")).collect();
+    format!("#x{hex}")
+}
+
+/// Write the registry keys, each one with its own component in the feature of the key,
+/// `default_feature` when not set.
+/// The component key and GUID are derived from the root and the path of the key so they stay the
+/// same across versions.
+pub fn register_registry_keys<F: Read + Seek + Write>(
+    package: &mut Package<F>,
+    context: &Context,
+    default_feature: &str,
+    keys: &[RegistryKeyOptions],
+) -> Result<(), LivraisonError> {
+    let mut components: Vec<Component> = Vec::new();
+    let mut registry_items: Vec<Registry> = Vec::new();
+    let mut feature_components: Vec<FeatureComponents> = Vec::new();
+    let mut paths = HashMap::<String, &str>::new();
+    for key in keys {
+        if key.values.is_empty() {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Registry key {} has no values",
+                key.key
+            )));
+        }
+        // Registry keys are case insensitive
+        let path = format!("{}\\{}", key.root as i32, key.key.to_lowercase());
+        if let Some(other) = paths.insert(path.clone(), &key.key) {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Registry keys {} and {} are the same key",
+                other, key.key
+            )));
+        }
+        let component_id = unique_identifier(&format!("reg_{path}"));
+        let uuid = Uuid::new_v5(&context.upgrade_code, path.as_bytes());
+        let value_id = |suffix: &str| identifier(&format!("{component_id}_{suffix}"));
+
+        for (value_index, value) in key.values.iter().enumerate() {
+            registry_items.push(Registry {
+                registry: value_id(&value_index.to_string()),
+                root: key.root,
+                key: key.key.clone(),
+                name: value.name.clone(),
+                // An empty value is stored as null which writes an empty string
+                value: Some(value.value.encode()).filter(|value| !value.is_empty()),
+                component: component_id.clone(),
+            });
+        }
+        if key.remove_on_uninstall {
+            registry_items.push(Registry {
+                registry: value_id("remove"),
+                root: key.root,
+                key: key.key.clone(),
+                name: Some("-".to_string()),
+                value: None,
+                component: component_id.clone(),
+            });
+        }
+
+        components.push(Component {
+            component: component_id.clone(),
+            id: Some(uuid),
+            directory: "INSTALLDIR".to_string(),
+            attributes: ComponentAttributes::Bit64 | ComponentAttributes::RegistryKeyPath,
+            condition: None,
+            key_path: Some(value_id("0")),
+        });
+
+        feature_components.push(FeatureComponents {
+            feature: key
+                .feature
+                .clone()
+                .unwrap_or_else(|| default_feature.to_string()),
+            component: component_id,
+        });
+    }
+
+    Component::insert(package, &components)?;
+    Registry::insert(package, &registry_items)?;
+    FeatureComponents::insert(package, &feature_components)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegistryValue;
+
+    #[test]
+    fn encode_values() {
+        assert_eq!(RegistryValue::String("text".into()).encode(), "text");
+        assert_eq!(RegistryValue::String("#1".into()).encode(), "##1");
+        assert_eq!(
+            RegistryValue::Expandable("%PATH%".into()).encode(),
+            "#%%PATH%"
+        );
+        assert_eq!(RegistryValue::Dword(42).encode(), "#42");
+        assert_eq!(RegistryValue::Dword(u32::MAX).encode(), "#-1");
+        assert_eq!(RegistryValue::Binary(vec![0xca, 0xfe]).encode(), "#xCAFE");
+        assert_eq!(RegistryValue::MultiString(vec![]).encode(), "[~]");
+        assert_eq!(
+            RegistryValue::MultiString(vec!["a".into()]).encode(),
+            "[~]a[~]"
+        );
+        assert_eq!(
+            RegistryValue::MultiString(vec!["a".into(), "b".into()]).encode(),
+            "a[~]b"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, Write},
+};
+
+use msi::Package;
+use msi_installer::{
+    identifier::identifier,
+    tables::{
+        Entity, MsiServiceConfigFailureActions, ServiceConfigEvent, ServiceControl,
+        ServiceControlEvent, ServiceErrorControl, ServiceErrorLevel, ServiceFailureActionType,
+        ServiceInstall, ServiceStartType, ServiceType,
+    },
+};
+
+use crate::LivraisonError;
+
+/// Config to register a windows service
+#[derive(Debug, Clone, Default)]
+pub struct ServiceOptions {
+    /// Name of the service used to start or stop it. e.g. `net start <name>`
+    pub name: String,
+
+    /// Name shown in the services management console
+    pub display_name: Option<String>,
+
+    pub description: Option<String>,
+
+    /// Name of the binary file, as declared in `binaries`, which is the service executable.
+    pub binary: String,
+
+    /// Command line arguments passed to the service executable.
+    pub arguments: Option<String>,
+
+    pub start: ServiceStart,
+
+    pub account: ServiceAccount,
+
+    /// Names of the services that must be running before this service starts.
+    pub dependencies: Vec<String>,
+
+    /// Abort the install if the service fails to install.
+    pub vital: bool,
+
+    /// What to do when the service fails. Actions are applied in order on the first, second and subsequent failures.
+    pub on_failure: Vec<ServiceFailureAction>,
+
+    /// Time in seconds without failure after which the failure count is reset.
+    pub failure_reset_period: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ServiceStart {
+    /// Start the service at boot and right after install.
+    #[default]
+    Auto,
+    /// Only start the service when requested.
+    Manual,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ServiceAccount {
+    #[default]
+    LocalSystem,
+    LocalService,
+    NetworkService,
+    User {
+        name: String,
+        password: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceFailureAction {
+    None,
+    /// Restart the service after the given delay in milliseconds.
+    Restart {
+        delay: u32,
+    },
+    /// Reboot the computer after the given delay in milliseconds.
+    Reboot {
+        delay: u32,
+    },
+}
+
+pub fn register_services<F: Read + Seek + Write>(
+    package: &mut Package<F>,
+    services: &[ServiceOptions],
+    component_of: impl Fn(&str) -> Option<String>,
+) -> Result<(), LivraisonError> {
+    ServiceInstall::create_table(package)?;
+    ServiceControl::create_table(package)?;
+
+    let mut installs: Vec<ServiceInstall> = Vec::new();
+    let mut controls: Vec<ServiceControl> = Vec::new();
+    let mut failure_actions: Vec<MsiServiceConfigFailureActions> = Vec::new();
+    let mut ids = HashMap::<String, &str>::new();
+    for service in services {
+        let component = match component_of(&service.binary) {
+            Some(component) => component,
+            None => {
+                return Err(LivraisonError::InvalidOption(format!(
+                    "Service '{}' executable '{}' is not one of the packaged binaries",
+                    service.name, service.binary
+                )));
+            }
+        };
+        // Service names are case insensitive
+        let id = identifier(&format!("svc_{}", service.name.to_lowercase()));
+        if let Some(other) = ids.insert(id.clone(), &service.name) {
+            return Err(LivraisonError::InvalidOption(format!(
+                "Services '{}' and '{}' have the same key {id}",
+                other, service.name
+            )));
+        }
+        let (start_name, password) = get_service_account(&service.account);
+
+        installs.push(ServiceInstall {
+            service_install: id.clone(),
+            name: service.name.clone(),
+            display_name: service.display_name.clone(),
+            service_type: ServiceType::OwnProcess,
+            start_type: match service.start {
+                ServiceStart::Auto => ServiceStartType::Auto,
+                ServiceStart::Manual => ServiceStartType::Demand,
+                ServiceStart::Disabled => ServiceStartType::Disabled,
+            },
+            error_control: ServiceErrorControl {
+                level: ServiceErrorLevel::Normal,
+                vital: service.vital,
+            },
+            load_order_group: None,
+            dependencies: if service.dependencies.is_empty() {
+                None
+            } else {
+                // A multi-string, terminated by an empty string
+                Some(format!("{}[~][~]", service.dependencies.join("[~]")))
+            },
+            start_name,
+            password,
+            arguments: service.arguments.clone(),
+            component: component.clone(),
+            description: service.description.clone(),
+        });
+
+        // Stop the service before its files are replaced and remove it on uninstall.
+        let mut event = ServiceControlEvent::Stop
+            | ServiceControlEvent::UninstallStop
+            | ServiceControlEvent::UninstallDelete;
+        if service.start == ServiceStart::Auto {
+            event |= ServiceControlEvent::Start;
+        }
+        controls.push(ServiceControl {
+            service_control: id.clone(),
+            name: service.name.clone(),
+            event,
+            arguments: None,
+            wait: Some(true),
+            component: component.clone(),
+        });
+
+        if !service.on_failure.is_empty() {
+            failure_actions.push(MsiServiceConfigFailureActions {
+                msi_service_config_failure_actions: id.clone(),
+                name: service.name.clone(),
+                event: ServiceConfigEvent::Install | ServiceConfigEvent::Reinstall,
+                reset_period: service.failure_reset_period.map(|x| x as i32),
+                reboot_message: None,
+                command: None,
+                actions: Some(join_failure_actions(&service.on_failure, |action| {
+                    get_failure_action_type(action) as i32
+                })),
+                delay_actions: Some(join_failure_actions(
+                    &service.on_failure,
+                    |action| match action {
+                        ServiceFailureAction::None => 0,
+                        ServiceFailureAction::Restart { delay } => *delay as i32,
+                        ServiceFailureAction::Reboot { delay } => *delay as i32,
+                    },
+                )),
+                component,
+            });
+        }
+    }
+
+    ServiceInstall::insert(package, &installs)?;
+    ServiceControl::insert(package, &controls)?;
+    // The table schedules the MsiConfigureServices action, only create it when needed
+    if has_failure_actions(services) {
+        MsiServiceConfigFailureActions::create_table(package)?;
+        MsiServiceConfigFailureActions::insert(package, &failure_actions)?;
+    }
+    Ok(())
+}
+
+/// Returns true if any of the services needs the `MsiConfigureServices` action.
+fn has_failure_actions(services: &[ServiceOptions]) -> bool {
+    services.iter().any(|x| !x.on_failure.is_empty())
+}
+
+fn get_service_account(account: &ServiceAccount) -> (Option<String>, Option<String>) {
+    match account {
+        ServiceAccount::LocalSystem => (None, None),
+        ServiceAccount::LocalService => (Some("NT AUTHORITY\\LocalService".to_string()), None),
+        ServiceAccount::NetworkService => (Some("NT AUTHORITY\\NetworkService".to_string()), None),
+        ServiceAccount::User { name, password } => (Some(name.clone()), password.clone()),
+    }
+}
+
+fn get_failure_action_type(action: &ServiceFailureAction) -> ServiceFailureActionType {
+    match action {
+        ServiceFailureAction::None => ServiceFailureActionType::None,
+        ServiceFailureAction::Restart { .. } => ServiceFailureActionType::Restart,
+        ServiceFailureAction::Reboot { .. } => ServiceFailureActionType::Reboot,
+    }
+}
+
+fn join_failure_actions(
+    actions: &[ServiceFailureAction],
+    f: impl Fn(&ServiceFailureAction) -> i32,
+) -> String {
+    actions
+        .iter()
+        .map(|action| f(action).to_string())
+        .collect::<Vec<_>>()
+        .join("[~]")
+}
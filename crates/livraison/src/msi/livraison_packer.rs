@@ -2,9 +2,9 @@ use std::fs;
 
 use super::packer;
 use crate::{
-    LivraisonResult,
+    LivraisonError, LivraisonResult,
     actions::pack::{CommonOptions, LivraisonPacker},
-    msi::packer::MsiInstallerOptions,
+    msi::packer::{MergeModuleOptions, MsiInstallerOptions, MsiLanguage, MsiSigningOptions},
 };
 
 #[derive(Debug, Default, Clone)]
@@ -13,24 +13,63 @@ pub struct MsiLivraisonPacker {}
 impl LivraisonPacker for MsiLivraisonPacker {
     fn pack(&self, options: CommonOptions) -> LivraisonResult<()> {
         let out_file = options.out.join(options.name.clone()).with_extension("msi");
+        let languages = options
+            .languages
+            .iter()
+            .map(|tag| tag.parse())
+            .collect::<LivraisonResult<Vec<MsiLanguage>>>()?;
+        let signing = match (options.sign_certificate, options.sign_key) {
+            (Some(certificate), Some(private_key)) => Some(MsiSigningOptions {
+                certificate,
+                private_key,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(LivraisonError::InvalidOption(
+                    "Signing requires both a certificate and a private key".to_string(),
+                ));
+            }
+        };
         fs::create_dir_all(options.out)?;
-        packer::pack(
-            MsiInstallerOptions {
-                name: options.name.clone(),
-                bundle_name: options.name.clone(),
-                version: match options.version {
-                    Some(v) => v,
-                    None => "0.0.0".to_string(),
-                },
-                description: options.description.unwrap_or("No description.".to_string()),
-                author: match options.author {
-                    Some(author) => author.name,
-                    None => "Unknown".to_string(),
-                },
-                ..Default::default()
+        let msi_options = MsiInstallerOptions {
+            name: options.name.clone(),
+            bundle_name: options.name.clone(),
+            version: match options.version {
+                Some(v) => v,
+                None => "0.0.0".to_string(),
             },
-            &out_file,
-        )?;
+            description: options.description.unwrap_or("No description.".to_string()),
+            author: match options.author {
+                Some(author) => author.name,
+                None => "Unknown".to_string(),
+            },
+            icon: options.icon.map(|icon| icon.to_string_lossy().to_string()),
+            homepage: options.homepage,
+            support_url: options.support_url,
+            signing,
+            merge_modules: options
+                .merge_modules
+                .into_iter()
+                .map(|path| MergeModuleOptions {
+                    path,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        match languages.as_slice() {
+            [] => packer::pack(msi_options, &out_file)?,
+            [language] => packer::pack(
+                MsiInstallerOptions {
+                    language: *language,
+                    ..msi_options
+                },
+                &out_file,
+            )?,
+            _ => {
+                packer::pack_languages(msi_options, &languages, &out_file)?;
+            }
+        }
         Ok(())
     }
 }
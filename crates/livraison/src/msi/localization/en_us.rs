@@ -0,0 +1,48 @@
+use super::Strings;
+
+pub const STRINGS: Strings = Strings {
+    font: "Segoe UI",
+    setup_title: "[ProductName] Setup",
+
+    text_action: "installation",
+    text_agent: "installer",
+    text_doing: "installing",
+    text_done: "installed",
+
+    install: "Install",
+    cancel: "Cancel",
+    finish: "Finish",
+    next: "Next",
+    remove: "Remove",
+    reset: "Reset",
+    change: "Change...",
+    ok: "OK",
+    up: "Up",
+    new_folder: "New",
+
+    welcome_title: "Welcome to the [ProductName] installer",
+    welcome_description: "This will install [ProductName] on your computer. Click Install to continue or Cancel to exit the installer.",
+    install_scope_title: "Installation scope",
+    install_scope_description: "Install [ProductName] just for you or for all users of this computer?",
+    per_user: "Just for me",
+    per_machine: "Everyone",
+    install_dir_title: "Destination folder",
+    install_dir_label: "Install [ProductName] to:",
+    browse_look_in: "Look in:",
+    customize_title: "Custom setup",
+    customize_description: "Select the features you want to install.",
+    remove_title: "Uninstall [ProductName]",
+    remove_description: "This will remove [ProductName] from your computer. Click Remove to continue or Cancel to exit the uninstaller.",
+    progress_title: "Installing [ProductName]",
+    progress_description: "Please wait while [ProductName] is [Text_done]. This may take several minutes.",
+    exit_title: "[ProductName] [Text_action] complete",
+    fatal_error_title: "[ProductName] [Text_agent] ended prematurely",
+    fatal_error_description: "[ProductName] [Text_action] ended because of an error. The program has not been installed. This installer can be run again at a later time.",
+
+    requires_windows: "[ProductName] requires {version} or later.",
+    requires_64bit: "[ProductName] requires a 64-bit version of Windows.",
+
+    // The standard messages are already in English
+    errors: &[],
+    action_texts: &[],
+};
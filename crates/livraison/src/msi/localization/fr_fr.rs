@@ -0,0 +1,326 @@
+use super::Strings;
+
+pub const STRINGS: Strings = Strings {
+    font: "Segoe UI",
+    setup_title: "Installation de [ProductName]",
+
+    text_action: "installation",
+    text_agent: "programme d'installation",
+    text_doing: "installation en cours",
+    text_done: "installé",
+
+    install: "Installer",
+    cancel: "Annuler",
+    finish: "Terminer",
+    next: "Suivant",
+    remove: "Supprimer",
+    reset: "Réinitialiser",
+    change: "Modifier...",
+    ok: "OK",
+    up: "Haut",
+    new_folder: "Nouveau",
+
+    welcome_title: "Bienvenue dans l'installation de [ProductName]",
+    welcome_description: "[ProductName] va être installé sur votre ordinateur. Cliquez sur Installer pour continuer ou sur Annuler pour quitter l'installation.",
+    install_scope_title: "Étendue de l'installation",
+    install_scope_description: "Installer [ProductName] uniquement pour vous ou pour tous les utilisateurs de cet ordinateur ?",
+    per_user: "Pour moi uniquement",
+    per_machine: "Pour tous",
+    install_dir_title: "Dossier de destination",
+    install_dir_label: "Installer [ProductName] dans :",
+    browse_look_in: "Dossier :",
+    customize_title: "Installation personnalisée",
+    customize_description: "Sélectionnez les fonctionnalités à installer.",
+    remove_title: "Désinstaller [ProductName]",
+    remove_description: "[ProductName] va être supprimé de votre ordinateur. Cliquez sur Supprimer pour continuer ou sur Annuler pour quitter la désinstallation.",
+    progress_title: "Installation de [ProductName]",
+    progress_description: "Veuillez patienter pendant que [ProductName] est [Text_done]. Cela peut prendre plusieurs minutes.",
+    exit_title: "L'[Text_action] de [ProductName] est terminée",
+    fatal_error_title: "Le [Text_agent] de [ProductName] s'est arrêté prématurément",
+    fatal_error_description: "L'[Text_action] de [ProductName] s'est arrêtée à cause d'une erreur. Le programme n'a pas été installé. Vous pourrez relancer l'installation plus tard.",
+
+    requires_windows: "[ProductName] nécessite {version} ou une version ultérieure.",
+    requires_64bit: "[ProductName] nécessite une version 64 bits de Windows.",
+
+    errors: ERRORS,
+    action_texts: ACTION_TEXTS,
+};
+
+const ERRORS: &[(i32, &str)] = &[
+    (0, "{{Erreur fatale : }}"),
+    (1, "{{Erreur [1]. }}"),
+    (2, "Avertissement [1]. "),
+    (4, "Info [1]. "),
+    (
+        5,
+        "Le programme d'installation a rencontré une erreur inattendue lors de l'installation de ce package. Cela peut indiquer un problème avec ce package. Le code d'erreur est [1]. {{Les arguments sont : [2], [3], [4]}}",
+    ),
+    (7, "{{Disque plein : }}"),
+    (16, "Temps restant : {[1] minutes }{[2] secondes}"),
+    (
+        17,
+        "Mémoire insuffisante. Fermez les autres applications avant de réessayer.",
+    ),
+    (18, "Le programme d'installation ne répond plus."),
+    (
+        19,
+        "Le programme d'installation s'est arrêté prématurément.",
+    ),
+    (
+        20,
+        "Veuillez patienter pendant que Windows configure [ProductName]",
+    ),
+    (21, "Collecte des informations nécessaires..."),
+    (
+        22,
+        "Suppression des versions précédentes de cette application...",
+    ),
+    (
+        23,
+        "Préparation de la suppression des versions précédentes de cette application...",
+    ),
+    (
+        32,
+        "{[ProductName] }L'installation s'est terminée avec succès.",
+    ),
+    (33, "{[ProductName] }L'installation a échoué."),
+    (
+        1303,
+        "Le programme d'installation ne dispose pas des droits suffisants pour accéder à ce dossier : [2]. L'installation ne peut pas continuer. Ouvrez une session en tant qu'administrateur ou contactez votre administrateur système.",
+    ),
+    (
+        1306,
+        "Une autre application a un accès exclusif au fichier '[2]'. Fermez toutes les autres applications, puis cliquez sur Réessayer.",
+    ),
+    (
+        1307,
+        "L'espace disque est insuffisant pour installer ce fichier : [2]. Libérez de l'espace disque et cliquez sur Réessayer, ou cliquez sur Annuler pour quitter.",
+    ),
+    (
+        1308,
+        "Fichier source introuvable : [2]. Vérifiez que le fichier existe et que vous pouvez y accéder.",
+    ),
+    (
+        1500,
+        "Une autre installation est en cours. Vous devez terminer cette installation avant de poursuivre celle-ci.",
+    ),
+    (
+        1601,
+        "Espace disque insuffisant -- Volume : '[2]' ; espace requis : [3] Ko ; espace disponible : [4] Ko. Libérez de l'espace disque et réessayez.",
+    ),
+    (1602, "Voulez-vous vraiment annuler ?"),
+    (
+        1603,
+        "Le fichier [2][3] est en cours d'utilisation{ par le processus suivant : Nom : [4], Id : [5], Titre de la fenêtre : '[6]'}. Fermez cette application et réessayez.",
+    ),
+    (
+        1604,
+        "Le produit '[2]' est déjà installé, ce qui empêche l'installation de ce produit. Les deux produits sont incompatibles.",
+    ),
+    (1707, "L'installation s'est terminée avec succès."),
+    (1708, "L'installation a échoué."),
+    (1709, "Produit : [2] -- [3]"),
+    (1715, "[2] a été installé"),
+    (1716, "[2] a été configuré"),
+    (1717, "[2] a été supprimé"),
+    (
+        1720,
+        "Ce package Windows Installer présente un problème. Un script nécessaire à cette installation n'a pas pu être exécuté. Contactez votre support technique ou l'éditeur du package. {{Action personnalisée [2], erreur de script [3], [4] : [5] Ligne [6], Colonne [7], [8] }}",
+    ),
+    (
+        1721,
+        "Ce package Windows Installer présente un problème. Un programme nécessaire à cette installation n'a pas pu être exécuté. Contactez votre support technique ou l'éditeur du package. {{Action : [2], emplacement : [3], commande : [4] }}",
+    ),
+    (
+        1722,
+        "Ce package Windows Installer présente un problème. Un programme exécuté pendant l'installation ne s'est pas terminé correctement. Contactez votre support technique ou l'éditeur du package. {{Action [2], emplacement : [3], commande : [4] }}",
+    ),
+    (
+        1723,
+        "Ce package Windows Installer présente un problème. Une DLL nécessaire à cette installation n'a pas pu être exécutée. Contactez votre support technique ou l'éditeur du package. {{Action [2], point d'entrée : [3], bibliothèque : [4] }}",
+    ),
+    (1724, "La suppression s'est terminée avec succès."),
+    (1725, "La suppression a échoué."),
+    (
+        1730,
+        "Vous devez être administrateur pour supprimer cette application. Ouvrez une session en tant qu'administrateur ou contactez votre support technique.",
+    ),
+    (
+        1920,
+        "Le service '[2]' ([3]) n'a pas pu démarrer. Vérifiez que vous disposez des droits suffisants pour démarrer des services système.",
+    ),
+    (
+        1923,
+        "Le service '[2]' ([3]) n'a pas pu être installé. Vérifiez que vous disposez des droits suffisants pour installer des services système.",
+    ),
+    (
+        1925,
+        "Vous ne disposez pas des droits suffisants pour effectuer cette installation pour tous les utilisateurs de l'ordinateur. Ouvrez une session en tant qu'administrateur, puis relancez l'installation.",
+    ),
+];
+
+const ACTION_TEXTS: &[(&str, &str, Option<&str>)] = &[
+    (
+        "AppSearch",
+        "Recherche des applications installées",
+        Some("Propriété : [1], Signature : [2]"),
+    ),
+    ("CostFinalize", "Calcul de l'espace nécessaire", None),
+    ("CostInitialize", "Calcul de l'espace nécessaire", None),
+    (
+        "CreateFolders",
+        "Création des dossiers",
+        Some("Dossier : [1]"),
+    ),
+    (
+        "CreateShortcuts",
+        "Création des raccourcis",
+        Some("Raccourci : [1]"),
+    ),
+    (
+        "DeleteServices",
+        "Suppression des services",
+        Some("Service : [1]"),
+    ),
+    ("FileCost", "Calcul de l'espace nécessaire", None),
+    (
+        "FindRelatedProducts",
+        "Recherche des applications associées",
+        Some("Application trouvée : [1]"),
+    ),
+    (
+        "GenerateScript",
+        "Génération des opérations de script pour l'action :",
+        Some("[1]"),
+    ),
+    (
+        "InstallFiles",
+        "Copie des nouveaux fichiers",
+        Some("Fichier : [1], Dossier : [9], Taille : [6]"),
+    ),
+    (
+        "InstallServices",
+        "Installation des nouveaux services",
+        Some("Service : [2]"),
+    ),
+    ("InstallValidate", "Validation de l'installation", None),
+    (
+        "LaunchConditions",
+        "Évaluation des conditions de lancement",
+        None,
+    ),
+    (
+        "MigrateFeatureStates",
+        "Migration de l'état des fonctionnalités des applications associées",
+        Some("Application : [1]"),
+    ),
+    (
+        "MsiConfigureServices",
+        "Configuration des services",
+        Some("Service : [1]"),
+    ),
+    (
+        "ProcessComponents",
+        "Mise à jour de l'inscription des composants",
+        None,
+    ),
+    (
+        "PublishFeatures",
+        "Publication des fonctionnalités du produit",
+        Some("Fonctionnalité : [1]"),
+    ),
+    (
+        "PublishProduct",
+        "Publication des informations du produit",
+        None,
+    ),
+    (
+        "RegisterExtensionInfo",
+        "Inscription des serveurs d'extensions",
+        Some("Extension : [1]"),
+    ),
+    (
+        "RegisterMIMEInfo",
+        "Inscription des types MIME",
+        Some("Type de contenu MIME : [1], Extension : [2]"),
+    ),
+    ("RegisterProduct", "Inscription du produit", Some("[1]")),
+    (
+        "RegisterProgIdInfo",
+        "Inscription des identificateurs de programme",
+        Some("ProgId : [1]"),
+    ),
+    ("RegisterUser", "Inscription de l'utilisateur", Some("[1]")),
+    (
+        "RemoveEnvironmentStrings",
+        "Mise à jour des variables d'environnement",
+        Some("Nom : [1], Valeur : [2], Action [3]"),
+    ),
+    (
+        "RemoveExistingProducts",
+        "Suppression des applications",
+        Some("Application : [1], Ligne de commande : [2]"),
+    ),
+    (
+        "RemoveFiles",
+        "Suppression des fichiers",
+        Some("Fichier : [1], Dossier : [9]"),
+    ),
+    (
+        "RemoveFolders",
+        "Suppression des dossiers",
+        Some("Dossier : [1]"),
+    ),
+    (
+        "RemoveRegistryValues",
+        "Suppression des valeurs du registre",
+        Some("Clé : [1], Nom : [2]"),
+    ),
+    (
+        "RemoveShortcuts",
+        "Suppression des raccourcis",
+        Some("Raccourci : [1]"),
+    ),
+    ("Rollback", "Annulation de l'action :", Some("[1]")),
+    (
+        "RollbackCleanup",
+        "Suppression des fichiers de sauvegarde",
+        Some("Fichier : [1]"),
+    ),
+    (
+        "StartServices",
+        "Démarrage des services",
+        Some("Service : [1]"),
+    ),
+    ("StopServices", "Arrêt des services", Some("Service : [1]")),
+    (
+        "UnpublishFeatures",
+        "Annulation de la publication des fonctionnalités du produit",
+        Some("Fonctionnalité : [1]"),
+    ),
+    (
+        "UnregisterExtensionInfo",
+        "Désinscription des serveurs d'extensions",
+        Some("Extension : [1]"),
+    ),
+    (
+        "UnregisterMIMEInfo",
+        "Désinscription des types MIME",
+        Some("Type de contenu MIME : [1], Extension : [2]"),
+    ),
+    (
+        "UnregisterProgIdInfo",
+        "Désinscription des identificateurs de programme",
+        Some("ProgId : [1]"),
+    ),
+    (
+        "WriteEnvironmentStrings",
+        "Mise à jour des variables d'environnement",
+        Some("Nom : [1], Valeur : [2], Action [3]"),
+    ),
+    (
+        "WriteRegistryValues",
+        "Écriture des valeurs du registre",
+        Some("Clé : [1], Nom : [2], Valeur : [3]"),
+    ),
+];
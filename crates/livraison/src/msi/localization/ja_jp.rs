@@ -0,0 +1,320 @@
+use super::Strings;
+
+pub const STRINGS: Strings = Strings {
+    font: "Meiryo UI",
+    setup_title: "[ProductName] セットアップ",
+
+    text_action: "インストール",
+    text_agent: "インストーラー",
+    text_doing: "インストール中",
+    text_done: "インストール",
+
+    install: "インストール",
+    cancel: "キャンセル",
+    finish: "完了",
+    next: "次へ",
+    remove: "削除",
+    reset: "リセット",
+    change: "変更...",
+    ok: "OK",
+    up: "上へ",
+    new_folder: "新規",
+
+    welcome_title: "[ProductName] セットアップへようこそ",
+    welcome_description: "[ProductName] をこのコンピューターにインストールします。続行するには [インストール] を、終了するには [キャンセル] をクリックしてください。",
+    install_scope_title: "インストールの範囲",
+    install_scope_description: "[ProductName] をあなただけにインストールしますか、このコンピューターのすべてのユーザーにインストールしますか?",
+    per_user: "自分のみ",
+    per_machine: "すべてのユーザー",
+    install_dir_title: "インストール先フォルダー",
+    install_dir_label: "[ProductName] のインストール先:",
+    browse_look_in: "場所:",
+    customize_title: "カスタム セットアップ",
+    customize_description: "インストールする機能を選択してください。",
+    remove_title: "[ProductName] のアンインストール",
+    remove_description: "[ProductName] をこのコンピューターから削除します。続行するには [削除] を、終了するには [キャンセル] をクリックしてください。",
+    progress_title: "[ProductName] をインストールしています",
+    progress_description: "[ProductName] を[Text_done]しています。しばらくお待ちください。数分かかる場合があります。",
+    exit_title: "[ProductName] の[Text_action]が完了しました",
+    fatal_error_title: "[ProductName] の[Text_agent]が途中で終了しました",
+    fatal_error_description: "エラーのため [ProductName] の[Text_action]が終了しました。プログラムはインストールされていません。後でもう一度インストーラーを実行できます。",
+
+    requires_windows: "[ProductName] には {version} 以降が必要です。",
+    requires_64bit: "[ProductName] には 64 ビット版の Windows が必要です。",
+
+    errors: ERRORS,
+    action_texts: ACTION_TEXTS,
+};
+
+const ERRORS: &[(i32, &str)] = &[
+    (0, "{{致命的なエラー: }}"),
+    (1, "{{エラー [1]。}}"),
+    (2, "警告 [1]。"),
+    (4, "情報 [1]。"),
+    (
+        5,
+        "このパッケージのインストール中に予期しないエラーが発生しました。パッケージに問題がある可能性があります。エラー コードは [1] です。{{引数: [2]、[3]、[4]}}",
+    ),
+    (7, "{{ディスクがいっぱいです: }}"),
+    (16, "残り時間: {[1] 分 }{[2] 秒}"),
+    (
+        17,
+        "メモリが不足しています。他のアプリケーションを終了してから再試行してください。",
+    ),
+    (18, "インストーラーが応答していません。"),
+    (19, "インストーラーが途中で終了しました。"),
+    (
+        20,
+        "Windows が [ProductName] を構成しています。しばらくお待ちください",
+    ),
+    (21, "必要な情報を収集しています..."),
+    (
+        22,
+        "このアプリケーションの以前のバージョンを削除しています...",
+    ),
+    (
+        23,
+        "このアプリケーションの以前のバージョンの削除を準備しています...",
+    ),
+    (32, "{[ProductName] }セットアップは正常に完了しました。"),
+    (33, "{[ProductName] }セットアップに失敗しました。"),
+    (
+        1303,
+        "インストーラーにはこのフォルダーにアクセスする権限がありません: [2]。インストールを続行できません。管理者としてログオンするか、システム管理者に問い合わせてください。",
+    ),
+    (
+        1306,
+        "別のアプリケーションがファイル '[2]' を排他的に使用しています。他のアプリケーションをすべて終了してから [再試行] をクリックしてください。",
+    ),
+    (
+        1307,
+        "このファイルをインストールするためのディスク領域が不足しています: [2]。ディスク領域を空けて [再試行] をクリックするか、[キャンセル] をクリックして終了してください。",
+    ),
+    (
+        1308,
+        "ソース ファイルが見つかりません: [2]。ファイルが存在し、アクセスできることを確認してください。",
+    ),
+    (
+        1500,
+        "別のインストールが実行中です。このインストールを続行する前に、そのインストールを完了してください。",
+    ),
+    (
+        1601,
+        "ディスク領域が不足しています -- ボリューム: '[2]'、必要な領域: [3] KB、空き領域: [4] KB。ディスク領域を空けてから再試行してください。",
+    ),
+    (1602, "キャンセルしてもよろしいですか?"),
+    (
+        1603,
+        "ファイル [2][3] は使用中です{ (プロセス: 名前: [4]、ID: [5]、ウィンドウ タイトル: '[6]')}。そのアプリケーションを終了してから再試行してください。",
+    ),
+    (
+        1604,
+        "製品 '[2]' が既にインストールされているため、この製品をインストールできません。2 つの製品には互換性がありません。",
+    ),
+    (1707, "インストールは正常に完了しました。"),
+    (1708, "インストールに失敗しました。"),
+    (1709, "製品: [2] -- [3]"),
+    (1715, "[2] をインストールしました"),
+    (1716, "[2] を構成しました"),
+    (1717, "[2] を削除しました"),
+    (
+        1720,
+        "この Windows Installer パッケージには問題があります。インストールの完了に必要なスクリプトを実行できませんでした。サポート担当者またはパッケージの提供元に問い合わせてください。{{カスタム アクション [2] スクリプト エラー [3]、[4]: [5] 行 [6]、列 [7]、[8] }}",
+    ),
+    (
+        1721,
+        "この Windows Installer パッケージには問題があります。インストールの完了に必要なプログラムを実行できませんでした。サポート担当者またはパッケージの提供元に問い合わせてください。{{アクション: [2]、場所: [3]、コマンド: [4] }}",
+    ),
+    (
+        1722,
+        "この Windows Installer パッケージには問題があります。セットアップの一部として実行されたプログラムが正常に終了しませんでした。サポート担当者またはパッケージの提供元に問い合わせてください。{{アクション [2]、場所: [3]、コマンド: [4] }}",
+    ),
+    (
+        1723,
+        "この Windows Installer パッケージには問題があります。インストールの完了に必要な DLL を実行できませんでした。サポート担当者またはパッケージの提供元に問い合わせてください。{{アクション [2]、エントリ: [3]、ライブラリ: [4] }}",
+    ),
+    (1724, "削除は正常に完了しました。"),
+    (1725, "削除に失敗しました。"),
+    (
+        1730,
+        "このアプリケーションを削除するには管理者である必要があります。管理者としてログオンするか、サポート担当者に問い合わせてください。",
+    ),
+    (
+        1920,
+        "サービス '[2]' ([3]) を開始できませんでした。システム サービスを開始する権限があることを確認してください。",
+    ),
+    (
+        1923,
+        "サービス '[2]' ([3]) をインストールできませんでした。システム サービスをインストールする権限があることを確認してください。",
+    ),
+    (
+        1925,
+        "このコンピューターのすべてのユーザーに対してインストールを完了する権限がありません。管理者としてログオンしてから、インストールを再試行してください。",
+    ),
+];
+
+const ACTION_TEXTS: &[(&str, &str, Option<&str>)] = &[
+    (
+        "AppSearch",
+        "インストールされているアプリケーションを検索しています",
+        Some("プロパティ: [1]、シグネチャ: [2]"),
+    ),
+    ("CostFinalize", "必要なディスク領域を計算しています", None),
+    ("CostInitialize", "必要なディスク領域を計算しています", None),
+    (
+        "CreateFolders",
+        "フォルダーを作成しています",
+        Some("フォルダー: [1]"),
+    ),
+    (
+        "CreateShortcuts",
+        "ショートカットを作成しています",
+        Some("ショートカット: [1]"),
+    ),
+    (
+        "DeleteServices",
+        "サービスを削除しています",
+        Some("サービス: [1]"),
+    ),
+    ("FileCost", "必要なディスク領域を計算しています", None),
+    (
+        "FindRelatedProducts",
+        "関連するアプリケーションを検索しています",
+        Some("見つかったアプリケーション: [1]"),
+    ),
+    (
+        "GenerateScript",
+        "アクションのスクリプト操作を生成しています:",
+        Some("[1]"),
+    ),
+    (
+        "InstallFiles",
+        "新しいファイルをコピーしています",
+        Some("ファイル: [1]、フォルダー: [9]、サイズ: [6]"),
+    ),
+    (
+        "InstallServices",
+        "新しいサービスをインストールしています",
+        Some("サービス: [2]"),
+    ),
+    ("InstallValidate", "インストールを検証しています", None),
+    ("LaunchConditions", "起動条件を評価しています", None),
+    (
+        "MigrateFeatureStates",
+        "関連するアプリケーションから機能の状態を移行しています",
+        Some("アプリケーション: [1]"),
+    ),
+    (
+        "MsiConfigureServices",
+        "サービスを構成しています",
+        Some("サービス: [1]"),
+    ),
+    (
+        "ProcessComponents",
+        "コンポーネントの登録を更新しています",
+        None,
+    ),
+    (
+        "PublishFeatures",
+        "製品の機能を公開しています",
+        Some("機能: [1]"),
+    ),
+    ("PublishProduct", "製品情報を公開しています", None),
+    (
+        "RegisterExtensionInfo",
+        "拡張子サーバーを登録しています",
+        Some("拡張子: [1]"),
+    ),
+    (
+        "RegisterMIMEInfo",
+        "MIME 情報を登録しています",
+        Some("MIME コンテンツ タイプ: [1]、拡張子: [2]"),
+    ),
+    ("RegisterProduct", "製品を登録しています", Some("[1]")),
+    (
+        "RegisterProgIdInfo",
+        "プログラム識別子を登録しています",
+        Some("ProgId: [1]"),
+    ),
+    ("RegisterUser", "ユーザーを登録しています", Some("[1]")),
+    (
+        "RemoveEnvironmentStrings",
+        "環境変数を更新しています",
+        Some("名前: [1]、値: [2]、アクション [3]"),
+    ),
+    (
+        "RemoveExistingProducts",
+        "アプリケーションを削除しています",
+        Some("アプリケーション: [1]、コマンド ライン: [2]"),
+    ),
+    (
+        "RemoveFiles",
+        "ファイルを削除しています",
+        Some("ファイル: [1]、フォルダー: [9]"),
+    ),
+    (
+        "RemoveFolders",
+        "フォルダーを削除しています",
+        Some("フォルダー: [1]"),
+    ),
+    (
+        "RemoveRegistryValues",
+        "レジストリの値を削除しています",
+        Some("キー: [1]、名前: [2]"),
+    ),
+    (
+        "RemoveShortcuts",
+        "ショートカットを削除しています",
+        Some("ショートカット: [1]"),
+    ),
+    (
+        "Rollback",
+        "アクションをロールバックしています:",
+        Some("[1]"),
+    ),
+    (
+        "RollbackCleanup",
+        "バックアップ ファイルを削除しています",
+        Some("ファイル: [1]"),
+    ),
+    (
+        "StartServices",
+        "サービスを開始しています",
+        Some("サービス: [1]"),
+    ),
+    (
+        "StopServices",
+        "サービスを停止しています",
+        Some("サービス: [1]"),
+    ),
+    (
+        "UnpublishFeatures",
+        "製品の機能の公開を解除しています",
+        Some("機能: [1]"),
+    ),
+    (
+        "UnregisterExtensionInfo",
+        "拡張子サーバーの登録を解除しています",
+        Some("拡張子: [1]"),
+    ),
+    (
+        "UnregisterMIMEInfo",
+        "MIME 情報の登録を解除しています",
+        Some("MIME コンテンツ タイプ: [1]、拡張子: [2]"),
+    ),
+    (
+        "UnregisterProgIdInfo",
+        "プログラム識別子の登録を解除しています",
+        Some("ProgId: [1]"),
+    ),
+    (
+        "WriteEnvironmentStrings",
+        "環境変数を更新しています",
+        Some("名前: [1]、値: [2]、アクション [3]"),
+    ),
+    (
+        "WriteRegistryValues",
+        "レジストリの値を書き込んでいます",
+        Some("キー: [1]、名前: [2]、値: [3]"),
+    ),
+];
@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use msi::{CodePage, Language};
+
+use crate::LivraisonError;
+
+mod en_us;
+mod fr_fr;
+mod ja_jp;
+
+/// Language of the installer UI, each language gets its own package.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MsiLanguage {
+    #[default]
+    EnglishUS,
+    French,
+    Japanese,
+}
+
+impl MsiLanguage {
+    pub const ALL: &[MsiLanguage] = &[
+        MsiLanguage::EnglishUS,
+        MsiLanguage::French,
+        MsiLanguage::Japanese,
+    ];
+
+    /// RFC 5646 tag of the language, e.g. `fr-FR`
+    pub fn tag(self) -> &'static str {
+        match self {
+            MsiLanguage::EnglishUS => "en-US",
+            MsiLanguage::French => "fr-FR",
+            MsiLanguage::Japanese => "ja-JP",
+        }
+    }
+
+    pub fn language(self) -> Language {
+        Language::from_tag(self.tag())
+    }
+
+    /// ANSI code page of the language, Windows Installer doesn't support UTF-8 databases.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/code-page-handling-windows-installer-
+    pub fn codepage(self) -> CodePage {
+        match self {
+            MsiLanguage::EnglishUS | MsiLanguage::French => CodePage::Windows1252,
+            MsiLanguage::Japanese => CodePage::Windows932,
+        }
+    }
+
+    pub(crate) fn strings(self) -> &'static Strings {
+        match self {
+            MsiLanguage::EnglishUS => &en_us::STRINGS,
+            MsiLanguage::French => &fr_fr::STRINGS,
+            MsiLanguage::Japanese => &ja_jp::STRINGS,
+        }
+    }
+}
+
+impl FromStr for MsiLanguage {
+    type Err = LivraisonError;
+
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        MsiLanguage::ALL
+            .iter()
+            .find(|language| language.tag().eq_ignore_ascii_case(tag))
+            .copied()
+            .ok_or_else(|| {
+                LivraisonError::InvalidOption(format!("Unsupported installer language {tag}"))
+            })
+    }
+}
+
+/// Texts of the installer in one language. Texts are formatted by Windows Installer,
+/// they may contain properties, e.g. `[ProductName]`.
+#[derive(Debug)]
+pub struct Strings {
+    /// Font of the dialogs, it must have the glyphs of the language.
+    pub font: &'static str,
+    /// Title of the dialog windows
+    pub setup_title: &'static str,
+
+    // Values of the Text_* properties used in the dialogs
+    pub text_action: &'static str,
+    pub text_agent: &'static str,
+    pub text_doing: &'static str,
+    pub text_done: &'static str,
+
+    // Buttons
+    pub install: &'static str,
+    pub cancel: &'static str,
+    pub finish: &'static str,
+    pub next: &'static str,
+    pub remove: &'static str,
+    pub reset: &'static str,
+    pub change: &'static str,
+    pub ok: &'static str,
+    pub up: &'static str,
+    pub new_folder: &'static str,
+
+    pub welcome_title: &'static str,
+    pub welcome_description: &'static str,
+    pub install_scope_title: &'static str,
+    pub install_scope_description: &'static str,
+    pub per_user: &'static str,
+    pub per_machine: &'static str,
+    pub install_dir_title: &'static str,
+    pub install_dir_label: &'static str,
+    pub browse_look_in: &'static str,
+    pub customize_title: &'static str,
+    pub customize_description: &'static str,
+    pub remove_title: &'static str,
+    pub remove_description: &'static str,
+    pub progress_title: &'static str,
+    pub progress_description: &'static str,
+    pub exit_title: &'static str,
+    pub fatal_error_title: &'static str,
+    pub fatal_error_description: &'static str,
+
+    // Messages of the launch conditions
+    /// `{version}` is replaced by the name of the Windows version.
+    pub requires_windows: &'static str,
+    pub requires_64bit: &'static str,
+
+    /// Translations of the standard messages of the Error table, missing ones stay in English.
+    pub errors: &'static [(i32, &'static str)],
+    /// Translations of the description and template of the standard actions.
+    pub action_texts: &'static [(&'static str, &'static str, Option<&'static str>)],
+}
+
+impl Strings {
+    pub fn requires_windows(&self, version: &str) -> String {
+        self.requires_windows.replace("{version}", version)
+    }
+}
@@ -2,7 +2,9 @@ mod context;
 mod dialogs;
 mod features;
 mod livraison_packer;
+mod localization;
 pub mod packer;
+pub mod patch;
 
 pub use context::Context;
 pub use livraison_packer::MsiLivraisonPacker;
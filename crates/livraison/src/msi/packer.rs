@@ -7,22 +7,46 @@ use std::{
 };
 
 use crate::{
-    LivraisonResult,
-    msi::features::environment_vars::{
-        EnvironmentAction, EnvironmentActionKind, register_environment_vars,
+    LivraisonError, LivraisonResult,
+    common::Version,
+    msi::features::{
+        associations::register_file_associations,
+        environment_vars::{EnvironmentAction, EnvironmentActionKind, register_environment_vars},
+        feature_tree::{feature_rows, has_optional_features, validate_feature_ids},
+        key_path::registry_key_path,
+        launch_conditions::register_launch_conditions,
+        registry::register_registry_keys,
+        services::register_services,
     },
 };
-use msi::Language;
+
+pub use super::features::associations::{FileAssociationOptions, UrlProtocolOptions};
+pub use super::features::feature_tree::{FeatureOptions, FeatureState};
+pub use super::features::launch_conditions::{
+    LaunchConditionOptions, LaunchConditionsOptions, PrerequisiteOptions, PrerequisiteSearch,
+    WindowsVersion,
+};
+pub use super::features::registry::{RegistryKeyOptions, RegistryValue, RegistryValueOptions};
+pub use super::features::services::{
+    ServiceAccount, ServiceFailureAction, ServiceOptions, ServiceStart,
+};
+pub use super::localization::MsiLanguage;
+pub use msi_installer::tables::RegistryRoot;
 use msi_installer::{
-    PropertiesBuilder, RequiredProperties,
+    InstallScope, MergeModule, PropertiesBuilder, RequiredProperties,
+    identifier::{identifier, unique_identifier},
+    sequence::{self, SequenceTable},
     tables::{
-        Binary, Component, ComponentAttributes, Directory, Entity, FeatureComponents, File,
-        FileAttributes,
+        ActionText, Binary, Component, ComponentAttributes, Directory, Entity, ErrorMessage,
+        Feature, FeatureAttributes, FeatureComponents, File, FileAttributes, Icon, Media, Registry,
     },
 };
 use uuid::Uuid;
 
-use super::{Context, dialogs::minimalist};
+use super::{
+    Context,
+    dialogs::{SHOW_CUSTOMIZE_DLG, SHOW_INSTALLDIR_DLG, SHOW_INSTALLSCOPE_DLG, minimalist},
+};
 
 // Namespace to construct uuid v5
 const UUID_NAMESPACE: Uuid = uuid::uuid!("3941a426-8f68-469a-a7c5-99944d6067d8");
@@ -34,9 +58,15 @@ const CABINET_MAX_FILES: usize = 1000;
 // The maximum number of data bytes we'll put in one cabinet:
 const CABINET_MAX_SIZE: u64 = 0x1000_0000;
 
-// The name of the installer package's sole Feature:
+// The name of the installer package's root Feature, parent of the declared features:
 const MAIN_FEATURE_NAME: &str = "MainFeature";
 
+// Name of the Icon table entry of the product icon, without extension:
+const PRODUCT_ICON_NAME: &str = "ProductIcon";
+
+// Directory key of $LOCALAPPDATA/Programs where per user packages are installed:
+const LOCAL_PROGRAMS_FOLDER: &str = "LocalProgramsFolder";
+
 #[derive(Default, Clone, Debug)]
 pub struct MsiInstallerOptions {
     /// Unique name that should never change to generate the same UUID
@@ -45,7 +75,7 @@ pub struct MsiInstallerOptions {
     /// Application name
     pub name: String,
 
-    /// Application version
+    /// Application version, a semantic version which fits in a MSI ProductVersion
     pub version: String,
 
     /// Unique name that should never change to generate the same UUID
@@ -54,15 +84,99 @@ pub struct MsiInstallerOptions {
     /// Author name
     pub author: String,
 
-    /// Icon path
+    /// Icon path, shown in Add or Remove Programs and used by the file associations.
     pub icon: Option<String>,
     pub binaries: Option<Vec<BinaryFile>>,
+
+    /// Home page of the product, linked in Add or Remove Programs.
+    pub homepage: Option<String>,
+
+    /// Technical support page, linked in Add or Remove Programs.
+    pub support_url: Option<String>,
+
+    /// Hide the Repair button in Add or Remove Programs.
+    pub no_repair: bool,
+
+    /// Features the user can select in the customize dialog, children of the product feature.
+    /// Binaries not assigned to a feature and the other components are part of the product feature.
+    pub features: Vec<FeatureOptions>,
+
+    /// Let the user pick the install location in a dialog after the welcome dialog.
+    pub install_dir_dialog: bool,
+
+    /// Install for the current user, for all users or let the user choose.
+    pub scope: InstallScope,
+
+    /// Windows services to register. Services can only be installed per machine.
+    pub services: Vec<ServiceOptions>,
+
+    /// Registry keys written on install and removed on uninstall.
+    pub registry: Vec<RegistryKeyOptions>,
+
+    /// File extensions opened by one of the binaries.
+    pub file_associations: Vec<FileAssociationOptions>,
+
+    /// URL schemes, e.g. `foo://`, opened by one of the binaries.
+    pub url_protocols: Vec<UrlProtocolOptions>,
+
+    /// Requirements checked before installing: Windows version, 64-bit OS, prerequisites...
+    pub launch_conditions: LaunchConditionsOptions,
+
+    /// Language of the dialogs and messages, it also sets the code page of the package.
+    pub language: MsiLanguage,
+
+    /// Sign the package with Authenticode once it is written.
+    pub signing: Option<MsiSigningOptions>,
+
+    /// Merge modules (.msm) merged into the package, e.g. the VC++ runtime.
+    pub merge_modules: Vec<MergeModuleOptions>,
 }
 
 #[derive(Default, Clone, Debug)]
 pub struct BinaryFile {
     pub name: String,
     pub path: PathBuf,
+    /// Id of the feature installing the file, the product feature when `None`.
+    pub feature: Option<String>,
+}
+
+/// Certificate and private key files used to sign the package.
+#[derive(Default, Clone, Debug)]
+pub struct MsiSigningOptions {
+    /// PEM or DER certificate of the signer. A PEM file may list intermediate certificates after it.
+    pub certificate: PathBuf,
+    /// PEM or DER file with the unencrypted RSA private key of the signer, in PKCS#8 or PKCS#1 format.
+    pub private_key: PathBuf,
+}
+
+/// A merge module and where its components are installed.
+#[derive(Default, Clone, Debug)]
+pub struct MergeModuleOptions {
+    pub path: PathBuf,
+    /// Id of the feature installing the module's components, the product feature when `None`.
+    pub feature: Option<String>,
+    /// Directory key the module's TARGETDIR is redirected to, the install dir when `None`.
+    pub directory: Option<String>,
+}
+
+impl MsiSigningOptions {
+    fn load(&self) -> LivraisonResult<(Vec<msi::Certificate>, msi::PrivateKey)> {
+        let chain = msi::Certificate::load_chain(&self.certificate).map_err(|err| {
+            LivraisonError::InvalidOption(format!(
+                "Cannot load signing certificate {}: {}",
+                self.certificate.display(),
+                err
+            ))
+        })?;
+        let key = msi::PrivateKey::load(&self.private_key).map_err(|err| {
+            LivraisonError::InvalidOption(format!(
+                "Cannot load signing key {}: {}",
+                self.private_key.display(),
+                err
+            ))
+        })?;
+        Ok((chain, key))
+    }
 }
 
 pub struct MsiInstallerPacker<W: Read + Write + Seek> {
@@ -85,11 +199,39 @@ pub fn pack(options: MsiInstallerOptions, dest: &Path) -> LivraisonResult<()> {
     Ok(())
 }
 
+/// Create one package per language, named after `dest` with the tag of the language,
+/// e.g. `foo.fr-FR.msi`. Returns the paths of the packages.
+pub fn pack_languages(
+    options: MsiInstallerOptions,
+    languages: &[MsiLanguage],
+    dest: &Path,
+) -> LivraisonResult<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for language in languages {
+        let extension = match dest.extension() {
+            Some(extension) => format!("{}.{}", language.tag(), extension.to_string_lossy()),
+            None => language.tag().to_string(),
+        };
+        let path = dest.with_extension(extension);
+        pack(
+            MsiInstallerOptions {
+                language: *language,
+                ..options.clone()
+            },
+            &path,
+        )?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
 impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
-    pub fn new(out: W, options: MsiInstallerOptions) -> LivraisonResult<MsiInstallerPacker<W>> {
+    pub fn new(out: W, mut options: MsiInstallerOptions) -> LivraisonResult<MsiInstallerPacker<W>> {
+        options.version = Version::parse(&options.version)?.to_msi()?;
         let package = msi::Package::create(msi::PackageType::Installer, out)?;
         let product_code = compute_product_code(&options.bundle_name, &options.version);
         let upgrade_code = compute_upgrade_code(&options.bundle_name);
+        let scope = options.scope;
 
         Ok(MsiInstallerPacker {
             package,
@@ -97,35 +239,140 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
             context: Context {
                 product_code,
                 upgrade_code,
+                scope,
             },
         })
     }
 
     pub fn write(&mut self) -> LivraisonResult<()> {
+        // Dual purpose packages can be installed per user from the scope dialog, without the
+        // rights to install services.
+        if !self.options.services.is_empty() && self.context.scope != InstallScope::PerMachine {
+            return Err(LivraisonError::InvalidOption(
+                "Services require a per machine install scope".to_string(),
+            ));
+        }
+        validate_feature_ids(&self.options.features)?;
+        // Load the signing files first, so a bad path fails before doing any work:
+        let signer = self
+            .options
+            .signing
+            .as_ref()
+            .map(MsiSigningOptions::load)
+            .transpose()?;
+        let mut modules = self
+            .options
+            .merge_modules
+            .iter()
+            .map(|options| {
+                let module = fs::File::open(&options.path).and_then(MergeModule::open);
+                module.map_err(|err| merge_module_error(options, err))
+            })
+            .collect::<LivraisonResult<Vec<_>>>()?;
         self.set_summary_info();
         self.package.flush()?;
         self.create_property_table()?;
+        self.create_message_tables()?;
 
         // Copy resource files into package:
         let mut resources = self.collect_resource_info()?;
         let directories = self.collect_directory_info(&mut resources)?;
-        let cabinets = self.divide_resources_into_cabinets(resources);
+        let mut cabinets = self.divide_resources_into_cabinets(resources);
+        // The files of the merge modules are numbered after the resources, so they go in the last cabinet:
+        let mut module_files = Vec::new();
+        for (module, options) in modules.iter_mut().zip(self.options.merge_modules.iter()) {
+            let files = module
+                .read_files()
+                .map_err(|err| merge_module_error(options, err))?;
+            module_files.extend(files.into_iter().map(|(file, data)| ModuleFileInfo {
+                file_key: file.file,
+                data,
+            }));
+        }
+        if !module_files.is_empty() {
+            if cabinets.is_empty() {
+                cabinets.push(CabinetInfo {
+                    name: "cab1.cab".to_string(),
+                    resources: Vec::new(),
+                    module_files: Vec::new(),
+                });
+            }
+            cabinets.last_mut().unwrap().module_files = module_files;
+        }
         self.generate_resource_cabinets(&cabinets)?;
 
         FeatureComponents::create_table(&mut self.package)?;
         Binary::create_table(&mut self.package)?;
+        Registry::create_table(&mut self.package)?;
+        Icon::create_table(&mut self.package)?;
 
         self.add_binary_data("ClassicImage", include_bytes!("./assets/classic_bg.jpg"))?;
+        if let (Some(path), Some(icon)) = (&self.options.icon, self.product_icon()) {
+            let data = fs::read(path)?;
+            self.add_icon(&icon, &data)?;
+        }
         // Set up installer database tables:
         self.create_directory_table(&directories)?;
-        self.create_feature_table()?;
-        self.create_component_table(&directories)?;
-        self.create_feature_components_table(&directories)?;
+        self.create_feature_table(&cabinets)?;
+        self.create_component_table(&cabinets)?;
+        self.create_feature_components_table(&cabinets)?;
+        let mut registry_keys = self.options.registry.clone();
+        for protocol in self.options.url_protocols.iter() {
+            let (file_key, feature) = binary_component_and_feature(&cabinets, &protocol.binary)
+                .ok_or_else(|| {
+                    LivraisonError::InvalidOption(format!(
+                        "URL protocol {} uses the unknown binary {}",
+                        protocol.scheme, protocol.binary
+                    ))
+                })?;
+            registry_keys.extend(protocol.registry_keys(&file_key, &feature));
+        }
+        // Per user associations are written in the registry, see `FileAssociationOptions::registry_keys`
+        let per_user_associations = self.context.scope == InstallScope::PerUser;
+        if per_user_associations {
+            for association in self.options.file_associations.iter() {
+                let (file_key, feature) =
+                    binary_component_and_feature(&cabinets, &association.binary).ok_or_else(
+                        || {
+                            LivraisonError::InvalidOption(format!(
+                                "File association .{} uses the unknown binary {}",
+                                association.extension.trim_start_matches('.'),
+                                association.binary
+                            ))
+                        },
+                    )?;
+                registry_keys.extend(association.registry_keys(&file_key, &feature));
+            }
+        }
+        register_registry_keys(
+            &mut self.package,
+            &self.context,
+            MAIN_FEATURE_NAME,
+            &registry_keys,
+        )?;
         self.create_media_table(&cabinets)?;
         self.create_file_table(&cabinets)?;
-        self.create_install_execute_sequence_table(&cabinets)?;
-        minimalist::create().insert(&mut self.package)?;
-
+        if !self.options.services.is_empty() {
+            register_services(&mut self.package, &self.options.services, |binary| {
+                binary_component_and_feature(&cabinets, binary).map(|(component, _)| component)
+            })?;
+        }
+        if !self.options.file_associations.is_empty() && !per_user_associations {
+            let icon = self.product_icon();
+            register_file_associations(
+                &mut self.package,
+                &self.options.file_associations,
+                icon.as_deref(),
+                |binary| binary_component_and_feature(&cabinets, binary),
+            )?;
+        }
+        if !self.options.launch_conditions.is_empty() {
+            register_launch_conditions(
+                &mut self.package,
+                &self.options.launch_conditions,
+                self.options.language.strings(),
+            )?;
+        }
         register_environment_vars(
             &mut self.package,
             &self.context,
@@ -145,47 +392,140 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
             ],
         )?;
 
+        // Once all the tables are created, the standard actions are only scheduled when their tables exist
+        minimalist::create(self.options.language.strings()).insert(&mut self.package)?;
+        for table in [
+            SequenceTable::InstallExecute,
+            SequenceTable::AdminExecute,
+            SequenceTable::AdvtExecute,
+        ] {
+            sequence::new(table).insert(&mut self.package)?;
+        }
+        // Merged last, so the actions of the modules are scheduled relative to the standard actions:
+        let mut first_sequence = cabinets
+            .iter()
+            .map(|cabinet| cabinet.resources.len() as i32)
+            .sum::<i32>()
+            + 1;
+        for (module, options) in modules.iter_mut().zip(self.options.merge_modules.iter()) {
+            let feature = options.feature.as_deref().unwrap_or(MAIN_FEATURE_NAME);
+            let directory = options.directory.as_deref().unwrap_or("INSTALLDIR");
+            module
+                .merge_into(&mut self.package, feature, directory, first_sequence)
+                .map_err(|err| merge_module_error(options, err))?;
+            first_sequence += module
+                .files()
+                .map_err(|err| merge_module_error(options, err))?
+                .len() as i32;
+        }
+
         self.package.flush()?;
+        if let Some((chain, key)) = signer {
+            self.package.add_digital_signature(&chain, &key)?;
+            self.package.flush()?;
+        }
         Ok(())
     }
 
     // Populates the summary metadata for the package from the bundle settings.
     fn set_summary_info(&mut self) {
+        let language = self.options.language;
+        self.package.set_database_codepage(language.codepage());
         let summary_info = self.package.summary_info_mut();
-        summary_info.set_codepage(msi::CodePage::Iso88591);
+        summary_info.set_codepage(language.codepage());
         summary_info.set_creation_time_to_now();
         summary_info.set_arch("x64");
-        summary_info.set_languages(&[msi::Language::from_tag("en-US")]);
+        summary_info.set_languages(&[language.language()]);
         summary_info.set_subject(&self.options.name);
         summary_info.set_uuid(self.context.product_code);
         summary_info.set_comments(&self.options.description);
         summary_info.set_author(&self.options.author);
         summary_info.set_creating_application("livraison".to_string());
-        summary_info.set_word_count(10);
+        summary_info.set_word_count(match self.context.scope {
+            // Compressed files, elevated privileges not required
+            InstallScope::PerUser => 10,
+            // Compressed files
+            InstallScope::PerMachine | InstallScope::DualPurpose => 2,
+        });
     }
 
     // Creates and populates the `Property` database table for the package.
     fn create_property_table(&mut self) -> LivraisonResult<()> {
-        PropertiesBuilder::new(RequiredProperties {
+        let strings = self.options.language.strings();
+        let mut properties = PropertiesBuilder::new(RequiredProperties {
             product_code: self.context.product_code,
-            product_language: Language::from_tag("en-US"),
+            product_language: self.options.language.language(),
             manufacturer: self.options.author.clone(),
             product_name: self.options.name.clone(),
             product_version: self.options.version.clone(),
-        })
-        .upgrade_code(&self.context.upgrade_code)
-        .install_per_user()
-        .default_ui_font("DefaultFont")
-        .insert("Mode", "Install")
-        .insert("Text_action", "installation")
-        .insert("Text_agent", "installer")
-        .insert("Text_Doing", "installing")
-        .insert("Text_done", "installed")
-        .create_table(&mut self.package)?;
+        });
+        properties
+            .upgrade_code(&self.context.upgrade_code)
+            .install_scope(self.context.scope)
+            .default_ui_font("DefaultFont")
+            .insert("Mode", "Install")
+            .insert("Text_action", strings.text_action)
+            .insert("Text_agent", strings.text_agent)
+            .insert("Text_Doing", strings.text_doing)
+            .insert("Text_done", strings.text_done);
+
+        if let Some(icon) = self.product_icon() {
+            properties.arp_product_icon(&icon);
+        }
+        if let Some(homepage) = &self.options.homepage {
+            properties.arp_url_info_about(homepage);
+        }
+        if let Some(support_url) = &self.options.support_url {
+            properties.arp_help_link(support_url);
+        }
+        if !self.options.author.is_empty() {
+            properties.arp_contact(&self.options.author);
+        }
+        if !self.options.description.is_empty() {
+            properties.arp_comments(&self.options.description);
+        }
+        properties.arp_no_repair(self.options.no_repair);
+        if self.options.install_dir_dialog {
+            properties.insert(SHOW_INSTALLDIR_DLG, "1");
+        }
+        if self.context.scope == InstallScope::DualPurpose {
+            properties.insert(SHOW_INSTALLSCOPE_DLG, "1");
+        }
+        if has_optional_features(&self.options.features) {
+            properties.insert(SHOW_CUSTOMIZE_DLG, "1");
+        }
+        properties.create_table(&mut self.package)?;
 
         Ok(())
     }
 
+    // Creates and populates the `Error` and `ActionText` database tables with the
+    // standard messages, translated in the language of the package when available.
+    fn create_message_tables(&mut self) -> LivraisonResult<()> {
+        let strings = self.options.language.strings();
+        let mut errors = ErrorMessage::standard();
+        for (error, message) in strings.errors.iter() {
+            match errors.iter_mut().find(|row| row.error == *error) {
+                Some(row) => row.message = Some(message.to_string()),
+                None => errors.push(ErrorMessage::new(*error, message)),
+            }
+        }
+        let mut action_texts = ActionText::standard();
+        for (action, description, template) in strings.action_texts.iter() {
+            let text = ActionText::new(action, description, *template);
+            match action_texts.iter_mut().find(|row| row.action == *action) {
+                Some(row) => *row = text,
+                None => action_texts.push(text),
+            }
+        }
+
+        ErrorMessage::create_table(&mut self.package)?;
+        ErrorMessage::insert(&mut self.package, &errors)?;
+        ActionText::create_table(&mut self.package)?;
+        ActionText::insert(&mut self.package, &action_texts)?;
+        Ok(())
+    }
+
     /// Add a binary file to use in the installer.
     fn add_binary_data(&mut self, name: &str, data: &[u8]) -> LivraisonResult<()> {
         let row = Binary {
@@ -198,6 +538,29 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
         Ok(())
     }
 
+    /// Add an icon to use in shortcuts, file associations or Add or Remove Programs.
+    fn add_icon(&mut self, name: &str, data: &[u8]) -> LivraisonResult<()> {
+        let row = Icon {
+            name: name.to_string(),
+        };
+
+        let writer = self.package.write_stream(&row.stream_name());
+        writer?.write_all(data)?;
+        Icon::insert(&mut self.package, &[row])?;
+        Ok(())
+    }
+
+    // Returns the Icon table key of the product icon. The key keeps the extension of the
+    // icon file as Windows uses it to find out the type of the icon.
+    fn product_icon(&self) -> Option<String> {
+        let icon = Path::new(self.options.icon.as_ref()?);
+        let extension = icon
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or("ico".to_string());
+        Some(format!("{PRODUCT_ICON_NAME}.{extension}"))
+    }
+
     // Returns a list of `ResourceInfo` structs for the binary executable and all
     // the resource files that should be included in the package.
     fn collect_resource_info(&self) -> LivraisonResult<Vec<ResourceInfo>> {
@@ -208,9 +571,18 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
                 resources.push(ResourceInfo {
                     source_path: PathBuf::from(&binary.path),
                     dest_path: PathBuf::from(&binary.name),
-                    filename: binary.name.clone(),
+                    filename: Path::new(&binary.name)
+                        .file_name()
+                        .map_or(binary.name.clone(), |name| {
+                            name.to_string_lossy().to_string()
+                        }),
                     size: metadata.len(),
                     component_key: String::new(),
+                    directory_key: String::new(),
+                    feature: binary
+                        .feature
+                        .clone()
+                        .unwrap_or(MAIN_FEATURE_NAME.to_string()),
                 });
             }
         }
@@ -228,6 +600,8 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
         //         filename,
         //         size,
         //         component_key: String::new(),
+        //         directory_key: String::new(),
+        //         feature: MAIN_FEATURE_NAME.to_string(),
         //     };
         //     resources.push(info);
         // }
@@ -236,9 +610,11 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
 
     // Based on the list of all resource files to be bundled, returns a list of
     // all the directories that need to be created during installation.  Also,
-    // modifies each `ResourceInfo` object to populate its `component_key` field
-    // with the database key of the Component that the resource will be associated
-    // with.
+    // modifies each `ResourceInfo` object to populate its `component_key` and
+    // `directory_key` fields with the database keys of the Component that the
+    // resource will be associated with and of its Directory.  Component keys
+    // are derived from the install path alone so files with the same name in
+    // different directories don't collide.
     fn collect_directory_info(
         &self,
         resources: &mut [ResourceInfo],
@@ -249,11 +625,11 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
             PathBuf::new(),
             DirectoryInfo {
                 key: "INSTALLDIR".to_string(),
-                parent_key: "ProgramFilesFolder".to_string(),
+                parent_key: self.install_dir_parent().to_string(),
                 name: self.options.name.to_string(),
-                files: Vec::new(),
             },
         );
+        let mut install_paths = HashSet::<String>::new();
         for resource in resources.iter_mut() {
             let mut dir_key = "INSTALLDIR".to_string();
             let mut dir_path = PathBuf::new();
@@ -270,7 +646,6 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
                                 key: new_key.clone(),
                                 parent_key: dir_key.clone(),
                                 name: name.to_string_lossy().to_string(),
-                                files: Vec::new(),
                             },
                         );
                         dir_key = new_key;
@@ -278,39 +653,47 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
                     }
                 }
             }
-            let directory = dir_map.get_mut(&dir_path).unwrap();
-            debug_assert_eq!(directory.key, dir_key);
-            directory.files.push(resource.filename.clone());
-            resource.component_key = resource.filename.to_string();
+            debug_assert_eq!(dir_map.get(&dir_path).unwrap().key, dir_key);
+            let install_path = install_path(&resource.dest_path);
+            // Paths are case insensitive on Windows, and component GUIDs are
+            // derived from the lowercased path
+            if !install_paths.insert(install_path.to_lowercase()) {
+                return Err(LivraisonError::InvalidOption(format!(
+                    "Multiple files are installed to {install_path} (paths are case insensitive on Windows)"
+                )));
+            }
+            resource.component_key = component_key(&install_path);
+            resource.directory_key = dir_key;
         }
         Ok(dir_map.into_values().collect())
     }
 
     // Divides up the list of resource into some number of cabinets, subject to a
     // few constraints: 1) no one cabinet will have two resources with the same
-    // filename, 2) no one cabinet will have more than `CABINET_MAX_FILES` files
+    // file key, 2) no one cabinet will have more than `CABINET_MAX_FILES` files
     // in it, and 3) no one cabinet will contain more than `CABINET_MAX_SIZE`
     // bytes of data (unless that cabinet consists of a single file that is
     // already bigger than that).
     fn divide_resources_into_cabinets(&self, mut resources: Vec<ResourceInfo>) -> Vec<CabinetInfo> {
         let mut cabinets = Vec::new();
         while !resources.is_empty() {
-            let mut filenames = HashSet::<String>::new();
+            let mut file_keys = HashSet::<String>::new();
             let mut total_size = 0;
             let mut leftovers = Vec::<ResourceInfo>::new();
             let mut cabinet = CabinetInfo {
                 name: format!("cab{}.cab", cabinets.len() + 1),
                 resources: Vec::new(),
+                module_files: Vec::new(),
             };
             for resource in resources.into_iter() {
                 if cabinet.resources.len() >= CABINET_MAX_FILES
                     || (!cabinet.resources.is_empty()
                         && total_size + resource.size > CABINET_MAX_SIZE)
-                    || filenames.contains(&resource.filename)
+                    || file_keys.contains(&resource.component_key)
                 {
                     leftovers.push(resource);
                 } else {
-                    filenames.insert(resource.filename.clone());
+                    file_keys.insert(resource.component_key.clone());
                     total_size += resource.size;
                     cabinet.resources.push(resource);
                 }
@@ -336,15 +719,28 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
                 {
                     let resource = &cabinet_info.resources[resource_index];
                     folder_size += resource.size;
-                    folder.add_file(resource.filename.as_str());
-                    debug_assert!(!file_map.contains_key(&resource.filename));
-                    file_map.insert(resource.filename.clone(), &resource.source_path);
+                    // Files are looked up in the cabinets by their key in the File table
+                    folder.add_file(resource.component_key.as_str());
+                    debug_assert!(!file_map.contains_key(&resource.component_key));
+                    file_map.insert(resource.component_key.clone(), &resource.source_path);
                     resource_index += 1;
                 }
             }
+            let mut module_data = HashMap::<&str, &[u8]>::new();
+            if !cabinet_info.module_files.is_empty() {
+                let folder = builder.add_folder(cab::CompressionType::MsZip);
+                for module_file in cabinet_info.module_files.iter() {
+                    folder.add_file(module_file.file_key.as_str());
+                    module_data.insert(module_file.file_key.as_str(), &module_file.data);
+                }
+            }
             let stream = self.package.write_stream(cabinet_info.name.as_str())?;
             let mut cabinet_writer = builder.build(stream)?;
             while let Some(mut file_writer) = cabinet_writer.next_file()? {
+                if let Some(data) = module_data.get(file_writer.file_name()) {
+                    file_writer.write_all(data)?;
+                    continue;
+                }
                 debug_assert!(file_map.contains_key(file_writer.file_name()));
                 let file_path = file_map.get(file_writer.file_name()).unwrap();
                 let mut file = fs::File::open(file_path)?;
@@ -359,18 +755,34 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
     fn create_directory_table(&mut self, user_dirs: &[DirectoryInfo]) -> LivraisonResult<()> {
         Directory::create_table(&mut self.package)?;
 
-        let mut dirs = vec![
-            Directory {
-                directory: "TARGETDIR".to_string(),
-                parent: None,
-                default_dir: "SourceDir".to_string(),
-            },
-            Directory {
-                directory: "ProgramFilesFolder".to_string(),
-                parent: Some("TARGETDIR".to_string()),
-                default_dir: "Program Files".to_string(),
-            },
-        ];
+        let mut dirs = vec![Directory {
+            directory: "TARGETDIR".to_string(),
+            parent: None,
+            default_dir: "SourceDir".to_string(),
+        }];
+        match self.context.scope {
+            InstallScope::PerUser => {
+                dirs.push(Directory {
+                    directory: "LocalAppDataFolder".to_string(),
+                    parent: Some("TARGETDIR".to_string()),
+                    default_dir: "AppData".to_string(),
+                });
+                dirs.push(Directory {
+                    directory: LOCAL_PROGRAMS_FOLDER.to_string(),
+                    parent: Some("LocalAppDataFolder".to_string()),
+                    default_dir: "Programs".to_string(),
+                });
+            }
+            // Dual purpose packages get ProgramFiles64Folder redirected to $LOCALAPPDATA/Programs
+            // by Windows Installer when installed per user.
+            InstallScope::PerMachine | InstallScope::DualPurpose => {
+                dirs.push(Directory {
+                    directory: "ProgramFiles64Folder".to_string(),
+                    parent: Some("TARGETDIR".to_string()),
+                    default_dir: "PFiles64".to_string(),
+                });
+            }
+        }
 
         for dir in user_dirs.iter() {
             dirs.push(Directory {
@@ -385,129 +797,128 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
         Ok(())
     }
 
-    // Creates and populates the `Feature` database table for the package.  The
-    // package will have a single main feature that installs everything.
-    fn create_feature_table(&mut self) -> LivraisonResult<()> {
-        self.package.create_table(
-            "Feature",
-            vec![
-                msi::Column::build("Feature").primary_key().id_string(38),
-                msi::Column::build("Feature_Parent")
-                    .nullable()
-                    .foreign_key("Feature", 1)
-                    .id_string(38),
-                msi::Column::build("Title").nullable().text_string(64),
-                msi::Column::build("Description")
-                    .nullable()
-                    .text_string(255),
-                msi::Column::build("Display")
-                    .nullable()
-                    .range(0, 0x7fff)
-                    .int16(),
-                msi::Column::build("Level").range(0, 0x7fff).int16(),
-                msi::Column::build("Directory_")
-                    .nullable()
-                    .foreign_key("Directory", 1)
-                    .id_string(72),
-                msi::Column::build("Attributes").int16(),
-            ],
-        )?;
-        self.package
-            .insert_rows(msi::Insert::into("Feature").row(vec![
-                msi::Value::from(MAIN_FEATURE_NAME),
-                msi::Value::Null,
-                msi::Value::from(self.options.name.clone()),
-                msi::Value::Null,
-                msi::Value::Int(1),
-                msi::Value::Int(1),
-                msi::Value::from("INSTALLDIR"),
-                msi::Value::Int(24),
-            ]))?;
+    // Returns the key of the directory INSTALLDIR is created in.
+    fn install_dir_parent(&self) -> &'static str {
+        match self.context.scope {
+            InstallScope::PerUser => LOCAL_PROGRAMS_FOLDER,
+            InstallScope::PerMachine | InstallScope::DualPurpose => "ProgramFiles64Folder",
+        }
+    }
+
+    // Creates and populates the `Feature` database table for the package. The
+    // package has a main feature that installs everything not assigned to one of
+    // the declared features, which are its children.
+    fn create_feature_table(&mut self, cabinets: &[CabinetInfo]) -> LivraisonResult<()> {
+        Feature::create_table(&mut self.package)?;
+        let mut rows = vec![Feature {
+            feature: MAIN_FEATURE_NAME.to_string(),
+            parent: None,
+            title: Some(self.options.name.clone()),
+            description: None,
+            display: Some(1),
+            level: 1,
+            directory: Some("INSTALLDIR".to_string()),
+            attributes: FeatureAttributes::DisallowAdvertise | FeatureAttributes::UIDisallowAbsent,
+        }];
+        rows.extend(feature_rows(
+            MAIN_FEATURE_NAME,
+            "INSTALLDIR",
+            &self.options.features,
+        )?);
+
+        for resource in cabinets.iter().flat_map(|cabinet| cabinet.resources.iter()) {
+            if !rows.iter().any(|row| row.feature == resource.feature) {
+                return Err(LivraisonError::InvalidOption(format!(
+                    "Binary {} is assigned to the unknown feature {}",
+                    resource.filename, resource.feature
+                )));
+            }
+        }
+        for key in self.options.registry.iter() {
+            if let Some(feature) = &key.feature
+                && !rows.iter().any(|row| &row.feature == feature)
+            {
+                return Err(LivraisonError::InvalidOption(format!(
+                    "Registry key {} is assigned to the unknown feature {}",
+                    key.key, feature
+                )));
+            }
+        }
+        Feature::insert(&mut self.package, &rows)?;
         Ok(())
     }
 
     // Creates and populates the `Component` database table for the package.  One
-    // component is created for each subdirectory under in the install dir.
-    fn create_component_table(&mut self, directories: &[DirectoryInfo]) -> LivraisonResult<()> {
+    // component is created for each file, its GUID is derived from the upgrade
+    // code and the install path so it stays the same across versions.
+    // Per user components use a registry value as key path as files in the user
+    // profile can't be used as key path (ICE38).
+    fn create_component_table(&mut self, cabinets: &[CabinetInfo]) -> LivraisonResult<()> {
         Component::create_table(&mut self.package)?;
         let mut rows = Vec::new();
-        for directory in directories.iter() {
-            for file in directory.files.iter() {
-                let uuid = Uuid::new_v5(&self.context.product_code, file.as_bytes());
-                rows.push(Component {
-                    component: file.clone(),
-                    id: Some(uuid),
-                    directory: directory.key.clone(),
-                    attributes: ComponentAttributes::empty(),
-                    condition: None,
-                    key_path: Some(file.clone()),
-                });
-            }
+        let mut registry_items = Vec::new();
+        for resource in cabinets.iter().flat_map(|cabinet| cabinet.resources.iter()) {
+            // Paths are case insensitive on Windows
+            let install_path = install_path(&resource.dest_path).to_lowercase();
+            let uuid = Uuid::new_v5(&self.context.upgrade_code, install_path.as_bytes());
+            let (attributes, key_path) = match self.context.scope {
+                InstallScope::PerUser => {
+                    let registry = registry_key_path(&self.context, &resource.component_key);
+                    let key = registry.registry.clone();
+                    registry_items.push(registry);
+                    (
+                        ComponentAttributes::Bit64 | ComponentAttributes::RegistryKeyPath,
+                        key,
+                    )
+                }
+                InstallScope::PerMachine | InstallScope::DualPurpose => {
+                    (ComponentAttributes::Bit64, resource.component_key.clone())
+                }
+            };
+            rows.push(Component {
+                component: resource.component_key.clone(),
+                id: Some(uuid),
+                directory: resource.directory_key.clone(),
+                attributes,
+                condition: None,
+                key_path: Some(key_path),
+            });
         }
         Component::insert(&mut self.package, &rows)?;
+        Registry::insert(&mut self.package, &registry_items)?;
         Ok(())
     }
 
     // Creates and populates the `FeatureComponents` database table for the
-    // package.  All components are added to the package's single main feature.
-    fn create_feature_components_table(
-        &mut self,
-        directories: &[DirectoryInfo],
-    ) -> LivraisonResult<()> {
+    // package.  Each file component is added to the feature of its file.
+    fn create_feature_components_table(&mut self, cabinets: &[CabinetInfo]) -> LivraisonResult<()> {
         let mut rows = Vec::new();
-        for directory in directories.iter() {
-            for file in directory.files.iter() {
-                rows.push(vec![
-                    msi::Value::from(MAIN_FEATURE_NAME),
-                    msi::Value::Str(file.clone()),
-                ]);
-            }
+        for resource in cabinets.iter().flat_map(|cabinet| cabinet.resources.iter()) {
+            rows.push(FeatureComponents {
+                feature: resource.feature.clone(),
+                component: resource.component_key.clone(),
+            });
         }
-        self.package
-            .insert_rows(msi::Insert::into("FeatureComponents").rows(rows))?;
+        FeatureComponents::insert(&mut self.package, &rows)?;
         Ok(())
     }
 
     // Creates and populates the `Media` database table for the package, with one
     // entry for each CAB archive within the package.
     fn create_media_table(&mut self, cabinets: &[CabinetInfo]) -> LivraisonResult<()> {
-        self.package.create_table(
-            "Media",
-            vec![
-                msi::Column::build("DiskId")
-                    .primary_key()
-                    .range(1, 0x7fff)
-                    .int16(),
-                msi::Column::build("LastSequence").range(0, 0x7fff).int16(),
-                msi::Column::build("DiskPrompt").nullable().text_string(64),
-                msi::Column::build("Cabinet")
-                    .nullable()
-                    .category(msi::Category::Cabinet)
-                    .string(255),
-                msi::Column::build("VolumeLabel").nullable().text_string(32),
-                msi::Column::build("Source")
-                    .nullable()
-                    .category(msi::Category::Property)
-                    .string(32),
-            ],
-        )?;
-        let mut disk_id: i32 = 0;
-        let mut last_seq: i32 = 0;
-        let mut rows = Vec::new();
-        for cabinet in cabinets.iter() {
-            disk_id += 1;
-            last_seq += cabinet.resources.len() as i32;
-            rows.push(vec![
-                msi::Value::Int(disk_id),
-                msi::Value::Int(last_seq),
-                msi::Value::Null,
-                msi::Value::Str(format!("#{}", cabinet.name)),
-                msi::Value::Null,
-                msi::Value::Null,
-            ]);
-        }
-        self.package
-            .insert_rows(msi::Insert::into("Media").rows(rows))?;
+        Media::create_table(&mut self.package)?;
+        let mut last_sequence: i32 = 0;
+        let mut rows: Vec<Media> = Vec::new();
+        for (index, cabinet) in cabinets.iter().enumerate() {
+            last_sequence += (cabinet.resources.len() + cabinet.module_files.len()) as i32;
+            rows.push(Media {
+                disk_id: index as i32 + 1,
+                last_sequence,
+                cabinet: Some(format!("#{}", cabinet.name)),
+                ..Default::default()
+            });
+        }
+        Media::insert(&mut self.package, &rows)?;
         Ok(())
     }
 
@@ -521,7 +932,7 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
         for cabinet in cabinets.iter() {
             for resource in cabinet.resources.iter() {
                 rows.push(File {
-                    file: resource.filename.clone(),
+                    file: resource.component_key.clone(),
                     component: resource.component_key.clone(),
                     filename: resource.filename.clone(),
                     size: resource.size as i32,
@@ -537,108 +948,49 @@ impl<W: Read + Write + Seek> MsiInstallerPacker<W> {
         File::insert(&mut self.package, &rows)?;
         Ok(())
     }
+}
 
-    fn create_install_execute_sequence_table(
-        &mut self,
-        _cabinets: &[CabinetInfo],
-    ) -> LivraisonResult<()> {
-        self.package.create_table(
-            "InstallExecuteSequence",
-            vec![
-                msi::Column::build("Action").primary_key().id_string(72),
-                msi::Column::build("Condition")
-                    .nullable()
-                    .category(msi::Category::Condition)
-                    .string(255),
-                msi::Column::build("Sequence")
-                    .nullable()
-                    .range(-4, 0x7fff)
-                    .int16(),
-            ],
-        )?;
-        let mut rows = Vec::new();
-        let actions: [(&str, &str, i32); 28] = [
-            //("LaunchConditions", "", 100), // Requires a LaunchCondition table
-            //("FindRelatedProducts", "", 200), // Requires an Upgrade table
-            //("AppSearch", "", 400), // Requires a Signature table
-            //("CCPSearch", "NOT Installed", 500), // Requires a Signature or *Locator table
-            //("RMCCPSearch", "NOT Installed", 600), // Requires the CCP_DRIVE property and a DrLocator table
-            ("ValidateProductID", "", 700),
-            ("CostInitialize", "", 800),
-            ("FileCost", "", 900),
-            ("CostFinalize", "", 1000),
-            ("SetODBCFolders", "", 1100),
-            //("MigrateFeatureStates", "", 1200),
-            ("InstallValidate", "", 1400),
-            ("InstallInitialize", "", 1500),
-            ("AllocateRegistrySpace", "NOT Installed", 1550),
-            ("ProcessComponents", "", 1600),
-            ("UnpublishComponents", "", 1700),
-            ("UnpublishFeatures", "", 1800),
-            //("StopServices", "VersionNT", 1900), // Requires a ServiceControl table
-            //("DeleteServices", "VersionNT", 2000), // Requires a ServiceControl table
-            ("UnregisterComPlus", "", 2100),
-            //("SelfUnregModules", "", 2200), // Requires a SelfReg table
-            //("UnregisterTypeLibraries", "", 2300), // Requires a TypeLib table
-            //("RemoveODBC", "", 2400), // Requires an ODBC* table
-            //("UnregisterFonts", "", 2500), // Requires a Font table
-            ("RemoveRegistryValues", "", 2600), // Requires a Registry table
-            //("UnregisterClassInfo", "", 2700), // Requires a Class table
-            //("UnregisterExtensionInfo", "", 2800), // Requires an Extension table
-            //("UnregisterProgIdInfo", "", 2900), // Requires ProgId, Extension or Class table
-            //("UnregisterMIMEInfo", "", 3000), // Requires a MIME table
-            //("RemoveIniValues", "", 3100), // Requires an IniFile table
-            //("RemoveShortcuts", "", 3200), // Requires a Shortcut table
-            ("RemoveEnvironmentStrings", "", 3300), // Requires an Environment table
-            //("RemoveDuplicateFiles", "", 3400), // Requires a DuplicateFile table
-            ("RemoveFiles", "", 3500),
-            ("RemoveFolders", "", 3600),
-            ("CreateFolders", "", 3700),
-            ("MoveFiles", "", 3800),
-            ("InstallFiles", "", 4000),
-            //("PatchFiles", "", 4090), // Requires a Patch table
-            //("DuplicateFiles", "", 4210), // Requires a DuplicateFile table
-            //("BindImage", "", 4300), // Requires a BindImage table
-            //("CreateShortcuts", "", 4500), // Requires a Shortcut table
-            //("RegisterClassInfo", "", 4600), // Requires a Class table
-            //("RegisterExtensionInfo", "", 4700), // Requires an Extension table
-            //("RegisterProgIdInfo", "", 4800), // Requires a ProgId table
-            //("RegisterMIMEInfo", "", 4900), // Requires a MIME table
-            ("WriteRegistryValues", "", 5000), // Requires a Registry table
-            //("WriteIniValues", "", 5100), // Requires an IniFile table
-            ("WriteEnvironmentStrings", "", 5200), // Requires an Environment table
-            //("RegisterFonts", "", 5300), // Requires a Font table
-            //("InstallODBC", "", 5400), // Requires an ODBC* table
-            //("RegisterTypeLibraries", "", 5500), // Requires a TypeLib table
-            //("SelfRegModules", "", 5600), // Requires a SelfReg table
-            ("RegisterComPlus", "", 5700),
-            //("InstallServices", "VersionNT", 5800), // Requires a ServiceInstall table
-            //("StartServices", "VersionNT", 5900), // Requires a SelfReg ServiceControl
-            ("RegisterUser", "", 6000),
-            ("RegisterProduct", "", 6100),
-            ("PublishComponents", "", 6200),
-            ("PublishFeatures", "", 6300),
-            ("PublishProduct", "", 6400),
-            ("InstallFinalize", "", 6600),
-            //("RemoveExistingProducts", "", 6700), // Requires an Upgrade table
-        ];
-        for action in actions {
-            rows.push(vec![
-                msi::Value::Str(action.0.to_string()),
-                if !action.1.is_empty() {
-                    msi::Value::Str(action.1.to_string())
-                } else {
-                    msi::Value::Null
-                },
-                msi::Value::Int(action.2),
-            ]);
-        }
-        self.package
-            .insert_rows(msi::Insert::into("InstallExecuteSequence").rows(rows))?;
-        Ok(())
+// Returns the path of the resource relative to the install dir, with `/` separators.
+fn install_path(dest_path: &Path) -> String {
+    dest_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Key of the component and file installing the given path.  It only depends on
+// the path so it stays the same across versions whatever other files are added:
+// paths that don't map to an identifier as-is get a hash suffix.
+fn component_key(install_path: &str) -> String {
+    let key = identifier(install_path);
+    if key == install_path {
+        key
+    } else {
+        unique_identifier(install_path)
     }
 }
 
+// Returns the keys of the component and of the feature installing the given binary.
+fn binary_component_and_feature(
+    cabinets: &[CabinetInfo],
+    binary: &str,
+) -> Option<(String, String)> {
+    cabinets
+        .iter()
+        .flat_map(|cabinet| cabinet.resources.iter())
+        .find(|resource| resource.dest_path == Path::new(binary))
+        .map(|resource| (resource.component_key.clone(), resource.feature.clone()))
+}
+
+fn merge_module_error(options: &MergeModuleOptions, err: io::Error) -> LivraisonError {
+    LivraisonError::InvalidOption(format!(
+        "Cannot merge module {}: {}",
+        options.path.display(),
+        err
+    ))
+}
+
 fn compute_upgrade_code(bundle_name: &str) -> uuid::Uuid {
     Uuid::new_v5(&UUID_NAMESPACE, bundle_name.as_bytes())
 }
@@ -658,8 +1010,13 @@ struct ResourceInfo {
     filename: String,
     // The size of this resource file, in bytes.
     size: u64,
-    // The database key for the Component that this resource is part of.
+    // The database key for the Component that this resource is part of, also
+    // used as the key of the File.
     component_key: String,
+    // The database key for the Directory this resource is installed in.
+    directory_key: String,
+    // The database key for the Feature installing this resource.
+    feature: String,
 }
 
 // Info about a directory that needs to be created during installation.
@@ -670,8 +1027,6 @@ struct DirectoryInfo {
     parent_key: String,
     // The name of this directory in the filesystem.
     name: String,
-    // List of files in this directory, not counting subdirectories.
-    files: Vec<String>,
 }
 
 // Info about a CAB archive within the installer package.
@@ -680,4 +1035,14 @@ struct CabinetInfo {
     name: String,
     // The resource files that are in this cabinet.
     resources: Vec<ResourceInfo>,
+    // The files of the merge modules that are in this cabinet, after the resources.
+    module_files: Vec<ModuleFileInfo>,
+}
+
+// A file extracted from the cabinet of a merge module.
+struct ModuleFileInfo {
+    // The key of the file in the package's File table, re-keyed from the module's one.
+    file_key: String,
+    // The contents of the file.
+    data: Vec<u8>,
 }
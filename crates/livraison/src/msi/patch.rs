@@ -0,0 +1,68 @@
+use std::{fs, path::Path};
+
+use msi_installer::PatchBuilder;
+use uuid::Uuid;
+
+use crate::{LivraisonError, LivraisonResult};
+
+#[derive(Default, Clone, Debug)]
+pub struct MsiPatchOptions {
+    /// Code identifying the patch, a new one is generated when `None`.
+    pub patch_code: Option<Uuid>,
+
+    /// Patches of the same family supersede each other, the product name when `None`.
+    pub family: Option<String>,
+
+    /// Name of the patch in Add or Remove Programs, the product name and version when `None`.
+    pub display_name: Option<String>,
+
+    /// Description of the patch, shown in Add or Remove Programs.
+    pub description: Option<String>,
+
+    /// Kind of patch, e.g. `Hotfix` or `Security Rollup`, `Update` when `None`.
+    pub classification: Option<String>,
+
+    /// Prevent uninstalling the patch without uninstalling the product.
+    pub no_removal: bool,
+}
+
+/// Create a patch (.msp) updating the installs of the `from` package to the `to` package.
+/// Both packages must be versions of the same bundle, the changed files are copied whole in the patch.
+pub fn create_patch(
+    options: &MsiPatchOptions,
+    from: &Path,
+    to: &Path,
+    dest: &Path,
+) -> LivraisonResult<()> {
+    let mut builder = PatchBuilder::new(options.patch_code.unwrap_or_else(Uuid::new_v4))
+        .allow_removal(!options.no_removal);
+    if let Some(family) = &options.family {
+        builder = builder.family(family);
+    }
+    if let Some(display_name) = &options.display_name {
+        builder = builder.display_name(display_name);
+    }
+    if let Some(description) = &options.description {
+        builder = builder.description(description);
+    }
+    if let Some(classification) = &options.classification {
+        builder = builder.classification(classification);
+    }
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(dest)?;
+    builder
+        .build(fs::File::open(from)?, fs::File::open(to)?, file)
+        .map_err(|err| {
+            LivraisonError::InvalidOption(format!(
+                "Cannot create a patch from {} to {}: {}",
+                from.display(),
+                to.display(),
+                err
+            ))
+        })?;
+    Ok(())
+}
@@ -3,6 +3,7 @@ use std::fs;
 use crate::{
     LivraisonResult,
     actions::pack::{CommonOptions, LivraisonPacker},
+    common::Version,
     rpm::{
         metadata::{RpmMetadata, User},
         package::{DataFile, RpmPackage},
@@ -19,10 +20,12 @@ impl LivraisonPacker for RpmLivraisonPacker {
             .clone()
             .unwrap_or_else(|| "No description.".to_string());
 
+        let version =
+            Version::parse(options.version.as_deref().unwrap_or("1.0.0"))?.to_rpm(None)?;
         let metadata = RpmMetadata {
             name: options.name.clone(),
-            version: options.version.unwrap_or_else(|| "1.0.0".to_string()),
-            release: "1".to_string(),
+            version: version.version,
+            release: version.release,
             // RPM requires a non-empty summary; fall back to the first line of
             // the description.
             summary: description.lines().next().unwrap_or("").to_string(),
@@ -1,8 +1,32 @@
-use std::{path::PathBuf, sync::LazyLock};
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
 mod test_utils;
 use test_utils::TestTempDir;
 
-use livraison::msi::packer::{BinaryFile, MsiInstallerOptions, pack};
+use livraison::msi::packer::{
+    BinaryFile, FeatureOptions, FeatureState, FileAssociationOptions, LaunchConditionOptions,
+    LaunchConditionsOptions, MergeModuleOptions, MsiInstallerOptions, MsiLanguage,
+    MsiSigningOptions, PrerequisiteOptions, PrerequisiteSearch, RegistryKeyOptions, RegistryRoot,
+    RegistryValue, RegistryValueOptions, ServiceAccount, ServiceFailureAction, ServiceOptions,
+    UrlProtocolOptions, WindowsVersion, pack, pack_languages,
+};
+use livraison::msi::patch::{MsiPatchOptions, create_patch};
+use msi_installer::{
+    InstallScope, MergeModule,
+    merge_module::MODULE_CABINET_STREAM,
+    tables::{
+        ActionText, AdminExecuteSequence, AdvtExecuteSequence, AppSearch, Component, Control,
+        ControlEvent, CustomAction, CustomActionType, Directory, DrLocator, Entity, Environment,
+        ErrorMessage, EventMapping, Extension, Feature, FeatureComponents, File, FileAttributes,
+        Icon, InstallExecuteSequence, InstallUISequence, LaunchCondition, Media, Mime,
+        MsiPatchMetadata, MsiPatchSequence, MsiServiceConfigFailureActions, PatchPackage, ProgId,
+        Property, RegLocator, Registry, ServiceControl, ServiceInstall, ServiceStartType,
+        Signature, Verb,
+    },
+};
 
 pub static TESTDIR: LazyLock<TestTempDir> = LazyLock::new(|| {
     let dir = TestTempDir::new("msi");
@@ -27,6 +51,7 @@ fn basic_msi() {
         binaries: Some(vec![BinaryFile {
             name: "test_bin.txt".to_string(),
             path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
         }]),
         ..Default::default()
     };
@@ -37,8 +62,1709 @@ fn basic_msi() {
     pack(options.clone(), &msi_path).unwrap();
 }
 
+#[test]
+fn install_dir_dialog() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        install_dir_dialog: true,
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+
+    let dir = TESTDIR.mkdir("install_dir_dialog").expect("Worked");
+    let msi_path = dir.join("install_dir_dialog.msi");
+    pack(options, &msi_path).unwrap();
+
+    let mut package = msi::open(&msi_path).unwrap();
+    let properties = Property::list(&mut package).unwrap();
+    assert!(
+        properties
+            .iter()
+            .any(|p| p.property == "SHOW_INSTALLDIR_DLG" && p.value == "1")
+    );
+    let events = ControlEvent::list(&mut package).unwrap();
+    assert!(events.iter().any(|e| e.dialog == "WelcomeDlg"
+        && e.event == "NewDialog"
+        && e.argument == "InstallDirDlg"));
+    assert!(events.iter().any(|e| e.dialog == "InstallDirDlg"
+        && e.event == "SetTargetPath"
+        && e.argument == "INSTALLDIR"));
+}
+
+#[test]
+fn per_machine_scope() {
+    let mut package = pack_with_scope("per_machine", InstallScope::PerMachine);
+
+    let properties = Property::list(&mut package).unwrap();
+    assert!(
+        properties
+            .iter()
+            .any(|p| p.property == "ALLUSERS" && p.value == "1")
+    );
+    assert!(!properties.iter().any(|p| p.property == "MSIINSTALLPERUSER"));
+    let directories = Directory::list(&mut package).unwrap();
+    let install_dir = directories
+        .iter()
+        .find(|d| d.directory == "INSTALLDIR")
+        .unwrap();
+    assert_eq!(install_dir.parent.as_deref(), Some("ProgramFiles64Folder"));
+    let environments = Environment::list(&mut package).unwrap();
+    assert_eq!(
+        environments.iter().map(|e| &e.name).collect::<Vec<_>>(),
+        vec!["=-*PATH"]
+    );
+}
+
+#[test]
+fn dual_purpose_scope() {
+    let mut package = pack_with_scope("dual_purpose", InstallScope::DualPurpose);
+
+    let properties = Property::list(&mut package).unwrap();
+    for (name, value) in [
+        ("ALLUSERS", "2"),
+        ("MSIINSTALLPERUSER", "1"),
+        ("SHOW_INSTALLSCOPE_DLG", "1"),
+    ] {
+        assert!(
+            properties
+                .iter()
+                .any(|p| p.property == name && p.value == value)
+        );
+    }
+    let mut environments = Environment::list(&mut package)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect::<Vec<_>>();
+    environments.sort();
+    assert_eq!(environments, vec!["=-*PATH", "=-PATH"]);
+}
+
+#[test]
+fn services() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        scope: InstallScope::PerMachine,
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        services: vec![ServiceOptions {
+            name: "TestAgent".to_string(),
+            display_name: Some("Test Agent".to_string()),
+            binary: "test_bin.txt".to_string(),
+            arguments: Some("--service".to_string()),
+            account: ServiceAccount::LocalService,
+            on_failure: vec![
+                ServiceFailureAction::Restart { delay: 60000 },
+                ServiceFailureAction::None,
+            ],
+            failure_reset_period: Some(86400),
+            dependencies: vec!["Tcpip".to_string(), "Dhcp".to_string()],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("services").expect("Worked");
+    let msi_path = dir.join("services.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let installs = ServiceInstall::list(&mut package).unwrap();
+    assert_eq!(installs.len(), 1);
+    assert_eq!(installs[0].name, "TestAgent");
+    assert_eq!(installs[0].start_type, ServiceStartType::Auto);
+    assert_eq!(
+        installs[0].start_name.as_deref(),
+        Some("NT AUTHORITY\\LocalService")
+    );
+    assert_eq!(installs[0].component, "test_bin.txt");
+    assert_eq!(
+        installs[0].dependencies.as_deref(),
+        Some("Tcpip[~]Dhcp[~][~]")
+    );
+    assert_eq!(ServiceControl::list(&mut package).unwrap().len(), 1);
+    let failure_actions = MsiServiceConfigFailureActions::list(&mut package).unwrap();
+    assert_eq!(failure_actions[0].actions.as_deref(), Some("1[~]0"));
+    assert_eq!(
+        failure_actions[0].delay_actions.as_deref(),
+        Some("60000[~]0")
+    );
+
+    let actions = InstallExecuteSequence::list(&mut package)
+        .unwrap()
+        .into_iter()
+        .map(|item| item.action)
+        .collect::<Vec<_>>();
+    for action in [
+        "StopServices",
+        "DeleteServices",
+        "InstallServices",
+        "StartServices",
+        "MsiConfigureServices",
+    ] {
+        assert!(actions.contains(&action.to_string()), "missing {action}");
+    }
+}
+
+#[test]
+fn services_require_per_machine_scope() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        services: vec![ServiceOptions {
+            name: "TestAgent".to_string(),
+            binary: "test_bin.txt".to_string(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("services_per_user").expect("Worked");
+    assert!(pack(options.clone(), &dir.join("services_per_user.msi")).is_err());
+
+    // Dual purpose packages can be installed per user
+    let options = MsiInstallerOptions {
+        scope: InstallScope::DualPurpose,
+        ..options
+    };
+    assert!(pack(options, &dir.join("services_dual_purpose.msi")).is_err());
+}
+
+#[test]
+fn service_keys() {
+    let service = |name: &str| ServiceOptions {
+        name: name.to_string(),
+        binary: "test_bin.txt".to_string(),
+        ..Default::default()
+    };
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        scope: InstallScope::PerMachine,
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        services: vec![service("Test Agent-2"), service("Agent")],
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("service_keys").expect("Worked");
+    let msi_path = dir.join("service_keys.msi");
+    pack(options.clone(), &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+    let mut keys = ServiceInstall::list(&mut package)
+        .unwrap()
+        .into_iter()
+        .map(|install| install.service_install)
+        .collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec!["svc_agent", "svc_test_agent_2"]);
+
+    // Service names are case insensitive
+    let options = MsiInstallerOptions {
+        services: vec![service("Agent"), service("agent")],
+        ..options
+    };
+    let err = pack(options, &dir.join("duplicate.msi")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid option: Services 'Agent' and 'agent' have the same key svc_agent"
+    );
+}
+
+#[test]
+fn registry() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        features: vec![FeatureOptions {
+            id: "Settings".to_string(),
+            title: "Settings".to_string(),
+            ..Default::default()
+        }],
+        registry: vec![
+            RegistryKeyOptions::app_path("test_bin.txt"),
+            RegistryKeyOptions {
+                root: RegistryRoot::CurrentUser,
+                key: "Software\\[Manufacturer]\\Settings".to_string(),
+                values: vec![
+                    RegistryValueOptions::new(Some("Level"), RegistryValue::Dword(3)),
+                    RegistryValueOptions::new(
+                        Some("Paths"),
+                        RegistryValue::MultiString(vec!["a".to_string(), "b".to_string()]),
+                    ),
+                    RegistryValueOptions::new(Some("Data"), RegistryValue::Binary(vec![1, 255])),
+                ],
+                remove_on_uninstall: false,
+                feature: Some("Settings".to_string()),
+            },
+        ],
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("registry").expect("Worked");
+    let msi_path = dir.join("registry.msi");
+    pack(options.clone(), &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let registry = Registry::list(&mut package).unwrap();
+    let value_of = |key: &str, name: Option<&str>| {
+        registry
+            .iter()
+            .find(|item| item.key.ends_with(key) && item.name.as_deref() == name)
+            .and_then(|item| item.value.clone())
+    };
+    assert_eq!(
+        value_of("App Paths\\test_bin.txt", None).as_deref(),
+        Some("[INSTALLDIR]test_bin.txt")
+    );
+    assert!(
+        registry
+            .iter()
+            .any(|item| item.key.ends_with("test_bin.txt") && item.name.as_deref() == Some("-"))
+    );
+    assert_eq!(value_of("Settings", Some("Level")).as_deref(), Some("#3"));
+    assert_eq!(
+        value_of("Settings", Some("Paths")).as_deref(),
+        Some("a[~]b")
+    );
+    assert_eq!(
+        value_of("Settings", Some("Data")).as_deref(),
+        Some("#x01FF")
+    );
+
+    // Component keys are derived from the root and the path of the key, not from its position
+    let component_of = |key: &str| {
+        registry
+            .iter()
+            .find(|item| item.key.ends_with(key))
+            .map(|item| item.component.clone())
+            .unwrap()
+    };
+    let settings = component_of("Settings");
+    assert!(settings.starts_with("reg_1_software__manufacturer__settings_"));
+    let mut reordered = options.clone();
+    reordered.registry.reverse();
+    let reordered_path = dir.join("registry_reordered.msi");
+    pack(reordered, &reordered_path).unwrap();
+    let reordered_registry = Registry::list(&mut msi::open(&reordered_path).unwrap()).unwrap();
+    assert!(
+        reordered_registry
+            .iter()
+            .any(|item| item.component == settings)
+    );
+
+    let feature_components = FeatureComponents::list(&mut package).unwrap();
+    let feature_of = |component: &str| {
+        feature_components
+            .iter()
+            .find(|row| row.component == component)
+            .map(|row| row.feature.clone())
+    };
+    assert_eq!(feature_of(&settings).as_deref(), Some("Settings"));
+    assert_eq!(
+        feature_of(&component_of("App Paths\\test_bin.txt")).as_deref(),
+        Some("MainFeature")
+    );
+
+    let mut duplicate = options.clone();
+    duplicate.registry.push(RegistryKeyOptions {
+        key: "SOFTWARE\\[Manufacturer]\\Settings".to_string(),
+        feature: None,
+        ..options.registry[1].clone()
+    });
+    let err = pack(duplicate, &dir.join("duplicate.msi")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid option: Registry keys Software\\[Manufacturer]\\Settings and \
+         SOFTWARE\\[Manufacturer]\\Settings are the same key"
+    );
+
+    let mut unknown_feature = options;
+    unknown_feature.registry[1].feature = Some("Unknown".to_string());
+    let err = pack(unknown_feature, &dir.join("unknown_feature.msi")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid option: Registry key Software\\[Manufacturer]\\Settings is assigned to \
+         the unknown feature Unknown"
+    );
+}
+
+#[test]
+fn file_associations() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        file_associations: vec![FileAssociationOptions {
+            extension: ".foo".to_string(),
+            binary: "test_bin.txt".to_string(),
+            description: Some("Foo document".to_string()),
+            content_type: Some("application/x-foo".to_string()),
+            ..Default::default()
+        }],
+        url_protocols: vec![UrlProtocolOptions {
+            scheme: "foo".to_string(),
+            binary: "test_bin.txt".to_string(),
+            description: None,
+        }],
+        // Per user associations are written in the registry
+        scope: InstallScope::PerMachine,
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("file_associations").expect("Worked");
+    let msi_path = dir.join("file_associations.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let extensions = Extension::list(&mut package).unwrap();
+    assert_eq!(extensions.len(), 1);
+    assert_eq!(extensions[0].extension, "foo");
+    assert_eq!(extensions[0].component, "test_bin.txt");
+    assert_eq!(extensions[0].prog_id.as_deref(), Some("test_bin.foo"));
+    let prog_ids = ProgId::list(&mut package).unwrap();
+    assert_eq!(prog_ids[0].description.as_deref(), Some("Foo document"));
+    assert_eq!(Verb::list(&mut package).unwrap()[0].verb, "open");
+    assert_eq!(
+        Mime::list(&mut package).unwrap()[0].content_type,
+        "application/x-foo"
+    );
+
+    let registry = Registry::list(&mut package).unwrap();
+    assert!(registry.iter().any(|item| item.key == "foo"
+        && item.name.as_deref() == Some("URL Protocol")
+        && item.value.is_none()));
+    assert!(
+        registry
+            .iter()
+            .any(|item| item.key == "foo\\shell\\open\\command"
+                && item.value.as_deref() == Some("\"[#test_bin.txt]\" \"%1\""))
+    );
+
+    let actions = package
+        .select_rows(msi::Select::table("InstallExecuteSequence"))
+        .unwrap()
+        .map(|row| row[0].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    for action in [
+        "RegisterExtensionInfo",
+        "RegisterProgIdInfo",
+        "RegisterMIMEInfo",
+    ] {
+        assert!(actions.contains(&action.to_string()), "missing {action}");
+    }
+}
+
+#[test]
+fn url_protocol_feature() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            feature: Some("Extras".to_string()),
+        }]),
+        features: vec![FeatureOptions {
+            id: "Extras".to_string(),
+            title: "Extras".to_string(),
+            optional: true,
+            ..Default::default()
+        }],
+        url_protocols: vec![UrlProtocolOptions {
+            scheme: "foo".to_string(),
+            binary: "test_bin.txt".to_string(),
+            description: None,
+        }],
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("url_protocol_feature").expect("Worked");
+    let msi_path = dir.join("url_protocol_feature.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    // The protocol is only registered when the binary it launches is installed
+    let registry = Registry::list(&mut package).unwrap();
+    let feature_components = FeatureComponents::list(&mut package).unwrap();
+    for key in ["foo", "foo\\shell\\open\\command"] {
+        let component = &registry
+            .iter()
+            .find(|item| item.key == key)
+            .unwrap()
+            .component;
+        let features = feature_components
+            .iter()
+            .filter(|row| &row.component == component)
+            .map(|row| row.feature.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(features, vec!["Extras"], "feature of {key}");
+    }
+}
+
+#[test]
+fn per_user_file_association_key_path() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            feature: Some("Extras".to_string()),
+        }]),
+        features: vec![FeatureOptions {
+            id: "Extras".to_string(),
+            title: "Extras".to_string(),
+            ..Default::default()
+        }],
+        file_associations: vec![FileAssociationOptions {
+            extension: "foo".to_string(),
+            binary: "test_bin.txt".to_string(),
+            description: Some("Foo document".to_string()),
+            content_type: Some("application/x-foo".to_string()),
+            ..Default::default()
+        }],
+        scope: InstallScope::PerUser,
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("per_user_file_association").expect("Worked");
+    let msi_path = dir.join("per_user_file_association.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    // The Extension table would build the open command from the key path of the
+    // binary's component, which must be a HKCU registry value (ICE38)
+    assert!(Extension::list(&mut package).is_err());
+    let registry = Registry::list(&mut package).unwrap();
+    let components = Component::list(&mut package).unwrap();
+    let component = components
+        .iter()
+        .find(|component| component.component == "test_bin.txt")
+        .unwrap();
+    assert!(registry.iter().any(|item| {
+        Some(item.registry.as_str()) == component.key_path.as_deref()
+            && item.root == RegistryRoot::CurrentUser
+    }));
+
+    let value_of = |key: &str, name: Option<&str>| {
+        registry
+            .iter()
+            .find(|item| item.key == key && item.name.as_deref() == name)
+            .unwrap()
+    };
+    assert_eq!(
+        value_of(".foo", None).value.as_deref(),
+        Some("test_bin.foo")
+    );
+    assert_eq!(
+        value_of(".foo", Some("Content Type")).value.as_deref(),
+        Some("application/x-foo")
+    );
+    assert_eq!(
+        value_of("test_bin.foo", None).value.as_deref(),
+        Some("Foo document")
+    );
+    assert_eq!(
+        value_of("test_bin.foo\\shell\\open\\command", None)
+            .value
+            .as_deref(),
+        Some("\"[#test_bin.txt]\" \"%1\"")
+    );
+    assert_eq!(
+        value_of(
+            "MIME\\Database\\Content Type\\application/x-foo",
+            Some("Extension")
+        )
+        .value
+        .as_deref(),
+        Some(".foo")
+    );
+
+    // The association is only registered when its binary is installed
+    let feature_components = FeatureComponents::list(&mut package).unwrap();
+    let association = value_of(".foo", None);
+    assert!(
+        feature_components
+            .iter()
+            .any(|row| row.component == association.component && row.feature == "Extras")
+    );
+    assert_eq!(association.root, RegistryRoot::ClassesRoot);
+}
+
+#[test]
+fn add_remove_programs_metadata() {
+    let icon_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/computer.ico");
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        icon: Some(icon_path.to_string_lossy().to_string()),
+        homepage: Some("https://example.com".to_string()),
+        support_url: Some("https://example.com/support".to_string()),
+        no_repair: true,
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("arp").expect("Worked");
+    let msi_path = dir.join("arp.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let properties = Property::list(&mut package).unwrap();
+    for (name, value) in [
+        ("ARPPRODUCTICON", "ProductIcon.ico"),
+        ("ARPURLINFOABOUT", "https://example.com"),
+        ("ARPHELPLINK", "https://example.com/support"),
+        ("ARPCONTACT", "John Smith"),
+        ("ARPCOMMENTS", "Test"),
+        ("ARPNOREPAIR", "1"),
+    ] {
+        assert!(
+            properties
+                .iter()
+                .any(|p| p.property == name && p.value == value),
+            "{name} should be {value}"
+        );
+    }
+
+    let icons = Icon::list(&mut package).unwrap();
+    assert_eq!(icons.len(), 1);
+    let mut data = Vec::new();
+    package
+        .read_stream(&icons[0].stream_name())
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+    assert_eq!(data, std::fs::read(icon_path).unwrap());
+}
+
+#[test]
+fn normalized_version() {
+    let dir = TESTDIR.mkdir("version").expect("Worked");
+    let pack_version = |version: &str| {
+        let options = MsiInstallerOptions {
+            name: "test".to_string(),
+            version: version.to_string(),
+            description: "Test".to_string(),
+            author: "John Smith".to_string(),
+            ..Default::default()
+        };
+        pack(options, &dir.join("version.msi"))
+    };
+
+    pack_version("1.2").unwrap();
+    let mut package = msi::open(dir.join("version.msi")).unwrap();
+    let properties = Property::list(&mut package).unwrap();
+    assert!(
+        properties
+            .iter()
+            .any(|p| p.property == "ProductVersion" && p.value == "1.2.0")
+    );
+
+    assert!(pack_version("1.2.3-beta.1").is_err());
+    assert!(pack_version("1.2.3+build5").is_err());
+    assert!(pack_version("256.0.0").is_err());
+}
+
+#[test]
+fn feature_tree() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        binaries: Some(vec![
+            BinaryFile {
+                name: "test_bin.txt".to_string(),
+                path: fixture_path("msi/test-bin.txt"),
+                ..Default::default()
+            },
+            BinaryFile {
+                name: "docs.txt".to_string(),
+                path: fixture_path("msi/test-bin.txt"),
+                feature: Some("Docs".to_string()),
+            },
+        ]),
+        features: vec![FeatureOptions {
+            id: "Extras".to_string(),
+            title: "Extras".to_string(),
+            optional: true,
+            children: vec![FeatureOptions {
+                id: "Docs".to_string(),
+                title: "Documentation".to_string(),
+                description: Some("User guide".to_string()),
+                default_state: FeatureState::Absent,
+                optional: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("feature_tree").expect("Worked");
+    let msi_path = dir.join("feature_tree.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let features = Feature::list(&mut package).unwrap();
+    assert_eq!(features.len(), 3);
+    let docs = features.iter().find(|f| f.feature == "Docs").unwrap();
+    assert_eq!(docs.parent.as_deref(), Some("Extras"));
+    assert!(docs.level > 1);
+    let extras = features.iter().find(|f| f.feature == "Extras").unwrap();
+    assert_eq!(extras.parent.as_deref(), Some("MainFeature"));
+    assert_eq!(extras.level, 1);
+
+    let feature_components = FeatureComponents::list(&mut package).unwrap();
+    let feature_of = |component: &str| {
+        feature_components
+            .iter()
+            .find(|fc| fc.component == component)
+            .map(|fc| fc.feature.clone())
+    };
+    assert_eq!(feature_of("docs.txt").as_deref(), Some("Docs"));
+    assert_eq!(feature_of("test_bin.txt").as_deref(), Some("MainFeature"));
+
+    let properties = Property::list(&mut package).unwrap();
+    assert!(
+        properties
+            .iter()
+            .any(|p| p.property == "SHOW_CUSTOMIZE_DLG" && p.value == "1")
+    );
+}
+
+#[test]
+fn unknown_feature() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            feature: Some("Missing".to_string()),
+        }]),
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("unknown_feature").expect("Worked");
+    assert!(pack(options, &dir.join("unknown_feature.msi")).is_err());
+}
+
+#[test]
+fn invalid_feature_ids() {
+    let dir = TESTDIR.mkdir("invalid_feature_ids").expect("Worked");
+    let pack_feature = |id: &str| {
+        let options = MsiInstallerOptions {
+            name: "test".to_string(),
+            version: "1.0.0".to_string(),
+            features: vec![FeatureOptions {
+                id: "Extras".to_string(),
+                title: "Extras".to_string(),
+                children: vec![FeatureOptions {
+                    id: id.to_string(),
+                    title: "Child".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        pack(options, &dir.join("invalid_feature_ids.msi")).map_err(|err| err.to_string())
+    };
+
+    for id in ["My Feature", "1st", "docs-en", "Extras;Docs"] {
+        assert_eq!(
+            pack_feature(id).unwrap_err(),
+            format!(
+                "Invalid option: Feature id {id:?} must only contain letters, digits, `_` and \
+                 `.`, and start with a letter or `_`"
+            )
+        );
+    }
+    assert_eq!(
+        pack_feature(&"a".repeat(39)).unwrap_err(),
+        format!(
+            "Invalid option: Feature id {:?} must be between 1 and 38 characters",
+            "a".repeat(39)
+        )
+    );
+    pack_feature("_docs.en_2").unwrap();
+}
+
+#[test]
+fn stable_component_ids() {
+    let long_dir = "a".repeat(80);
+    let components_of = |version: &str, names: &[&str]| {
+        let options = MsiInstallerOptions {
+            name: "test".to_string(),
+            bundle_name: "test".to_string(),
+            version: version.to_string(),
+            description: "Test".to_string(),
+            author: "John Smith".to_string(),
+            scope: InstallScope::PerMachine,
+            binaries: Some(
+                names
+                    .iter()
+                    .map(|name| BinaryFile {
+                        name: name.to_string(),
+                        path: fixture_path("msi/test-bin.txt"),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let dir = TESTDIR
+            .mkdir(&format!("stable_ids_{version}"))
+            .expect("Worked");
+        let msi_path = dir.join("stable_ids.msi");
+        pack(options, &msi_path).unwrap();
+        let mut package = msi::open(&msi_path).unwrap();
+        let mut components = Component::list(&mut package).unwrap();
+        components.sort_by(|a, b| a.component.cmp(&b.component));
+        components
+            .into_iter()
+            .filter(|component| !component.component.starts_with("env_"))
+            .map(|component| (component.component, component.id))
+            .collect::<Vec<_>>()
+    };
+
+    let long_path = format!("{long_dir}/readme.txt");
+    let v1 = components_of(
+        "1.0.0",
+        &["docs/readme.txt", "examples/readme.txt", &long_path],
+    );
+    // A new file whose path maps to the same identifier doesn't rename the
+    // existing components
+    let v2 = components_of(
+        "1.1.0",
+        &[
+            "docs_readme.txt",
+            "docs/readme.txt",
+            "examples/readme.txt",
+            &long_path,
+        ],
+    );
+    assert_eq!(v1.len(), 3);
+    assert_eq!(v2.len(), 4);
+    assert!(v1.iter().all(|component| v2.contains(component)));
+    for (key, _) in v2.iter() {
+        assert!(key.len() <= 72, "{key} is too long");
+    }
+    assert!(v2.iter().any(|(key, _)| key == "docs_readme.txt"));
+    assert!(
+        v1.iter()
+            .any(|(key, _)| key.starts_with("docs_readme.txt_"))
+    );
+    assert!(
+        v1.iter()
+            .any(|(key, _)| key.starts_with("examples_readme.txt_"))
+    );
+}
+
+#[test]
+fn case_only_duplicate_paths() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        binaries: Some(
+            ["Readme.txt", "readme.txt"]
+                .iter()
+                .map(|name| BinaryFile {
+                    name: name.to_string(),
+                    path: fixture_path("msi/test-bin.txt"),
+                    ..Default::default()
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("case_only_duplicate_paths").expect("Worked");
+    let err = pack(options, &dir.join("case_only_duplicate_paths.msi")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid option: Multiple files are installed to readme.txt (paths are case insensitive on Windows)"
+    );
+}
+
+#[test]
+fn launch_conditions() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        launch_conditions: LaunchConditionsOptions {
+            minimum_windows: Some(WindowsVersion::Windows10),
+            require_64bit: true,
+            prerequisites: vec![
+                PrerequisiteOptions {
+                    property: "DOTNET_RUNTIME".to_string(),
+                    search: PrerequisiteSearch::Registry {
+                        root: RegistryRoot::LocalMachine,
+                        key: "SOFTWARE\\dotnet\\Setup\\InstalledVersions\\x64".to_string(),
+                        name: Some("InstallLocation".to_string()),
+                        win64: true,
+                    },
+                    message: "Install the .NET runtime first".to_string(),
+                },
+                PrerequisiteOptions {
+                    property: "VC_RUNTIME".to_string(),
+                    search: PrerequisiteSearch::File {
+                        directory: "[System64Folder]".to_string(),
+                        file_name: "vcruntime140.dll".to_string(),
+                        min_version: Some("14.0.0".to_string()),
+                        depth: 0,
+                    },
+                    message: "Install the Visual C++ runtime first".to_string(),
+                },
+            ],
+            conditions: vec![LaunchConditionOptions {
+                condition: "Privileged".to_string(),
+                message: "Run the installer as administrator".to_string(),
+            }],
+        },
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("launch_conditions").expect("Worked");
+    let msi_path = dir.join("launch_conditions.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let conditions = LaunchCondition::list(&mut package).unwrap();
+    let condition_of = |message: &str| {
+        conditions
+            .iter()
+            .find(|condition| condition.description.contains(message))
+            .map(|condition| condition.condition.clone())
+    };
+    assert_eq!(
+        condition_of("Windows 10").as_deref(),
+        Some("Installed OR (VersionNT >= 603 AND WINDOWS_BUILD >= 10240)")
+    );
+    assert_eq!(
+        condition_of("64-bit").as_deref(),
+        Some("Installed OR (VersionNT64)")
+    );
+    assert_eq!(
+        condition_of(".NET").as_deref(),
+        Some("Installed OR (DOTNET_RUNTIME)")
+    );
+    assert_eq!(
+        condition_of("administrator").as_deref(),
+        Some("Installed OR (Privileged)")
+    );
+
+    let searches = AppSearch::list(&mut package).unwrap();
+    assert_eq!(searches.len(), 3);
+    let reg_locators = RegLocator::list(&mut package).unwrap();
+    assert!(
+        reg_locators
+            .iter()
+            .any(|locator| locator.name.as_deref() == Some("CurrentBuildNumber"))
+    );
+    let signatures = Signature::list(&mut package).unwrap();
+    assert_eq!(signatures.len(), 1);
+    assert_eq!(signatures[0].file_name, "vcruntime140.dll");
+    let dr_locators = DrLocator::list(&mut package).unwrap();
+    assert_eq!(dr_locators[0].signature, signatures[0].signature);
+
+    let ui_sequence = InstallUISequence::list(&mut package).unwrap();
+    let order_of = |action: &str| {
+        ui_sequence
+            .iter()
+            .find(|item| item.dialog == action)
+            .map(|item| item.order)
+    };
+    assert!(order_of("AppSearch").unwrap() < order_of("LaunchConditions").unwrap());
+}
+
+#[test]
+fn prerequisite_property_must_be_public() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        launch_conditions: LaunchConditionsOptions {
+            prerequisites: vec![PrerequisiteOptions {
+                property: "runtime".to_string(),
+                search: PrerequisiteSearch::Directory {
+                    path: "[ProgramFiles64Folder]runtime".to_string(),
+                },
+                message: "Install the runtime first".to_string(),
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("private_prerequisite").expect("Worked");
+    assert!(pack(options, &dir.join("private_prerequisite.msi")).is_err());
+}
+
+#[test]
+fn standard_sequences() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("standard_sequences").expect("Worked");
+    let msi_path = dir.join("standard_sequences.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+    assert_eq!(package.validation_warnings(), &[]);
+
+    let execute = InstallExecuteSequence::list(&mut package).unwrap();
+    let order_of = |action: &str| {
+        execute
+            .iter()
+            .find(|item| item.action == action)
+            .map(|item| item.order)
+    };
+    assert_eq!(order_of("InstallInitialize"), Some(1500));
+    assert_eq!(order_of("WriteRegistryValues"), Some(5000));
+    assert_eq!(order_of("WriteEnvironmentStrings"), Some(5200));
+    // Their tables don't exist in the package
+    for action in [
+        "AppSearch",
+        "LaunchConditions",
+        "InstallServices",
+        "RegisterExtensionInfo",
+        "CreateShortcuts",
+    ] {
+        assert_eq!(order_of(action), None, "unexpected {action}");
+    }
+
+    let ui = InstallUISequence::list(&mut package).unwrap();
+    assert!(ui.iter().any(|item| item.dialog == "ExecuteAction"));
+    assert!(ui.iter().any(|item| item.dialog == "WelcomeDlg"));
+    let admin = AdminExecuteSequence::list(&mut package).unwrap();
+    assert!(
+        admin
+            .iter()
+            .any(|item| item.action == "InstallAdminPackage")
+    );
+    let advt = AdvtExecuteSequence::list(&mut package).unwrap();
+    assert!(advt.iter().any(|item| item.action == "PublishProduct"));
+    assert!(!advt.iter().any(|item| item.action == "RegisterProgIdInfo"));
+}
+
+#[test]
+fn message_tables() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("message_tables").expect("Worked");
+    let msi_path = dir.join("message_tables.msi");
+    pack(options, &msi_path).unwrap();
+    let mut package = msi::open(&msi_path).unwrap();
+
+    let errors = ErrorMessage::list(&mut package).unwrap();
+    let message_of = |error: i32| {
+        errors
+            .iter()
+            .find(|row| row.error == error)
+            .and_then(|row| row.message.clone())
+    };
+    assert_eq!(
+        message_of(1602).as_deref(),
+        Some("Are you sure you want to cancel?")
+    );
+    assert_eq!(message_of(9).as_deref(), Some("[ProductName]"));
+
+    let action_texts = ActionText::list(&mut package).unwrap();
+    let install_files = action_texts
+        .iter()
+        .find(|row| row.action == "InstallFiles")
+        .unwrap();
+    assert_eq!(
+        install_files.description.as_deref(),
+        Some("Copying new files")
+    );
+
+    // The progress dialog shows the description of the running action
+    let mappings = EventMapping::list(&mut package).unwrap();
+    assert!(
+        mappings
+            .iter()
+            .any(|mapping| mapping.dialog == "ProgressDlg"
+                && mapping.event == "ActionText"
+                && mapping.attribute == "Text")
+    );
+}
+
+#[test]
+fn localized_installers() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        launch_conditions: LaunchConditionsOptions {
+            require_64bit: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("localized_installers").expect("Worked");
+    let paths = pack_languages(
+        options,
+        &[MsiLanguage::French, MsiLanguage::Japanese],
+        &dir.join("localized.msi"),
+    )
+    .unwrap();
+    assert_eq!(
+        paths,
+        vec![
+            dir.join("localized.fr-FR.msi"),
+            dir.join("localized.ja-JP.msi")
+        ]
+    );
+
+    let cases = [
+        (
+            &paths[0],
+            msi::CodePage::Windows1252,
+            "1036",
+            "Installer",
+            "nécessite une version 64 bits",
+            "Voulez-vous vraiment annuler ?",
+        ),
+        (
+            &paths[1],
+            msi::CodePage::Windows932,
+            "1041",
+            "インストール",
+            "64 ビット版の Windows",
+            "キャンセルしてもよろしいですか?",
+        ),
+    ];
+    for (path, codepage, language, install, requires_64bit, cancel) in cases {
+        let mut package = msi::open(path).unwrap();
+        assert_eq!(package.database_codepage(), codepage);
+        assert_eq!(package.summary_info().codepage(), codepage);
+        assert_eq!(
+            package.summary_info().languages()[0].code().to_string(),
+            language
+        );
+        let properties = Property::list(&mut package).unwrap();
+        assert!(
+            properties
+                .iter()
+                .any(|p| p.property == "ProductLanguage" && p.value == language)
+        );
+        let controls = Control::list(&mut package).unwrap();
+        let next = controls
+            .iter()
+            .find(|control| control.dialog == "WelcomeDlg" && control.control == "Next")
+            .unwrap();
+        assert_eq!(next.text.as_deref(), Some(install));
+        let conditions = LaunchCondition::list(&mut package).unwrap();
+        assert!(conditions[0].description.contains(requires_64bit));
+        let errors = ErrorMessage::list(&mut package).unwrap();
+        let confirm_cancel = errors.iter().find(|row| row.error == 1602).unwrap();
+        assert_eq!(confirm_cancel.message.as_deref(), Some(cancel));
+    }
+
+    // Decoding with the same encoding can't tell a wrong one apart, so look at the bytes of the
+    // string pool: "インストール" in Shift-JIS
+    let install_bytes = b"\x83\x43\x83\x93\x83\x58\x83\x67\x81\x5b\x83\x8b";
+    let mut comp = cfb::open(&paths[1]).unwrap();
+    let stream_paths = comp
+        .walk()
+        .filter(|entry| entry.is_stream())
+        .map(|entry| entry.path().to_path_buf())
+        .collect::<Vec<_>>();
+    let has_install_bytes = stream_paths.iter().any(|path| {
+        let mut data = Vec::new();
+        comp.open_stream(path)
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        data.windows(install_bytes.len())
+            .any(|window| window == install_bytes)
+    });
+    assert!(has_install_bytes);
+}
+
+#[test]
+fn signed_msi() {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        signing: Some(MsiSigningOptions {
+            certificate: signing_data_path("signer.crt"),
+            private_key: signing_data_path("signer.key"),
+        }),
+        ..Default::default()
+    };
+    let dir = TESTDIR.mkdir("signed").expect("Worked");
+    let msi_path = dir.join("signed.msi");
+    pack(options.clone(), &msi_path).unwrap();
+
+    let mut package = msi::open(&msi_path).unwrap();
+    assert!(package.has_digital_signature());
+    let certificate = msi::Certificate::load(signing_data_path("signer.crt")).unwrap();
+    package.verify_digital_signature(&certificate).unwrap();
+
+    // A missing key fails before writing anything.
+    let options = MsiInstallerOptions {
+        signing: Some(MsiSigningOptions {
+            certificate: signing_data_path("signer.crt"),
+            private_key: signing_data_path("missing.key"),
+        }),
+        ..options
+    };
+    assert!(pack(options, &dir.join("unsigned.msi")).is_err());
+}
+
+#[test]
+fn merge_module() {
+    let dir = TESTDIR.mkdir("merge_module").expect("Worked");
+    let module_path = dir.join("runtime.msm");
+    create_merge_module(&module_path);
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        merge_modules: vec![MergeModuleOptions {
+            path: module_path.clone(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let msi_path = dir.join("merge_module.msi");
+    pack(options.clone(), &msi_path).unwrap();
+
+    let mut package = msi::open(&msi_path).unwrap();
+    let signatures = package
+        .select_rows(msi::Select::table("ModuleSignature"))
+        .unwrap()
+        .map(|row| row["ModuleID"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(signatures, vec![RUNTIME_MODULE_ID]);
+
+    // The `.GUID` suffix of the module's identifiers is replaced by a short hash
+    let components = MergeModule::open(std::fs::File::open(&module_path).unwrap())
+        .unwrap()
+        .components()
+        .unwrap();
+    let suffix = components[0].strip_prefix("Runtime").unwrap().to_string();
+    assert!(suffix.starts_with('_') && !suffix.contains(RUNTIME_MODULE_GUID));
+    let rekeyed = |name: &str| format!("{name}{suffix}");
+    let module_components = package
+        .select_rows(msi::Select::table("ModuleComponents"))
+        .unwrap()
+        .map(|row| {
+            (
+                row["Component"].as_str().unwrap().to_string(),
+                row["ModuleID"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        module_components,
+        vec![(rekeyed("Runtime"), RUNTIME_MODULE_ID.to_string())]
+    );
+
+    // The module's TARGETDIR is the install dir, and its components are installed by the product feature
+    let directories = Directory::list(&mut package).unwrap();
+    let runtime_dir = directories
+        .iter()
+        .find(|dir| dir.directory == rekeyed("RuntimeDir"))
+        .unwrap();
+    assert_eq!(runtime_dir.parent.as_deref(), Some("INSTALLDIR"));
+    assert_eq!(
+        directories
+            .iter()
+            .filter(|dir| dir.directory == "TARGETDIR")
+            .count(),
+        1
+    );
+    // The standard folders stay in TARGETDIR, and the package's ones are kept
+    let folder = |key: &str| directories.iter().find(|dir| dir.directory == key).unwrap();
+    assert_eq!(
+        folder("System64Folder").parent.as_deref(),
+        Some("TARGETDIR")
+    );
+    assert_eq!(folder("LocalAppDataFolder").default_dir, "AppData");
+    let feature_components = FeatureComponents::list(&mut package).unwrap();
+    assert!(
+        feature_components
+            .iter()
+            .any(|row| row.feature == "MainFeature" && row.component == rekeyed("Runtime"))
+    );
+    let component = Component::list(&mut package)
+        .unwrap()
+        .into_iter()
+        .find(|component| component.component == rekeyed("Runtime"))
+        .unwrap();
+    assert_eq!(component.directory, rekeyed("RuntimeDir"));
+    assert_eq!(component.key_path, Some(rekeyed("runtime_dll")));
+
+    // The module's files are numbered after the package's files, in the last cabinet
+    let mut files = File::list(&mut package).unwrap();
+    files.sort_by_key(|file| file.sequence);
+    let sequences = files
+        .iter()
+        .map(|file| (file.filename.as_str(), file.sequence))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sequences,
+        vec![("test_bin.txt", 1), ("runtime.dll", 2), ("readme.txt", 3)]
+    );
+    let media = Media::list(&mut package).unwrap();
+    assert_eq!(media.last().unwrap().last_sequence, 3);
+    let mut data = Vec::new();
+    package
+        .read_stream("cab1.cab")
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+    let mut cabinet = cab::Cabinet::new(std::io::Cursor::new(data)).unwrap();
+    let mut runtime = String::new();
+    cabinet
+        .read_file(&rekeyed("runtime_dll"))
+        .unwrap()
+        .read_to_string(&mut runtime)
+        .unwrap();
+    assert_eq!(runtime, "runtime library");
+
+    // The module's actions are scheduled relative to the standard actions
+    let sequence = InstallExecuteSequence::list(&mut package).unwrap();
+    let order = |action: &str| {
+        sequence
+            .iter()
+            .find(|row| row.action == action)
+            .map(|row| row.order)
+            .unwrap()
+    };
+    let register = order(&rekeyed("RegisterRuntime"));
+    assert!(register > order("InstallFiles"));
+    assert!(register < order("InstallFinalize"));
+    // References to the module's identifiers in formatted values are re-keyed too
+    let actions = CustomAction::list(&mut package).unwrap();
+    let action = actions
+        .iter()
+        .find(|action| action.action == rekeyed("RegisterRuntime"))
+        .unwrap();
+    assert_eq!(
+        action.target.as_deref(),
+        Some(format!("[#{}]", rekeyed("runtime_dll")).as_str())
+    );
+
+    let options = MsiInstallerOptions {
+        merge_modules: vec![MergeModuleOptions {
+            path: module_path,
+            feature: Some("Nope".to_string()),
+            ..Default::default()
+        }],
+        ..options
+    };
+    let err = pack(options, &dir.join("unknown_feature.msi")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Feature Nope does not exist in the package")
+    );
+}
+
+const RUNTIME_MODULE_GUID: &str = "1B4C2C8A_8D0C_4E3A_9F6E_2B8A7F7E1C11";
+const RUNTIME_MODULE_ID: &str = "Runtime.1B4C2C8A_8D0C_4E3A_9F6E_2B8A7F7E1C11";
+
+// Writes a merge module installing two files in a `runtime` directory, with a custom action.
+fn create_merge_module(path: &Path) {
+    let id = |name: &str| format!("{name}.{RUNTIME_MODULE_GUID}");
+    let mut package = msi::Package::create(
+        msi::PackageType::Installer,
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .unwrap(),
+    )
+    .unwrap();
+    package
+        .create_table(
+            "ModuleSignature",
+            vec![
+                msi::Column::build("ModuleID").primary_key().id_string(72),
+                msi::Column::build("Language").primary_key().int16(),
+                msi::Column::build("Version").string(32),
+            ],
+        )
+        .unwrap();
+    package
+        .insert_rows(msi::Insert::into("ModuleSignature").row(vec![
+            RUNTIME_MODULE_ID.into(),
+            msi::Value::Int(1033),
+            "1.0.0".into(),
+        ]))
+        .unwrap();
+    package
+        .create_table(
+            "ModuleComponents",
+            vec![
+                msi::Column::build("Component").primary_key().id_string(72),
+                msi::Column::build("ModuleID").primary_key().id_string(72),
+                msi::Column::build("Language").primary_key().int16(),
+            ],
+        )
+        .unwrap();
+    package
+        .insert_rows(msi::Insert::into("ModuleComponents").row(vec![
+            id("Runtime").as_str().into(),
+            RUNTIME_MODULE_ID.into(),
+            msi::Value::Int(1033),
+        ]))
+        .unwrap();
+
+    Directory::create_table(&mut package).unwrap();
+    Directory::insert(
+        &mut package,
+        &[
+            Directory {
+                directory: "TARGETDIR".to_string(),
+                parent: None,
+                default_dir: "SourceDir".to_string(),
+            },
+            Directory {
+                directory: id("RuntimeDir"),
+                parent: Some("TARGETDIR".to_string()),
+                default_dir: "runtime".to_string(),
+            },
+            Directory {
+                directory: "LocalAppDataFolder".to_string(),
+                parent: Some("TARGETDIR".to_string()),
+                default_dir: "LocalApp".to_string(),
+            },
+            Directory {
+                directory: "System64Folder".to_string(),
+                parent: Some("TARGETDIR".to_string()),
+                default_dir: "System64".to_string(),
+            },
+        ],
+    )
+    .unwrap();
+    Component::create_table(&mut package).unwrap();
+    Component::insert(
+        &mut package,
+        &[Component {
+            component: id("Runtime"),
+            id: Some(uuid::uuid!("6f1d3c2e-8a4b-4c5d-9e6f-7a8b9c0d1e2f")),
+            directory: id("RuntimeDir"),
+            key_path: Some(id("runtime_dll")),
+            ..Default::default()
+        }],
+    )
+    .unwrap();
+    let contents = [
+        ("runtime_dll", "runtime.dll", "runtime library"),
+        ("readme", "readme.txt", "read me"),
+    ];
+    File::create_table(&mut package).unwrap();
+    let files = contents
+        .iter()
+        .zip(1..)
+        .map(|((key, filename, data), sequence)| File {
+            file: id(key),
+            component: id("Runtime"),
+            filename: filename.to_string(),
+            size: data.len() as i32,
+            sequence,
+            attributes: FileAttributes::Vital,
+            version: None,
+            language: None,
+        })
+        .collect::<Vec<_>>();
+    File::insert(&mut package, &files).unwrap();
+    let mut builder = cab::CabinetBuilder::new();
+    let folder = builder.add_folder(cab::CompressionType::MsZip);
+    for (key, _, _) in contents {
+        folder.add_file(id(key));
+    }
+    let mut writer = builder
+        .build(package.write_stream(MODULE_CABINET_STREAM).unwrap())
+        .unwrap();
+    for (_, _, data) in contents {
+        let mut file = writer.next_file().unwrap().unwrap();
+        file.write_all(data.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    CustomAction::create_table(&mut package).unwrap();
+    CustomAction::insert(
+        &mut package,
+        &[CustomAction {
+            action: id("RegisterRuntime"),
+            action_type: CustomActionType::TextData,
+            source: Some("RUNTIME_REGISTERED".to_string()),
+            target: Some(format!("[#{}]", id("runtime_dll"))),
+        }],
+    )
+    .unwrap();
+    package
+        .create_table(
+            "ModuleInstallExecuteSequence",
+            vec![
+                msi::Column::build("Action").primary_key().id_string(72),
+                msi::Column::build("Sequence").nullable().int16(),
+                msi::Column::build("BaseAction").nullable().id_string(72),
+                msi::Column::build("After").nullable().int16(),
+                msi::Column::build("Condition").nullable().text_string(255),
+            ],
+        )
+        .unwrap();
+    package
+        .insert_rows(
+            msi::Insert::into("ModuleInstallExecuteSequence")
+                .row(vec![
+                    "InstallFiles".into(),
+                    msi::Value::Int(4000),
+                    msi::Value::Null,
+                    msi::Value::Null,
+                    msi::Value::Null,
+                ])
+                .row(vec![
+                    id("RegisterRuntime").as_str().into(),
+                    msi::Value::Null,
+                    "InstallFiles".into(),
+                    msi::Value::Int(1),
+                    msi::Value::Null,
+                ]),
+        )
+        .unwrap();
+    package.flush().unwrap();
+}
+
+#[test]
+fn patch_msi() {
+    let dir = TESTDIR.mkdir("patch").expect("Worked");
+    std::fs::write(dir.join("app-v2.txt"), "app version 2").unwrap();
+    std::fs::write(dir.join("new.txt"), "new file").unwrap();
+    let pack_version = |bundle_name: &str, version: &str, binaries: &[(&str, PathBuf)]| {
+        let options = MsiInstallerOptions {
+            name: "test".to_string(),
+            bundle_name: bundle_name.to_string(),
+            version: version.to_string(),
+            description: "Test".to_string(),
+            author: "John Smith".to_string(),
+            binaries: Some(
+                binaries
+                    .iter()
+                    .map(|(name, path)| BinaryFile {
+                        name: name.to_string(),
+                        path: path.clone(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let msi_path = dir.join(format!("{bundle_name}-{version}.msi"));
+        pack(options, &msi_path).unwrap();
+        msi_path
+    };
+    let v1 = pack_version(
+        "test",
+        "1.0.0",
+        &[
+            ("app.txt", fixture_path("msi/test-bin.txt")),
+            ("docs/readme.txt", fixture_path("msi/test-bin.txt")),
+        ],
+    );
+    let v2 = pack_version(
+        "test",
+        "1.1.0",
+        &[
+            ("app.txt", dir.join("app-v2.txt")),
+            ("docs/readme.txt", fixture_path("msi/test-bin.txt")),
+            ("new.txt", dir.join("new.txt")),
+        ],
+    );
+    let msp_path = dir.join("test-1.1.0.msp");
+    let options = MsiPatchOptions {
+        description: Some("Fixes the app".to_string()),
+        ..Default::default()
+    };
+    create_patch(&options, &v1, &v2, &msp_path).unwrap();
+
+    let property = |path: &Path, name: &str| {
+        let mut package = msi::open(path).unwrap();
+        Property::list(&mut package)
+            .unwrap()
+            .into_iter()
+            .find(|property| property.property == name)
+            .unwrap()
+            .value
+    };
+    let mut patch = msi::open(&msp_path).unwrap();
+    assert_eq!(patch.package_type(), msi::PackageType::Patch);
+    assert_eq!(
+        patch.summary_info().template(),
+        Some(property(&v1, "ProductCode").to_uppercase().as_str())
+    );
+    assert_eq!(
+        patch.summary_info().last_author(),
+        Some(":Upgrade;:#Upgrade")
+    );
+    let mut transforms = patch.embedded_transforms();
+    transforms.sort();
+    assert_eq!(transforms, vec!["#Upgrade", "Upgrade"]);
+    let metadata = MsiPatchMetadata::list(&mut patch).unwrap();
+    let metadata_value = |name: &str| {
+        metadata
+            .iter()
+            .find(|row| row.property == name)
+            .map(|row| row.value.as_str())
+    };
+    assert_eq!(metadata_value("Description"), Some("Fixes the app"));
+    assert_eq!(metadata_value("Classification"), Some("Update"));
+    assert_eq!(metadata_value("AllowRemoval"), Some("1"));
+    let sequences = MsiPatchSequence::list(&mut patch).unwrap();
+    assert_eq!(sequences.len(), 1);
+    assert_eq!(sequences[0].patch_family, "test");
+    assert_eq!(sequences[0].sequence, property(&v2, "ProductVersion"));
+
+    // Applying the transforms to the installed package gives the new version, whose changed files
+    // are in the patch cabinet
+    let applied_path = dir.join("applied.msi");
+    std::fs::copy(&v1, &applied_path).unwrap();
+    let mut applied = msi::open_rw(&applied_path).unwrap();
+    let v1_last_sequence = Media::list(&mut applied)
+        .unwrap()
+        .iter()
+        .map(|media| media.last_sequence)
+        .max()
+        .unwrap();
+    for name in ["Upgrade", "#Upgrade"] {
+        let transform = patch.read_embedded_transform(name).unwrap();
+        applied.apply_transform(&transform).unwrap();
+    }
+    applied.flush().unwrap();
+    drop(applied);
+    assert_eq!(
+        property(&applied_path, "ProductVersion"),
+        property(&v2, "ProductVersion")
+    );
+    let mut applied = msi::open(&applied_path).unwrap();
+    let files = File::list(&mut applied).unwrap();
+    let file = |name: &str| files.iter().find(|file| file.filename == name).unwrap();
+    assert!(file("readme.txt").sequence <= v1_last_sequence);
+    assert_eq!(file("app.txt").sequence, v1_last_sequence + 1);
+    assert_eq!(file("new.txt").sequence, v1_last_sequence + 2);
+    assert!(
+        file("new.txt")
+            .attributes
+            .contains(FileAttributes::PatchAdded)
+    );
+    assert!(
+        !file("app.txt")
+            .attributes
+            .contains(FileAttributes::PatchAdded)
+    );
+    let media = Media::list(&mut applied).unwrap();
+    let patch_media = media.iter().max_by_key(|media| media.disk_id).unwrap();
+    assert_eq!(patch_media.last_sequence, v1_last_sequence + 2);
+    let patch_packages = PatchPackage::list(&mut applied).unwrap();
+    assert_eq!(patch_packages.len(), 1);
+    assert_eq!(patch_packages[0].media, patch_media.disk_id);
+
+    let cabinet = patch_media.cabinet.as_deref().unwrap();
+    let mut data = Vec::new();
+    patch
+        .read_stream(cabinet.strip_prefix('#').unwrap())
+        .unwrap()
+        .read_to_end(&mut data)
+        .unwrap();
+    let mut cabinet = cab::Cabinet::new(std::io::Cursor::new(data)).unwrap();
+    let mut contents = String::new();
+    cabinet
+        .read_file(&file("app.txt").file)
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+    assert_eq!(contents, "app version 2");
+    assert!(cabinet.get_file_entry(&file("readme.txt").file).is_none());
+
+    // Only versions of the same bundle can be patched
+    let other = pack_version("other", "1.1.0", &[("app.txt", dir.join("app-v2.txt"))]);
+    let err = create_patch(&options, &v1, &other, &dir.join("other.msp")).unwrap_err();
+    assert!(err.to_string().contains("differs from the target's"));
+}
+
+fn pack_with_scope(name: &str, scope: InstallScope) -> msi::Package<std::fs::File> {
+    let options = MsiInstallerOptions {
+        name: "test".to_string(),
+        version: "1.0.0".to_string(),
+        description: "Test".to_string(),
+        author: "John Smith".to_string(),
+        scope,
+        binaries: Some(vec![BinaryFile {
+            name: "test_bin.txt".to_string(),
+            path: fixture_path("msi/test-bin.txt"),
+            ..Default::default()
+        }]),
+        ..Default::default()
+    };
+
+    let dir = TESTDIR.mkdir(name).expect("Worked");
+    let msi_path = dir.join(format!("{name}.msi"));
+    pack(options, &msi_path).unwrap();
+    msi::open(&msi_path).unwrap()
+}
+
 fn fixture_path(path: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("fixtures")
         .join(path)
 }
+
+// The test certificate and key are shared with the signing tests of the msi crate
+fn signing_data_path(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../msi/tests/data")
+        .join(path)
+}
@@ -0,0 +1,51 @@
+//! Measures how the time to insert rows with distinct strings grows with the
+//! number of rows.  Since each new string is interned in O(1), the time per
+//! row should stay roughly constant as the table grows.
+//!
+//! Run with `cargo bench -p msi --bench insert`.
+
+use msi::{Category, Column, Insert, Package, PackageType, Value};
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+//===========================================================================//
+
+const SIZES: &[i32] = &[5_000, 10_000, 20_000, 40_000, 60_000];
+
+const ITERATIONS: u32 = 5;
+
+fn insert_files(num_rows: i32) -> Duration {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("File").primary_key().id_string(72),
+        Column::build("FileName").category(Category::Filename).string(255),
+        Column::build("Sequence").int32(),
+    ];
+    package.create_table("File", columns).unwrap();
+    let rows: Vec<Vec<Value>> = (0..num_rows)
+        .map(|index| {
+            vec![
+                Value::Str(format!("file{index}")),
+                Value::Str(format!("name{index}.txt")),
+                Value::Int(index),
+            ]
+        })
+        .collect();
+    let start = Instant::now();
+    package.insert_rows(Insert::into("File").rows(rows)).unwrap();
+    package.flush().unwrap();
+    start.elapsed()
+}
+
+fn main() {
+    println!("{:>8}  {:>12}  {:>12}", "rows", "total", "per row");
+    for &num_rows in SIZES {
+        let total =
+            (0..ITERATIONS).map(|_| insert_files(num_rows)).min().unwrap();
+        let per_row = total / num_rows as u32;
+        println!("{num_rows:>8}  {total:>12.2?}  {per_row:>12.2?}");
+    }
+}
+
+//===========================================================================//
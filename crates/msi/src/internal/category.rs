@@ -1,5 +1,7 @@
 use std::fmt;
 use std::io;
+use std::iter;
+use std::slice;
 use std::str;
 use uuid::Uuid;
 
@@ -135,42 +137,118 @@ pub enum Category {
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/filename) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Filename.validate("readme.txt"));
+    /// assert!(msi::Category::Filename.validate(
+    ///     "README~1.TXT|Read Me First.txt"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Filename.validate("docs\\readme.txt"));
+    /// assert!(!msi::Category::Filename.validate(
+    ///     "Read Me First.txt|README~1.TXT"));
+    /// ```
     Filename,
     /// A filename that can contain shell glob wildcards.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/wildcardfilename)
     /// for this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::WildCardFilename.validate("*.txt"));
+    /// assert!(msi::Category::WildCardFilename.validate("LOG?.TXT|log?.txt"));
+    /// // Invalid:
+    /// assert!(!msi::Category::WildCardFilename.validate("logs/*.txt"));
+    /// ```
     WildCardFilename,
     /// A string containing an absolute filepath.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/path) for this
     /// data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Path.validate("C:\\Program Files\\Foo"));
+    /// assert!(msi::Category::Path.validate("[INSTALLDIR]bin"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Path.validate("C:\\Foo\\*.txt"));
+    /// assert!(!msi::Category::Path.validate("[INSTALLDIR"));
+    /// ```
     Path,
     /// A string containing a semicolon-separated list of absolute filepaths.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/paths) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Paths.validate("C:\\Foo;[INSTALLDIR]bin"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Paths.validate("C:\\Foo;"));
+    /// ```
     Paths,
     /// A string containing an absolute or relative filepath.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/anypath) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::AnyPath.validate("bin\\foo.exe"));
+    /// assert!(msi::Category::AnyPath.validate("FOO~1.EXE|foo app.exe"));
+    /// // Invalid:
+    /// assert!(!msi::Category::AnyPath.validate(""));
+    /// assert!(!msi::Category::AnyPath.validate("foo<bar>.exe"));
+    /// ```
     AnyPath,
     /// A string containing either a filename or an identifier.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/defaultdir)
     /// for this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::DefaultDir.validate("SourceDir"));
+    /// assert!(msi::Category::DefaultDir.validate("FOO~1|Foo App:."));
+    /// // Invalid:
+    /// assert!(!msi::Category::DefaultDir.validate("Foo\\Bar"));
+    /// assert!(!msi::Category::DefaultDir.validate("Foo:Bar:Baz"));
+    /// ```
     DefaultDir,
     /// A string containing a registry path.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/regpath) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::RegPath.validate(
+    ///     "Software\\[Manufacturer]\\[ProductName]"));
+    /// // Invalid:
+    /// assert!(!msi::Category::RegPath.validate("\\Software\\Foo"));
+    /// assert!(!msi::Category::RegPath.validate("Software\\[Manufacturer"));
+    /// ```
     RegPath,
     /// A string containing special formatting escapes, such as environment
     /// variables.
@@ -178,6 +256,20 @@ pub enum Category {
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/formatted) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Formatted.validate(
+    ///     "[#foo.exe] --dir \"[INSTALLDIR]\""));
+    /// assert!(msi::Category::Formatted.validate(
+    ///     "[\\[]literal brackets[\\]]"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Formatted.validate("[INSTALLDIR"));
+    /// assert!(!msi::Category::Formatted.validate("INSTALLDIR]"));
+    /// assert!(!msi::Category::Formatted.validate("[\\ab]"));
+    /// ```
     Formatted,
     /// A security descriptor definition language (SDDL) text string written in
     /// valid [Security Descriptor String
@@ -186,18 +278,58 @@ pub enum Category {
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/formattedsddltext)
     /// for this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::FormattedSddlText.validate(
+    ///     "D:(A;;GA;;;SY)(A;;GR;;;BU)"));
+    /// assert!(msi::Category::FormattedSddlText.validate(
+    ///     "D:(XA;;FX;;;S-1-1-0;(@User.Title==\"PM\"))"));
+    /// assert!(msi::Category::FormattedSddlText.validate(
+    ///     "S:(RA;;;;;WD;(\"Project\",TS,0,\"x\"))"));
+    /// // Invalid:
+    /// assert!(!msi::Category::FormattedSddlText.validate("D:(A;;GA;;;SY"));
+    /// assert!(!msi::Category::FormattedSddlText.validate(
+    ///     "D:(A;;GA;;;SY))"));
+    /// ```
     FormattedSddlText,
     /// Like `Formatted`, but allows additional escapes.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/template) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Template.validate("Copying [1] to [9]"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Template.validate("Copying [1 to [9]"));
+    /// ```
     Template,
     /// A string represeting a boolean predicate.
     ///
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/condition) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Condition.validate(""));
+    /// assert!(msi::Category::Condition.validate("NOT Installed"));
+    /// assert!(msi::Category::Condition.validate(
+    ///     "VersionNT >= 601 AND (REMOVE ~= \"ALL\" OR $Foo = 3)"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Condition.validate("NOT"));
+    /// assert!(!msi::Category::Condition.validate("Installed AND"));
+    /// assert!(!msi::Category::Condition.validate("(VersionNT >= 601"));
+    /// assert!(!msi::Category::Condition.validate("REMOVE = \"ALL"));
+    /// ```
     Condition,
     /// A hyphenated, uppercase GUID string, enclosed in curly braces.
     ///
@@ -275,6 +407,15 @@ pub enum Category {
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/customsource)
     /// for this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::CustomSource.validate("INSTALLDIR"));
+    /// // Invalid:
+    /// assert!(!msi::Category::CustomSource.validate("[INSTALLDIR]"));
+    /// ```
     CustomSource,
     /// A string that refers to a cabinet.  If it starts with a `#` character,
     /// then the rest of the string is an identifier (see above) indicating a
@@ -302,6 +443,17 @@ pub enum Category {
     /// For more details, see the [MSI
     /// docs](https://docs.microsoft.com/en-us/windows/win32/msi/shortcut) for
     /// this data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Valid:
+    /// assert!(msi::Category::Shortcut.validate("MainFeature"));
+    /// assert!(msi::Category::Shortcut.validate("[#foo.exe]"));
+    /// // Invalid:
+    /// assert!(!msi::Category::Shortcut.validate("C:\\foo.exe"));
+    /// assert!(!msi::Category::Shortcut.validate("[#foo.exe"));
+    /// ```
     Shortcut,
 }
 
@@ -425,10 +577,290 @@ impl Category {
                         && (parts.len() < 2 || parts[1].len() <= 3)
                 }
             }
-            // TODO: Validate other categories.
-            _ => true,
+            // Binary columns store stream data rather than strings.
+            Category::Binary => true,
+            Category::TimeDate => string.parse::<i32>().is_ok(),
+            Category::Filename => is_valid_filename(string, false),
+            Category::WildCardFilename => is_valid_filename(string, true),
+            Category::Path => is_valid_path(string),
+            Category::Paths => string.split(';').all(is_valid_path),
+            Category::AnyPath => match string.split_once('|') {
+                Some((short, long)) => {
+                    is_valid_short_filename(short, false)
+                        && is_valid_long_filename(long, false)
+                }
+                None => is_valid_path(string),
+            },
+            Category::DefaultDir => {
+                let is_valid_dir =
+                    |name: &str| name == "." || is_valid_filename(name, false);
+                match string.split_once(':') {
+                    Some((target, source)) => {
+                        is_valid_dir(target) && is_valid_dir(source)
+                    }
+                    None => is_valid_dir(string),
+                }
+            }
+            Category::RegPath => {
+                !string.starts_with('\\') && is_valid_formatted(string)
+            }
+            Category::Formatted | Category::Template => {
+                is_valid_formatted(string)
+            }
+            Category::FormattedSddlText => {
+                is_valid_formatted(string) && is_valid_sddl_nesting(string)
+            }
+            Category::Condition => is_valid_condition(string),
+            Category::CustomSource => Category::Identifier.validate(string),
+            Category::Shortcut => {
+                // A target that isn't a feature key must be a formatted
+                // string referring to a file or directory.
+                Category::Identifier.validate(string)
+                    || (string.contains('[') && is_valid_formatted(string))
+            }
+        }
+    }
+}
+
+// ========================================================================= //
+
+const INVALID_FILENAME_CHARS: &str = "\\/:*?\"<>|";
+const INVALID_SHORT_FILENAME_CHARS: &str = "+,;=[] ";
+const INVALID_PATH_CHARS: &str = "*?\"<>|";
+const MAX_LONG_FILENAME_LEN: usize = 255;
+
+fn is_valid_filename_char(chr: char, wildcards: bool) -> bool {
+    !chr.is_control()
+        && (!INVALID_FILENAME_CHARS.contains(chr)
+            || (wildcards && (chr == '*' || chr == '?')))
+}
+
+/// Returns true if the string is an 8.3 filename.
+fn is_valid_short_filename(string: &str, wildcards: bool) -> bool {
+    let (base, extension) = string.split_once('.').unwrap_or((string, ""));
+    !base.is_empty()
+        && base.len() <= 8
+        && extension.len() <= 3
+        && string.chars().filter(|&chr| chr == '.').count() <= 1
+        && string.chars().all(|chr| {
+            chr.is_ascii()
+                && !INVALID_SHORT_FILENAME_CHARS.contains(chr)
+                && is_valid_filename_char(chr, wildcards)
+        })
+}
+
+fn is_valid_long_filename(string: &str, wildcards: bool) -> bool {
+    !string.is_empty()
+        && string.chars().count() <= MAX_LONG_FILENAME_LEN
+        && string.chars().all(|chr| is_valid_filename_char(chr, wildcards))
+}
+
+/// Returns true if the string is either a filename, or a short filename and
+/// a long filename separated by a `|` character.
+fn is_valid_filename(string: &str, wildcards: bool) -> bool {
+    match string.split_once('|') {
+        Some((short, long)) => {
+            is_valid_short_filename(short, wildcards)
+                && is_valid_long_filename(long, wildcards)
+        }
+        None => is_valid_long_filename(string, wildcards),
+    }
+}
+
+fn is_valid_path(string: &str) -> bool {
+    !string.is_empty()
+        && !string.contains(|chr: char| {
+            chr.is_control() || INVALID_PATH_CHARS.contains(chr)
+        })
+        && is_valid_formatted(string)
+}
+
+/// Returns true if every `[` in the string is matched by a `]` (and vice
+/// versa), and if every escape sequence (such as `[\[]`) contains exactly
+/// one escaped character.
+fn is_valid_formatted(string: &str) -> bool {
+    let mut depth: usize = 0;
+    let mut chars = string.chars();
+    while let Some(chr) = chars.next() {
+        match chr {
+            '[' => {
+                if chars.as_str().starts_with('\\') {
+                    chars.next();
+                    if chars.next().is_none() || chars.next() != Some(']') {
+                        return false;
+                    }
+                } else {
+                    depth += 1;
+                }
+            }
+            ']' => match depth.checked_sub(1) {
+                Some(new_depth) => depth = new_depth,
+                None => return false,
+            },
+            _ => {}
         }
     }
+    depth == 0
+}
+
+/// Returns true if the parentheses of an SDDL string are balanced.  ACE
+/// strings may nest parentheses, e.g. in the condition of a conditional ACE or
+/// the attribute of a resource attribute ACE; parentheses within quoted
+/// strings are ignored.
+fn is_valid_sddl_nesting(string: &str) -> bool {
+    let mut depth: u32 = 0;
+    let mut in_quotes = false;
+    for chr in string.chars() {
+        match chr {
+            '"' if depth > 0 => in_quotes = !in_quotes,
+            _ if in_quotes => {}
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            ')' => return false,
+            _ => {}
+        }
+    }
+    depth == 0 && !in_quotes
+}
+
+// ========================================================================= //
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ConditionToken {
+    Operand,
+    Comparison,
+    Logical,
+    Not,
+    OpenParen,
+    CloseParen,
+}
+
+fn take_condition_identifier(
+    chars: &mut iter::Peekable<str::Chars>,
+) -> Option<String> {
+    let mut identifier = String::new();
+    identifier
+        .push(chars.next_if(|&chr| chr.is_ascii_alphabetic() || chr == '_')?);
+    while let Some(chr) = chars.next_if(|&chr| {
+        chr.is_ascii_alphanumeric() || chr == '_' || chr == '.'
+    }) {
+        identifier.push(chr);
+    }
+    Some(identifier)
+}
+
+fn tokenize_condition(string: &str) -> Option<Vec<ConditionToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = string.chars().peekable();
+    while let Some(&chr) = chars.peek() {
+        if chr.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let token = match chr {
+            '(' => {
+                chars.next();
+                ConditionToken::OpenParen
+            }
+            ')' => {
+                chars.next();
+                ConditionToken::CloseParen
+            }
+            '"' => {
+                chars.next();
+                if !chars.any(|chr| chr == '"') {
+                    return None;
+                }
+                ConditionToken::Operand
+            }
+            '~' | '=' | '<' | '>' => {
+                if chr == '~' {
+                    chars.next();
+                }
+                match chars.next()? {
+                    '=' => {}
+                    '<' => {
+                        chars.next_if(|&chr| matches!(chr, '>' | '=' | '<'));
+                    }
+                    '>' => {
+                        chars.next_if(|&chr| matches!(chr, '<' | '=' | '>'));
+                    }
+                    _ => return None,
+                }
+                ConditionToken::Comparison
+            }
+            '$' | '?' | '&' | '!' | '%' => {
+                chars.next();
+                take_condition_identifier(&mut chars)?;
+                ConditionToken::Operand
+            }
+            '-' | '0'..='9' => {
+                chars.next();
+                let mut has_digits = chr != '-';
+                while chars.next_if(char::is_ascii_digit).is_some() {
+                    has_digits = true;
+                }
+                if !has_digits {
+                    return None;
+                }
+                ConditionToken::Operand
+            }
+            _ if chr.is_ascii_alphabetic() || chr == '_' => {
+                let word = take_condition_identifier(&mut chars)?;
+                match word.to_ascii_uppercase().as_str() {
+                    "NOT" => ConditionToken::Not,
+                    "AND" | "OR" | "XOR" | "EQV" | "IMP" => {
+                        ConditionToken::Logical
+                    }
+                    _ => ConditionToken::Operand,
+                }
+            }
+            _ => return None,
+        };
+        tokens.push(token);
+    }
+    Some(tokens)
+}
+
+fn parse_condition_expr(
+    tokens: &mut iter::Peekable<slice::Iter<ConditionToken>>,
+) -> bool {
+    loop {
+        if !parse_condition_term(tokens) {
+            return false;
+        }
+        if tokens.next_if_eq(&&ConditionToken::Logical).is_none() {
+            return true;
+        }
+    }
+}
+
+fn parse_condition_term(
+    tokens: &mut iter::Peekable<slice::Iter<ConditionToken>>,
+) -> bool {
+    match tokens.next() {
+        Some(ConditionToken::Not) => parse_condition_term(tokens),
+        Some(ConditionToken::OpenParen) => {
+            parse_condition_expr(tokens)
+                && tokens.next() == Some(&ConditionToken::CloseParen)
+        }
+        Some(ConditionToken::Operand) => {
+            tokens.next_if_eq(&&ConditionToken::Comparison).is_none()
+                || tokens.next() == Some(&ConditionToken::Operand)
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if the string is a well-formed conditional statement.  An
+/// empty condition is always true, and so is valid.
+fn is_valid_condition(string: &str) -> bool {
+    let Some(tokens) = tokenize_condition(string) else {
+        return false;
+    };
+    let mut tokens = tokens.iter().peekable();
+    tokens.peek().is_none()
+        || (parse_condition_expr(&mut tokens) && tokens.next().is_none())
 }
 
 impl fmt::Display for Category {
@@ -217,8 +217,8 @@ impl CodePage {
 
     fn encoding(&self) -> &'static Encoding {
         match *self {
-            CodePage::Windows932 => encoding_rs::EUC_JP,
-            CodePage::Windows936 => encoding_rs::BIG5,
+            CodePage::Windows932 => encoding_rs::SHIFT_JIS,
+            CodePage::Windows936 => encoding_rs::GBK,
             CodePage::Windows949 => encoding_rs::EUC_KR,
             CodePage::Windows950 | CodePage::Windows951 => encoding_rs::BIG5,
             CodePage::Windows1250 => encoding_rs::WINDOWS_1250,
@@ -348,6 +348,14 @@ mod tests {
             &CodePage::Utf8.encode("¿Qué pasa?") as &[u8],
             b"\xc2\xbfQu\xc3\xa9 pasa?"
         );
+        assert_eq!(
+            &CodePage::Windows932.encode("日本") as &[u8],
+            b"\x93\xfa\x96\x7b"
+        );
+        assert_eq!(
+            &CodePage::Windows936.encode("中文") as &[u8],
+            b"\xd6\xd0\xce\xc4"
+        );
     }
 
     #[test]
@@ -0,0 +1,267 @@
+use std::io;
+
+// ========================================================================= //
+
+// This module implements just enough of DER (the Distinguished Encoding Rules
+// for ASN.1) to build and parse Authenticode signatures and to pick apart
+// X.509 certificates.  Only definite-length, single-byte tags are supported.
+
+pub const TAG_INTEGER: u8 = 0x02;
+pub const TAG_OCTET_STRING: u8 = 0x04;
+pub const TAG_NULL: u8 = 0x05;
+pub const TAG_OID: u8 = 0x06;
+pub const TAG_SEQUENCE: u8 = 0x30;
+pub const TAG_SET: u8 = 0x31;
+
+/// Returns the tag for a context-specific, constructed element, as used for
+/// `[n] EXPLICIT` fields and constructed `[n] IMPLICIT` fields.
+pub const fn context_tag(number: u8) -> u8 {
+    0xa0 | number
+}
+
+// ========================================================================= //
+
+/// Encodes a single element with the given tag and contents.
+pub fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(contents.len() + 6);
+    output.push(tag);
+    let length = contents.len();
+    if length < 0x80 {
+        output.push(length as u8);
+    } else {
+        let bytes = (length as u64).to_be_bytes();
+        let skip = bytes.iter().take_while(|&&byte| byte == 0).count();
+        output.push(0x80 | (bytes.len() - skip) as u8);
+        output.extend_from_slice(&bytes[skip..]);
+    }
+    output.extend_from_slice(contents);
+    output
+}
+
+/// Encodes a constructed element whose contents are the given (already
+/// encoded) elements, in order.
+pub fn constructed(tag: u8, elements: &[&[u8]]) -> Vec<u8> {
+    encode(tag, &elements.concat())
+}
+
+/// Encodes a SEQUENCE of the given (already encoded) elements.
+pub fn sequence(elements: &[&[u8]]) -> Vec<u8> {
+    constructed(TAG_SEQUENCE, elements)
+}
+
+/// Encodes a SET OF the given (already encoded) elements, sorting them as
+/// DER requires.
+pub fn set_of(elements: &[&[u8]]) -> Vec<u8> {
+    let mut elements = elements.to_vec();
+    elements.sort();
+    constructed(TAG_SET, &elements)
+}
+
+/// Encodes a non-negative INTEGER.
+pub fn integer(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&byte| byte == 0).count().min(7);
+    let mut contents = bytes[skip..].to_vec();
+    if contents[0] & 0x80 != 0 {
+        contents.insert(0, 0);
+    }
+    encode(TAG_INTEGER, &contents)
+}
+
+/// Encodes an OCTET STRING.
+pub fn octet_string(data: &[u8]) -> Vec<u8> {
+    encode(TAG_OCTET_STRING, data)
+}
+
+/// Encodes a NULL.
+pub fn null() -> Vec<u8> {
+    encode(TAG_NULL, &[])
+}
+
+/// Encodes an OBJECT IDENTIFIER from its arcs.
+pub fn oid(arcs: &[u64]) -> Vec<u8> {
+    debug_assert!(arcs.len() >= 2);
+    let mut contents = Vec::new();
+    let mut push_arc = |arc: u64| {
+        let high_bit = 63 - (arc | 1).leading_zeros();
+        let mut shift = high_bit / 7 * 7;
+        while shift > 0 {
+            contents.push(0x80 | ((arc >> shift) & 0x7f) as u8);
+            shift -= 7;
+        }
+        contents.push((arc & 0x7f) as u8);
+    };
+    push_arc(arcs[0] * 40 + arcs[1]);
+    for &arc in &arcs[2..] {
+        push_arc(arc);
+    }
+    encode(TAG_OID, &contents)
+}
+
+// ========================================================================= //
+
+/// A single parsed DER element, borrowing from the input data.
+#[derive(Clone, Copy, Debug)]
+pub struct Element<'a> {
+    tag: u8,
+    contents: &'a [u8],
+    encoded: &'a [u8],
+}
+
+impl<'a> Element<'a> {
+    /// Parses data that must consist of exactly one element.
+    pub fn parse(data: &'a [u8]) -> io::Result<Element<'a>> {
+        let (element, rest) = Element::parse_prefix(data)?;
+        if !rest.is_empty() {
+            invalid_data!("Trailing data after DER element");
+        }
+        Ok(element)
+    }
+
+    /// Parses the first element in the data, returning it along with the
+    /// remaining data.
+    fn parse_prefix(data: &'a [u8]) -> io::Result<(Element<'a>, &'a [u8])> {
+        if data.len() < 2 {
+            invalid_data!("Truncated DER element");
+        }
+        let tag = data[0];
+        if tag & 0x1f == 0x1f {
+            invalid_data!("Unsupported DER tag {:#04x}", tag);
+        }
+        let (length, header_length) = if data[1] < 0x80 {
+            (data[1] as usize, 2)
+        } else {
+            let num_bytes = (data[1] & 0x7f) as usize;
+            if num_bytes == 0 || num_bytes > 4 || data.len() < 2 + num_bytes {
+                invalid_data!("Invalid DER length");
+            }
+            let length = data[2..2 + num_bytes]
+                .iter()
+                .fold(0usize, |length, &byte| (length << 8) | byte as usize);
+            (length, 2 + num_bytes)
+        };
+        if data.len() - header_length < length {
+            invalid_data!("Truncated DER element");
+        }
+        let end = header_length + length;
+        let element = Element {
+            tag,
+            contents: &data[header_length..end],
+            encoded: &data[..end],
+        };
+        Ok((element, &data[end..]))
+    }
+
+    /// Returns the tag of this element.
+    pub fn tag(&self) -> u8 {
+        self.tag
+    }
+
+    /// Returns the contents of this element, without its tag and length.
+    pub fn contents(&self) -> &'a [u8] {
+        self.contents
+    }
+
+    /// Returns the full encoding of this element, including its tag and
+    /// length.
+    pub fn encoded(&self) -> &'a [u8] {
+        self.encoded
+    }
+
+    /// Returns an error unless this element has the given tag.
+    pub fn expect(self, tag: u8) -> io::Result<Element<'a>> {
+        if self.tag != tag {
+            invalid_data!(
+                "Expected DER tag {:#04x}, but found {:#04x}",
+                tag,
+                self.tag
+            );
+        }
+        Ok(self)
+    }
+
+    /// Returns true if this element is the OBJECT IDENTIFIER with the given
+    /// arcs.
+    pub fn is_oid(&self, arcs: &[u64]) -> bool {
+        self.encoded == oid(arcs).as_slice()
+    }
+
+    /// Parses the contents of this (constructed) element as a series of
+    /// elements.
+    pub fn children(&self) -> io::Result<Vec<Element<'a>>> {
+        let mut children = Vec::new();
+        let mut data = self.contents;
+        while !data.is_empty() {
+            let (child, rest) = Element::parse_prefix(data)?;
+            children.push(child);
+            data = rest;
+        }
+        Ok(children)
+    }
+}
+
+// ========================================================================= //
+
+#[cfg(test)]
+mod tests {
+    use super::{Element, TAG_SEQUENCE, encode, integer, oid, sequence};
+
+    #[test]
+    fn encode_lengths() {
+        assert_eq!(encode(0x04, &[1, 2]), vec![0x04, 0x02, 1, 2]);
+        let long = encode(0x04, &[0; 200]);
+        assert_eq!(&long[..3], &[0x04, 0x81, 200]);
+        let longer = encode(0x04, &[0; 300]);
+        assert_eq!(&longer[..4], &[0x04, 0x82, 0x01, 0x2c]);
+    }
+
+    #[test]
+    fn encode_integers() {
+        assert_eq!(integer(0), vec![0x02, 0x01, 0x00]);
+        assert_eq!(integer(1), vec![0x02, 0x01, 0x01]);
+        assert_eq!(integer(0x80), vec![0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(integer(0x1234), vec![0x02, 0x02, 0x12, 0x34]);
+    }
+
+    #[test]
+    fn encode_oids() {
+        assert_eq!(
+            oid(&[1, 2, 840, 113549, 1, 7, 2]),
+            vec![
+                0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07,
+                0x02
+            ]
+        );
+        assert_eq!(
+            oid(&[1, 3, 6, 1, 4, 1, 311, 2, 1, 4]),
+            vec![
+                0x06, 0x0a, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x02,
+                0x01, 0x04
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_nested_elements() {
+        let data =
+            sequence(&[&integer(7), &encode(0x04, &[0; 300]), &oid(&[2, 5])]);
+        let element = Element::parse(&data).unwrap();
+        assert_eq!(element.tag(), TAG_SEQUENCE);
+        assert_eq!(element.encoded(), data.as_slice());
+        let children = element.children().unwrap();
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[0].contents(), &[7]);
+        assert_eq!(children[1].contents().len(), 300);
+        assert!(children[2].is_oid(&[2, 5]));
+    }
+
+    #[test]
+    fn parse_invalid_elements() {
+        assert!(Element::parse(&[0x30]).is_err());
+        assert!(Element::parse(&[0x30, 0x05, 0x00]).is_err());
+        assert!(Element::parse(&[0x04, 0x00, 0x00]).is_err());
+        assert!(Element::parse(&[0x04, 0x85, 0, 0, 0, 0, 1]).is_err());
+    }
+}
+
+// ========================================================================= //
@@ -0,0 +1,184 @@
+use crate::internal::stringpool::StringPool;
+use crate::internal::table::Table;
+use crate::internal::value::Value;
+use cfb;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Read, Seek};
+use std::rc::Rc;
+
+// ========================================================================= //
+
+/// How foreign keys are enforced when rows are inserted, updated or deleted.
+///
+/// A column is a foreign key when its `_Validation` entry names a key table
+/// and a key column; every non-null value of the column must then appear in
+/// that column of the key table.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ReferentialIntegrity {
+    /// Foreign keys are not checked.
+    #[default]
+    Ignore,
+    /// Inserting or updating a row that refers to a missing key, or deleting
+    /// or changing a key that other rows still refer to, is an error.
+    Restrict,
+    /// Inserting or updating a row that refers to a missing key is an error;
+    /// deleting a key also deletes the rows that refer to it, and changing a
+    /// key also changes the rows that refer to it.
+    Cascade,
+}
+
+// ========================================================================= //
+
+/// A column of some table that refers to a column of another table.
+pub(crate) struct Reference {
+    /// The table holding the foreign key column.
+    pub table: Rc<Table>,
+    /// The index of the foreign key column in `table`.
+    pub column: usize,
+    /// The index of the referred column in the key table.
+    pub key_column: usize,
+}
+
+/// Returns all the columns, in any table, that refer to a column of the given
+/// table.
+pub(crate) fn references_to(
+    tables: &BTreeMap<String, Rc<Table>>,
+    table_name: &str,
+) -> Vec<Reference> {
+    let mut references = Vec::new();
+    for table in tables.values() {
+        for (index, column) in table.columns().iter().enumerate() {
+            if let Some((key_tables, key_column)) = column.foreign_key()
+                && key_column >= 1
+                && key_tables.split(';').any(|name| name == table_name)
+            {
+                references.push(Reference {
+                    table: table.clone(),
+                    column: index,
+                    key_column: key_column as usize - 1,
+                });
+            }
+        }
+    }
+    references
+}
+
+/// Reads all the rows of a table as values.
+pub(crate) fn read_values<F: Read + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &StringPool,
+    table: &Table,
+) -> io::Result<Vec<Vec<Value>>> {
+    let stream_name = table.stream_name();
+    if !comp.exists(&stream_name) {
+        return Ok(Vec::new());
+    }
+    let stream = comp.open_stream(&stream_name)?;
+    Ok(table
+        .read_rows(stream)?
+        .into_iter()
+        .map(|value_refs| {
+            value_refs
+                .into_iter()
+                .map(|value_ref| value_ref.to_value(string_pool))
+                .collect()
+        })
+        .collect())
+}
+
+/// Formats the primary key of a row, for use in error messages.
+pub(crate) fn describe_row(table: &Table, values: &[Value]) -> String {
+    let keys: Vec<String> = table
+        .primary_key_indices()
+        .into_iter()
+        .map(|index| values[index].to_string())
+        .collect();
+    format!("({})", keys.join(", "))
+}
+
+/// Checks that the given foreign key columns of `rows` only refer to existing
+/// keys.  `own_rows` are the rows that `table` will contain once modified,
+/// which are used instead of the stored ones when a table refers to itself.
+pub(crate) fn check_foreign_keys<F: Read + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &StringPool,
+    tables: &BTreeMap<String, Rc<Table>>,
+    table: &Table,
+    columns: &[usize],
+    rows: &[Vec<Value>],
+    own_rows: &[Vec<Value>],
+) -> io::Result<()> {
+    let mut known_keys = HashMap::<(String, usize), HashSet<Value>>::new();
+    for &index in columns.iter() {
+        let column = &table.columns()[index];
+        let (key_tables, key_column) = match column.foreign_key() {
+            Some(foreign_key) => foreign_key,
+            None => continue,
+        };
+        if key_column < 1 {
+            invalid_data!(
+                "Column {:?} of table {:?} has invalid key column {}",
+                column.name(),
+                table.name(),
+                key_column
+            );
+        }
+        let key_column = key_column as usize - 1;
+        for key_table in key_tables.split(';') {
+            let key = (key_table.to_string(), key_column);
+            if known_keys.contains_key(&key) {
+                continue;
+            }
+            let values = if key_table == table.name() {
+                own_rows.iter().map(|row| row[key_column].clone()).collect()
+            } else if let Some(key_table) = tables.get(key_table) {
+                if key_column >= key_table.columns().len() {
+                    invalid_data!(
+                        "Column {:?} of table {:?} refers to column {} of \
+                         table {:?}, which has only {} columns",
+                        column.name(),
+                        table.name(),
+                        key_column + 1,
+                        key_table.name(),
+                        key_table.columns().len()
+                    );
+                }
+                read_values(comp, string_pool, key_table)?
+                    .into_iter()
+                    .map(|mut row| row.swap_remove(key_column))
+                    .collect()
+            } else {
+                HashSet::new()
+            };
+            known_keys.insert(key, values);
+        }
+        for row in rows.iter() {
+            let value = &row[index];
+            if value.is_null() {
+                continue;
+            }
+            let found = key_tables.split(';').any(|key_table| {
+                known_keys[&(key_table.to_string(), key_column)]
+                    .contains(value)
+            });
+            if !found {
+                let key_tables: Vec<String> = key_tables
+                    .split(';')
+                    .map(|name| format!("{name:?}"))
+                    .collect();
+                invalid_input!(
+                    "Row {} of table {:?} refers to {} in column {:?}, but \
+                     there is no such key in table {}",
+                    describe_row(table, row),
+                    table.name(),
+                    value,
+                    column.name(),
+                    key_tables.join(" or ")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+// ========================================================================= //
@@ -4,15 +4,23 @@ mod macros;
 pub mod category;
 pub mod codepage;
 pub mod column;
+#[cfg(feature = "signing")]
+pub mod der;
 pub mod expr;
+pub mod integrity;
 pub mod language;
 pub mod package;
 pub mod propset;
 pub mod query;
+#[cfg(feature = "signing")]
+pub mod signature;
+pub mod sql;
 pub mod stream;
 pub mod streamname;
 pub mod stringpool;
 pub mod summary;
 pub mod table;
 pub mod timestamp;
+pub mod transform;
+pub mod validation;
 pub mod value;
@@ -1,8 +1,12 @@
 use crate::internal::category::Category;
 use crate::internal::codepage::CodePage;
-use crate::internal::column::Column;
+use crate::internal::column::{Column, ColumnType};
 use crate::internal::expr::Expr;
-use crate::internal::query::{Delete, Insert, Select, Update};
+use crate::internal::integrity::{self, ReferentialIntegrity};
+use crate::internal::query::{self, Delete, Insert, Select, Update};
+#[cfg(feature = "signing")]
+use crate::internal::signature::{self, Certificate, PrivateKey};
+use crate::internal::sql::Statement;
 use crate::internal::stream::{StreamReader, StreamWriter, Streams};
 use crate::internal::streamname::{
     self, DIGITAL_SIGNATURE_STREAM_NAME, MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME,
@@ -10,12 +14,15 @@ use crate::internal::streamname::{
 };
 use crate::internal::stringpool::{StringPool, StringPoolBuilder};
 use crate::internal::summary::SummaryInfo;
-use crate::internal::table::{Rows, Table};
+use crate::internal::table::{self, Rows, Table};
+use crate::internal::transform::{self, RowChange, Transform};
+use crate::internal::validation::{self, ValidationWarning};
 use crate::internal::value::{Value, ValueRef};
 use cfb;
 use std::borrow::Borrow;
 use std::collections::{BTreeMap, HashMap, HashSet, btree_map};
 use std::io::{self, Read, Seek, Write};
+use std::path::PathBuf;
 use std::rc::Rc;
 use uuid::Uuid;
 
@@ -25,12 +32,12 @@ const INSTALLER_PACKAGE_CLSID: &str = "000C1084-0000-0000-C000-000000000046";
 const PATCH_PACKAGE_CLSID: &str = "000C1086-0000-0000-C000-000000000046";
 const TRANSFORM_PACKAGE_CLSID: &str = "000C1082-0000-0000-C000-000000000046";
 
-const COLUMNS_TABLE_NAME: &str = "_Columns";
-const TABLES_TABLE_NAME: &str = "_Tables";
-const VALIDATION_TABLE_NAME: &str = "_Validation";
+pub(crate) const COLUMNS_TABLE_NAME: &str = "_Columns";
+pub(crate) const TABLES_TABLE_NAME: &str = "_Tables";
+pub(crate) const VALIDATION_TABLE_NAME: &str = "_Validation";
 
-const STRING_DATA_TABLE_NAME: &str = "_StringData";
-const STRING_POOL_TABLE_NAME: &str = "_StringPool";
+pub(crate) const STRING_DATA_TABLE_NAME: &str = "_StringData";
+pub(crate) const STRING_POOL_TABLE_NAME: &str = "_StringPool";
 
 const MAX_NUM_TABLE_COLUMNS: usize = 32;
 
@@ -84,6 +91,92 @@ fn make_validation_table(long_string_refs: bool) -> Rc<Table> {
     )
 }
 
+/// Returns true if the given columns match the hard-coded `_Validation` table
+/// definition (ignoring the metadata that only `_Validation` itself records).
+fn is_standard_validation_table(columns: &[Column]) -> bool {
+    let standard = make_validation_columns();
+    columns.len() == standard.len()
+        && columns.iter().zip(standard.iter()).all(|(column, expected)| {
+            column.name() == expected.name()
+                && column.coltype() == expected.coltype()
+        })
+}
+
+fn make_validation_row(table_name: &str, column: &Column) -> Vec<Value> {
+    let (min_value, max_value) = if let Some((min, max)) = column.value_range()
+    {
+        (Value::Int(min), Value::Int(max))
+    } else {
+        (Value::Null, Value::Null)
+    };
+    let (key_table, key_column) =
+        if let Some((table, column)) = column.foreign_key() {
+            (Value::Str(table.to_string()), Value::Int(column))
+        } else {
+            (Value::Null, Value::Null)
+        };
+    vec![
+        Value::Str(table_name.to_string()),
+        Value::Str(column.name().to_string()),
+        Value::Str(if column.is_nullable() {
+            "Y".to_string()
+        } else {
+            "N".to_string()
+        }),
+        min_value,
+        max_value,
+        key_table,
+        key_column,
+        if let Some(category) = column.category() {
+            Value::Str(category.to_string())
+        } else {
+            Value::Null
+        },
+        if let Some(values) = column.enum_values() {
+            Value::Str(values.join(";"))
+        } else {
+            Value::Null
+        },
+        Value::Null,
+    ]
+}
+
+/// Builds a column from its `_Columns` type bitfield and, if present, its row
+/// in the `_Validation` table.
+fn make_column(
+    name: &str,
+    bitfield: i32,
+    validation: Option<&[Value]>,
+) -> io::Result<Column> {
+    let mut builder = Column::build(name);
+    if let Some(values) = validation {
+        if values[2].as_str() == Some("Y") {
+            builder = builder.nullable();
+        }
+        if let (Some(min), Some(max)) =
+            (values[3].as_int(), values[4].as_int())
+        {
+            builder = builder.range(min, max);
+        }
+        if let (Some(key_table), Some(key_column)) =
+            (values[5].as_str(), values[6].as_int())
+        {
+            builder = builder.foreign_key(key_table, key_column);
+        }
+        let category = values[7]
+            .as_str()
+            .and_then(|category| category.parse::<Category>().ok());
+        if let Some(category) = category {
+            builder = builder.category(category);
+        }
+        if let Some(enum_values) = values[8].as_str() {
+            let enum_values: Vec<&str> = enum_values.split(';').collect();
+            builder = builder.enum_values(&enum_values);
+        }
+    }
+    builder.with_bitfield(bitfield)
+}
+
 fn is_reserved_table_name(table_name: &str) -> bool {
     table_name == COLUMNS_TABLE_NAME
         || table_name == TABLES_TABLE_NAME
@@ -117,7 +210,7 @@ impl PackageType {
         }
     }
 
-    fn clsid(&self) -> Uuid {
+    pub(crate) fn clsid(&self) -> Uuid {
         match *self {
             PackageType::Installer => {
                 Uuid::parse_str(INSTALLER_PACKAGE_CLSID).unwrap()
@@ -131,7 +224,7 @@ impl PackageType {
         }
     }
 
-    fn default_title(&self) -> &str {
+    pub(crate) fn default_title(&self) -> &str {
         match *self {
             PackageType::Installer => "Installation Database",
             PackageType::Patch => "Patch",
@@ -196,6 +289,8 @@ pub struct Package<F> {
     is_summary_info_modified: bool,
     string_pool: StringPool,
     tables: BTreeMap<String, Rc<Table>>,
+    referential_integrity: ReferentialIntegrity,
+    validation_warnings: Vec<ValidationWarning>,
     finisher: Option<Box<dyn Finish<F>>>,
 }
 
@@ -215,6 +310,18 @@ impl<F> Package<F> {
         self.string_pool.codepage()
     }
 
+    /// Returns how foreign keys are enforced when modifying rows.
+    pub fn referential_integrity(&self) -> ReferentialIntegrity {
+        self.referential_integrity
+    }
+
+    /// Returns the disagreements between the `_Validation` table and the
+    /// column types that were found when the package was opened.  See
+    /// [`Package::repair_validation`] for fixing them.
+    pub fn validation_warnings(&self) -> &[ValidationWarning] {
+        &self.validation_warnings
+    }
+
     /// Returns true if the database has a table with the given name.
     pub fn has_table(&self, table_name: &str) -> bool {
         self.tables.contains_key(table_name)
@@ -241,6 +348,26 @@ impl<F> Package<F> {
         Streams::new(self.comp().read_root_storage())
     }
 
+    /// Returns true if the package has an embedded transform with the given
+    /// name.  Patches embed their transforms as sub-storages of the package,
+    /// which the "last saved by" summary property refers to as `:name`.
+    pub fn has_embedded_transform(&self, name: &str) -> bool {
+        match embedded_transform_path(name) {
+            Ok(path) => self.comp().is_storage(path),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the names of the transforms embedded in this package, in no
+    /// particular order.
+    pub fn embedded_transforms(&self) -> Vec<String> {
+        self.comp()
+            .read_root_storage()
+            .filter(|entry| entry.is_storage())
+            .map(|entry| entry.name().to_string())
+            .collect()
+    }
+
     /// Returns true if the package has been digitally signed.  Note that this
     /// method only checks whether a signature is present; it does *not* verify
     /// that the signature is actually valid.
@@ -308,7 +435,13 @@ impl<F: Read + Seek> Package<F> {
                     table.read_rows(stream)?,
                 );
                 for row in rows {
-                    let table_name = row[0].as_str().unwrap().to_string();
+                    let table_name = match row[0].as_str() {
+                        Some(name) => name.to_string(),
+                        None => invalid_data!(
+                            "{:?} table has a row with no table name",
+                            TABLES_TABLE_NAME
+                        ),
+                    };
                     if names.contains(&table_name) {
                         invalid_data!(
                             "Repeated key in {:?} table: {:?}",
@@ -323,7 +456,7 @@ impl<F: Read + Seek> Package<F> {
             names
         };
         // Read in _Columns table:
-        let mut columns_map: HashMap<String, BTreeMap<i32, (String, i32)>> =
+        let mut columns_map: BTreeMap<String, BTreeMap<i32, (String, i32)>> =
             table_names
                 .into_iter()
                 .map(|name| (name, BTreeMap::new()))
@@ -339,9 +472,21 @@ impl<F: Read + Seek> Package<F> {
                     table.read_rows(stream)?,
                 );
                 for row in rows {
-                    let table_name = row[0].as_str().unwrap();
+                    let (table_name, col_index, col_name, type_bits) = match (
+                        row[0].as_str(),
+                        row[1].as_int(),
+                        row[2].as_str(),
+                        row[3].as_int(),
+                    ) {
+                        (Some(table), Some(index), Some(name), Some(bits)) => {
+                            (table, index, name, bits)
+                        }
+                        _ => invalid_data!(
+                            "{:?} table has a row with a null value",
+                            COLUMNS_TABLE_NAME
+                        ),
+                    };
                     if let Some(cols) = columns_map.get_mut(table_name) {
-                        let col_index = row[1].as_int().unwrap();
                         if cols.contains_key(&col_index) {
                             invalid_data!(
                                 "Repeated key in {:?} table: {:?}",
@@ -349,9 +494,10 @@ impl<F: Read + Seek> Package<F> {
                                 (table_name, col_index)
                             );
                         }
-                        let col_name = row[2].as_str().unwrap().to_string();
-                        let type_bits = row[3].as_int().unwrap();
-                        cols.insert(col_index, (col_name, type_bits));
+                        cols.insert(
+                            col_index,
+                            (col_name.to_string(), type_bits),
+                        );
                     } else {
                         invalid_data!(
                             "_Columns mentions table {:?}, which isn't in \
@@ -364,27 +510,46 @@ impl<F: Read + Seek> Package<F> {
             all_tables.insert(table.name().to_string(), table);
         }
         // Read in _Validation table:
+        let mut validation_warnings = Vec::<ValidationWarning>::new();
         let mut validation_map =
             HashMap::<(String, String), Vec<ValueRef>>::new();
+        let has_validation_table = match columns_map.get(VALIDATION_TABLE_NAME)
         {
+            Some(column_specs) => {
+                let columns = column_specs
+                    .values()
+                    .map(|(name, bitfield)| make_column(name, *bitfield, None))
+                    .collect::<io::Result<Vec<Column>>>()?;
+                let is_standard = is_standard_validation_table(&columns);
+                if !is_standard {
+                    validation_warnings
+                        .push(ValidationWarning::NonStandardTable);
+                }
+                is_standard
+            }
+            None => {
+                validation_warnings.push(ValidationWarning::MissingTable);
+                false
+            }
+        };
+        if has_validation_table {
             let table = make_validation_table(string_pool.long_string_refs());
-            // TODO: Ensure that columns_map["_Validation"].columns() matches
-            // the hard-coded validation table definition.
             let stream_name = table.stream_name();
             if comp.exists(&stream_name) {
                 let stream = comp.open_stream(&stream_name)?;
                 for value_refs in table.read_rows(stream)?.into_iter() {
-                    let table_name = value_refs[0]
-                        .to_value(&string_pool)
-                        .as_str()
-                        .unwrap()
-                        .to_string();
-                    let column_name = value_refs[1]
-                        .to_value(&string_pool)
-                        .as_str()
-                        .unwrap()
-                        .to_string();
-                    let key = (table_name, column_name);
+                    let table_name = value_refs[0].to_value(&string_pool);
+                    let column_name = value_refs[1].to_value(&string_pool);
+                    let key = match (table_name.as_str(), column_name.as_str())
+                    {
+                        (Some(table), Some(column)) => {
+                            (table.to_string(), column.to_string())
+                        }
+                        _ => invalid_data!(
+                            "{:?} table has a row with a null key",
+                            VALIDATION_TABLE_NAME
+                        ),
+                    };
                     if validation_map.contains_key(&key) {
                         invalid_data!(
                             "Repeated key in {:?} table: {:?}",
@@ -412,47 +577,30 @@ impl<F: Read + Seek> Package<F> {
             }
             let mut columns = Vec::<Column>::with_capacity(column_specs.len());
             for (_, (column_name, bitfield)) in column_specs.into_iter() {
-                let mut builder = Column::build(column_name.as_str());
                 let key = (table_name.clone(), column_name);
-                if let Some(value_refs) = validation_map.get(&key) {
-                    let is_nullable = value_refs[2].to_value(&string_pool);
-                    if is_nullable.as_str().unwrap() == "Y" {
-                        builder = builder.nullable();
-                    }
-                    let min_value = value_refs[3].to_value(&string_pool);
-                    let max_value = value_refs[4].to_value(&string_pool);
-                    if !min_value.is_null() && !max_value.is_null() {
-                        let min = min_value.as_int().unwrap();
-                        let max = max_value.as_int().unwrap();
-                        builder = builder.range(min, max);
-                    }
-                    let key_table = value_refs[5].to_value(&string_pool);
-                    let key_column = value_refs[6].to_value(&string_pool);
-                    if !key_table.is_null() && !key_column.is_null() {
-                        builder = builder.foreign_key(
-                            key_table.as_str().unwrap(),
-                            key_column.as_int().unwrap(),
-                        );
-                    }
-                    let category_value = value_refs[7].to_value(&string_pool);
-                    if !category_value.is_null() {
-                        let category = category_value
-                            .as_str()
-                            .unwrap()
-                            .parse::<Category>()
-                            .ok();
-                        if let Some(category) = category {
-                            builder = builder.category(category);
-                        }
-                    }
-                    let enum_values = value_refs[8].to_value(&string_pool);
-                    if !enum_values.is_null() {
-                        let enum_values: Vec<&str> =
-                            enum_values.as_str().unwrap().split(';').collect();
-                        builder = builder.enum_values(&enum_values);
-                    }
+                let validation: Option<Vec<Value>> =
+                    validation_map.remove(&key).map(|value_refs| {
+                        value_refs
+                            .iter()
+                            .map(|value_ref| value_ref.to_value(&string_pool))
+                            .collect()
+                    });
+                if let Some(ref values) = validation {
+                    let stored = make_column(&key.1, bitfield, None)?;
+                    validation_warnings.extend(validation::check_column(
+                        &table_name,
+                        &stored,
+                        values,
+                    ));
+                } else if has_validation_table {
+                    validation_warnings.push(ValidationWarning::MissingRow {
+                        table: key.0.clone(),
+                        column: key.1.clone(),
+                    });
                 }
-                columns.push(builder.with_bitfield(bitfield)?);
+                let column =
+                    make_column(&key.1, bitfield, validation.as_deref())?;
+                columns.push(column);
             }
             let table = Table::new(
                 table_name,
@@ -461,6 +609,15 @@ impl<F: Read + Seek> Package<F> {
             );
             all_tables.insert(table.name().to_string(), table);
         }
+        let mut unknown_columns: Vec<(String, String)> =
+            validation_map.into_keys().collect();
+        unknown_columns.sort();
+        validation_warnings.extend(unknown_columns.into_iter().map(
+            |(table, column)| ValidationWarning::UnknownColumn {
+                table,
+                column,
+            },
+        ));
         Ok(Package {
             comp: Some(comp),
             package_type,
@@ -468,6 +625,8 @@ impl<F: Read + Seek> Package<F> {
             is_summary_info_modified: false,
             string_pool,
             tables: all_tables,
+            referential_integrity: ReferentialIntegrity::default(),
+            validation_warnings,
             finisher: None,
         })
     }
@@ -498,7 +657,63 @@ impl<F: Read + Seek> Package<F> {
         Ok(StreamReader::new(self.comp_mut().open_stream(&encoded_name)?))
     }
 
-    // TODO: pub fn has_valid_digital_signature(&mut self) -> io::Result<bool>
+    /// Reads a transform embedded in the package.
+    pub fn read_embedded_transform(
+        &mut self,
+        name: &str,
+    ) -> io::Result<Transform> {
+        let path = embedded_transform_path(name)?;
+        if !self.comp().is_storage(&path) {
+            not_found!("Embedded transform {:?} does not exist", name);
+        }
+        Transform::read_storage(self.comp_mut(), &path)
+    }
+
+    /// Checks that the package has a valid Authenticode signature made with
+    /// the given certificate, and that the package has not been modified
+    /// since it was signed.  Returns a `NotFound` error if the package is
+    /// unsigned, or an `InvalidData` error explaining why the signature is
+    /// invalid.  Only SHA-256 signatures are supported.
+    ///
+    /// This does not check whether the certificate itself is trusted.
+    /// Changes that have not yet been flushed are not taken into account.
+    #[cfg(feature = "signing")]
+    pub fn verify_digital_signature(
+        &mut self,
+        certificate: &Certificate,
+    ) -> io::Result<()> {
+        if !self.has_digital_signature() {
+            not_found!("Package is not digitally signed");
+        }
+        let mut data = Vec::new();
+        self.comp_mut()
+            .open_stream(DIGITAL_SIGNATURE_STREAM_NAME)?
+            .read_to_end(&mut data)?;
+        let signed_digest = signature::verify(&data, certificate)?;
+        let metadata_digest =
+            if self.comp().is_stream(MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME) {
+                let mut stored_digest = Vec::new();
+                self.comp_mut()
+                    .open_stream(MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME)?
+                    .read_to_end(&mut stored_digest)?;
+                if stored_digest != signature::metadata_digest(self.comp())? {
+                    invalid_data!(
+                        "Package metadata has changed since it was signed"
+                    );
+                }
+                Some(stored_digest)
+            } else {
+                None
+            };
+        let digest = signature::content_digest(
+            self.comp_mut(),
+            metadata_digest.as_deref(),
+        )?;
+        if digest != signed_digest {
+            invalid_data!("Package contents have changed since it was signed");
+        }
+        Ok(())
+    }
 }
 
 impl<F: Read + Write + Seek> Package<F> {
@@ -528,6 +743,8 @@ impl<F: Read + Write + Seek> Package<F> {
             is_summary_info_modified: true,
             string_pool,
             tables,
+            referential_integrity: ReferentialIntegrity::default(),
+            validation_warnings: Vec::new(),
             finisher: None,
         };
         package
@@ -547,6 +764,14 @@ impl<F: Read + Write + Seek> Package<F> {
         &mut self.summary_info
     }
 
+    /// Sets how foreign keys are enforced by `insert_rows()`,
+    /// `update_rows()` and `delete_rows()`.  Foreign keys are not enforced by
+    /// default, since tables are often filled in an order where rows refer to
+    /// keys that are only inserted later.
+    pub fn set_referential_integrity(&mut self, mode: ReferentialIntegrity) {
+        self.referential_integrity = mode;
+    }
+
     /// Sets the code page used for serializing strings in the database.
     pub fn set_database_codepage(&mut self, codepage: CodePage) {
         self.set_finisher();
@@ -628,51 +853,16 @@ impl<F: Read + Write + Seek> Package<F> {
         )?;
         let validation_rows: Vec<Vec<Value>> = columns
             .iter()
-            .map(|column| {
-                let (min_value, max_value) =
-                    if let Some((min, max)) = column.value_range() {
-                        (Value::Int(min), Value::Int(max))
-                    } else {
-                        (Value::Null, Value::Null)
-                    };
-                let (key_table, key_column) =
-                    if let Some((table, column)) = column.foreign_key() {
-                        (Value::Str(table.to_string()), Value::Int(column))
-                    } else {
-                        (Value::Null, Value::Null)
-                    };
-                vec![
-                    Value::Str(table_name.clone()),
-                    Value::Str(column.name().to_string()),
-                    Value::Str(if column.is_nullable() {
-                        "Y".to_string()
-                    } else {
-                        "N".to_string()
-                    }),
-                    min_value,
-                    max_value,
-                    key_table,
-                    key_column,
-                    if let Some(category) = column.category() {
-                        Value::Str(category.to_string())
-                    } else {
-                        Value::Null
-                    },
-                    if let Some(values) = column.enum_values() {
-                        Value::Str(values.join(";"))
-                    } else {
-                        Value::Null
-                    },
-                    Value::Null,
-                ]
-            })
+            .map(|column| make_validation_row(&table_name, column))
             .collect();
         let long_string_refs = self.string_pool.long_string_refs();
         let table = Table::new(table_name.clone(), columns, long_string_refs);
         self.tables.insert(table_name, table);
-        self.insert_rows(
-            Insert::into(VALIDATION_TABLE_NAME).rows(validation_rows),
-        )?;
+        if self.tables.contains_key(VALIDATION_TABLE_NAME) {
+            self.insert_rows(
+                Insert::into(VALIDATION_TABLE_NAME).rows(validation_rows),
+            )?;
+        }
         Ok(())
     }
 
@@ -692,10 +882,12 @@ impl<F: Read + Write + Seek> Package<F> {
         if self.comp().exists(&stream_name) {
             self.comp_mut().remove_stream(&stream_name)?;
         }
-        self.delete_rows(
-            Delete::from(VALIDATION_TABLE_NAME)
-                .with(Expr::col("Table").eq(Expr::string(table_name))),
-        )?;
+        if self.tables.contains_key(VALIDATION_TABLE_NAME) {
+            self.delete_rows(
+                Delete::from(VALIDATION_TABLE_NAME)
+                    .with(Expr::col("Table").eq(Expr::string(table_name))),
+            )?;
+        }
         self.delete_rows(
             Delete::from(COLUMNS_TABLE_NAME)
                 .with(Expr::col("Table").eq(Expr::string(table_name))),
@@ -708,41 +900,562 @@ impl<F: Read + Write + Seek> Package<F> {
         Ok(())
     }
 
+    /// Adds a new column at the end of an existing database table.  Existing
+    /// rows get a null value for the new column, so it must be nullable
+    /// unless the table is empty.  Returns an error without modifying the
+    /// database if the column is invalid or the table does not exist.
+    pub fn add_column(
+        &mut self,
+        table_name: &str,
+        column: Column,
+    ) -> io::Result<()> {
+        if is_reserved_table_name(table_name) {
+            invalid_input!("Cannot alter special {:?} table", table_name);
+        }
+        let table = match self.tables.get(table_name) {
+            Some(table) => table.clone(),
+            None => not_found!("Table {:?} does not exist", table_name),
+        };
+        let name = column.name();
+        if !Column::is_valid_name(name) {
+            invalid_input!("{:?} is not a valid column name", name);
+        }
+        if table.columns().iter().any(|col| col.name() == name) {
+            already_exists!(
+                "Table {:?} already has a column named {:?}",
+                table_name,
+                name
+            );
+        }
+        if column.is_primary_key() {
+            invalid_input!(
+                "Cannot add primary key column {:?} to an existing table",
+                name
+            );
+        }
+        if table.columns().len() >= MAX_NUM_TABLE_COLUMNS {
+            invalid_input!(
+                "Cannot create a table with more than {} columns",
+                MAX_NUM_TABLE_COLUMNS
+            );
+        }
+        let stream_name = table.stream_name();
+        let mut rows = if self.comp().exists(&stream_name) {
+            let stream = self.comp_mut().open_stream(&stream_name)?;
+            table.read_rows(stream)?
+        } else {
+            Vec::new()
+        };
+        if !rows.is_empty() && !column.is_nullable() {
+            invalid_input!(
+                "Cannot add non-nullable column {:?} to non-empty table {:?}",
+                name,
+                table_name
+            );
+        }
+        self.insert_rows(Insert::into(COLUMNS_TABLE_NAME).row(vec![
+            Value::Str(table_name.to_string()),
+            Value::Int(1 + table.columns().len() as i32),
+            Value::Str(name.to_string()),
+            Value::Int(column.bitfield()),
+        ]))?;
+        if self.tables.contains_key(VALIDATION_TABLE_NAME) {
+            self.insert_rows(
+                Insert::into(VALIDATION_TABLE_NAME)
+                    .row(make_validation_row(table_name, &column)),
+            )?;
+        }
+        let mut columns = table.columns().to_vec();
+        columns.push(column);
+        let table = Table::new(
+            table_name.to_string(),
+            columns,
+            table.long_string_refs(),
+        );
+        if !rows.is_empty() {
+            for row in rows.iter_mut() {
+                row.push(ValueRef::Null);
+            }
+            let stream = self.comp_mut().create_stream(&stream_name)?;
+            table.write_rows(stream, rows)?;
+        }
+        self.tables.insert(table_name.to_string(), table);
+        Ok(())
+    }
+
+    /// Regenerates the `_Validation` table from the columns of the database
+    /// tables as they were loaded, so that it agrees with them again (see
+    /// [`Package::validation_warnings`]).  The table is recreated if it is
+    /// missing or has non-standard columns; otherwise, the descriptions of
+    /// the columns it already describes are kept.  Columns that only their
+    /// `_Validation` row made nullable are marked nullable in `_Columns` as
+    /// well.  Returns an error without modifying the database if some
+    /// column's metadata can't be stored in a `_Validation` row.
+    pub fn repair_validation(&mut self) -> io::Result<()> {
+        let validation_columns = make_validation_columns();
+        let is_standard =
+            self.tables.get(VALIDATION_TABLE_NAME).is_some_and(|table| {
+                is_standard_validation_table(table.columns())
+            });
+        let mut descriptions = HashMap::<(String, String), Value>::new();
+        if is_standard {
+            for row in
+                self.select_rows(Select::table(VALIDATION_TABLE_NAME))?
+            {
+                if let (Some(table_name), Some(column_name)) =
+                    (row["Table"].as_str(), row["Column"].as_str())
+                {
+                    let key =
+                        (table_name.to_string(), column_name.to_string());
+                    descriptions.insert(key, row["Description"].clone());
+                }
+            }
+        }
+        let mut rows = Vec::<Vec<Value>>::new();
+        let mut tables = Vec::<(String, Vec<Column>)>::new();
+        let mut table_names: Vec<&str> =
+            self.tables.keys().map(String::as_str).collect();
+        if !self.tables.contains_key(VALIDATION_TABLE_NAME) {
+            table_names.push(VALIDATION_TABLE_NAME);
+            table_names.sort();
+        }
+        for table_name in table_names {
+            let columns = match table_name {
+                COLUMNS_TABLE_NAME | TABLES_TABLE_NAME => continue,
+                VALIDATION_TABLE_NAME => &validation_columns,
+                _ => self.tables[table_name].columns(),
+            };
+            let mut repaired_columns = Vec::with_capacity(columns.len());
+            for column in columns {
+                let mut row = make_validation_row(table_name, column);
+                // Drop the metadata that can't apply to the column's type:
+                let coltype = column.coltype();
+                if !matches!(coltype, ColumnType::Int16 | ColumnType::Int32) {
+                    row[3] = Value::Null;
+                    row[4] = Value::Null;
+                }
+                if let Some(category) = column.category()
+                    && !validation::is_category_compatible(coltype, category)
+                {
+                    row[7] = Value::Null;
+                }
+                let key = (table_name.to_string(), column.name().to_string());
+                if let Some(description) = descriptions.remove(&key) {
+                    row[9] = description;
+                }
+                for (value, column) in
+                    row.iter().zip(validation_columns.iter())
+                {
+                    if !column.is_valid_value(value) {
+                        invalid_input!(
+                            "Cannot repair {:?} table: {} is not a valid \
+                             value for column {:?}",
+                            VALIDATION_TABLE_NAME,
+                            value,
+                            column.name()
+                        );
+                    }
+                }
+                repaired_columns.push(make_column(
+                    column.name(),
+                    column.bitfield(),
+                    Some(&row),
+                )?);
+                rows.push(row);
+            }
+            if table_name != VALIDATION_TABLE_NAME {
+                tables.push((table_name.to_string(), repaired_columns));
+            }
+        }
+        // Fix up the columns whose type doesn't say they are nullable:
+        let column_types: Vec<(String, i32, i32)> = self
+            .select_rows(Select::table(COLUMNS_TABLE_NAME))?
+            .filter_map(|row| {
+                Some((
+                    row["Table"].as_str()?.to_string(),
+                    row["Number"].as_int()?,
+                    row["Type"].as_int()?,
+                ))
+            })
+            .collect();
+        let mut nullable_columns = Vec::<(String, i32, i32)>::new();
+        for (table_name, number, bitfield) in column_types {
+            if table_name == VALIDATION_TABLE_NAME {
+                continue;
+            }
+            let column = match self.tables.get(&table_name).and_then(|table| {
+                table.columns().get((number as usize).wrapping_sub(1))
+            }) {
+                Some(column) => column,
+                None => continue,
+            };
+            if column.is_nullable()
+                && !make_column(column.name(), bitfield, None)?.is_nullable()
+            {
+                nullable_columns.push((table_name, number, column.bitfield()));
+            }
+        }
+        // Replace the contents of the _Validation table:
+        if !is_standard {
+            if self.tables.remove(VALIDATION_TABLE_NAME).is_some() {
+                self.delete_rows(Delete::from(COLUMNS_TABLE_NAME).with(
+                    Expr::col("Table").eq(Expr::string(VALIDATION_TABLE_NAME)),
+                ))?;
+                self.delete_rows(Delete::from(TABLES_TABLE_NAME).with(
+                    Expr::col("Name").eq(Expr::string(VALIDATION_TABLE_NAME)),
+                ))?;
+            }
+            let long_string_refs = self.string_pool.long_string_refs();
+            let stream_name =
+                make_validation_table(long_string_refs).stream_name();
+            if self.comp().exists(&stream_name) {
+                self.comp_mut().remove_stream(&stream_name)?;
+            }
+            self.create_table_with_name(
+                VALIDATION_TABLE_NAME.to_string(),
+                validation_columns,
+            )?;
+        }
+        self.delete_rows(Delete::from(VALIDATION_TABLE_NAME))?;
+        self.insert_rows(Insert::into(VALIDATION_TABLE_NAME).rows(rows))?;
+        for (table_name, number, bitfield) in nullable_columns {
+            self.update_rows(
+                Update::table(COLUMNS_TABLE_NAME)
+                    .set("Type", Value::Int(bitfield))
+                    .with(
+                        Expr::col("Table").eq(Expr::string(table_name)).and(
+                            Expr::col("Number").eq(Expr::integer(number)),
+                        ),
+                    ),
+            )?;
+        }
+        let long_string_refs = self.string_pool.long_string_refs();
+        for (table_name, columns) in tables {
+            let table =
+                Table::new(table_name.clone(), columns, long_string_refs);
+            self.tables.insert(table_name, table);
+        }
+        self.validation_warnings.clear();
+        Ok(())
+    }
+
+    /// Applies a transform to the package: creates and drops the tables,
+    /// appends the columns, inserts, updates and deletes the rows, and writes
+    /// the binary streams that the transform holds.  Foreign keys are not
+    /// enforced while the transform is applied.
+    ///
+    /// Transforms cannot remove or change existing columns, so a transform
+    /// that tries to is an error.  Unlike the other modifying methods, a
+    /// failed `apply_transform()` may leave the changes to some tables applied.
+    pub fn apply_transform(
+        &mut self,
+        transform: &Transform,
+    ) -> io::Result<()> {
+        self.set_finisher();
+        let columns = self.tables[TABLES_TABLE_NAME].columns().to_vec();
+        let mut new_tables = Vec::<String>::new();
+        for change in transform.row_changes(TABLES_TABLE_NAME, &columns)? {
+            match change {
+                RowChange::Insert(values) => {
+                    let table_name = values[0].as_str().unwrap_or_default();
+                    if !self.has_table(table_name) {
+                        new_tables.push(table_name.to_string());
+                    }
+                }
+                RowChange::Delete(values) => {
+                    let table_name = values[0].as_str().unwrap_or_default();
+                    if self.has_table(table_name) {
+                        self.drop_table(table_name)?;
+                    }
+                }
+                RowChange::Update(..) => {
+                    invalid_data!(
+                        "Transform updates a row of {:?}",
+                        TABLES_TABLE_NAME
+                    )
+                }
+            }
+        }
+        let columns = self.tables[COLUMNS_TABLE_NAME].columns().to_vec();
+        let mut new_columns = BTreeMap::<String, Vec<(String, i32)>>::new();
+        for change in transform.row_changes(COLUMNS_TABLE_NAME, &columns)? {
+            let values = match change {
+                RowChange::Insert(values) => values,
+                RowChange::Update(_, values) | RowChange::Delete(values) => {
+                    invalid_data!(
+                        "Transform cannot change or remove existing columns \
+                         of table {:?}",
+                        values[0]
+                    )
+                }
+            };
+            // Added columns are appended in order, so the column numbers
+            // (which some transforms leave null) are not needed.
+            let table_name = values[0].as_str().unwrap_or_default();
+            let column_name = values[2].as_str().unwrap_or_default();
+            let bitfield = match values[3].as_int() {
+                Some(bitfield) => bitfield,
+                None => invalid_data!(
+                    "Transform adds column {:?} to table {:?} without a type",
+                    column_name,
+                    table_name
+                ),
+            };
+            new_columns
+                .entry(table_name.to_string())
+                .or_default()
+                .push((column_name.to_string(), bitfield));
+        }
+        // A package without a _Validation table (see
+        // ValidationWarning::MissingTable) has nowhere to record the
+        // transform's validation rows, so they are skipped.
+        let mut validation_map =
+            HashMap::<(String, String), Vec<Value>>::new();
+        if let Some(table) = self.tables.get(VALIDATION_TABLE_NAME) {
+            let columns = table.columns().to_vec();
+            for change in
+                transform.row_changes(VALIDATION_TABLE_NAME, &columns)?
+            {
+                if let RowChange::Insert(values) = change {
+                    let key = (
+                        values[0].as_str().unwrap_or_default().to_string(),
+                        values[1].as_str().unwrap_or_default().to_string(),
+                    );
+                    validation_map.insert(key, values);
+                }
+            }
+        }
+        for table_name in new_tables {
+            let specs = new_columns.remove(&table_name).unwrap_or_default();
+            if specs.is_empty() {
+                invalid_data!(
+                    "Transform adds table {:?} without any columns",
+                    table_name
+                );
+            }
+            let mut columns = Vec::with_capacity(specs.len());
+            for (column_name, bitfield) in specs {
+                let key = (table_name.clone(), column_name);
+                let validation = validation_map.get(&key).map(Vec::as_slice);
+                columns.push(make_column(&key.1, bitfield, validation)?);
+            }
+            self.create_table(table_name, columns)?;
+        }
+        for (table_name, specs) in new_columns {
+            if !self.has_table(&table_name) {
+                not_found!(
+                    "Transform adds columns to table {:?}, which does not \
+                     exist",
+                    table_name
+                );
+            }
+            for (column_name, bitfield) in specs {
+                if self.tables[&table_name].has_column(&column_name) {
+                    continue;
+                }
+                let key = (table_name.clone(), column_name);
+                let validation = validation_map.get(&key).map(Vec::as_slice);
+                let column = make_column(&key.1, bitfield, validation)?;
+                self.add_column(&table_name, column)?;
+            }
+        }
+        for table_name in transform.data_table_names() {
+            let table = match self.tables.get(table_name) {
+                Some(table) => table.clone(),
+                None if table_name == VALIDATION_TABLE_NAME => continue,
+                None => not_found!(
+                    "Transform changes table {:?}, which does not exist",
+                    table_name
+                ),
+            };
+            let changes =
+                transform.row_changes(table_name, table.columns())?;
+            self.apply_row_changes(&table, changes)?;
+        }
+        for stream_name in transform.stream_names() {
+            let data = transform.stream_data(stream_name);
+            self.write_stream(stream_name)?.write_all(data)?;
+        }
+        Ok(())
+    }
+
+    fn apply_row_changes(
+        &mut self,
+        table: &Rc<Table>,
+        changes: Vec<RowChange>,
+    ) -> io::Result<()> {
+        let key_indices = table.primary_key_indices();
+        let key_of = |values: &[Value]| -> Vec<Value> {
+            key_indices.iter().map(|&index| values[index].clone()).collect()
+        };
+        let comp = self.comp.as_mut().unwrap();
+        let old_rows: HashMap<Vec<Value>, Vec<Value>> =
+            integrity::read_values(comp, &self.string_pool, table)?
+                .into_iter()
+                .map(|row| (key_of(&row), row))
+                .collect();
+        let mut new_rows = BTreeMap::<Vec<Value>, Option<Vec<Value>>>::new();
+        for change in changes {
+            match change {
+                RowChange::Insert(values) => {
+                    new_rows.insert(key_of(&values), Some(values));
+                }
+                RowChange::Update(mask, values) => {
+                    let key = key_of(&values);
+                    let old_row = match new_rows.get(&key) {
+                        Some(row) => row.clone(),
+                        None => old_rows.get(&key).cloned(),
+                    };
+                    let mut row = match old_row {
+                        Some(row) => row,
+                        None => invalid_data!(
+                            "Transform updates a missing row of table {:?} \
+                             (key: {:?})",
+                            table.name(),
+                            key
+                        ),
+                    };
+                    for (index, value) in values.into_iter().enumerate() {
+                        if transform::mask_has_column(mask, index) {
+                            row[index] = value;
+                        }
+                    }
+                    new_rows.insert(key, Some(row));
+                }
+                RowChange::Delete(values) => {
+                    new_rows.insert(key_of(&values), None);
+                }
+            }
+        }
+        let removed: HashSet<Vec<Value>> = new_rows
+            .keys()
+            .filter(|key| old_rows.contains_key(*key))
+            .cloned()
+            .collect();
+        if !removed.is_empty() {
+            query::delete_rows_where(
+                comp,
+                &mut self.string_pool,
+                &self.tables,
+                table,
+                ReferentialIntegrity::Ignore,
+                &|row| {
+                    let key: Vec<Value> = key_indices
+                        .iter()
+                        .map(|&index| row[index].clone())
+                        .collect();
+                    removed.contains(&key)
+                },
+            )?;
+        }
+        let rows: Vec<Vec<Value>> = new_rows.into_values().flatten().collect();
+        if !rows.is_empty() {
+            Insert::into(table.name()).rows(rows).exec(
+                comp,
+                &mut self.string_pool,
+                &self.tables,
+                ReferentialIntegrity::Ignore,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Attempts to execute a delete query.  Returns an error without modifying
-    /// the database if the query fails (e.g. due to the table not existing).
+    /// the database if the query fails (e.g. due to the table not existing,
+    /// or other rows still referring to a deleted key when foreign keys are
+    /// restricted).  When foreign keys cascade, the rows referring to the
+    /// deleted keys are deleted as well.
     pub fn delete_rows(&mut self, query: Delete) -> io::Result<()> {
         self.set_finisher();
         query.exec(
             self.comp.as_mut().unwrap(),
             &mut self.string_pool,
             &self.tables,
+            self.referential_integrity,
         )
     }
 
     /// Attempts to execute an insert query.  Returns an error without
     /// modifying the database if the query fails (e.g. due to values being
-    /// invalid, or keys not being unique, or the table not existing).
+    /// invalid, or keys not being unique, or the table not existing, or
+    /// foreign keys referring to missing rows when they are enforced).
     pub fn insert_rows(&mut self, query: Insert) -> io::Result<()> {
         self.set_finisher();
         query.exec(
             self.comp.as_mut().unwrap(),
             &mut self.string_pool,
             &self.tables,
+            self.referential_integrity,
         )
     }
 
     /// Attempts to execute an update query.  Returns an error without
     /// modifying the database if the query fails (e.g. due to values being
-    /// invalid, or column names being incorrect, or the table not existing).
+    /// invalid, or column names being incorrect, or the table not existing,
+    /// or foreign keys referring to missing rows when they are enforced).
+    /// When foreign keys cascade, changing a key also changes the rows
+    /// referring to it.
     pub fn update_rows(&mut self, query: Update) -> io::Result<()> {
         self.set_finisher();
         query.exec(
             self.comp.as_mut().unwrap(),
             &mut self.string_pool,
             &self.tables,
+            self.referential_integrity,
         )
     }
 
+    /// Executes a parsed SQL statement.  Returns the selected rows for a
+    /// `SELECT` statement, and `None` for every other kind of statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use msi::{Package, PackageType, Statement};
+    /// use std::io::Cursor;
+    ///
+    /// let cursor = Cursor::new(Vec::new());
+    /// let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    /// for sql in [
+    ///     "CREATE TABLE `Numbers` (`Number` SHORT NOT NULL, \
+    ///      `Word` CHAR(16) PRIMARY KEY `Number`)",
+    ///     "INSERT INTO `Numbers` (`Number`, `Word`) VALUES (2, 'Two')",
+    ///     "INSERT INTO `Numbers` (`Number`, `Word`) VALUES (1, 'One')",
+    /// ] {
+    ///     package.execute(sql.parse().unwrap()).unwrap();
+    /// }
+    /// let query = Statement::parse(
+    ///     "SELECT `Word` FROM `Numbers` WHERE `Number` > 1",
+    /// )
+    /// .unwrap();
+    /// let rows: Vec<_> = package.execute(query).unwrap().unwrap().collect();
+    /// assert_eq!(rows.len(), 1);
+    /// assert_eq!(rows[0]["Word"].as_str(), Some("Two"));
+    /// ```
+    pub fn execute(
+        &mut self,
+        statement: Statement,
+    ) -> io::Result<Option<Rows<'_>>> {
+        match statement {
+            Statement::Select(query) => {
+                return self.select_rows(query).map(Some);
+            }
+            Statement::Insert(query) => self.insert_rows(query)?,
+            Statement::Update(query) => self.update_rows(query)?,
+            Statement::Delete(query) => self.delete_rows(query)?,
+            Statement::CreateTable(table_name, columns) => {
+                self.create_table(table_name, columns)?
+            }
+            Statement::DropTable(table_name) => {
+                self.drop_table(&table_name)?
+            }
+            Statement::AddColumn(table_name, column) => {
+                self.add_column(&table_name, column)?
+            }
+        }
+        Ok(None)
+    }
+
     /// Creates (or overwrites) a binary stream in the package.
     pub fn write_stream(
         &mut self,
@@ -767,7 +1480,68 @@ impl<F: Read + Write + Seek> Package<F> {
         self.comp_mut().remove_stream(&encoded_name)
     }
 
-    // TODO: pub fn add_digital_signature(&mut self, ...) -> io::Result<()>
+    /// Embeds a transform in the package, replacing any existing embedded
+    /// transform with the same name.  Names are limited to 31 characters.
+    pub fn write_embedded_transform(
+        &mut self,
+        name: &str,
+        transform: &Transform,
+    ) -> io::Result<()> {
+        let path = embedded_transform_path(name)?;
+        if self.comp().is_storage(&path) {
+            self.comp_mut().remove_storage_all(&path)?;
+        }
+        self.comp_mut().create_storage(&path)?;
+        transform.write_storage(self.comp_mut(), &path)
+    }
+
+    /// Removes an existing embedded transform from the package.
+    pub fn remove_embedded_transform(&mut self, name: &str) -> io::Result<()> {
+        let path = embedded_transform_path(name)?;
+        if !self.comp().is_storage(&path) {
+            not_found!("Embedded transform {:?} does not exist", name);
+        }
+        self.comp_mut().remove_storage_all(&path)
+    }
+
+    /// Signs the package with an Authenticode signature, replacing any
+    /// existing signature.  The first certificate in the chain must be the
+    /// signer's certificate, matching the private key; any further
+    /// certificates (such as intermediate CA certificates) are embedded in
+    /// the signature so that verifiers can build a chain of trust.
+    ///
+    /// Any pending changes are flushed first.  Modifying the package after
+    /// signing it will invalidate the signature.
+    #[cfg(feature = "signing")]
+    pub fn add_digital_signature(
+        &mut self,
+        chain: &[Certificate],
+        key: &PrivateKey,
+    ) -> io::Result<()> {
+        if chain.is_empty() {
+            invalid_input!("No signing certificate given");
+        }
+        if !key.matches(&chain[0]) {
+            invalid_input!(
+                "Private key does not match the signing certificate"
+            );
+        }
+        self.remove_digital_signature()?;
+        self.flush()?;
+        let metadata_digest = signature::metadata_digest(self.comp())?;
+        let digest = signature::content_digest(
+            self.comp_mut(),
+            Some(&metadata_digest),
+        )?;
+        let data = signature::sign(&digest, chain, key)?;
+        self.comp_mut()
+            .create_stream(MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME)?
+            .write_all(&metadata_digest)?;
+        self.comp_mut()
+            .create_stream(DIGITAL_SIGNATURE_STREAM_NAME)?
+            .write_all(&data)?;
+        Ok(())
+    }
 
     /// Removes any existing digital signature from the package.  This can be
     /// useful if you need to modify a signed package (which will invalidate
@@ -809,6 +1583,20 @@ impl<F> Drop for Package<F> {
 
 // ========================================================================= //
 
+// Embedded transforms are sub-storages of the root storage, whose names are
+// not encoded like stream names.
+fn embedded_transform_path(name: &str) -> io::Result<PathBuf> {
+    if name.is_empty()
+        || name.encode_utf16().count() > 31
+        || name.contains(['/', '\\', ':', '!'])
+    {
+        invalid_input!("{:?} is not a valid embedded transform name", name);
+    }
+    Ok(PathBuf::from("/").join(name))
+}
+
+// ========================================================================= //
+
 /// An iterator over the database tables in a package.
 ///
 /// No guarantees are made about the order in which items are returned.
@@ -851,6 +1639,11 @@ impl<F: Read + Write + Seek> Finish<F> for FinishImpl {
             package.is_summary_info_modified = false;
         }
         if package.string_pool.is_modified() {
+            table::upgrade_string_refs(
+                package.comp.as_mut().unwrap(),
+                &package.string_pool,
+                &package.tables,
+            )?;
             {
                 let name = streamname::encode(STRING_POOL_TABLE_NAME, true);
                 let stream =
@@ -873,13 +1666,13 @@ impl<F: Read + Write + Seek> Finish<F> for FinishImpl {
 
 #[cfg(test)]
 mod tests {
-    use super::{Package, PackageType};
+    use super::{Package, PackageType, make_validation_table};
     use crate::internal::codepage::CodePage;
     use crate::internal::column::Column;
     use crate::internal::expr::Expr;
     use crate::internal::query::{Insert, Select, Update};
-    use crate::internal::value::Value;
-    use std::io::Cursor;
+    use crate::internal::value::{Value, ValueRef};
+    use std::io::{Cursor, ErrorKind};
 
     #[test]
     fn set_database_codepage() {
@@ -895,6 +1688,30 @@ mod tests {
         assert_eq!(package.database_codepage(), CodePage::MacintoshRoman);
     }
 
+    #[test]
+    fn validation_row_with_null_key() {
+        let cursor = Cursor::new(Vec::new());
+        let mut package =
+            Package::create(PackageType::Installer, cursor).expect("create");
+        let columns = vec![Column::build("Key").primary_key().id_string(72)];
+        package.create_table("Things", columns).expect("create_table");
+        let table = make_validation_table(false);
+        let stream_name = table.stream_name();
+        let stream = package.comp_mut().open_stream(&stream_name).unwrap();
+        let mut rows = table.read_rows(stream).unwrap();
+        rows[0][0] = ValueRef::Null;
+        let stream = package.comp_mut().create_stream(&stream_name).unwrap();
+        table.write_rows(stream, rows).unwrap();
+
+        let cursor = package.into_inner().expect("into_inner");
+        let error = Package::open(cursor).err().expect("open");
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "\"_Validation\" table has a row with a null key"
+        );
+    }
+
     #[test]
     fn insert_rows() {
         let cursor = Cursor::new(Vec::new());
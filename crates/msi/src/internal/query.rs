@@ -1,6 +1,7 @@
 use crate::internal::expr::Expr;
+use crate::internal::integrity::{self, ReferentialIntegrity};
 use crate::internal::stringpool::StringPool;
-use crate::internal::table::{Row, Rows, Table};
+use crate::internal::table::{self, Row, Rows, Table};
 use crate::internal::value::{Value, ValueRef};
 use cfb;
 use std::collections::{BTreeMap, HashSet};
@@ -40,6 +41,7 @@ impl Delete {
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &mut StringPool,
         tables: &BTreeMap<String, Rc<Table>>,
+        integrity: ReferentialIntegrity,
     ) -> io::Result<()>
     where
         F: Read + Write + Seek,
@@ -60,42 +62,133 @@ impl Delete {
                 }
             }
         }
-        // Read in the rows from the table.
-        let stream_name = table.stream_name();
-        let mut rows = if comp.exists(&stream_name) {
-            let stream = comp.open_stream(&stream_name)?;
-            table.read_rows(stream)?
-        } else {
-            Vec::new()
-        };
-        // Delete rows from the table.
-        rows.retain(|value_refs| {
-            let should_delete = match self.condition {
-                Some(ref expr) => {
-                    let values: Vec<Value> = value_refs
-                        .iter()
-                        .map(|value_ref| value_ref.to_value(string_pool))
-                        .collect();
-                    let row = Row::new(table.clone(), values);
-                    expr.eval(&row).to_bool()
-                }
+        delete_rows_where(
+            comp,
+            string_pool,
+            tables,
+            table,
+            integrity,
+            &|row| match self.condition {
+                Some(ref expr) => expr.eval(row).to_bool(),
                 None => true,
+            },
+        )
+    }
+}
+
+/// Deletes the rows of `table` that match the predicate, then enforces
+/// foreign keys referring to the deleted rows.
+pub(crate) fn delete_rows_where<F>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &mut StringPool,
+    tables: &BTreeMap<String, Rc<Table>>,
+    table: &Rc<Table>,
+    integrity: ReferentialIntegrity,
+    should_delete: &dyn Fn(&Row) -> bool,
+) -> io::Result<()>
+where
+    F: Read + Write + Seek,
+{
+    // Read in the rows from the table.
+    let stream_name = table.stream_name();
+    let rows = if comp.exists(&stream_name) {
+        let stream = comp.open_stream(&stream_name)?;
+        table.read_rows(stream)?
+    } else {
+        Vec::new()
+    };
+    // Sort out which rows to delete.
+    let mut kept_rows = Vec::<Vec<ValueRef>>::with_capacity(rows.len());
+    let mut kept_values = Vec::<Vec<Value>>::with_capacity(rows.len());
+    let mut deleted_rows = Vec::<Vec<ValueRef>>::new();
+    let mut deleted_values = Vec::<Vec<Value>>::new();
+    for value_refs in rows.into_iter() {
+        let values: Vec<Value> = value_refs
+            .iter()
+            .map(|value_ref| value_ref.to_value(string_pool))
+            .collect();
+        let row = Row::new(table.clone(), values);
+        if should_delete(&row) {
+            deleted_rows.push(value_refs);
+            deleted_values.push(row.into_values());
+        } else {
+            kept_rows.push(value_refs);
+            kept_values.push(row.into_values());
+        }
+    }
+    if deleted_rows.is_empty() {
+        return Ok(());
+    }
+    // Find the rows of other tables that refer to the deleted keys.
+    let mut cascades = Vec::<(Rc<Table>, usize, HashSet<Value>)>::new();
+    if integrity != ReferentialIntegrity::Ignore {
+        for reference in integrity::references_to(tables, table.name()) {
+            let remaining: HashSet<&Value> = kept_values
+                .iter()
+                .map(|values| &values[reference.key_column])
+                .collect();
+            let removed: HashSet<Value> = deleted_values
+                .iter()
+                .map(|values| &values[reference.key_column])
+                .filter(|value| !value.is_null() && !remaining.contains(value))
+                .cloned()
+                .collect();
+            if removed.is_empty() {
+                continue;
+            }
+            let referring_values = if reference.table.name() == table.name() {
+                kept_values.clone()
+            } else {
+                integrity::read_values(comp, string_pool, &reference.table)?
             };
-            // TODO: Handle deleting rows referred to by foreign keys.
-            if should_delete {
-                for value_ref in value_refs.iter() {
-                    value_ref.remove(string_pool);
+            let referring_row = referring_values
+                .iter()
+                .find(|values| removed.contains(&values[reference.column]));
+            if let Some(referring_row) = referring_row {
+                if integrity == ReferentialIntegrity::Restrict {
+                    let value = &referring_row[reference.column];
+                    let deleted_row = deleted_values
+                        .iter()
+                        .find(|values| values[reference.key_column] == *value)
+                        .unwrap();
+                    invalid_input!(
+                        "Cannot delete row {} of table {:?}, because row {} \
+                         of table {:?} refers to it in column {:?}",
+                        integrity::describe_row(table, deleted_row),
+                        table.name(),
+                        integrity::describe_row(
+                            &reference.table,
+                            referring_row
+                        ),
+                        reference.table.name(),
+                        reference.table.columns()[reference.column].name()
+                    );
                 }
-                false
-            } else {
-                true
+                cascades.push((reference.table, reference.column, removed));
             }
-        });
-        // Write the table back out to the file.
-        let stream = comp.create_stream(&stream_name)?;
-        table.write_rows(stream, rows)?;
-        Ok(())
+        }
+    }
+    // Delete rows from the table, and write it back out to the file.
+    for value_refs in deleted_rows.into_iter() {
+        for value_ref in value_refs.into_iter() {
+            value_ref.remove(string_pool);
+        }
     }
+    table::upgrade_string_refs(comp, string_pool, tables)?;
+    let stream = comp.create_stream(&stream_name)?;
+    table.write_rows(stream, kept_rows)?;
+    // Delete the rows referring to the deleted keys.
+    for (referring_table, column, removed) in cascades.into_iter() {
+        delete_rows_where(
+            comp,
+            string_pool,
+            tables,
+            &referring_table,
+            integrity,
+            &|row| removed.contains(&row[column]),
+        )?;
+    }
+    Ok(())
 }
 
 impl fmt::Display for Delete {
@@ -115,13 +208,29 @@ impl fmt::Display for Delete {
 /// A database query to insert new rows.
 pub struct Insert {
     table_name: String,
+    column_names: Vec<String>,
     new_rows: Vec<Vec<Value>>,
 }
 
 impl Insert {
     /// Starts building a query that will insert rows into the specified table.
     pub fn into<S: Into<String>>(table_name: S) -> Insert {
-        Insert { table_name: table_name.into(), new_rows: Vec::new() }
+        Insert {
+            table_name: table_name.into(),
+            column_names: Vec::new(),
+            new_rows: Vec::new(),
+        }
+    }
+
+    /// Makes the rows to be inserted only provide values for the specified
+    /// columns, in the order given; all other columns will be set to null.
+    pub fn columns<S>(mut self, column_names: &[S]) -> Insert
+    where
+        S: Clone + Into<String>,
+    {
+        self.column_names =
+            column_names.iter().cloned().map(|name| name.into()).collect();
+        self
     }
 
     /// Adds a new row to be inserted into the table.
@@ -137,10 +246,11 @@ impl Insert {
     }
 
     pub(crate) fn exec<F>(
-        self,
+        mut self,
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &mut StringPool,
         tables: &BTreeMap<String, Rc<Table>>,
+        integrity: ReferentialIntegrity,
     ) -> io::Result<()>
     where
         F: Read + Write + Seek,
@@ -149,6 +259,38 @@ impl Insert {
             Some(table) => table,
             None => not_found!("Table {:?} does not exist", self.table_name),
         };
+        // Expand rows given for a subset of the columns into full rows.
+        if !self.column_names.is_empty() {
+            let mut column_indices =
+                Vec::<usize>::with_capacity(self.column_names.len());
+            for column_name in self.column_names.iter() {
+                match table.index_for_column_name(column_name) {
+                    Some(index) => column_indices.push(index),
+                    None => invalid_input!(
+                        "Table {:?} has no column named {:?}",
+                        self.table_name,
+                        column_name
+                    ),
+                }
+            }
+            for values in self.new_rows.iter_mut() {
+                if values.len() != column_indices.len() {
+                    invalid_input!(
+                        "{} columns were specified, but a row with {} values \
+                         was provided",
+                        column_indices.len(),
+                        values.len()
+                    );
+                }
+                let mut row = vec![Value::Null; table.columns().len()];
+                for (&index, value) in
+                    column_indices.iter().zip(values.drain(..))
+                {
+                    row[index] = value;
+                }
+                *values = row;
+            }
+        }
         // Validate the new rows.
         for values in self.new_rows.iter() {
             if values.len() != table.columns().len() {
@@ -168,9 +310,26 @@ impl Insert {
                         column.name()
                     );
                 }
-                // TODO: Validate foreign keys.
             }
         }
+        // Check that the new rows only refer to existing keys.
+        let foreign_keys = table.foreign_key_indices();
+        if integrity != ReferentialIntegrity::Ignore
+            && !foreign_keys.is_empty()
+        {
+            let mut own_rows =
+                integrity::read_values(comp, string_pool, table)?;
+            own_rows.extend(self.new_rows.iter().cloned());
+            integrity::check_foreign_keys(
+                comp,
+                string_pool,
+                tables,
+                table,
+                &foreign_keys,
+                &self.new_rows,
+                &own_rows,
+            )?;
+        }
         // Read in the rows from the table.
         let stream_name = table.stream_name();
         let key_indices = table.primary_key_indices();
@@ -235,6 +394,7 @@ impl Insert {
         }
 
         // Write the table back out to the file.
+        table::upgrade_string_refs(comp, string_pool, tables)?;
         let stream = comp.create_stream(&stream_name)?;
 
         let rows2: Vec<Vec<ValueRef>> = rows_map.into_values().collect();
@@ -247,6 +407,11 @@ impl fmt::Display for Insert {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         formatter.write_str("INSERT INTO ")?;
         formatter.write_str(&self.table_name)?;
+        if !self.column_names.is_empty() {
+            formatter.write_str(" (")?;
+            formatter.write_str(&self.column_names.join(", "))?;
+            formatter.write_str(")")?;
+        }
         if !self.new_rows.is_empty() {
             formatter.write_str(" VALUES ")?;
             let mut outer_comma = false;
@@ -439,6 +604,7 @@ pub struct Select {
     from: Join,
     column_names: Vec<String>,
     condition: Option<Expr>,
+    order_by: Vec<String>,
 }
 
 impl Select {
@@ -448,6 +614,7 @@ impl Select {
             from: Join::Table(table_name.into()),
             column_names: vec![],
             condition: None,
+            order_by: vec![],
         }
     }
 
@@ -458,6 +625,7 @@ impl Select {
             from: Join::Inner(Box::new(self), Box::new(rhs), on),
             column_names: vec![],
             condition: None,
+            order_by: vec![],
         }
     }
 
@@ -467,6 +635,7 @@ impl Select {
             from: Join::Left(Box::new(self), Box::new(rhs), on),
             column_names: vec![],
             condition: None,
+            order_by: vec![],
         }
     }
 
@@ -496,6 +665,17 @@ impl Select {
         self
     }
 
+    /// Sorts the selected rows in ascending order of the specified columns;
+    /// later columns are only used to break ties between earlier ones.
+    pub fn order_by<S>(mut self, column_names: &[S]) -> Select
+    where
+        S: Clone + Into<String>,
+    {
+        self.order_by =
+            column_names.iter().cloned().map(|name| name.into()).collect();
+        self
+    }
+
     pub(crate) fn exec<'a, F>(
         self,
         comp: &mut cfb::CompoundFile<F>,
@@ -523,6 +703,21 @@ impl Select {
                 }
             }
         }
+        // Validate the sort column names.
+        let mut sort_indices =
+            Vec::<usize>::with_capacity(self.order_by.len());
+        for column_name in self.order_by.iter() {
+            match table.index_for_column_name(column_name.as_str()) {
+                Some(index) => sort_indices.push(index),
+                None => {
+                    invalid_input!(
+                        "Table {:?} has no column named {:?}",
+                        table.name(),
+                        column_name
+                    );
+                }
+            }
+        }
         // Validate the condition.
         if let Some(ref expr) = self.condition {
             for column_name in expr.column_names().into_iter() {
@@ -546,6 +741,15 @@ impl Select {
                 condition.eval(&row).to_bool()
             });
         }
+        // Sort the rows by the specified columns.
+        if !sort_indices.is_empty() {
+            rows.sort_by_cached_key(|value_refs| {
+                sort_indices
+                    .iter()
+                    .map(|&index| value_refs[index].to_value(string_pool))
+                    .collect::<Vec<Value>>()
+            });
+        }
         // Limit the table to the specified columns.
         if !column_indices.is_empty() {
             let columns = column_indices
@@ -570,6 +774,7 @@ impl Select {
     ) -> Result<(), fmt::Error> {
         if self.column_names.is_empty()
             && self.condition.is_none()
+            && self.order_by.is_empty()
             && let Join::Table(ref name) = self.from
         {
             return formatter.write_str(name.as_str());
@@ -603,6 +808,10 @@ impl fmt::Display for Select {
             formatter.write_str(" WHERE ")?;
             expr.fmt(formatter)?;
         }
+        if !self.order_by.is_empty() {
+            formatter.write_str(" ORDER BY ")?;
+            formatter.write_str(&self.order_by.join(", "))?;
+        }
         Ok(())
     }
 }
@@ -654,6 +863,7 @@ impl Update {
         comp: &mut cfb::CompoundFile<F>,
         string_pool: &mut StringPool,
         tables: &BTreeMap<String, Rc<Table>>,
+        integrity: ReferentialIntegrity,
     ) -> io::Result<()>
     where
         F: Read + Write + Seek,
@@ -663,23 +873,24 @@ impl Update {
             None => not_found!("Table {:?} does not exist", self.table_name),
         };
         // Validate the updates.
-        for (column_name, value) in self.updates.iter() {
-            if !table.has_column(column_name.as_str()) {
-                invalid_input!(
+        let mut updates = Vec::<(usize, Value)>::new();
+        for (column_name, value) in self.updates.into_iter() {
+            let index = match table.index_for_column_name(&column_name) {
+                Some(index) => index,
+                None => invalid_input!(
                     "Table {:?} has no column named {:?}",
                     self.table_name,
                     column_name
-                );
-            }
-            let column = table.get_column(column_name).unwrap();
-            if !column.is_valid_value(value) {
+                ),
+            };
+            if !table.columns()[index].is_valid_value(&value) {
                 invalid_input!(
                     "{} is not a valid value for column {:?}",
                     value,
                     column_name
                 );
             }
-            // TODO: Validate foreign keys.
+            updates.push((index, value));
         }
         // Validate the condition.
         if let Some(ref expr) = self.condition {
@@ -693,42 +904,169 @@ impl Update {
                 }
             }
         }
-        // Read in the rows from the table.
-        let stream_name = table.stream_name();
-        let mut rows = if comp.exists(&stream_name) {
-            let stream = comp.open_stream(&stream_name)?;
-            table.read_rows(stream)?
+        let condition = self.condition;
+        update_rows_where(
+            comp,
+            string_pool,
+            tables,
+            table,
+            integrity,
+            &updates,
+            &|row| match condition {
+                Some(ref expr) => expr.eval(row).to_bool(),
+                None => true,
+            },
+        )
+    }
+}
+
+/// Sets the given column values in the rows of `table` that match the
+/// predicate, enforcing foreign keys of and referring to the updated columns.
+fn update_rows_where<F>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &mut StringPool,
+    tables: &BTreeMap<String, Rc<Table>>,
+    table: &Rc<Table>,
+    integrity: ReferentialIntegrity,
+    updates: &[(usize, Value)],
+    should_update: &dyn Fn(&Row) -> bool,
+) -> io::Result<()>
+where
+    F: Read + Write + Seek,
+{
+    // Read in the rows from the table.
+    let stream_name = table.stream_name();
+    let mut rows = if comp.exists(&stream_name) {
+        let stream = comp.open_stream(&stream_name)?;
+        table.read_rows(stream)?
+    } else {
+        Vec::new()
+    };
+    // Compute the updated rows.
+    let mut updated_indices = Vec::<usize>::new();
+    let mut old_values = Vec::<Vec<Value>>::with_capacity(rows.len());
+    let mut new_values = Vec::<Vec<Value>>::with_capacity(rows.len());
+    for (row_index, value_refs) in rows.iter().enumerate() {
+        let values: Vec<Value> = value_refs
+            .iter()
+            .map(|value_ref| value_ref.to_value(string_pool))
+            .collect();
+        let row = Row::new(table.clone(), values);
+        let values = if should_update(&row) {
+            updated_indices.push(row_index);
+            let mut values = row.into_values();
+            old_values.push(values.clone());
+            for (index, value) in updates.iter() {
+                values[*index] = value.clone();
+            }
+            values
         } else {
-            Vec::new()
+            row.into_values()
         };
-        // Update the rows.
-        for value_refs in rows.iter_mut() {
-            let should_update = match self.condition {
-                Some(ref expr) => {
-                    let values: Vec<Value> = value_refs
-                        .iter()
-                        .map(|value_ref| value_ref.to_value(string_pool))
-                        .collect();
-                    let row = Row::new(table.clone(), values);
-                    expr.eval(&row).to_bool()
+        new_values.push(values);
+    }
+    // Enforce foreign keys of and referring to the updated columns.
+    let mut cascades =
+        Vec::<(Rc<Table>, usize, BTreeMap<Value, Value>)>::new();
+    if integrity != ReferentialIntegrity::Ignore && !updated_indices.is_empty()
+    {
+        let foreign_keys: Vec<usize> = updates
+            .iter()
+            .map(|&(index, _)| index)
+            .filter(|&index| table.columns()[index].is_foreign_key())
+            .collect();
+        let updated_rows: Vec<Vec<Value>> = updated_indices
+            .iter()
+            .map(|&index| new_values[index].clone())
+            .collect();
+        integrity::check_foreign_keys(
+            comp,
+            string_pool,
+            tables,
+            table,
+            &foreign_keys,
+            &updated_rows,
+            &new_values,
+        )?;
+        for reference in integrity::references_to(tables, table.name()) {
+            let key_column = reference.key_column;
+            if !updates.iter().any(|&(index, _)| index == key_column) {
+                continue;
+            }
+            let remaining: HashSet<&Value> =
+                new_values.iter().map(|values| &values[key_column]).collect();
+            let mut changes = BTreeMap::<Value, Value>::new();
+            for (old, new) in old_values.iter().zip(updated_rows.iter()) {
+                let value = &old[key_column];
+                if !value.is_null() && !remaining.contains(value) {
+                    changes.insert(value.clone(), new[key_column].clone());
                 }
-                None => true,
+            }
+            if changes.is_empty() {
+                continue;
+            }
+            let referring_values = if reference.table.name() == table.name() {
+                new_values.clone()
+            } else {
+                integrity::read_values(comp, string_pool, &reference.table)?
             };
-            if should_update {
-                for (column_name, value) in self.updates.iter() {
-                    let index =
-                        table.index_for_column_name(column_name).unwrap();
-                    let value_ref = &mut value_refs[index];
-                    value_ref.remove(string_pool);
-                    *value_ref = ValueRef::create(value.clone(), string_pool);
+            let referring_row = referring_values.iter().find(|values| {
+                changes.contains_key(&values[reference.column])
+            });
+            if let Some(referring_row) = referring_row {
+                if integrity == ReferentialIntegrity::Restrict {
+                    let value = &referring_row[reference.column];
+                    let old_row = old_values
+                        .iter()
+                        .find(|values| values[key_column] == *value)
+                        .unwrap();
+                    invalid_input!(
+                        "Cannot change {} in column {:?} of row {} of table \
+                         {:?}, because row {} of table {:?} refers to it in \
+                         column {:?}",
+                        value,
+                        table.columns()[key_column].name(),
+                        integrity::describe_row(table, old_row),
+                        table.name(),
+                        integrity::describe_row(
+                            &reference.table,
+                            referring_row
+                        ),
+                        reference.table.name(),
+                        reference.table.columns()[reference.column].name()
+                    );
                 }
+                cascades.push((reference.table, reference.column, changes));
             }
         }
-        // Write the table back out to the file.
-        let stream = comp.create_stream(&stream_name)?;
-        table.write_rows(stream, rows)?;
-        Ok(())
     }
+    // Update the rows, and write the table back out to the file.
+    for &row_index in updated_indices.iter() {
+        let value_refs = &mut rows[row_index];
+        for (index, value) in updates.iter() {
+            let value_ref = &mut value_refs[*index];
+            value_ref.remove(string_pool);
+            *value_ref = ValueRef::create(value.clone(), string_pool);
+        }
+    }
+    table::upgrade_string_refs(comp, string_pool, tables)?;
+    let stream = comp.create_stream(&stream_name)?;
+    table.write_rows(stream, rows)?;
+    // Update the rows referring to the changed keys.
+    for (referring_table, column, changes) in cascades.into_iter() {
+        for (old, new) in changes.into_iter() {
+            update_rows_where(
+                comp,
+                string_pool,
+                tables,
+                &referring_table,
+                integrity,
+                &[(column, new)],
+                &|row| row[column] == old,
+            )?;
+        }
+    }
+    Ok(())
 }
 
 impl fmt::Display for Update {
@@ -800,6 +1138,14 @@ mod tests {
             "INSERT INTO Foobar VALUES (1, 2), (3, 4), (5, 6), (7, 8)"
                 .to_string()
         );
+
+        let query = Insert::into("Foobar")
+            .columns(&["Foo", "Bar"])
+            .row(vec![Value::Int(1), Value::from("One")]);
+        assert_eq!(
+            format!("{query}"),
+            "INSERT INTO Foobar (Foo, Bar) VALUES (1, \"One\")".to_string()
+        );
     }
 
     #[test]
@@ -815,6 +1161,15 @@ mod tests {
             "SELECT Foo, Bar FROM Foobar WHERE Foo < 17".to_string()
         );
 
+        let query = Select::table("Foobar")
+            .with(Expr::col("Foo").lt(Expr::integer(17)))
+            .order_by(&["Bar", "Foo"]);
+        assert_eq!(
+            format!("{query}"),
+            "SELECT * FROM Foobar WHERE Foo < 17 ORDER BY Bar, Foo"
+                .to_string()
+        );
+
         let query = Select::table("Foobar")
             .inner_join(
                 Select::table("Quux"),
@@ -0,0 +1,606 @@
+use crate::internal::der::{
+    self, Element, TAG_INTEGER, TAG_OCTET_STRING, TAG_SEQUENCE, TAG_SET,
+    context_tag,
+};
+use crate::internal::streamname::{
+    DIGITAL_SIGNATURE_STREAM_NAME, MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME,
+};
+use crate::internal::timestamp::Timestamp;
+use cfb;
+use rand_core::OsRng;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fs;
+use std::io::{self, Read, Seek};
+use std::path::Path;
+
+// ========================================================================= //
+
+const OID_SIGNED_DATA: &[u64] = &[1, 2, 840, 113549, 1, 7, 2];
+const OID_RSA_ENCRYPTION: &[u64] = &[1, 2, 840, 113549, 1, 1, 1];
+const OID_CONTENT_TYPE: &[u64] = &[1, 2, 840, 113549, 1, 9, 3];
+const OID_MESSAGE_DIGEST: &[u64] = &[1, 2, 840, 113549, 1, 9, 4];
+const OID_SHA256: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 2, 1];
+const OID_SPC_INDIRECT_DATA: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 1, 4];
+const OID_SPC_STATEMENT_TYPE: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 1, 11];
+const OID_SPC_SP_OPUS_INFO: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 1, 12];
+const OID_SPC_INDIVIDUAL_SP_KEY_PURPOSE: &[u64] =
+    &[1, 3, 6, 1, 4, 1, 311, 2, 1, 21];
+const OID_SPC_SIPINFO: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 1, 30];
+
+// The subject interface package GUID that identifies the signed content as
+// an MSI package, in the byte order Authenticode stores it.
+const MSI_SIP_GUID: [u8; 16] = [
+    0xf1, 0x10, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x46,
+];
+
+// ========================================================================= //
+
+/// An X.509 certificate with an RSA public key, used for signing packages
+/// and verifying their digital signatures.
+#[derive(Clone)]
+pub struct Certificate {
+    der: Vec<u8>,
+    issuer: Vec<u8>,
+    serial_number: Vec<u8>,
+    public_key: RsaPublicKey,
+}
+
+impl Certificate {
+    /// Parses a DER-encoded certificate.
+    pub fn from_der(der: &[u8]) -> io::Result<Certificate> {
+        let certificate = Element::parse(der)?.expect(TAG_SEQUENCE)?;
+        let fields = certificate.children()?;
+        if fields.len() != 3 {
+            invalid_data!("Malformed X.509 certificate");
+        }
+        let mut tbs_fields =
+            fields[0].expect(TAG_SEQUENCE)?.children()?.into_iter();
+        let mut next_field = || {
+            tbs_fields.next().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Malformed X.509 certificate",
+                )
+            })
+        };
+        let mut serial_number = next_field()?;
+        if serial_number.tag() == context_tag(0) {
+            // Skip the optional version field.
+            serial_number = next_field()?;
+        }
+        let serial_number = serial_number.expect(TAG_INTEGER)?;
+        let _signature_algorithm = next_field()?;
+        let issuer = next_field()?.expect(TAG_SEQUENCE)?;
+        let _validity = next_field()?;
+        let _subject = next_field()?;
+        let public_key_info = next_field()?.expect(TAG_SEQUENCE)?;
+        let public_key =
+            match RsaPublicKey::from_public_key_der(public_key_info.encoded())
+            {
+                Ok(public_key) => public_key,
+                Err(_) => {
+                    invalid_data!(
+                        "Certificate does not have an RSA public key"
+                    )
+                }
+            };
+        Ok(Certificate {
+            der: der.to_vec(),
+            issuer: issuer.encoded().to_vec(),
+            serial_number: serial_number.encoded().to_vec(),
+            public_key,
+        })
+    }
+
+    /// Parses a PEM-encoded certificate.  If the data contains several
+    /// certificates, returns the first one.
+    pub fn from_pem(pem: &str) -> io::Result<Certificate> {
+        match Certificate::chain_from_pem(pem)?.into_iter().next() {
+            Some(certificate) => Ok(certificate),
+            None => invalid_data!("No certificate found in PEM data"),
+        }
+    }
+
+    /// Parses all the PEM-encoded certificates in the data, in order.
+    pub fn chain_from_pem(pem: &str) -> io::Result<Vec<Certificate>> {
+        pem_blocks(pem)?
+            .into_iter()
+            .filter(|(label, _)| label == "CERTIFICATE")
+            .map(|(_, der)| Certificate::from_der(&der))
+            .collect()
+    }
+
+    /// Reads a certificate from a PEM or DER file.  If a PEM file contains
+    /// several certificates, returns the first one.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Certificate> {
+        let data = fs::read(path)?;
+        match pem_text(&data) {
+            Some(pem) => Certificate::from_pem(pem),
+            None => Certificate::from_der(&data),
+        }
+    }
+
+    /// Reads a certificate chain from a PEM or DER file.  A PEM file may
+    /// contain several certificates, starting with the signer's certificate
+    /// and followed by any intermediate certificates.
+    pub fn load_chain<P: AsRef<Path>>(
+        path: P,
+    ) -> io::Result<Vec<Certificate>> {
+        let data = fs::read(path)?;
+        let chain = match pem_text(&data) {
+            Some(pem) => Certificate::chain_from_pem(pem)?,
+            None => vec![Certificate::from_der(&data)?],
+        };
+        if chain.is_empty() {
+            invalid_data!("No certificate found in PEM data");
+        }
+        Ok(chain)
+    }
+
+    /// Returns the DER encoding of this certificate.
+    pub fn as_der(&self) -> &[u8] {
+        &self.der
+    }
+}
+
+// ========================================================================= //
+
+/// An RSA private key, used for signing packages.
+pub struct PrivateKey {
+    key: RsaPrivateKey,
+}
+
+impl PrivateKey {
+    /// Parses a DER-encoded, unencrypted private key in either PKCS#8 or
+    /// PKCS#1 format.
+    pub fn from_der(der: &[u8]) -> io::Result<PrivateKey> {
+        match RsaPrivateKey::from_pkcs8_der(der)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_der(der))
+        {
+            Ok(key) => Ok(PrivateKey { key }),
+            Err(_) => invalid_data!("Not an unencrypted RSA private key"),
+        }
+    }
+
+    /// Parses a PEM-encoded, unencrypted private key in either PKCS#8 or
+    /// PKCS#1 format.
+    pub fn from_pem(pem: &str) -> io::Result<PrivateKey> {
+        for (label, der) in pem_blocks(pem)? {
+            match label.as_str() {
+                "PRIVATE KEY" | "RSA PRIVATE KEY" => {
+                    return PrivateKey::from_der(&der);
+                }
+                "ENCRYPTED PRIVATE KEY" => {
+                    invalid_data!("Encrypted private keys are not supported");
+                }
+                _ => {}
+            }
+        }
+        invalid_data!("No private key found in PEM data")
+    }
+
+    /// Reads a private key from a PEM or DER file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<PrivateKey> {
+        let data = fs::read(path)?;
+        match pem_text(&data) {
+            Some(pem) => PrivateKey::from_pem(pem),
+            None => PrivateKey::from_der(&data),
+        }
+    }
+
+    /// Returns true if this is the private key for the given certificate.
+    pub fn matches(&self, certificate: &Certificate) -> bool {
+        self.key.to_public_key() == certificate.public_key
+    }
+}
+
+// ========================================================================= //
+
+/// Returns the data as text if it looks like it holds PEM blocks.
+fn pem_text(data: &[u8]) -> Option<&str> {
+    std::str::from_utf8(data).ok().filter(|text| text.contains("-----BEGIN "))
+}
+
+/// Decodes each PEM block in the text, returning its label and data.
+fn pem_blocks(text: &str) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let mut blocks = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("-----BEGIN ") {
+        rest = &rest[start..];
+        let end = match rest.find("-----END ") {
+            Some(end_start) => match rest[end_start + 9..].find("-----") {
+                Some(index) => end_start + 9 + index + 5,
+                None => invalid_data!("Unterminated PEM block"),
+            },
+            None => invalid_data!("Unterminated PEM block"),
+        };
+        match pem_rfc7468::decode_vec(&rest.as_bytes()[..end]) {
+            Ok((label, data)) => blocks.push((label.to_string(), data)),
+            Err(error) => invalid_data!("Invalid PEM block: {}", error),
+        }
+        rest = &rest[end..];
+    }
+    Ok(blocks)
+}
+
+// ========================================================================= //
+
+/// Computes the digest of the package's storage metadata (names, sizes,
+/// CLSIDs, state bits, and timestamps), which is stored in the
+/// `MsiDigitalSignatureEx` stream.
+pub(crate) fn metadata_digest<F>(
+    comp: &cfb::CompoundFile<F>,
+) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    hash_storage_metadata(comp, Path::new("/"), &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Computes the digest of the package's contents that the signature covers.
+/// If the package has an `MsiDigitalSignatureEx` stream, its metadata digest
+/// must be given, and is hashed first.
+pub(crate) fn content_digest<F: Read + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    metadata_digest: Option<&[u8]>,
+) -> io::Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    if let Some(metadata_digest) = metadata_digest {
+        hasher.update(metadata_digest);
+    }
+    hash_storage_content(comp, Path::new("/"), &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Returns the entries in the given storage that the signature covers.
+fn signed_children<F>(
+    comp: &cfb::CompoundFile<F>,
+    path: &Path,
+) -> io::Result<Vec<cfb::Entry>> {
+    let is_root = comp.entry(path)?.is_root();
+    Ok(comp
+        .read_storage(path)?
+        .filter(|entry| {
+            !(is_root
+                && (entry.name() == DIGITAL_SIGNATURE_STREAM_NAME
+                    || entry.name() == MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME))
+        })
+        .collect())
+}
+
+fn hash_storage_content<F: Read + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    path: &Path,
+    hasher: &mut Sha256,
+) -> io::Result<()> {
+    // Streams are hashed in order of their UTF-16LE names, compared bytewise.
+    let mut children = signed_children(comp, path)?;
+    children.sort_by_cached_key(|entry| utf16le_bytes(entry.name()));
+    for child in children {
+        if child.is_stream() {
+            io::copy(&mut comp.open_stream(child.path())?, hasher)?;
+        } else {
+            hash_storage_content(comp, child.path(), hasher)?;
+        }
+    }
+    hasher.update(comp.entry(path)?.clsid().to_bytes_le());
+    Ok(())
+}
+
+fn hash_storage_metadata<F>(
+    comp: &cfb::CompoundFile<F>,
+    path: &Path,
+    hasher: &mut Sha256,
+) -> io::Result<()> {
+    // Entries are hashed in the order of the compound file's directory tree.
+    hash_entry_metadata(&comp.entry(path)?, hasher)?;
+    let mut children = signed_children(comp, path)?;
+    children.sort_by(|a, b| compare_directory_names(a.name(), b.name()));
+    for child in children {
+        if child.is_stream() {
+            hash_entry_metadata(&child, hasher)?;
+        } else {
+            hash_storage_metadata(comp, child.path(), hasher)?;
+        }
+    }
+    Ok(())
+}
+
+fn hash_entry_metadata(
+    entry: &cfb::Entry,
+    hasher: &mut Sha256,
+) -> io::Result<()> {
+    if !entry.is_root() {
+        hasher.update(utf16le_bytes(entry.name()));
+    }
+    if entry.is_stream() {
+        hasher.update((entry.len() as u32).to_le_bytes());
+    } else {
+        hasher.update(entry.clsid().to_bytes_le());
+    }
+    hasher.update(entry.state_bits().to_le_bytes());
+    if !entry.is_root() {
+        Timestamp::from_system_time(entry.created()).write_to(hasher)?;
+        Timestamp::from_system_time(entry.modified()).write_to(hasher)?;
+    }
+    Ok(())
+}
+
+fn utf16le_bytes(name: &str) -> Vec<u8> {
+    name.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Compares names the way compound file directories order them: shorter
+/// names first, then case-insensitively by UTF-16 code unit.
+fn compare_directory_names(name1: &str, name2: &str) -> Ordering {
+    let uppercase = |unit: u16| {
+        char::from_u32(unit as u32)
+            .and_then(|chr| {
+                let mut upper = chr.to_uppercase();
+                match (upper.next(), upper.next()) {
+                    (Some(upper), None) => u16::try_from(upper as u32).ok(),
+                    _ => None,
+                }
+            })
+            .unwrap_or(unit)
+    };
+    let units1: Vec<u16> = name1.encode_utf16().collect();
+    let units2: Vec<u16> = name2.encode_utf16().collect();
+    units1.len().cmp(&units2.len()).then_with(|| {
+        units1
+            .iter()
+            .map(|&unit| uppercase(unit))
+            .cmp(units2.iter().map(|&unit| uppercase(unit)))
+    })
+}
+
+// ========================================================================= //
+
+/// Builds the contents of the `DigitalSignature` stream: a PKCS#7
+/// SignedData structure holding an Authenticode signature of the given
+/// content digest.  The first certificate in the chain must be the signer's.
+pub(crate) fn sign(
+    digest: &[u8],
+    chain: &[Certificate],
+    key: &PrivateKey,
+) -> io::Result<Vec<u8>> {
+    let signer = &chain[0];
+    let sha256 = algorithm_identifier(OID_SHA256);
+    let indirect_data = spc_indirect_data_content(digest);
+    let indirect_data_digest =
+        Sha256::digest(Element::parse(&indirect_data)?.contents());
+    let attributes = [
+        attribute(OID_CONTENT_TYPE, &der::oid(OID_SPC_INDIRECT_DATA)),
+        attribute(OID_SPC_SP_OPUS_INFO, &der::sequence(&[])),
+        attribute(
+            OID_SPC_STATEMENT_TYPE,
+            &der::sequence(&[&der::oid(OID_SPC_INDIVIDUAL_SP_KEY_PURPOSE)]),
+        ),
+        attribute(
+            OID_MESSAGE_DIGEST,
+            &der::octet_string(&indirect_data_digest),
+        ),
+    ];
+    let attributes: Vec<&[u8]> =
+        attributes.iter().map(Vec::as_slice).collect();
+    // The signature covers the attributes encoded as a SET OF, but they are
+    // stored with an implicit [0] tag instead.
+    let mut signed_attributes = der::set_of(&attributes);
+    let padding = Pkcs1v15Sign::new::<Sha256>();
+    let signature = match key.key.sign_with_rng(
+        &mut OsRng,
+        padding,
+        &Sha256::digest(&signed_attributes),
+    ) {
+        Ok(signature) => signature,
+        Err(error) => invalid_input!("Failed to sign package: {}", error),
+    };
+    signed_attributes[0] = context_tag(0);
+    let signer_info = der::sequence(&[
+        &der::integer(1),
+        &der::sequence(&[&signer.issuer, &signer.serial_number]),
+        &sha256,
+        &signed_attributes,
+        &algorithm_identifier(OID_RSA_ENCRYPTION),
+        &der::octet_string(&signature),
+    ]);
+    let certificates: Vec<&[u8]> =
+        chain.iter().map(Certificate::as_der).collect();
+    let signed_data = der::sequence(&[
+        &der::integer(1),
+        &der::set_of(&[&sha256]),
+        &der::sequence(&[
+            &der::oid(OID_SPC_INDIRECT_DATA),
+            &der::constructed(context_tag(0), &[&indirect_data]),
+        ]),
+        &der::constructed(context_tag(0), &certificates),
+        &der::set_of(&[&signer_info]),
+    ]);
+    Ok(der::sequence(&[
+        &der::oid(OID_SIGNED_DATA),
+        &der::constructed(context_tag(0), &[&signed_data]),
+    ]))
+}
+
+/// Checks that the contents of a `DigitalSignature` stream hold an
+/// Authenticode signature made with the given certificate, and returns the
+/// content digest that was signed.
+pub(crate) fn verify(
+    data: &[u8],
+    certificate: &Certificate,
+) -> io::Result<Vec<u8>> {
+    let content_info =
+        Element::parse(data)?.expect(TAG_SEQUENCE)?.children()?;
+    if content_info.len() != 2 || !content_info[0].is_oid(OID_SIGNED_DATA) {
+        invalid_data!("Digital signature is not a PKCS#7 SignedData");
+    }
+    let signed_data = only_child(content_info[1].expect(context_tag(0))?)?
+        .expect(TAG_SEQUENCE)?
+        .children()?;
+    if signed_data.len() < 4 {
+        invalid_data!("Malformed digital signature");
+    }
+    let inner_content_info =
+        signed_data[2].expect(TAG_SEQUENCE)?.children()?;
+    if inner_content_info.len() != 2
+        || !inner_content_info[0].is_oid(OID_SPC_INDIRECT_DATA)
+    {
+        invalid_data!("Digital signature is not an Authenticode signature");
+    }
+    let indirect_data =
+        only_child(inner_content_info[1].expect(context_tag(0))?)?
+            .expect(TAG_SEQUENCE)?;
+
+    let signer_infos =
+        signed_data[signed_data.len() - 1].expect(TAG_SET)?.children()?;
+    if signer_infos.len() != 1 {
+        invalid_data!(
+            "Digital signature has {} signers, but expected exactly one",
+            signer_infos.len()
+        );
+    }
+    let signer_info = signer_infos[0].expect(TAG_SEQUENCE)?.children()?;
+    if signer_info.len() < 6 {
+        invalid_data!("Malformed digital signature");
+    }
+    let issuer_and_serial_number =
+        signer_info[1].expect(TAG_SEQUENCE)?.children()?;
+    if issuer_and_serial_number.len() != 2
+        || issuer_and_serial_number[0].encoded() != certificate.issuer
+        || issuer_and_serial_number[1].encoded() != certificate.serial_number
+    {
+        invalid_data!("Package was not signed with the given certificate");
+    }
+    expect_sha256(signer_info[2])?;
+
+    // Check that the signed attributes cover the signed content.
+    let attributes = signer_info[3].expect(context_tag(0))?;
+    let mut message_digest = None;
+    for attribute in attributes.children()? {
+        let parts = attribute.expect(TAG_SEQUENCE)?.children()?;
+        if parts.len() == 2 && parts[0].is_oid(OID_MESSAGE_DIGEST) {
+            let value = only_child(parts[1].expect(TAG_SET)?)?;
+            message_digest = Some(value.expect(TAG_OCTET_STRING)?.contents());
+        }
+    }
+    match message_digest {
+        Some(message_digest) => {
+            if message_digest
+                != Sha256::digest(indirect_data.contents()).as_slice()
+            {
+                invalid_data!("Digital signature's content digest is invalid");
+            }
+        }
+        None => invalid_data!("Digital signature has no message digest"),
+    }
+
+    // Check the signature of the signed attributes.
+    let mut signed_attributes = attributes.encoded().to_vec();
+    signed_attributes[0] = TAG_SET;
+    let signature = signer_info[5].expect(TAG_OCTET_STRING)?.contents();
+    let padding = Pkcs1v15Sign::new::<Sha256>();
+    if certificate
+        .public_key
+        .verify(padding, &Sha256::digest(&signed_attributes), signature)
+        .is_err()
+    {
+        invalid_data!(
+            "Digital signature does not verify with the given certificate"
+        );
+    }
+
+    // Extract the signed content digest from the SpcIndirectDataContent.
+    let indirect_data = indirect_data.children()?;
+    if indirect_data.len() != 2 {
+        invalid_data!("Malformed digital signature");
+    }
+    let digest_info = indirect_data[1].expect(TAG_SEQUENCE)?.children()?;
+    if digest_info.len() != 2 {
+        invalid_data!("Malformed digital signature");
+    }
+    expect_sha256(digest_info[0])?;
+    Ok(digest_info[1].expect(TAG_OCTET_STRING)?.contents().to_vec())
+}
+
+fn only_child(element: Element<'_>) -> io::Result<Element<'_>> {
+    let children = element.children()?;
+    if children.len() != 1 {
+        invalid_data!("Malformed digital signature");
+    }
+    Ok(children[0])
+}
+
+fn expect_sha256(algorithm: Element<'_>) -> io::Result<()> {
+    let parts = algorithm.expect(TAG_SEQUENCE)?.children()?;
+    if !parts.first().is_some_and(|oid| oid.is_oid(OID_SHA256)) {
+        invalid_data!(
+            "Digital signature uses an unsupported digest algorithm \
+             (only SHA-256 is supported)"
+        );
+    }
+    Ok(())
+}
+
+fn algorithm_identifier(oid: &[u64]) -> Vec<u8> {
+    der::sequence(&[&der::oid(oid), &der::null()])
+}
+
+fn attribute(oid: &[u64], value: &[u8]) -> Vec<u8> {
+    der::sequence(&[&der::oid(oid), &der::set_of(&[value])])
+}
+
+fn spc_indirect_data_content(digest: &[u8]) -> Vec<u8> {
+    let zero = der::integer(0);
+    let sip_info = der::sequence(&[
+        &der::integer(1),
+        &der::octet_string(&MSI_SIP_GUID),
+        &zero,
+        &zero,
+        &zero,
+        &zero,
+        &zero,
+    ]);
+    der::sequence(&[
+        &der::sequence(&[&der::oid(OID_SPC_SIPINFO), &sip_info]),
+        &der::sequence(&[
+            &algorithm_identifier(OID_SHA256),
+            &der::octet_string(digest),
+        ]),
+    ])
+}
+
+// ========================================================================= //
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_directory_names, pem_blocks};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn directory_name_order() {
+        assert_eq!(compare_directory_names("b", "aa"), Ordering::Less);
+        assert_eq!(compare_directory_names("ab", "AB"), Ordering::Equal);
+        assert_eq!(compare_directory_names("ab", "AC"), Ordering::Less);
+        assert_eq!(compare_directory_names("Z", "a"), Ordering::Greater);
+    }
+
+    #[test]
+    fn parse_pem_blocks() {
+        let pem = "junk\n-----BEGIN FOO-----\nAQID\n-----END FOO-----\n\
+                   -----BEGIN BAR-----\nBA==\n-----END BAR-----\n";
+        assert_eq!(
+            pem_blocks(pem).unwrap(),
+            vec![
+                ("FOO".to_string(), vec![1, 2, 3]),
+                ("BAR".to_string(), vec![4])
+            ]
+        );
+        assert!(pem_blocks("-----BEGIN FOO-----\nAQID\n").is_err());
+    }
+}
+
+// ========================================================================= //
@@ -0,0 +1,1029 @@
+use crate::internal::column::{Column, ColumnType};
+use crate::internal::expr::Expr;
+use crate::internal::query::{Delete, Insert, Select, Update};
+use crate::internal::value::Value;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+// ========================================================================= //
+
+/// A database statement written in the SQL dialect understood by Windows
+/// Installer.
+///
+/// Besides the syntax accepted by `MsiDatabaseOpenView`, the parser also
+/// accepts the `INNER JOIN`/`LEFT JOIN` syntax, double-quoted strings and
+/// operators used by the `Display` implementations of the query objects, so
+/// that any query built with this crate can be parsed back.
+///
+/// # Examples
+///
+/// ```
+/// use msi::Statement;
+///
+/// let statement = Statement::parse(
+///     "SELECT `File`, `FileName` FROM `File` WHERE `Component_` = 'Main' \
+///      ORDER BY `Sequence`",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     statement.to_string(),
+///     "SELECT File, FileName FROM File WHERE Component_ = \"Main\" \
+///      ORDER BY Sequence"
+/// );
+/// ```
+pub enum Statement {
+    /// A `SELECT` query.
+    Select(Select),
+    /// An `INSERT INTO` query.
+    Insert(Insert),
+    /// An `UPDATE` query.
+    Update(Update),
+    /// A `DELETE FROM` query.
+    Delete(Delete),
+    /// A `CREATE TABLE` statement, with the name and columns of the table.
+    CreateTable(String, Vec<Column>),
+    /// A `DROP TABLE` statement, with the name of the table.
+    DropTable(String),
+    /// An `ALTER TABLE ... ADD` statement, with the name of the table and the
+    /// column to add.
+    AddColumn(String, Column),
+}
+
+impl Statement {
+    /// Parses a single SQL statement.  Returns an error of kind
+    /// `InvalidInput` if the statement is malformed or uses unsupported
+    /// syntax.
+    pub fn parse(sql: &str) -> io::Result<Statement> {
+        let mut parser = Parser { tokens: tokenize(sql)?, position: 0 };
+        let statement = parser.parse_statement()?;
+        if let Some(token) = parser.peek() {
+            invalid_input!("Unexpected {} after end of statement", token);
+        }
+        Ok(statement)
+    }
+}
+
+impl FromStr for Statement {
+    type Err = io::Error;
+
+    fn from_str(sql: &str) -> io::Result<Statement> {
+        Statement::parse(sql)
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Statement::Select(query) => query.fmt(formatter),
+            Statement::Insert(query) => query.fmt(formatter),
+            Statement::Update(query) => query.fmt(formatter),
+            Statement::Delete(query) => query.fmt(formatter),
+            Statement::CreateTable(table_name, columns) => {
+                formatter.write_str("CREATE TABLE ")?;
+                formatter.write_str(table_name)?;
+                formatter.write_str(" (")?;
+                let mut comma = false;
+                for column in columns.iter() {
+                    if comma {
+                        formatter.write_str(", ")?;
+                    } else {
+                        comma = true;
+                    }
+                    format_column(column, formatter)?;
+                }
+                let keys: Vec<&str> = columns
+                    .iter()
+                    .filter(|column| column.is_primary_key())
+                    .map(Column::name)
+                    .collect();
+                if !keys.is_empty() {
+                    formatter.write_str(" PRIMARY KEY ")?;
+                    formatter.write_str(&keys.join(", "))?;
+                }
+                formatter.write_str(")")
+            }
+            Statement::DropTable(table_name) => {
+                formatter.write_str("DROP TABLE ")?;
+                formatter.write_str(table_name)
+            }
+            Statement::AddColumn(table_name, column) => {
+                formatter.write_str("ALTER TABLE ")?;
+                formatter.write_str(table_name)?;
+                formatter.write_str(" ADD ")?;
+                format_column(column, formatter)
+            }
+        }
+    }
+}
+
+fn format_column(
+    column: &Column,
+    formatter: &mut fmt::Formatter,
+) -> Result<(), fmt::Error> {
+    formatter.write_str(column.name())?;
+    match column.coltype() {
+        ColumnType::Int16 => formatter.write_str(" SHORT")?,
+        ColumnType::Int32 => formatter.write_str(" LONG")?,
+        ColumnType::Str(0) => formatter.write_str(" LONGCHAR")?,
+        ColumnType::Str(max_len) => write!(formatter, " CHAR({max_len})")?,
+        ColumnType::Binary => formatter.write_str(" OBJECT")?,
+    }
+    if !column.is_nullable() {
+        formatter.write_str(" NOT NULL")?;
+    }
+    if column.is_localizable() {
+        formatter.write_str(" LOCALIZABLE")?;
+    }
+    Ok(())
+}
+
+// ========================================================================= //
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An unquoted word, which is either a keyword or an identifier.
+    Word(String),
+    /// An identifier quoted with backticks.
+    Quoted(String),
+    Int(i64),
+    Str(String),
+    Symbol(&'static str),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self {
+            Token::Word(word) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Token::Word(word) => write!(formatter, "{word:?}"),
+            Token::Quoted(name) => write!(formatter, "`{name}`"),
+            Token::Int(number) => write!(formatter, "{number}"),
+            Token::Str(string) => write!(formatter, "string {string:?}"),
+            Token::Symbol(symbol) => write!(formatter, "{symbol:?}"),
+        }
+    }
+}
+
+const SYMBOLS: &[&str] = &[
+    "<<", ">>", "<=", ">=", "<>", "!=", "=", "<", ">", "(", ")", ",", ".",
+    "*", "+", "-", "/", "&", "|", "^", "~", "?",
+];
+
+fn tokenize(sql: &str) -> io::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.char_indices().peekable();
+    while let Some(&(start, chr)) = chars.peek() {
+        if chr.is_whitespace() {
+            chars.next();
+        } else if chr.is_ascii_alphabetic() || chr == '_' {
+            let mut end = sql.len();
+            while let Some(&(index, chr)) = chars.peek() {
+                if !(chr.is_ascii_alphanumeric() || chr == '_') {
+                    end = index;
+                    break;
+                }
+                chars.next();
+            }
+            tokens.push(Token::Word(sql[start..end].to_string()));
+        } else if chr.is_ascii_digit() {
+            let mut end = sql.len();
+            while let Some(&(index, chr)) = chars.peek() {
+                if !chr.is_ascii_digit() {
+                    end = index;
+                    break;
+                }
+                chars.next();
+            }
+            match sql[start..end].parse::<i64>() {
+                Ok(number) if number <= 1 << 31 => {
+                    tokens.push(Token::Int(number))
+                }
+                _ => {
+                    invalid_input!("Integer {} is too large", &sql[start..end])
+                }
+            }
+        } else if chr == '`' {
+            chars.next();
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '`')) => break,
+                    Some((_, chr)) => name.push(chr),
+                    None => invalid_input!("Unterminated quoted identifier"),
+                }
+            }
+            tokens.push(Token::Quoted(name));
+        } else if chr == '\'' {
+            chars.next();
+            let mut string = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '\'')) => break,
+                    Some((_, chr)) => string.push(chr),
+                    None => invalid_input!("Unterminated string literal"),
+                }
+            }
+            tokens.push(Token::Str(string));
+        } else if chr == '"' {
+            chars.next();
+            let mut string = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => string.push('\n'),
+                        Some((_, 'r')) => string.push('\r'),
+                        Some((_, 't')) => string.push('\t'),
+                        Some((_, '0')) => string.push('\0'),
+                        Some((_, 'u')) => {
+                            let mut hex = String::new();
+                            if chars.next().map(|(_, chr)| chr) != Some('{') {
+                                invalid_input!("Malformed unicode escape");
+                            }
+                            loop {
+                                match chars.next() {
+                                    Some((_, '}')) => break,
+                                    Some((_, chr)) => hex.push(chr),
+                                    None => invalid_input!(
+                                        "Unterminated string literal"
+                                    ),
+                                }
+                            }
+                            match u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                            {
+                                Some(chr) => string.push(chr),
+                                None => {
+                                    invalid_input!("Malformed unicode escape")
+                                }
+                            }
+                        }
+                        Some((_, chr)) => string.push(chr),
+                        None => invalid_input!("Unterminated string literal"),
+                    },
+                    Some((_, chr)) => string.push(chr),
+                    None => invalid_input!("Unterminated string literal"),
+                }
+            }
+            tokens.push(Token::Str(string));
+        } else {
+            let rest = &sql[start..];
+            match SYMBOLS.iter().find(|&&symbol| rest.starts_with(symbol)) {
+                Some(&symbol) => {
+                    for _ in 0..symbol.len() {
+                        chars.next();
+                    }
+                    tokens.push(Token::Symbol(symbol));
+                }
+                None => invalid_input!("Unexpected character {:?}", chr),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// ========================================================================= //
+
+struct ColumnDef {
+    name: String,
+    coltype: ColumnType,
+    is_nullable: bool,
+    is_localizable: bool,
+}
+
+impl ColumnDef {
+    fn build(self, is_primary_key: bool) -> Column {
+        let mut builder = Column::build(self.name);
+        if self.is_nullable {
+            builder = builder.nullable();
+        }
+        if self.is_localizable {
+            builder = builder.localizable();
+        }
+        if is_primary_key {
+            builder = builder.primary_key();
+        }
+        match self.coltype {
+            ColumnType::Int16 => builder.int16(),
+            ColumnType::Int32 => builder.int32(),
+            ColumnType::Str(max_len) => builder.string(max_len),
+            ColumnType::Binary => builder.binary(),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> io::Result<T> {
+        match self.peek() {
+            Some(token) => {
+                invalid_input!("Expected {}, but found {}", expected, token)
+            }
+            None => invalid_input!(
+                "Expected {}, but found end of statement",
+                expected
+            ),
+        }
+    }
+
+    fn try_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|token| token.is_keyword(keyword)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> io::Result<()> {
+        if self.try_keyword(keyword) {
+            Ok(())
+        } else {
+            self.unexpected(keyword)
+        }
+    }
+
+    fn try_symbol(&mut self, symbol: &str) -> bool {
+        if let Some(Token::Symbol(next)) = self.peek()
+            && *next == symbol
+        {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn symbol(&mut self, symbol: &str) -> io::Result<()> {
+        if self.try_symbol(symbol) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("{symbol:?}"))
+        }
+    }
+
+    fn identifier(&mut self) -> io::Result<String> {
+        match self.peek() {
+            Some(Token::Word(word)) if !is_reserved_word(word) => {
+                let word = word.clone();
+                self.position += 1;
+                Ok(word)
+            }
+            Some(Token::Quoted(name)) => {
+                let name = name.clone();
+                self.position += 1;
+                Ok(name)
+            }
+            _ => self.unexpected("an identifier"),
+        }
+    }
+
+    /// Parses a table or column name, which may be qualified with the name of
+    /// its table (e.g. `File`.`Component_`).
+    fn name(&mut self) -> io::Result<String> {
+        let mut name = self.identifier()?;
+        while self.try_symbol(".") {
+            name.push('.');
+            name.push_str(&self.identifier()?);
+        }
+        Ok(name)
+    }
+
+    fn name_list(&mut self) -> io::Result<Vec<String>> {
+        let mut names = vec![self.name()?];
+        while self.try_symbol(",") {
+            names.push(self.name()?);
+        }
+        Ok(names)
+    }
+
+    fn integer(&mut self) -> io::Result<i32> {
+        let negative = self.try_symbol("-");
+        match self.peek() {
+            Some(&Token::Int(number)) => {
+                let number = if negative { -number } else { number };
+                match i32::try_from(number) {
+                    Ok(number) => {
+                        self.position += 1;
+                        Ok(number)
+                    }
+                    Err(_) => {
+                        invalid_input!("Integer {} is too large", number)
+                    }
+                }
+            }
+            _ => self.unexpected("an integer"),
+        }
+    }
+
+    fn value(&mut self) -> io::Result<Value> {
+        match self.peek() {
+            Some(Token::Str(string)) => {
+                let string = string.clone();
+                self.position += 1;
+                Ok(Value::Str(string))
+            }
+            Some(token) if token.is_keyword("NULL") => {
+                self.position += 1;
+                Ok(Value::Null)
+            }
+            Some(Token::Symbol("?")) => {
+                invalid_input!("Parameter markers are not supported")
+            }
+            _ => self.integer().map(Value::Int),
+        }
+    }
+
+    fn parse_statement(&mut self) -> io::Result<Statement> {
+        if self.try_keyword("SELECT") {
+            Ok(Statement::Select(self.parse_select()?))
+        } else if self.try_keyword("INSERT") {
+            Ok(Statement::Insert(self.parse_insert()?))
+        } else if self.try_keyword("UPDATE") {
+            Ok(Statement::Update(self.parse_update()?))
+        } else if self.try_keyword("DELETE") {
+            Ok(Statement::Delete(self.parse_delete()?))
+        } else if self.try_keyword("CREATE") {
+            self.parse_create_table()
+        } else if self.try_keyword("DROP") {
+            self.keyword("TABLE")?;
+            Ok(Statement::DropTable(self.name()?))
+        } else if self.try_keyword("ALTER") {
+            self.parse_alter_table()
+        } else {
+            self.unexpected("a statement")
+        }
+    }
+
+    fn parse_select(&mut self) -> io::Result<Select> {
+        if self.try_keyword("DISTINCT") {
+            invalid_input!("SELECT DISTINCT is not supported");
+        }
+        let column_names =
+            if self.try_symbol("*") { Vec::new() } else { self.name_list()? };
+        self.keyword("FROM")?;
+        let mut query = self.parse_join_operand()?;
+        loop {
+            if self.try_symbol(",") {
+                let rhs = self.parse_join_operand()?;
+                query = query.inner_join(rhs, Expr::boolean(true));
+            } else if self.try_keyword("INNER") {
+                self.keyword("JOIN")?;
+                let rhs = self.parse_join_operand()?;
+                self.keyword("ON")?;
+                query = query.inner_join(rhs, self.parse_expr()?);
+            } else if self.try_keyword("LEFT") {
+                self.try_keyword("OUTER");
+                self.keyword("JOIN")?;
+                let rhs = self.parse_join_operand()?;
+                self.keyword("ON")?;
+                query = query.left_join(rhs, self.parse_expr()?);
+            } else {
+                break;
+            }
+        }
+        if !column_names.is_empty() {
+            query = query.columns(&column_names);
+        }
+        if self.try_keyword("WHERE") {
+            query = query.with(self.parse_expr()?);
+        }
+        if self.try_keyword("ORDER") {
+            self.keyword("BY")?;
+            query = query.order_by(&self.name_list()?);
+        }
+        Ok(query)
+    }
+
+    fn parse_join_operand(&mut self) -> io::Result<Select> {
+        if self.try_symbol("(") {
+            self.keyword("SELECT")?;
+            let query = self.parse_select()?;
+            self.symbol(")")?;
+            Ok(query)
+        } else {
+            Ok(Select::table(self.name()?))
+        }
+    }
+
+    fn parse_insert(&mut self) -> io::Result<Insert> {
+        self.keyword("INTO")?;
+        let mut query = Insert::into(self.name()?);
+        if self.try_symbol("(") {
+            query = query.columns(&self.name_list()?);
+            self.symbol(")")?;
+        }
+        if self.try_keyword("VALUES") {
+            loop {
+                self.symbol("(")?;
+                let mut values = vec![self.value()?];
+                while self.try_symbol(",") {
+                    values.push(self.value()?);
+                }
+                self.symbol(")")?;
+                query = query.row(values);
+                if !self.try_symbol(",") {
+                    break;
+                }
+            }
+        }
+        if self.try_keyword("TEMPORARY") {
+            invalid_input!("Temporary rows are not supported");
+        }
+        Ok(query)
+    }
+
+    fn parse_update(&mut self) -> io::Result<Update> {
+        let mut query = Update::table(self.name()?);
+        self.keyword("SET")?;
+        loop {
+            let column_name = self.name()?;
+            self.symbol("=")?;
+            query = query.set(column_name, self.value()?);
+            if !self.try_symbol(",") {
+                break;
+            }
+        }
+        if self.try_keyword("WHERE") {
+            query = query.with(self.parse_expr()?);
+        }
+        Ok(query)
+    }
+
+    fn parse_delete(&mut self) -> io::Result<Delete> {
+        self.keyword("FROM")?;
+        let mut query = Delete::from(self.name()?);
+        if self.try_keyword("WHERE") {
+            query = query.with(self.parse_expr()?);
+        }
+        Ok(query)
+    }
+
+    fn parse_create_table(&mut self) -> io::Result<Statement> {
+        self.keyword("TABLE")?;
+        let table_name = self.name()?;
+        self.symbol("(")?;
+        let mut column_defs = vec![self.parse_column_def()?];
+        let mut keys = Vec::new();
+        loop {
+            let comma = self.try_symbol(",");
+            if self.try_keyword("PRIMARY") {
+                self.keyword("KEY")?;
+                keys = self.name_list()?;
+                break;
+            } else if comma {
+                column_defs.push(self.parse_column_def()?);
+            } else {
+                break;
+            }
+        }
+        self.symbol(")")?;
+        self.try_keyword("HOLD");
+        for key in keys.iter() {
+            if !column_defs.iter().any(|column| column.name == *key) {
+                invalid_input!(
+                    "Primary key {:?} is not a column of the table",
+                    key
+                );
+            }
+        }
+        let columns = column_defs
+            .into_iter()
+            .map(|column| {
+                let is_primary_key = keys.contains(&column.name);
+                column.build(is_primary_key)
+            })
+            .collect();
+        Ok(Statement::CreateTable(table_name, columns))
+    }
+
+    fn parse_alter_table(&mut self) -> io::Result<Statement> {
+        self.keyword("TABLE")?;
+        let table_name = self.name()?;
+        if self.peek().is_some_and(|token| {
+            token.is_keyword("HOLD") || token.is_keyword("FREE")
+        }) {
+            invalid_input!("ALTER TABLE HOLD/FREE is not supported");
+        }
+        self.keyword("ADD")?;
+        let column = self.parse_column_def()?.build(false);
+        self.try_keyword("HOLD");
+        Ok(Statement::AddColumn(table_name, column))
+    }
+
+    fn parse_column_def(&mut self) -> io::Result<ColumnDef> {
+        let name = self.identifier()?;
+        let coltype = match self.next() {
+            Some(token)
+                if token.is_keyword("CHAR")
+                    || token.is_keyword("CHARACTER") =>
+            {
+                if self.try_symbol("(") {
+                    let max_len = self.integer()?;
+                    self.symbol(")")?;
+                    if max_len < 0 {
+                        invalid_input!("Invalid string length {}", max_len);
+                    }
+                    ColumnType::Str(max_len as usize)
+                } else {
+                    ColumnType::Str(0)
+                }
+            }
+            Some(token) if token.is_keyword("LONGCHAR") => ColumnType::Str(0),
+            Some(token)
+                if token.is_keyword("SHORT") || token.is_keyword("INT") =>
+            {
+                ColumnType::Int16
+            }
+            Some(token)
+                if token.is_keyword("LONG") || token.is_keyword("INTEGER") =>
+            {
+                ColumnType::Int32
+            }
+            Some(token) if token.is_keyword("OBJECT") => ColumnType::Binary,
+            _ => {
+                self.position -= 1;
+                return self.unexpected("a column type");
+            }
+        };
+        let mut column = ColumnDef {
+            name,
+            coltype,
+            is_nullable: true,
+            is_localizable: false,
+        };
+        loop {
+            if self.try_keyword("NOT") {
+                self.keyword("NULL")?;
+                column.is_nullable = false;
+            } else if self.try_keyword("NULL") {
+                column.is_nullable = true;
+            } else if self.try_keyword("LOCALIZABLE") {
+                column.is_localizable = true;
+            } else if self.try_keyword("TEMPORARY") {
+                invalid_input!("Temporary columns are not supported");
+            } else {
+                break;
+            }
+        }
+        Ok(column)
+    }
+
+    fn parse_expr(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while self.try_keyword("OR") {
+            expr = expr.or(self.parse_and()?);
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_comparison()?;
+        while self.try_keyword("AND") {
+            expr = expr.and(self.parse_comparison()?);
+        }
+        Ok(expr)
+    }
+
+    fn parse_comparison(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_bitor()?;
+        loop {
+            if self.try_keyword("IS") {
+                expr = if self.try_keyword("NOT") {
+                    self.keyword("NULL")?;
+                    expr.ne(Expr::null())
+                } else {
+                    self.keyword("NULL")?;
+                    expr.eq(Expr::null())
+                };
+            } else if self.try_symbol("=") {
+                expr = expr.eq(self.parse_bitor()?);
+            } else if self.try_symbol("<>") || self.try_symbol("!=") {
+                expr = expr.ne(self.parse_bitor()?);
+            } else if self.try_symbol("<") {
+                expr = expr.lt(self.parse_bitor()?);
+            } else if self.try_symbol("<=") {
+                expr = expr.le(self.parse_bitor()?);
+            } else if self.try_symbol(">") {
+                expr = expr.gt(self.parse_bitor()?);
+            } else if self.try_symbol(">=") {
+                expr = expr.ge(self.parse_bitor()?);
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_bitor(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_bitxor()?;
+        while self.try_symbol("|") {
+            expr = expr | self.parse_bitxor()?;
+        }
+        Ok(expr)
+    }
+
+    fn parse_bitxor(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_bitand()?;
+        while self.try_symbol("^") {
+            expr = expr ^ self.parse_bitand()?;
+        }
+        Ok(expr)
+    }
+
+    fn parse_bitand(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_shift()?;
+        while self.try_symbol("&") {
+            expr = expr & self.parse_shift()?;
+        }
+        Ok(expr)
+    }
+
+    fn parse_shift(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_additive()?;
+        loop {
+            if self.try_symbol("<<") {
+                expr = expr << self.parse_additive()?;
+            } else if self.try_symbol(">>") {
+                expr = expr >> self.parse_additive()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_additive(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_multiplicative()?;
+        loop {
+            if self.try_symbol("+") {
+                expr = expr + self.parse_multiplicative()?;
+            } else if self.try_symbol("-") {
+                expr = expr - self.parse_multiplicative()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> io::Result<Expr> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            if self.try_symbol("*") {
+                expr = expr * self.parse_unary()?;
+            } else if self.try_symbol("/") {
+                expr = expr / self.parse_unary()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_unary(&mut self) -> io::Result<Expr> {
+        if self.try_symbol("-") {
+            // Parse negative literals directly, so that -2147483648 fits.
+            if let Some(&Token::Int(number)) = self.peek() {
+                self.position += 1;
+                return match i32::try_from(-number) {
+                    Ok(number) => Ok(Expr::integer(number)),
+                    Err(_) => {
+                        invalid_input!("Integer -{} is too large", number)
+                    }
+                };
+            }
+            Ok(-self.parse_unary()?)
+        } else if self.try_symbol("~") {
+            Ok(self.parse_unary()?.bitinv())
+        } else if self.try_keyword("NOT") {
+            Ok(self.parse_unary()?.not())
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> io::Result<Expr> {
+        match self.peek() {
+            Some(Token::Symbol("(")) => {
+                self.position += 1;
+                let expr = self.parse_expr()?;
+                self.symbol(")")?;
+                Ok(expr)
+            }
+            Some(&Token::Int(number)) => match i32::try_from(number) {
+                Ok(number) => {
+                    self.position += 1;
+                    Ok(Expr::integer(number))
+                }
+                Err(_) => invalid_input!("Integer {} is too large", number),
+            },
+            Some(Token::Str(string)) => {
+                let string = string.clone();
+                self.position += 1;
+                Ok(Expr::string(string))
+            }
+            Some(token) if token.is_keyword("NULL") => {
+                self.position += 1;
+                Ok(Expr::null())
+            }
+            Some(Token::Symbol("?")) => {
+                invalid_input!("Parameter markers are not supported")
+            }
+            Some(Token::Word(_)) | Some(Token::Quoted(_)) => {
+                Ok(Expr::col(self.name()?))
+            }
+            _ => self.unexpected("an expression"),
+        }
+    }
+}
+
+/// Returns true if the given unquoted word can only be used as a keyword.
+fn is_reserved_word(word: &str) -> bool {
+    const RESERVED_WORDS: &[&str] = &[
+        "AND", "BY", "FROM", "INNER", "IS", "JOIN", "LEFT", "NOT", "NULL",
+        "ON", "OR", "ORDER", "SELECT", "VALUES", "WHERE",
+    ];
+    RESERVED_WORDS.iter().any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+// ========================================================================= //
+
+#[cfg(test)]
+mod tests {
+    use super::Statement;
+    use crate::internal::column::ColumnType;
+    use std::io::ErrorKind;
+
+    fn round_trip(sql: &str) -> String {
+        Statement::parse(sql).expect("parse").to_string()
+    }
+
+    #[test]
+    fn parse_select() {
+        assert_eq!(round_trip("select * from `File`"), "SELECT * FROM File");
+        assert_eq!(
+            round_trip(
+                "SELECT `File`, `FileSize` FROM `File` \
+                 WHERE `FileSize` >= 1024 AND `Attributes` & 2 <> 0 \
+                 ORDER BY `Sequence`, `File`"
+            ),
+            "SELECT File, FileSize FROM File \
+             WHERE FileSize >= 1024 AND Attributes & 2 != 0 \
+             ORDER BY Sequence, File"
+        );
+        assert_eq!(
+            round_trip(
+                "SELECT `File`.`File`, `Component`.`Directory_` \
+                 FROM `File`, `Component` \
+                 WHERE `File`.`Component_` = `Component`.`Component`"
+            ),
+            "SELECT File.File, Component.Directory_ \
+             FROM File INNER JOIN Component ON 1 \
+             WHERE File.Component_ = Component.Component"
+        );
+        assert_eq!(
+            round_trip(
+                "SELECT * FROM `Property` \
+                 WHERE `Value` IS NULL OR `Value` IS NOT NULL"
+            ),
+            "SELECT * FROM Property WHERE Value = NULL OR Value != NULL"
+        );
+    }
+
+    #[test]
+    fn parse_expression_precedence() {
+        assert_eq!(
+            round_trip(
+                "SELECT * FROM T WHERE (A + 1) * 2 = -B OR NOT (C < 3)"
+            ),
+            "SELECT * FROM T WHERE (A + 1) * 2 = -B OR NOT (C < 3)"
+        );
+        assert_eq!(
+            round_trip("SELECT * FROM T WHERE A OR B AND C"),
+            "SELECT * FROM T WHERE A OR B AND C"
+        );
+        assert_eq!(
+            round_trip("SELECT * FROM T WHERE (A OR B) AND C"),
+            "SELECT * FROM T WHERE (A OR B) AND C"
+        );
+        assert_eq!(
+            round_trip("SELECT * FROM T WHERE A = -2147483648"),
+            "SELECT * FROM T WHERE A = -2147483648"
+        );
+    }
+
+    #[test]
+    fn parse_display_output() {
+        for sql in [
+            "SELECT Foobar.Foo, Quux.Baz FROM Foobar \
+             INNER JOIN (SELECT * FROM Quux WHERE Quay > 42) \
+             ON Foobar.Key = Quux.Quay",
+            "SELECT * FROM (SELECT * FROM A LEFT JOIN B ON A.X = B.Y) \
+             INNER JOIN C ON B.Z = C.Z",
+            "INSERT INTO Foobar VALUES (1, \"Foo\\\"s\"), (2, NULL)",
+            "INSERT INTO Foobar (Foo, Bar) VALUES (-3, \"a\\tb\")",
+            "UPDATE Foobar SET Foo = 17, Bar = NULL WHERE Foo < 17",
+            "DELETE FROM Foobar WHERE Foo < 17",
+            "DROP TABLE Foobar",
+        ] {
+            assert_eq!(round_trip(sql), sql);
+        }
+    }
+
+    #[test]
+    fn parse_create_table() {
+        let sql = "CREATE TABLE `Registry` (`Registry` CHAR(72) NOT NULL, \
+                   `Root` SHORT NOT NULL, `Key` CHAR(255) NOT NULL \
+                   LOCALIZABLE, `Name` CHAR(255) LOCALIZABLE, \
+                   `Value` LONGCHAR LOCALIZABLE, `Data` OBJECT, \
+                   `Size` LONG PRIMARY KEY `Registry`) HOLD";
+        let Statement::CreateTable(name, columns) =
+            Statement::parse(sql).expect("parse")
+        else {
+            panic!("Expected a CREATE TABLE statement");
+        };
+        assert_eq!(name, "Registry");
+        assert_eq!(columns.len(), 7);
+        assert!(columns[0].is_primary_key());
+        assert!(!columns[0].is_nullable());
+        assert_eq!(columns[0].coltype(), ColumnType::Str(72));
+        assert_eq!(columns[1].coltype(), ColumnType::Int16);
+        assert!(columns[2].is_localizable());
+        assert!(!columns[2].is_primary_key());
+        assert!(columns[3].is_nullable());
+        assert_eq!(columns[4].coltype(), ColumnType::Str(0));
+        assert_eq!(columns[5].coltype(), ColumnType::Binary);
+        assert_eq!(columns[6].coltype(), ColumnType::Int32);
+
+        assert_eq!(
+            round_trip(
+                "CREATE TABLE T (A SHORT NOT NULL, B CHAR(8) PRIMARY KEY A)"
+            ),
+            "CREATE TABLE T (A SHORT NOT NULL, B CHAR(8) PRIMARY KEY A)"
+        );
+        assert_eq!(
+            round_trip("ALTER TABLE `T` ADD `C` LONG LOCALIZABLE HOLD"),
+            "ALTER TABLE T ADD C LONG LOCALIZABLE"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (sql, message) in [
+            ("", "Expected a statement, but found end of statement"),
+            (
+                "SELECT * FROM",
+                "Expected an identifier, but found end of statement",
+            ),
+            (
+                "SELECT * FROM T WHERE",
+                "Expected an expression, but found end of statement",
+            ),
+            ("SELECT * FROM T T2", "Unexpected \"T2\" after end of statement"),
+            ("SELECT * FROM T WHERE A = 'B", "Unterminated string literal"),
+            (
+                "SELECT * FROM T WHERE A = ?",
+                "Parameter markers are not supported",
+            ),
+            (
+                "DELETE FROM T WHERE A = 3000000000",
+                "Integer 3000000000 is too large",
+            ),
+            (
+                "CREATE TABLE T (A FLOAT PRIMARY KEY A)",
+                "Expected a column type, but found \"FLOAT\"",
+            ),
+            (
+                "CREATE TABLE T (A SHORT PRIMARY KEY B)",
+                "Primary key \"B\" is not a column of the table",
+            ),
+            ("ALTER TABLE T FREE", "ALTER TABLE HOLD/FREE is not supported"),
+        ] {
+            let error = Statement::parse(sql).err().expect(sql);
+            assert_eq!(error.kind(), ErrorKind::InvalidInput);
+            assert_eq!(error.to_string(), message, "{sql}");
+        }
+    }
+}
+
+// ========================================================================= //
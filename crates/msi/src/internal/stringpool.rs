@@ -1,5 +1,6 @@
 use crate::internal::codepage::CodePage;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::collections::{BTreeSet, HashMap};
 use std::io::{self, Read, Write};
 
 // ========================================================================= //
@@ -122,12 +123,16 @@ impl StringPoolBuilder {
             reader.read_exact(&mut buffer)?;
             strings.push((self.codepage.decode(&buffer), refcount));
         }
-        Ok(StringPool {
+        let mut string_pool = StringPool {
             codepage: self.codepage,
             strings,
+            indices: HashMap::new(),
+            free_entries: BTreeSet::new(),
             long_string_refs: self.long_string_refs,
             is_modified: false,
-        })
+        };
+        string_pool.build_indices();
+        Ok(string_pool)
     }
 }
 
@@ -137,6 +142,12 @@ impl StringPoolBuilder {
 pub struct StringPool {
     codepage: CodePage,
     strings: Vec<(String, u16)>,
+    /// Maps each string in the pool to the index of an entry holding it whose
+    /// refcount is not yet saturated.
+    indices: HashMap<String, usize>,
+    /// The indices of the entries whose refcount is zero, which are reused
+    /// (lowest first) before new entries are added.
+    free_entries: BTreeSet<usize>,
     long_string_refs: bool,
     is_modified: bool,
 }
@@ -147,11 +158,25 @@ impl StringPool {
         StringPool {
             codepage,
             strings: Vec::new(),
+            indices: HashMap::new(),
+            free_entries: BTreeSet::new(),
             long_string_refs: false,
             is_modified: true,
         }
     }
 
+    fn build_indices(&mut self) {
+        for (index, (string, refcount)) in self.strings.iter_mut().enumerate()
+        {
+            if *refcount == 0 {
+                string.clear();
+                self.free_entries.insert(index);
+            } else if *refcount < u16::MAX {
+                self.indices.entry(string.clone()).or_insert(index);
+            }
+        }
+    }
+
     /// Gets the code page used for serializing the string data.
     pub fn codepage(&self) -> CodePage {
         self.codepage
@@ -205,35 +230,36 @@ impl StringPool {
     /// already in the pool, and returns the index of the string in the pool.
     pub fn incref(&mut self, string: String) -> StringRef {
         self.is_modified = true;
-        // TODO: change the internal representation of StringPool to make this
-        // more efficient.
-        for (index, &mut (ref mut st, ref mut refcount)) in
-            self.strings.iter_mut().enumerate()
-        {
-            if *refcount == 0 {
-                debug_assert_eq!(st, "");
-                *st = string;
-                *refcount = 1;
-                return StringRef((index + 1) as i32);
-            }
-            if *st == string && *refcount < u16::MAX {
-                *refcount += 1;
-                return StringRef((index + 1) as i32);
+        if let Some(&index) = self.indices.get(&string) {
+            let refcount = &mut self.strings[index].1;
+            *refcount += 1;
+            if *refcount == u16::MAX {
+                self.indices.remove(&string);
             }
+            return StringRef((index + 1) as i32);
         }
-        if self.strings.len() >= u16::MAX as usize && !self.long_string_refs {
-            // TODO: If this happens, we need to rewrite all database tables
-            // from short to long string refs.
-            panic!(
-                "Too many strings; rewriting to long string refs is not \
-                    yet supported"
-            );
-        }
-        if self.strings.len() >= MAX_STRING_REF as usize {
-            panic!("Too many distinct strings in string pool");
-        }
-        self.strings.push((string, 1));
-        StringRef(self.strings.len() as i32)
+        let index = if let Some(index) = self.free_entries.pop_first() {
+            debug_assert_eq!(self.strings[index], (String::new(), 0));
+            self.strings[index] = (string.clone(), 1);
+            index
+        } else {
+            if self.strings.len() >= u16::MAX as usize
+                && !self.long_string_refs
+            {
+                // The new reference won't fit in two bytes, so from now on
+                // all tables must be written with long string refs; the
+                // package rewrites the existing table streams before writing
+                // any of them.
+                self.long_string_refs = true;
+            }
+            if self.strings.len() >= MAX_STRING_REF as usize {
+                panic!("Too many distinct strings in string pool");
+            }
+            self.strings.push((string.clone(), 1));
+            self.strings.len() - 1
+        };
+        self.indices.insert(string, index);
+        StringRef((index + 1) as i32)
     }
 
     /// Decrements the refcount of a string in the pool.
@@ -253,7 +279,13 @@ impl StringPool {
         self.is_modified = true;
         *refcount -= 1;
         if *refcount == 0 {
+            if self.indices.get(string.as_str()) == Some(&index) {
+                self.indices.remove(string.as_str());
+            }
             string.clear();
+            self.free_entries.insert(index);
+        } else if *refcount == u16::MAX - 1 {
+            self.indices.entry(string.clone()).or_insert(index);
         }
     }
 
@@ -470,6 +502,66 @@ mod tests {
         assert_eq!(string_pool.get(StringRef(1)), "Quux");
     }
 
+    #[test]
+    fn reuse_lowest_free_entry() {
+        let mut string_pool = StringPool::new(CodePage::default());
+        assert_eq!(string_pool.incref("Foo".to_string()), StringRef(1));
+        assert_eq!(string_pool.incref("Bar".to_string()), StringRef(2));
+        assert_eq!(string_pool.incref("Baz".to_string()), StringRef(3));
+        string_pool.decref(StringRef(3));
+        string_pool.decref(StringRef(1));
+        assert_eq!(string_pool.incref("Bar".to_string()), StringRef(2));
+        assert_eq!(string_pool.refcount(StringRef(2)), 2);
+        assert_eq!(string_pool.incref("Quux".to_string()), StringRef(1));
+        assert_eq!(string_pool.incref("Foo".to_string()), StringRef(3));
+        assert_eq!(string_pool.incref("Quux".to_string()), StringRef(1));
+        assert_eq!(string_pool.refcount(StringRef(1)), 2);
+        assert_eq!(string_pool.incref("Baz".to_string()), StringRef(4));
+        assert_eq!(string_pool.num_strings(), 4);
+    }
+
+    #[test]
+    fn reuse_unsaturated_entry() {
+        let pool: &[u8] = b"\xe9\xfd\x00\x00\x06\x00\xff\xff\x00\x00\x00\x00";
+        let data: &[u8] = b"Foobar";
+        let builder = StringPoolBuilder::read_from_pool(pool).expect("pool");
+        let mut string_pool = builder.build_from_data(data).expect("data");
+        assert_eq!(string_pool.num_strings(), 2);
+        assert_eq!(string_pool.incref("Foobar".to_string()), StringRef(2));
+        assert_eq!(string_pool.incref("Foobar".to_string()), StringRef(2));
+        string_pool.decref(StringRef(2));
+        string_pool.decref(StringRef(2));
+        string_pool.decref(StringRef(1));
+        assert_eq!(string_pool.refcount(StringRef(1)), 0xfffe);
+        assert_eq!(string_pool.incref("Foobar".to_string()), StringRef(1));
+        assert_eq!(string_pool.refcount(StringRef(1)), 0xffff);
+        assert_eq!(string_pool.incref("Foobar".to_string()), StringRef(2));
+        assert_eq!(string_pool.num_strings(), 2);
+    }
+
+    #[test]
+    fn switch_to_long_string_refs() {
+        let mut pool = b"\xe9\xfd\x00\x00".to_vec();
+        for _ in 0..0xffff {
+            pool.extend_from_slice(b"\x01\x00\x01\x00");
+        }
+        let data = vec![b'x'; 0xffff];
+        let builder =
+            StringPoolBuilder::read_from_pool(pool.as_slice()).expect("pool");
+        let mut string_pool =
+            builder.build_from_data(data.as_slice()).expect("data");
+        assert!(!string_pool.long_string_refs());
+        assert_eq!(string_pool.num_strings(), 0xffff);
+        assert_eq!(string_pool.incref("x".to_string()), StringRef(1));
+        assert!(!string_pool.long_string_refs());
+        assert_eq!(string_pool.incref("Foo".to_string()), StringRef(0x10000));
+        assert!(string_pool.long_string_refs());
+        assert_eq!(string_pool.get(StringRef(0x10000)), "Foo");
+        let mut pool_output = Vec::<u8>::new();
+        string_pool.write_pool(&mut pool_output).expect("pool");
+        assert_eq!(&pool_output[..4], b"\xe9\xfd\x00\x80");
+    }
+
     #[test]
     #[should_panic(expected = "Unknown codepage for string pool (123456)")]
     fn invalid_codepage() {
@@ -24,10 +24,12 @@ const PROPERTY_SUBJECT: u32 = 3;
 const PROPERTY_AUTHOR: u32 = 4;
 const PROPERTY_COMMENTS: u32 = 6;
 const PROPERTY_TEMPLATE: u32 = 7;
+const PROPERTY_LAST_AUTHOR: u32 = 8;
 const PROPERTY_UUID: u32 = 9;
 const PROPERTY_CREATION_TIME: u32 = 12;
 const PROPERTY_PAGE_COUNT: u32 = 14;
 const PROPERTY_WORD_COUNT: u32 = 15;
+const PROPERTY_CHARACTER_COUNT: u32 = 16;
 const PROPERTY_CREATING_APP: u32 = 18;
 
 // ========================================================================= //
@@ -243,6 +245,50 @@ impl SummaryInfo {
         self.set_languages(&[]);
     }
 
+    /// Gets the "last saved by" property, if one is set.  For a patch, this
+    /// lists the transforms embedded in the patch (e.g. `":T1;:#T1"`); for a
+    /// transform, it holds the architecture and languages of the package the
+    /// transform produces.
+    pub fn last_author(&self) -> Option<&str> {
+        match self.properties.get(PROPERTY_LAST_AUTHOR) {
+            Some(PropertyValue::LpStr(last_author)) => {
+                Some(last_author.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the "last saved by" property.
+    pub fn set_last_author<S: Into<String>>(&mut self, last_author: S) {
+        self.properties.set(
+            PROPERTY_LAST_AUTHOR,
+            PropertyValue::LpStr(last_author.into()),
+        );
+    }
+
+    /// Clears the "last saved by" property.
+    pub fn clear_last_author(&mut self) {
+        self.properties.remove(PROPERTY_LAST_AUTHOR);
+    }
+
+    /// Gets the "revision number" property as a string, if one is set.  For
+    /// an installer package, this is the package code (see
+    /// [`uuid`](SummaryInfo::uuid)); for a patch, the patch code followed by
+    /// the codes of the patches it obsoletes; for a transform, the product
+    /// codes and versions of the packages it applies to and produces.
+    pub fn revision_number(&self) -> Option<&str> {
+        match self.properties.get(PROPERTY_UUID) {
+            Some(PropertyValue::LpStr(string)) => Some(string.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Sets the "revision number" property.
+    pub fn set_revision_number<S: Into<String>>(&mut self, revision: S) {
+        self.properties
+            .set(PROPERTY_UUID, PropertyValue::LpStr(revision.into()));
+    }
+
     /// Gets the "subject" property, if one is set.  This typically indicates
     /// the name of the application/software that will be installed by the
     /// package.
@@ -264,6 +310,24 @@ impl SummaryInfo {
         self.properties.remove(PROPERTY_SUBJECT);
     }
 
+    /// Gets the whole "template" property, if one is set.  For an installer
+    /// package or a transform, this holds the architecture and languages (see
+    /// [`arch`](SummaryInfo::arch) and
+    /// [`languages`](SummaryInfo::languages)); for a patch, the product codes
+    /// of the products that the patch applies to, separated by semicolons.
+    pub fn template(&self) -> Option<&str> {
+        match self.properties.get(PROPERTY_TEMPLATE) {
+            Some(PropertyValue::LpStr(template)) => Some(template.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Sets the whole "template" property.
+    pub fn set_template<S: Into<String>>(&mut self, template: S) {
+        self.properties
+            .set(PROPERTY_TEMPLATE, PropertyValue::LpStr(template.into()));
+    }
+
     /// Gets the "title" property, if one is set.  This indicates the type of
     /// the installer package (e.g. "Installation Database" or "Patch").
     pub fn title(&self) -> Option<&str> {
@@ -340,6 +404,27 @@ impl SummaryInfo {
     pub fn clear_word_count(&mut self) {
         self.properties.remove(PROPERTY_WORD_COUNT);
     }
+
+    /// Gets the "Character Count" property, if one is set.  For a transform,
+    /// this holds the validation flags checked before applying it, and the
+    /// error conditions it ignores.
+    pub fn character_count(&self) -> Option<i32> {
+        match self.properties.get(PROPERTY_CHARACTER_COUNT) {
+            Some(PropertyValue::I4(character_count)) => Some(*character_count),
+            _ => None,
+        }
+    }
+
+    /// Sets the "Character Count" property.
+    pub fn set_character_count(&mut self, character_count: i32) {
+        self.properties
+            .set(PROPERTY_CHARACTER_COUNT, PropertyValue::I4(character_count));
+    }
+
+    /// Clears the "Character Count" property.
+    pub fn clear_character_count(&mut self) {
+        self.properties.remove(PROPERTY_CHARACTER_COUNT);
+    }
 }
 
 // ========================================================================= //
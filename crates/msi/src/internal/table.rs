@@ -3,6 +3,9 @@ use crate::internal::column::Column;
 use crate::internal::streamname;
 use crate::internal::stringpool::StringPool;
 use crate::internal::value::{Value, ValueRef};
+use cfb;
+use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::ops::Index;
 use std::rc::Rc;
@@ -14,7 +17,7 @@ use std::rc::Rc;
 pub struct Table {
     name: String,
     columns: Vec<Column>,
-    long_string_refs: bool,
+    long_string_refs: Cell<bool>,
 }
 
 impl Table {
@@ -26,6 +29,7 @@ impl Table {
         columns: Vec<Column>,
         long_string_refs: bool,
     ) -> Rc<Table> {
+        let long_string_refs = Cell::new(long_string_refs);
         Rc::new(Table { name, columns, long_string_refs })
     }
 
@@ -45,7 +49,7 @@ impl Table {
     }
 
     pub(crate) fn long_string_refs(&self) -> bool {
-        self.long_string_refs
+        self.long_string_refs.get()
     }
 
     /// Returns the list of columns in this table.
@@ -77,6 +81,17 @@ impl Table {
             .collect()
     }
 
+    /// Returns the indices of table's foreign key columns.
+    pub(crate) fn foreign_key_indices(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, column)| {
+                if column.is_foreign_key() { Some(index) } else { None }
+            })
+            .collect()
+    }
+
     pub(crate) fn index_for_column_name(
         &self,
         column_name: &str,
@@ -86,6 +101,22 @@ impl Table {
                 return Some(index);
             }
         }
+        // Columns of joined tables are prefixed with their table name, but
+        // may still be referred to by their bare name if it is unambiguous.
+        if !column_name.contains('.') {
+            let mut found = None;
+            for (index, column) in self.columns.iter().enumerate() {
+                if let Some((_, name)) = column.name().rsplit_once('.')
+                    && name == column_name
+                {
+                    if found.is_some() {
+                        return None;
+                    }
+                    found = Some(index);
+                }
+            }
+            return found;
+        }
         None
     }
 
@@ -100,7 +131,7 @@ impl Table {
         let row_size = self
             .columns
             .iter()
-            .map(|col| col.coltype().width(self.long_string_refs))
+            .map(|col| col.coltype().width(self.long_string_refs()))
             .sum::<u64>();
         let num_columns = self.columns.len();
         let num_rows =
@@ -119,7 +150,8 @@ impl Table {
             let coltype = column.coltype();
             for row in rows.iter_mut() {
                 row.push(
-                    coltype.read_value(&mut reader, self.long_string_refs)?,
+                    coltype
+                        .read_value(&mut reader, self.long_string_refs())?,
                 );
             }
         }
@@ -137,7 +169,7 @@ impl Table {
                 coltype.write_value(
                     &mut writer,
                     row[index],
-                    self.long_string_refs,
+                    self.long_string_refs(),
                 )?;
             }
         }
@@ -145,6 +177,34 @@ impl Table {
     }
 }
 
+/// Rewrites the streams of any tables that still use short string refs after
+/// the string pool has switched to long string refs (which happens once the
+/// pool grows past 65535 entries).  This must be called before writing any
+/// table, since all tables in a package must use the same string ref size.
+pub(crate) fn upgrade_string_refs<F: Read + Write + Seek>(
+    comp: &mut cfb::CompoundFile<F>,
+    string_pool: &StringPool,
+    tables: &BTreeMap<String, Rc<Table>>,
+) -> io::Result<()> {
+    if !string_pool.long_string_refs() {
+        return Ok(());
+    }
+    for table in tables.values() {
+        if table.long_string_refs() {
+            continue;
+        }
+        let stream_name = table.stream_name();
+        if comp.exists(&stream_name) {
+            let rows = table.read_rows(comp.open_stream(&stream_name)?)?;
+            table.long_string_refs.set(true);
+            table.write_rows(comp.create_stream(&stream_name)?, rows)?;
+        } else {
+            table.long_string_refs.set(true);
+        }
+    }
+    Ok(())
+}
+
 // ========================================================================= //
 
 /// One row from a database table.
@@ -160,6 +220,10 @@ impl Row {
         Row { table, values }
     }
 
+    pub(crate) fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+
     /// Returns the number of values in the row.
     pub fn len(&self) -> usize {
         self.values.len()
@@ -0,0 +1,825 @@
+use crate::internal::column::{Column, ColumnType};
+use crate::internal::expr::Expr;
+use crate::internal::package::{
+    COLUMNS_TABLE_NAME, Package, PackageType, STRING_DATA_TABLE_NAME,
+    STRING_POOL_TABLE_NAME, TABLES_TABLE_NAME,
+};
+use crate::internal::query::Select;
+use crate::internal::streamname::{
+    self, DIGITAL_SIGNATURE_STREAM_NAME, DOCUMENT_SUMMARY_INFO_STREAM_NAME,
+    MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME, SUMMARY_INFO_STREAM_NAME,
+};
+use crate::internal::stringpool::{StringPool, StringPoolBuilder};
+use crate::internal::summary::SummaryInfo;
+use crate::internal::value::{Value, ValueRef};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use cfb;
+use std::collections::BTreeMap;
+use std::io::{self, Read, Seek, Write};
+use std::path::Path;
+
+// ========================================================================= //
+
+// If the low bit of a row's mask is set, the row holds the values of the
+// first `mask >> 8` columns, and is inserted (or replaces an existing row).
+// Otherwise, the row holds the primary key columns plus each column whose bit
+// is set in the mask, and a mask of zero deletes the row.
+const MASK_FULL_ROW_BIT: u16 = 0x1;
+const MAX_MASKED_COLUMNS: usize = 16;
+
+// The high word of a transform's "character count" holds the checks that
+// Windows Installer makes against the package before applying the transform;
+// the low word holds the errors to ignore while applying it.
+const VALIDATE_LANGUAGE: i32 = 0x0001;
+const VALIDATE_PRODUCT: i32 = 0x0002;
+const VALIDATE_UPGRADE_CODE: i32 = 0x0800;
+
+const PROPERTY_TABLE_NAME: &str = "Property";
+
+/// Returns true if the bit for the given column is set in a row's mask.
+pub(crate) fn mask_has_column(mask: u16, index: usize) -> bool {
+    index < MAX_MASKED_COLUMNS && (mask & (1 << index)) != 0
+}
+
+// ========================================================================= //
+
+/// A change that a transform makes to one row of a table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum RowChange {
+    /// Inserts a row, or replaces all the values of the existing row with the
+    /// same primary key.
+    Insert(Vec<Value>),
+    /// Changes the columns whose bits are set in the mask, in the row with
+    /// the same primary key.  Values of other non-key columns are null.
+    Update(u16, Vec<Value>),
+    /// Deletes the row with the given primary key.  Values of non-key columns
+    /// are null.
+    Delete(Vec<Value>),
+}
+
+/// A row change whose strings have been interned into a string pool.
+struct EncodedRow {
+    mask: u16,
+    values: Vec<(ColumnType, ValueRef)>,
+}
+
+impl EncodedRow {
+    fn new(
+        columns: &[Column],
+        change: RowChange,
+        string_pool: &mut StringPool,
+    ) -> EncodedRow {
+        let (mask, values) = match change {
+            RowChange::Insert(values) => {
+                let mask = MASK_FULL_ROW_BIT | ((values.len() as u16) << 8);
+                (mask, values)
+            }
+            RowChange::Update(mask, values) => (mask, values),
+            RowChange::Delete(values) => (0, values),
+        };
+        let full_row = (mask & MASK_FULL_ROW_BIT) != 0;
+        let values = columns
+            .iter()
+            .zip(values)
+            .enumerate()
+            .filter(|&(index, (column, _))| {
+                full_row
+                    || column.is_primary_key()
+                    || mask_has_column(mask, index)
+            })
+            .map(|(_, (column, value))| {
+                (column.coltype(), ValueRef::create(value, string_pool))
+            })
+            .collect();
+        EncodedRow { mask, values }
+    }
+
+    fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        long_string_refs: bool,
+    ) -> io::Result<()> {
+        writer.write_u16::<LittleEndian>(self.mask)?;
+        for &(coltype, value_ref) in self.values.iter() {
+            coltype.write_value(writer, value_ref, long_string_refs)?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a list of row changes to a table with the given columns.
+fn encode_rows(
+    columns: &[Column],
+    changes: Vec<RowChange>,
+    string_pool: &mut StringPool,
+) -> Vec<EncodedRow> {
+    changes
+        .into_iter()
+        .map(|change| EncodedRow::new(columns, change, string_pool))
+        .collect()
+}
+
+/// Decodes the row changes to a table with the given columns from the
+/// contents of a transform stream.
+fn decode_rows(
+    columns: &[Column],
+    mut data: &[u8],
+    string_pool: &StringPool,
+) -> io::Result<Vec<RowChange>> {
+    let long_string_refs = string_pool.long_string_refs();
+    let mut changes = Vec::new();
+    while !data.is_empty() {
+        let mask = data.read_u16::<LittleEndian>()?;
+        let full_row = (mask & MASK_FULL_ROW_BIT) != 0;
+        let num_columns =
+            if full_row { (mask >> 8) as usize } else { columns.len() };
+        if num_columns > columns.len() {
+            invalid_data!(
+                "Transform row has {} columns, but table has only {}",
+                num_columns,
+                columns.len()
+            );
+        }
+        let mut values = Vec::with_capacity(columns.len());
+        for (index, column) in columns.iter().enumerate() {
+            let present = if full_row {
+                index < num_columns
+            } else {
+                column.is_primary_key() || mask_has_column(mask, index)
+            };
+            values.push(if present {
+                column
+                    .coltype()
+                    .read_value(&mut data, long_string_refs)?
+                    .to_value(string_pool)
+            } else {
+                Value::Null
+            });
+        }
+        changes.push(if full_row {
+            RowChange::Insert(values)
+        } else if mask == 0 {
+            RowChange::Delete(values)
+        } else {
+            RowChange::Update(mask, values)
+        });
+    }
+    Ok(changes)
+}
+
+/// Compares the rows of a table before and after, keyed by primary key, and
+/// returns the changes that turn the former into the latter.
+fn diff_rows(
+    key_indices: &[usize],
+    old_rows: Vec<Vec<Value>>,
+    new_rows: Vec<Vec<Value>>,
+) -> Vec<RowChange> {
+    let key_of = |values: &[Value]| -> Vec<Value> {
+        key_indices.iter().map(|&index| values[index].clone()).collect()
+    };
+    let mut old_rows: BTreeMap<Vec<Value>, Vec<Value>> =
+        old_rows.into_iter().map(|row| (key_of(&row), row)).collect();
+    let mut changes = Vec::new();
+    for new_row in new_rows.into_iter() {
+        let key = key_of(&new_row);
+        let old_row = match old_rows.remove(&key) {
+            Some(old_row) => old_row,
+            None => {
+                changes.push(RowChange::Insert(new_row));
+                continue;
+            }
+        };
+        let changed: Vec<usize> = (0..new_row.len())
+            .filter(|&index| old_row.get(index) != Some(&new_row[index]))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        if changed.iter().any(|&index| index >= MAX_MASKED_COLUMNS) {
+            changes.push(RowChange::Insert(new_row));
+            continue;
+        }
+        let mask =
+            changed.iter().fold(0u16, |mask, &index| mask | (1 << index));
+        let values = new_row
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                if key_indices.contains(&index) || changed.contains(&index) {
+                    value
+                } else {
+                    Value::Null
+                }
+            })
+            .collect();
+        changes.push(RowChange::Update(mask, values));
+    }
+    for (_, old_row) in old_rows.into_iter() {
+        let values = old_row
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                if key_indices.contains(&index) { value } else { Value::Null }
+            })
+            .collect();
+        changes.push(RowChange::Delete(values));
+    }
+    changes
+}
+
+fn read_all_rows<F: Read + Seek>(
+    package: &mut Package<F>,
+    table_name: &str,
+    num_columns: usize,
+) -> io::Result<Vec<Vec<Value>>> {
+    Ok(package
+        .select_rows(Select::table(table_name))?
+        .map(|row| {
+            (0..num_columns)
+                .map(|index| {
+                    if index < row.len() {
+                        row[index].clone()
+                    } else {
+                        Value::Null
+                    }
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Formats the architecture and languages of a package the way the
+/// "template" property does (e.g. `"x64;1033"`).
+fn platform_and_languages(summary_info: &SummaryInfo) -> String {
+    let languages: Vec<String> = summary_info
+        .languages()
+        .iter()
+        .map(|language| language.code().to_string())
+        .collect();
+    format!("{};{}", summary_info.arch().unwrap_or(""), languages.join(","))
+}
+
+/// The properties of a package that a transform records to identify the
+/// products it applies to and produces.
+struct ProductInfo {
+    code: String,
+    version: String,
+    upgrade_code: Option<String>,
+}
+
+impl ProductInfo {
+    /// Reads the product code, version and upgrade code of a package, if it
+    /// has a product code and version.
+    fn read<F: Read + Seek>(
+        package: &mut Package<F>,
+    ) -> io::Result<Option<ProductInfo>> {
+        let code = read_property(package, "ProductCode")?;
+        let version = read_property(package, "ProductVersion")?;
+        let upgrade_code = read_property(package, "UpgradeCode")?;
+        Ok(match (code, version) {
+            (Some(code), Some(version)) => {
+                Some(ProductInfo { code, version, upgrade_code })
+            }
+            _ => None,
+        })
+    }
+}
+
+fn read_property<F: Read + Seek>(
+    package: &mut Package<F>,
+    name: &str,
+) -> io::Result<Option<String>> {
+    let has_columns = match package.get_table(PROPERTY_TABLE_NAME) {
+        Some(table) => {
+            table.has_column("Property") && table.has_column("Value")
+        }
+        None => false,
+    };
+    if !has_columns {
+        return Ok(None);
+    }
+    let query = Select::table(PROPERTY_TABLE_NAME)
+        .columns(&["Value"])
+        .with(Expr::col("Property").eq(Expr::string(name)));
+    Ok(package
+        .select_rows(query)?
+        .next()
+        .and_then(|row| row[0].as_str().map(str::to_string)))
+}
+
+fn read_stream_data<F: Read + Seek>(
+    package: &mut Package<F>,
+    stream_name: &str,
+) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    package.read_stream(stream_name)?.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+// ========================================================================= //
+
+/// A transform (*.mst* file), which holds a set of changes to the tables and
+/// streams of an installer database.
+///
+/// The rows of a transform are stored relative to the schema of the database
+/// it applies to, so a transform is decoded only when it is applied with
+/// [`Package::apply_transform`].
+///
+/// # Examples
+///
+/// ```
+/// use msi::{Column, Insert, Package, PackageType, Select, Transform, Value};
+/// use std::io::Cursor;
+///
+/// let mut packages = Vec::new();
+/// for name in ["Base", "Custom"] {
+///     let cursor = Cursor::new(Vec::new());
+///     let mut package =
+///         Package::create(PackageType::Installer, cursor).unwrap();
+///     let columns = vec![
+///         Column::build("Property").primary_key().id_string(72),
+///         Column::build("Value").text_string(0),
+///     ];
+///     package.create_table("Property", columns).unwrap();
+///     let query = Insert::into("Property")
+///         .row(vec![Value::from("ProductName"), Value::from(name)]);
+///     package.insert_rows(query).unwrap();
+///     packages.push(package);
+/// }
+/// let mut custom = packages.pop().unwrap();
+/// let mut base = packages.pop().unwrap();
+///
+/// // Generate a transform, and write it to an .mst file:
+/// let transform = Transform::diff(&mut base, &mut custom).unwrap();
+/// let cursor = transform.write(Cursor::new(Vec::new())).unwrap();
+///
+/// // Read the transform back in, and apply it to the base package:
+/// let transform = Transform::read(cursor).unwrap();
+/// base.apply_transform(&transform).unwrap();
+/// let rows: Vec<Value> = base
+///     .select_rows(Select::table("Property"))
+///     .unwrap()
+///     .map(|row| row["Value"].clone())
+///     .collect();
+/// assert_eq!(rows, vec![Value::from("Custom")]);
+/// ```
+pub struct Transform {
+    summary_info: SummaryInfo,
+    string_pool: StringPool,
+    tables: BTreeMap<String, Vec<u8>>,
+    streams: BTreeMap<String, Vec<u8>>,
+}
+
+impl Transform {
+    /// Reads a transform from the given reader.  Returns an error if the
+    /// reader does not hold a transform.
+    pub fn read<F: Read + Seek>(inner: F) -> io::Result<Transform> {
+        let mut comp = cfb::CompoundFile::open(inner)?;
+        Transform::read_storage(&mut comp, Path::new("/"))
+    }
+
+    /// Reads a transform from a storage of a compound file, which is either
+    /// the root storage of an *.mst* file, or a transform embedded in a
+    /// patch.
+    pub(crate) fn read_storage<F: Read + Seek>(
+        comp: &mut cfb::CompoundFile<F>,
+        path: &Path,
+    ) -> io::Result<Transform> {
+        let clsid = *comp.entry(path)?.clsid();
+        if clsid != PackageType::Transform.clsid() {
+            invalid_data!(
+                "Not a transform (package CLSID is {})",
+                clsid.hyphenated()
+            );
+        }
+        let summary_path = path.join(SUMMARY_INFO_STREAM_NAME);
+        let summary_info = if comp.is_stream(&summary_path) {
+            SummaryInfo::read(comp.open_stream(&summary_path)?)?
+        } else {
+            SummaryInfo::new()
+        };
+        let string_pool = {
+            let builder = {
+                let name = streamname::encode(STRING_POOL_TABLE_NAME, true);
+                let stream = comp.open_stream(path.join(name))?;
+                StringPoolBuilder::read_from_pool(stream)?
+            };
+            let name = streamname::encode(STRING_DATA_TABLE_NAME, true);
+            let stream = comp.open_stream(path.join(name))?;
+            builder.build_from_data(stream)?
+        };
+        let entry_names: Vec<String> = comp
+            .read_storage(path)?
+            .filter(|entry| entry.is_stream())
+            .map(|entry| entry.name().to_string())
+            .filter(|name| {
+                name != DIGITAL_SIGNATURE_STREAM_NAME
+                    && name != MSI_DIGITAL_SIGNATURE_EX_STREAM_NAME
+                    && name != SUMMARY_INFO_STREAM_NAME
+                    && name != DOCUMENT_SUMMARY_INFO_STREAM_NAME
+            })
+            .collect();
+        let mut tables = BTreeMap::new();
+        let mut streams = BTreeMap::new();
+        for entry_name in entry_names {
+            let mut data = Vec::new();
+            comp.open_stream(path.join(&entry_name))?
+                .read_to_end(&mut data)?;
+            let (name, is_table) = streamname::decode(&entry_name);
+            if !is_table {
+                streams.insert(name, data);
+            } else if name != STRING_POOL_TABLE_NAME
+                && name != STRING_DATA_TABLE_NAME
+            {
+                tables.insert(name, data);
+            }
+        }
+        Ok(Transform { summary_info, string_pool, tables, streams })
+    }
+
+    /// Writes the transform to the given reader/writer, which should be
+    /// initially empty, and returns it.
+    pub fn write<F: Read + Write + Seek>(&self, inner: F) -> io::Result<F> {
+        let mut comp = cfb::CompoundFile::create(inner)?;
+        self.write_storage(&mut comp, Path::new("/"))?;
+        comp.flush()?;
+        Ok(comp.into_inner())
+    }
+
+    /// Writes the transform to an existing, empty storage of a compound file.
+    pub(crate) fn write_storage<F: Read + Write + Seek>(
+        &self,
+        comp: &mut cfb::CompoundFile<F>,
+        path: &Path,
+    ) -> io::Result<()> {
+        comp.set_storage_clsid(path, PackageType::Transform.clsid())?;
+        self.summary_info
+            .write(comp.create_stream(path.join(SUMMARY_INFO_STREAM_NAME))?)?;
+        let name = streamname::encode(STRING_POOL_TABLE_NAME, true);
+        self.string_pool.write_pool(comp.create_stream(path.join(name))?)?;
+        let name = streamname::encode(STRING_DATA_TABLE_NAME, true);
+        self.string_pool.write_data(comp.create_stream(path.join(name))?)?;
+        for (table_name, data) in self.tables.iter() {
+            let name = streamname::encode(table_name, true);
+            comp.create_stream(path.join(name))?.write_all(data)?;
+        }
+        for (stream_name, data) in self.streams.iter() {
+            let name = streamname::encode(stream_name, false);
+            comp.create_stream(path.join(name))?.write_all(data)?;
+        }
+        Ok(())
+    }
+
+    /// Generates a transform that turns the `base` package into the `target`
+    /// package.
+    ///
+    /// The transform holds the rows that were inserted, deleted or changed,
+    /// the tables that were added or dropped, the columns that were appended
+    /// to existing tables, and the binary streams that were added or changed.
+    /// Returns an error if the columns of a table changed in any other way,
+    /// since transforms cannot express that.
+    ///
+    /// The summary information of the transform records the architecture
+    /// and languages of both packages and, when their `Property` tables hold
+    /// a `ProductCode` and `ProductVersion`, the product codes, versions and
+    /// upgrade code of both packages.  Its validation flags check the
+    /// language, product code and upgrade code of the package that the
+    /// transform is applied to, as far as those are known, and no errors are
+    /// ignored; use [`summary_info_mut`](Transform::summary_info_mut) to
+    /// change them.
+    pub fn diff<F, G>(
+        base: &mut Package<F>,
+        target: &mut Package<G>,
+    ) -> io::Result<Transform>
+    where
+        F: Read + Seek,
+        G: Read + Seek,
+    {
+        let mut summary_info = SummaryInfo::new();
+        summary_info.set_title(PackageType::Transform.default_title());
+        summary_info.set_codepage(target.summary_info().codepage());
+        if let Some(arch) = base.summary_info().arch() {
+            summary_info.set_arch(arch);
+        }
+        summary_info.set_languages(&base.summary_info().languages());
+        summary_info
+            .set_last_author(platform_and_languages(target.summary_info()));
+        let base_product = ProductInfo::read(base)?;
+        let target_product = ProductInfo::read(target)?;
+        let mut validation = 0;
+        if !summary_info.languages().is_empty() {
+            validation |= VALIDATE_LANGUAGE;
+        }
+        if let (Some(base_product), Some(target_product)) =
+            (&base_product, &target_product)
+        {
+            let mut revision = format!(
+                "{}{};{}{}",
+                base_product.code,
+                base_product.version,
+                target_product.code,
+                target_product.version
+            );
+            validation |= VALIDATE_PRODUCT;
+            if let Some(upgrade_code) = &base_product.upgrade_code {
+                revision.push(';');
+                revision.push_str(upgrade_code);
+                validation |= VALIDATE_UPGRADE_CODE;
+            }
+            summary_info.set_revision_number(revision);
+        }
+        summary_info.set_character_count(validation << 16);
+        let mut string_pool = StringPool::new(target.database_codepage());
+
+        let mut table_changes = Vec::new();
+        let mut column_changes = Vec::new();
+        let mut encoded_tables = BTreeMap::new();
+        for table in base.tables() {
+            if !target.has_table(table.name()) {
+                let name = Value::Str(table.name().to_string());
+                table_changes.push(RowChange::Delete(vec![name]));
+            }
+        }
+        let target_tables: Vec<(String, Vec<Column>)> = target
+            .tables()
+            .filter(|table| {
+                table.name() != TABLES_TABLE_NAME
+                    && table.name() != COLUMNS_TABLE_NAME
+            })
+            .map(|table| (table.name().to_string(), table.columns().to_vec()))
+            .collect();
+        for (table_name, columns) in target_tables {
+            let num_old_columns = match base.get_table(&table_name) {
+                Some(table) => {
+                    let old_columns = table.columns();
+                    let unchanged = old_columns.len() <= columns.len()
+                        && old_columns.iter().zip(columns.iter()).all(
+                            |(old, new)| {
+                                old.name() == new.name()
+                                    && old.bitfield() == new.bitfield()
+                            },
+                        );
+                    if !unchanged {
+                        invalid_input!(
+                            "Cannot express the column changes to table {:?} \
+                             in a transform",
+                            table_name
+                        );
+                    }
+                    old_columns.len()
+                }
+                None => {
+                    let name = Value::Str(table_name.clone());
+                    table_changes.push(RowChange::Insert(vec![name]));
+                    0
+                }
+            };
+            for (index, column) in
+                columns.iter().enumerate().skip(num_old_columns)
+            {
+                column_changes.push(RowChange::Insert(vec![
+                    Value::Str(table_name.clone()),
+                    Value::Int(1 + index as i32),
+                    Value::Str(column.name().to_string()),
+                    Value::Int(column.bitfield()),
+                ]));
+            }
+            let old_rows = if num_old_columns > 0 {
+                read_all_rows(base, &table_name, columns.len())?
+            } else {
+                Vec::new()
+            };
+            let new_rows = read_all_rows(target, &table_name, columns.len())?;
+            let key_indices: Vec<usize> = columns
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| column.is_primary_key())
+                .map(|(index, _)| index)
+                .collect();
+            let changes = diff_rows(&key_indices, old_rows, new_rows);
+            if !changes.is_empty() {
+                let rows = encode_rows(&columns, changes, &mut string_pool);
+                encoded_tables.insert(table_name, rows);
+            }
+        }
+        if !table_changes.is_empty() {
+            let columns =
+                target.get_table(TABLES_TABLE_NAME).unwrap().columns();
+            let rows = encode_rows(columns, table_changes, &mut string_pool);
+            encoded_tables.insert(TABLES_TABLE_NAME.to_string(), rows);
+        }
+        if !column_changes.is_empty() {
+            let columns =
+                target.get_table(COLUMNS_TABLE_NAME).unwrap().columns();
+            let rows = encode_rows(columns, column_changes, &mut string_pool);
+            encoded_tables.insert(COLUMNS_TABLE_NAME.to_string(), rows);
+        }
+        // Only serialize the rows once all the strings have been interned, in
+        // case the string pool switched to long string refs along the way.
+        let mut tables = BTreeMap::new();
+        for (table_name, rows) in encoded_tables.into_iter() {
+            let mut data = Vec::new();
+            for row in rows.iter() {
+                row.write(&mut data, string_pool.long_string_refs())?;
+            }
+            tables.insert(table_name, data);
+        }
+
+        let mut streams = BTreeMap::new();
+        let stream_names: Vec<String> = target.streams().collect();
+        for stream_name in stream_names {
+            let data = read_stream_data(target, &stream_name)?;
+            if base.has_stream(&stream_name)
+                && read_stream_data(base, &stream_name)? == data
+            {
+                continue;
+            }
+            streams.insert(stream_name, data);
+        }
+        Ok(Transform { summary_info, string_pool, tables, streams })
+    }
+
+    /// Returns summary information for this transform.
+    pub fn summary_info(&self) -> &SummaryInfo {
+        &self.summary_info
+    }
+
+    /// Returns a mutable reference to the summary information for this
+    /// transform.
+    pub fn summary_info_mut(&mut self) -> &mut SummaryInfo {
+        &mut self.summary_info
+    }
+
+    /// Returns the names of the tables (including the `_Tables` and
+    /// `_Columns` tables) that this transform changes.
+    pub fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.tables.keys().map(String::as_str)
+    }
+
+    /// Returns the names of the binary streams that this transform adds or
+    /// replaces.
+    pub fn stream_names(&self) -> impl Iterator<Item = &str> {
+        self.streams.keys().map(String::as_str)
+    }
+
+    /// Decodes this transform's changes to a table with the given columns.
+    pub(crate) fn row_changes(
+        &self,
+        table_name: &str,
+        columns: &[Column],
+    ) -> io::Result<Vec<RowChange>> {
+        match self.tables.get(table_name) {
+            Some(data) => decode_rows(columns, data, &self.string_pool),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Returns the contents of a binary stream that this transform adds or
+    /// replaces.
+    pub(crate) fn stream_data(&self, stream_name: &str) -> &[u8] {
+        &self.streams[stream_name]
+    }
+
+    /// Returns the names of the tables changed by this transform, other than
+    /// `_Tables` and `_Columns`.
+    pub(crate) fn data_table_names(&self) -> Vec<&str> {
+        self.table_names()
+            .filter(|&name| {
+                name != TABLES_TABLE_NAME && name != COLUMNS_TABLE_NAME
+            })
+            .collect()
+    }
+}
+
+// ========================================================================= //
+
+#[cfg(test)]
+mod tests {
+    use super::{EncodedRow, RowChange, decode_rows, diff_rows};
+    use crate::internal::codepage::CodePage;
+    use crate::internal::column::Column;
+    use crate::internal::stringpool::StringPool;
+    use crate::internal::value::Value;
+
+    fn columns() -> Vec<Column> {
+        vec![
+            Column::build("Key").primary_key().id_string(72),
+            Column::build("Number").nullable().int16(),
+            Column::build("Text").nullable().text_string(0),
+        ]
+    }
+
+    fn encode(change: RowChange, string_pool: &mut StringPool) -> Vec<u8> {
+        let row = EncodedRow::new(&columns(), change, string_pool);
+        let mut data = Vec::new();
+        row.write(&mut data, string_pool.long_string_refs()).unwrap();
+        data
+    }
+
+    #[test]
+    fn encode_row_changes() {
+        let mut string_pool = StringPool::new(CodePage::default());
+        let insert = RowChange::Insert(vec![
+            Value::from("Foo"),
+            Value::Int(5),
+            Value::from("Bar"),
+        ]);
+        assert_eq!(
+            encode(insert, &mut string_pool),
+            b"\x01\x03\x01\x00\x05\x80\x02\x00"
+        );
+        let update = RowChange::Update(
+            0x4,
+            vec![Value::from("Foo"), Value::Null, Value::from("Baz")],
+        );
+        assert_eq!(
+            encode(update, &mut string_pool),
+            b"\x04\x00\x01\x00\x03\x00"
+        );
+        let delete = RowChange::Delete(vec![
+            Value::from("Foo"),
+            Value::Null,
+            Value::Null,
+        ]);
+        assert_eq!(encode(delete, &mut string_pool), b"\x00\x00\x01\x00");
+    }
+
+    #[test]
+    fn decode_row_changes() {
+        let mut string_pool = StringPool::new(CodePage::default());
+        let changes = vec![
+            RowChange::Insert(vec![
+                Value::from("Foo"),
+                Value::Int(-7),
+                Value::Null,
+            ]),
+            RowChange::Update(
+                0x2,
+                vec![Value::from("Foo"), Value::Int(12), Value::Null],
+            ),
+            RowChange::Delete(vec![
+                Value::from("Bar"),
+                Value::Null,
+                Value::Null,
+            ]),
+        ];
+        let mut data = Vec::new();
+        for change in changes.iter() {
+            data.extend(encode(change.clone(), &mut string_pool));
+        }
+        assert_eq!(
+            decode_rows(&columns(), &data, &string_pool).unwrap(),
+            changes
+        );
+    }
+
+    #[test]
+    fn decode_short_full_row() {
+        let mut string_pool = StringPool::new(CodePage::default());
+        string_pool.incref("Foo".to_string());
+        let data = b"\x01\x01\x01\x00";
+        assert_eq!(
+            decode_rows(&columns(), data, &string_pool).unwrap(),
+            vec![RowChange::Insert(vec![
+                Value::from("Foo"),
+                Value::Null,
+                Value::Null
+            ])]
+        );
+        let data = b"\x01\x04\x01\x00";
+        assert_eq!(
+            decode_rows(&columns(), data, &string_pool)
+                .unwrap_err()
+                .to_string(),
+            "Transform row has 4 columns, but table has only 3"
+        );
+    }
+
+    #[test]
+    fn diff_table_rows() {
+        let row = |key: &str, number: i32, text: &str| {
+            vec![Value::from(key), Value::Int(number), Value::from(text)]
+        };
+        let old_rows =
+            vec![row("A", 1, "a"), row("B", 2, "b"), row("C", 3, "c")];
+        let new_rows =
+            vec![row("A", 1, "a"), row("B", 2, "x"), row("D", 4, "d")];
+        assert_eq!(
+            diff_rows(&[0], old_rows, new_rows),
+            vec![
+                RowChange::Update(
+                    0x4,
+                    vec![Value::from("B"), Value::Null, Value::from("x")]
+                ),
+                RowChange::Insert(row("D", 4, "d")),
+                RowChange::Delete(vec![
+                    Value::from("C"),
+                    Value::Null,
+                    Value::Null
+                ]),
+            ]
+        );
+    }
+}
+
+// ========================================================================= //
@@ -0,0 +1,207 @@
+use crate::internal::category::Category;
+use crate::internal::column::{Column, ColumnType};
+use crate::internal::value::Value;
+use std::fmt;
+
+// ========================================================================= //
+
+/// A disagreement between a package's `_Validation` table and the schema that
+/// its `_Columns` table actually records, found when opening the package.
+///
+/// Packages produced by other tools sometimes have such drift; none of it
+/// prevents reading the package, but the column metadata (nullability,
+/// categories, value ranges and foreign keys) may not be what the author
+/// intended.  [`Package::repair_validation`] regenerates the `_Validation`
+/// table from the columns as they were loaded.
+///
+/// [`Package::repair_validation`]: crate::Package::repair_validation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationWarning {
+    /// The package has no `_Validation` table at all.
+    MissingTable,
+    /// The columns of the `_Validation` table itself differ from the standard
+    /// definition, so its rows were ignored.
+    NonStandardTable,
+    /// A column has no row in the `_Validation` table.
+    MissingRow {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+    },
+    /// A `_Validation` row describes a column that doesn't exist.
+    UnknownColumn {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+    },
+    /// The `Nullable` value of a `_Validation` row disagrees with the column
+    /// type.  The column is loaded as nullable if either of them says so.
+    NullableMismatch {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+        /// Whether the `_Validation` row says the column is nullable.
+        nullable: bool,
+    },
+    /// A `_Validation` row gives a value range to a column that doesn't hold
+    /// integers.
+    RangeMismatch {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+        /// The type of the column.
+        coltype: ColumnType,
+    },
+    /// A `_Validation` row gives a column a category that doesn't apply to
+    /// its type.
+    CategoryMismatch {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+        /// The type of the column.
+        coltype: ColumnType,
+        /// The category given by the `_Validation` row.
+        category: Category,
+    },
+    /// A `_Validation` row gives a column an unrecognized category, which was
+    /// ignored.
+    UnknownCategory {
+        /// The name of the table.
+        table: String,
+        /// The name of the column.
+        column: String,
+        /// The category given by the `_Validation` row.
+        category: String,
+    },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ValidationWarning::MissingTable => {
+                write!(formatter, "The _Validation table is missing")
+            }
+            ValidationWarning::NonStandardTable => write!(
+                formatter,
+                "The _Validation table has non-standard columns"
+            ),
+            ValidationWarning::MissingRow { table, column } => write!(
+                formatter,
+                "Column {table:?}.{column:?} has no _Validation row"
+            ),
+            ValidationWarning::UnknownColumn { table, column } => write!(
+                formatter,
+                "_Validation describes column {table:?}.{column:?}, which \
+                 doesn't exist"
+            ),
+            ValidationWarning::NullableMismatch {
+                table,
+                column,
+                nullable,
+            } => {
+                write!(
+                    formatter,
+                    "_Validation says column {table:?}.{column:?} is {}, but \
+                     its type says otherwise",
+                    if *nullable { "nullable" } else { "not nullable" }
+                )
+            }
+            ValidationWarning::RangeMismatch { table, column, coltype } => {
+                write!(
+                    formatter,
+                    "_Validation gives a value range to {coltype} column \
+                     {table:?}.{column:?}"
+                )
+            }
+            ValidationWarning::CategoryMismatch {
+                table,
+                column,
+                coltype,
+                category,
+            } => write!(
+                formatter,
+                "_Validation gives category {category} to {coltype} column \
+                 {table:?}.{column:?}"
+            ),
+            ValidationWarning::UnknownCategory { table, column, category } => {
+                write!(
+                    formatter,
+                    "_Validation gives unknown category {category:?} to \
+                     column {table:?}.{column:?}"
+                )
+            }
+        }
+    }
+}
+
+// ========================================================================= //
+
+/// Returns true if a column of the given type can have the given category.
+pub(crate) fn is_category_compatible(
+    coltype: ColumnType,
+    category: Category,
+) -> bool {
+    match coltype {
+        ColumnType::Binary => category == Category::Binary,
+        ColumnType::Int16 | ColumnType::Int32 => matches!(
+            category,
+            Category::Integer | Category::DoubleInteger | Category::TimeDate
+        ),
+        ColumnType::Str(_) => category != Category::Binary,
+    }
+}
+
+/// Compares a column, as loaded from its `_Columns` type bitfield alone, with
+/// its row in the `_Validation` table.
+pub(crate) fn check_column(
+    table_name: &str,
+    column: &Column,
+    validation: &[Value],
+) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    let table = table_name.to_string();
+    let name = column.name().to_string();
+    let nullable = validation[2].as_str() == Some("Y");
+    if nullable != column.is_nullable() {
+        warnings.push(ValidationWarning::NullableMismatch {
+            table: table.clone(),
+            column: name.clone(),
+            nullable,
+        });
+    }
+    let coltype = column.coltype();
+    let has_range = !validation[3].is_null() || !validation[4].is_null();
+    if has_range && !matches!(coltype, ColumnType::Int16 | ColumnType::Int32) {
+        warnings.push(ValidationWarning::RangeMismatch {
+            table: table.clone(),
+            column: name.clone(),
+            coltype,
+        });
+    }
+    if let Some(category) = validation[7].as_str() {
+        match category.parse::<Category>() {
+            Ok(category) if !is_category_compatible(coltype, category) => {
+                warnings.push(ValidationWarning::CategoryMismatch {
+                    table,
+                    column: name,
+                    coltype,
+                    category,
+                });
+            }
+            Ok(_) => {}
+            Err(_) => warnings.push(ValidationWarning::UnknownCategory {
+                table,
+                column: name,
+                category: category.to_string(),
+            }),
+        }
+    }
+    warnings
+}
+
+// ========================================================================= //
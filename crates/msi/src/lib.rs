@@ -20,12 +20,18 @@ pub use crate::internal::category::Category;
 pub use crate::internal::codepage::CodePage;
 pub use crate::internal::column::{Column, ColumnBuilder, ColumnType};
 pub use crate::internal::expr::Expr;
+pub use crate::internal::integrity::ReferentialIntegrity;
 pub use crate::internal::language::Language;
 pub use crate::internal::package::{Package, PackageType, Tables};
 pub use crate::internal::query::{Delete, Insert, Select, Update};
+#[cfg(feature = "signing")]
+pub use crate::internal::signature::{Certificate, PrivateKey};
+pub use crate::internal::sql::Statement;
 pub use crate::internal::stream::{StreamReader, StreamWriter, Streams};
 pub use crate::internal::summary::SummaryInfo;
 pub use crate::internal::table::{Row, Rows, Table};
+pub use crate::internal::transform::Transform;
+pub use crate::internal::validation::ValidationWarning;
 pub use crate::internal::value::Value;
 use std::fs;
 use std::io;
@@ -0,0 +1,204 @@
+#[macro_use]
+mod testutil;
+
+use msi::{
+    Column, Delete, Expr, Insert, Package, PackageType, ReferentialIntegrity,
+    Select, Update, Value,
+};
+use std::io::{Cursor, ErrorKind};
+
+//===========================================================================//
+
+fn create_package() -> Package<Cursor<Vec<u8>>> {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Directory").primary_key().id_string(72),
+        Column::build("Directory_Parent")
+            .nullable()
+            .foreign_key("Directory", 1)
+            .id_string(72),
+    ];
+    package.create_table("Directory", columns).unwrap();
+    let columns = vec![
+        Column::build("Component").primary_key().id_string(72),
+        Column::build("Directory_").foreign_key("Directory", 1).id_string(72),
+    ];
+    package.create_table("Component", columns).unwrap();
+    let columns = vec![
+        Column::build("File").primary_key().id_string(72),
+        Column::build("Component_").foreign_key("Component", 1).id_string(72),
+    ];
+    package.create_table("File", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Directory")
+                .row(vec![Value::from("TARGETDIR"), Value::Null])
+                .row(vec![Value::from("APPDIR"), Value::from("TARGETDIR")])
+                .row(vec![Value::from("DOCDIR"), Value::from("APPDIR")]),
+        )
+        .unwrap();
+    package
+        .insert_rows(
+            Insert::into("Component")
+                .row(vec![Value::from("Main"), Value::from("APPDIR")])
+                .row(vec![Value::from("Docs"), Value::from("DOCDIR")]),
+        )
+        .unwrap();
+    package
+        .insert_rows(
+            Insert::into("File")
+                .row(vec![Value::from("app.exe"), Value::from("Main")])
+                .row(vec![Value::from("readme.txt"), Value::from("Docs")]),
+        )
+        .unwrap();
+    package
+}
+
+fn keys(package: &mut Package<Cursor<Vec<u8>>>, table: &str) -> Vec<Value> {
+    package
+        .select_rows(Select::table(table))
+        .unwrap()
+        .map(|row| row[0].clone())
+        .collect()
+}
+
+#[test]
+fn ignore_foreign_keys_by_default() {
+    let mut package = create_package();
+    assert_eq!(package.referential_integrity(), ReferentialIntegrity::Ignore);
+    let query = Insert::into("File")
+        .row(vec![Value::from("stray.dll"), Value::from("Missing")]);
+    package.insert_rows(query).unwrap();
+    package.delete_rows(Delete::from("Component")).unwrap();
+    assert_eq!(keys(&mut package, "File").len(), 3);
+}
+
+#[test]
+fn insert_dangling_reference() {
+    let mut package = create_package();
+    package.set_referential_integrity(ReferentialIntegrity::Restrict);
+    let query = Insert::into("File")
+        .row(vec![Value::from("lib.dll"), Value::from("Main")])
+        .row(vec![Value::from("stray.dll"), Value::from("Missing")]);
+    assert_error!(
+        package.insert_rows(query),
+        ErrorKind::InvalidInput,
+        "Row (\"stray.dll\") of table \"File\" refers to \"Missing\" in \
+         column \"Component_\", but there is no such key in table \
+         \"Component\""
+    );
+    assert_eq!(keys(&mut package, "File").len(), 2);
+
+    // Rows may refer to other rows inserted at the same time.
+    let query = Insert::into("Directory")
+        .row(vec![Value::from("SUBDIR"), Value::from("NEWDIR")])
+        .row(vec![Value::from("NEWDIR"), Value::from("TARGETDIR")]);
+    package.insert_rows(query).unwrap();
+}
+
+#[test]
+fn update_dangling_reference() {
+    let mut package = create_package();
+    package.set_referential_integrity(ReferentialIntegrity::Cascade);
+    let query = Update::table("Component")
+        .set("Directory_", Value::from("NOWHERE"))
+        .with(Expr::col("Component").eq(Expr::string("Main")));
+    assert_error!(
+        package.update_rows(query),
+        ErrorKind::InvalidInput,
+        "Row (\"Main\") of table \"Component\" refers to \"NOWHERE\" in \
+         column \"Directory_\", but there is no such key in table \
+         \"Directory\""
+    );
+}
+
+#[test]
+fn restrict_delete() {
+    let mut package = create_package();
+    package.set_referential_integrity(ReferentialIntegrity::Restrict);
+    let query = Delete::from("Component")
+        .with(Expr::col("Component").eq(Expr::string("Docs")));
+    assert_error!(
+        package.delete_rows(query),
+        ErrorKind::InvalidInput,
+        "Cannot delete row (\"Docs\") of table \"Component\", because row \
+         (\"readme.txt\") of table \"File\" refers to it in column \
+         \"Component_\""
+    );
+    assert_eq!(keys(&mut package, "Component").len(), 2);
+
+    let query = Delete::from("File")
+        .with(Expr::col("Component_").eq(Expr::string("Docs")));
+    package.delete_rows(query).unwrap();
+    let query = Delete::from("Component")
+        .with(Expr::col("Component").eq(Expr::string("Docs")));
+    package.delete_rows(query).unwrap();
+    assert_eq!(keys(&mut package, "Component"), vec![Value::from("Main")]);
+}
+
+#[test]
+fn cascade_delete() {
+    let mut package = create_package();
+    package.set_referential_integrity(ReferentialIntegrity::Cascade);
+    let query = Delete::from("Directory")
+        .with(Expr::col("Directory").eq(Expr::string("APPDIR")));
+    package.delete_rows(query).unwrap();
+    assert_eq!(
+        keys(&mut package, "Directory"),
+        vec![Value::from("TARGETDIR")]
+    );
+    assert!(keys(&mut package, "Component").is_empty());
+    assert!(keys(&mut package, "File").is_empty());
+}
+
+#[test]
+fn restrict_update() {
+    let mut package = create_package();
+    package.set_referential_integrity(ReferentialIntegrity::Restrict);
+    let query = Update::table("Component")
+        .set("Component", Value::from("Core"))
+        .with(Expr::col("Component").eq(Expr::string("Main")));
+    assert_error!(
+        package.update_rows(query),
+        ErrorKind::InvalidInput,
+        "Cannot change \"Main\" in column \"Component\" of row (\"Main\") of \
+         table \"Component\", because row (\"app.exe\") of table \"File\" \
+         refers to it in column \"Component_\""
+    );
+}
+
+#[test]
+fn cascade_update() {
+    let mut package = create_package();
+    package.set_referential_integrity(ReferentialIntegrity::Cascade);
+    let query = Update::table("Directory")
+        .set("Directory", Value::from("INSTALLDIR"))
+        .with(Expr::col("Directory").eq(Expr::string("APPDIR")));
+    package.update_rows(query).unwrap();
+    let mut rows: Vec<(Value, Value)> = package
+        .select_rows(Select::table("Directory"))
+        .unwrap()
+        .map(|row| (row[0].clone(), row[1].clone()))
+        .collect();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            (Value::from("DOCDIR"), Value::from("INSTALLDIR")),
+            (Value::from("INSTALLDIR"), Value::from("TARGETDIR")),
+            (Value::from("TARGETDIR"), Value::Null),
+        ]
+    );
+    let rows: Vec<Value> = package
+        .select_rows(
+            Select::table("Component")
+                .with(Expr::col("Component").eq(Expr::string("Main"))),
+        )
+        .unwrap()
+        .map(|row| row["Directory_"].clone())
+        .collect();
+    assert_eq!(rows, vec![Value::from("INSTALLDIR")]);
+}
+
+//===========================================================================//
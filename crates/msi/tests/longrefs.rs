@@ -0,0 +1,143 @@
+use msi::{
+    Column, Delete, Expr, Insert, Package, PackageType, Select, Update, Value,
+};
+use std::io::Cursor;
+
+//===========================================================================//
+
+const NUM_ROWS: i32 = 40000;
+
+#[test]
+fn switch_to_long_string_refs() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Key").primary_key().int32(),
+        Column::build("Name").string(32),
+        Column::build("Label").nullable().string(32),
+    ];
+    package.create_table("Strings", columns).unwrap();
+    let columns = vec![
+        Column::build("Property").primary_key().id_string(72),
+        Column::build("Value").text_string(0),
+    ];
+    package.create_table("Property", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Property").row(vec![
+                Value::from("ProductName"),
+                Value::from("Long Refs"),
+            ]),
+        )
+        .unwrap();
+
+    // Two distinct strings per row push the string pool past 65535 entries.
+    let rows: Vec<Vec<Value>> = (0..NUM_ROWS)
+        .map(|key| {
+            vec![
+                Value::Int(key),
+                Value::Str(format!("name{key}")),
+                Value::Str(format!("label{key}")),
+            ]
+        })
+        .collect();
+    package.insert_rows(Insert::into("Strings").rows(rows)).unwrap();
+    package
+        .update_rows(
+            Update::table("Property")
+                .set("Value", Value::from("Still Readable"))
+                .with(Expr::col("Property").eq(Expr::string("ProductName"))),
+        )
+        .unwrap();
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let values: Vec<Value> = package
+        .select_rows(Select::table("Property"))
+        .unwrap()
+        .map(|row| row["Value"].clone())
+        .collect();
+    assert_eq!(values, vec![Value::from("Still Readable")]);
+    let rows = package
+        .select_rows(
+            Select::table("Strings")
+                .with(Expr::col("Key").eq(Expr::integer(NUM_ROWS - 1))),
+        )
+        .unwrap();
+    let rows: Vec<(Value, Value)> =
+        rows.map(|row| (row[1].clone(), row[2].clone())).collect();
+    assert_eq!(
+        rows,
+        vec![(
+            Value::Str(format!("name{}", NUM_ROWS - 1)),
+            Value::Str(format!("label{}", NUM_ROWS - 1)),
+        )]
+    );
+    let num_rows =
+        package.select_rows(Select::table("Strings")).unwrap().len();
+    assert_eq!(num_rows, NUM_ROWS as usize);
+}
+
+#[test]
+fn reuse_freed_strings_with_long_refs() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("File").primary_key().id_string(72),
+        Column::build("FileName").string(255),
+        Column::build("Sequence").int32(),
+    ];
+    package.create_table("File", columns).unwrap();
+    let file_rows = |range: std::ops::Range<i32>| -> Vec<Vec<Value>> {
+        range
+            .map(|index| {
+                vec![
+                    Value::Str(format!("file{index}")),
+                    Value::Str(format!("name{index}.txt")),
+                    Value::Int(index),
+                ]
+            })
+            .collect()
+    };
+    package
+        .insert_rows(Insert::into("File").rows(file_rows(0..NUM_ROWS)))
+        .unwrap();
+
+    // Freed strings are reused by the next inserted ones, while the pool
+    // keeps long string refs.
+    let half = NUM_ROWS / 2;
+    package
+        .delete_rows(
+            Delete::from("File")
+                .with(Expr::col("Sequence").lt(Expr::integer(half))),
+        )
+        .unwrap();
+    package
+        .insert_rows(
+            Insert::into("File").rows(file_rows(NUM_ROWS..NUM_ROWS + half)),
+        )
+        .unwrap();
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let mut rows: Vec<(String, String, i32)> = package
+        .select_rows(Select::table("File"))
+        .unwrap()
+        .map(|row| {
+            (
+                row["File"].as_str().unwrap().to_string(),
+                row["FileName"].as_str().unwrap().to_string(),
+                row["Sequence"].as_int().unwrap(),
+            )
+        })
+        .collect();
+    rows.sort_by_key(|row| row.2);
+    let expected: Vec<(String, String, i32)> = (half..NUM_ROWS + half)
+        .map(|index| {
+            (format!("file{index}"), format!("name{index}.txt"), index)
+        })
+        .collect();
+    assert_eq!(rows, expected);
+}
+
+//===========================================================================//
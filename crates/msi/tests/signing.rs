@@ -0,0 +1,172 @@
+#[macro_use]
+mod testutil;
+
+use cfb::CompoundFile;
+use msi::{
+    Certificate, Column, Insert, Package, PackageType, PrivateKey, Value,
+};
+use std::io::{Cursor, ErrorKind, Write};
+
+//===========================================================================//
+
+const SIGNER_CERTIFICATE: &str = include_str!("data/signer.crt");
+const SIGNER_KEY: &str = include_str!("data/signer.key");
+const OTHER_CERTIFICATE: &str = include_str!("data/other.crt");
+
+fn create_package() -> Package<Cursor<Vec<u8>>> {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    package.summary_info_mut().set_title("Signed".to_string());
+    let columns = vec![
+        Column::build("Property").primary_key().id_string(72),
+        Column::build("Value").text_string(0),
+    ];
+    package.create_table("Property", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Property")
+                .row(vec![Value::from("ProductName"), Value::from("Widget")]),
+        )
+        .unwrap();
+    package.write_stream("Icon").unwrap().write_all(b"icon data").unwrap();
+    package
+}
+
+fn sign_package() -> Cursor<Vec<u8>> {
+    let mut package = create_package();
+    let chain = Certificate::chain_from_pem(SIGNER_CERTIFICATE).unwrap();
+    let key = PrivateKey::from_pem(SIGNER_KEY).unwrap();
+    package.add_digital_signature(&chain, &key).unwrap();
+    assert!(package.has_digital_signature());
+    package.into_inner().unwrap()
+}
+
+#[test]
+fn sign_and_verify() {
+    let cursor = sign_package();
+    let mut package = Package::open(cursor).unwrap();
+    assert!(package.has_digital_signature());
+    assert_eq!(package.streams().collect::<Vec<_>>(), vec!["Icon"]);
+    let certificate = Certificate::from_pem(SIGNER_CERTIFICATE).unwrap();
+    package.verify_digital_signature(&certificate).unwrap();
+}
+
+#[test]
+fn load_certificate_and_key_from_files() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    let chain = Certificate::load_chain(format!("{dir}/signer.crt")).unwrap();
+    assert_eq!(chain.len(), 1);
+    let key = PrivateKey::load(format!("{dir}/signer.key")).unwrap();
+    assert!(key.matches(&chain[0]));
+    let other = Certificate::load(format!("{dir}/other.crt")).unwrap();
+    assert!(!key.matches(&other));
+
+    // DER-encoded certificates work too.
+    let certificate = Certificate::from_der(chain[0].as_der()).unwrap();
+    assert!(key.matches(&certificate));
+}
+
+#[test]
+fn resign_package() {
+    let cursor = sign_package();
+    let mut package = Package::open(cursor).unwrap();
+    let chain = Certificate::chain_from_pem(SIGNER_CERTIFICATE).unwrap();
+    let key = PrivateKey::from_pem(SIGNER_KEY).unwrap();
+    package.write_stream("Icon").unwrap().write_all(b"new icon").unwrap();
+    package.add_digital_signature(&chain, &key).unwrap();
+    let mut package = Package::open(package.into_inner().unwrap()).unwrap();
+    package.verify_digital_signature(&chain[0]).unwrap();
+}
+
+#[test]
+fn verify_unsigned_package() {
+    let mut package = create_package();
+    let certificate = Certificate::from_pem(SIGNER_CERTIFICATE).unwrap();
+    assert_error!(
+        package.verify_digital_signature(&certificate),
+        ErrorKind::NotFound,
+        "Package is not digitally signed"
+    );
+}
+
+#[test]
+fn verify_with_wrong_certificate() {
+    let mut package = Package::open(sign_package()).unwrap();
+    let certificate = Certificate::from_pem(OTHER_CERTIFICATE).unwrap();
+    assert_error!(
+        package.verify_digital_signature(&certificate),
+        ErrorKind::InvalidData,
+        "Package was not signed with the given certificate"
+    );
+}
+
+#[test]
+fn sign_with_mismatched_key() {
+    let mut package = create_package();
+    let chain = Certificate::chain_from_pem(OTHER_CERTIFICATE).unwrap();
+    let key = PrivateKey::from_pem(SIGNER_KEY).unwrap();
+    assert_error!(
+        package.add_digital_signature(&chain, &key),
+        ErrorKind::InvalidInput,
+        "Private key does not match the signing certificate"
+    );
+    assert_error!(
+        package.add_digital_signature(&[], &key),
+        ErrorKind::InvalidInput,
+        "No signing certificate given"
+    );
+    assert!(!package.has_digital_signature());
+}
+
+#[test]
+fn verify_modified_contents() {
+    // Overwrite a stream with different data of the same length, which
+    // leaves the storage metadata unchanged.
+    let mut comp = CompoundFile::open(sign_package()).unwrap();
+    let name = comp
+        .read_root_storage()
+        .find(|entry| entry.is_stream() && entry.len() == 9)
+        .unwrap()
+        .name()
+        .to_string();
+    comp.open_stream(&name).unwrap().write_all(b"evil data").unwrap();
+    let mut package = Package::open(comp.into_inner()).unwrap();
+    let certificate = Certificate::from_pem(SIGNER_CERTIFICATE).unwrap();
+    assert_error!(
+        package.verify_digital_signature(&certificate),
+        ErrorKind::InvalidData,
+        "Package contents have changed since it was signed"
+    );
+}
+
+#[test]
+fn verify_modified_metadata() {
+    let mut package = Package::open(sign_package()).unwrap();
+    package.write_stream("Extra").unwrap().write_all(b"extra").unwrap();
+    let certificate = Certificate::from_pem(SIGNER_CERTIFICATE).unwrap();
+    assert_error!(
+        package.verify_digital_signature(&certificate),
+        ErrorKind::InvalidData,
+        "Package metadata has changed since it was signed"
+    );
+}
+
+#[test]
+fn verify_corrupt_signature() {
+    let mut package = Package::open(sign_package()).unwrap();
+    package.remove_digital_signature().unwrap();
+    let mut comp = CompoundFile::open(package.into_inner().unwrap()).unwrap();
+    comp.create_stream("\u{5}DigitalSignature")
+        .unwrap()
+        .write_all(b"\x30\x03\x02\x01\x01")
+        .unwrap();
+    let mut package = Package::open(comp.into_inner()).unwrap();
+    let certificate = Certificate::from_pem(SIGNER_CERTIFICATE).unwrap();
+    assert_error!(
+        package.verify_digital_signature(&certificate),
+        ErrorKind::InvalidData,
+        "Digital signature is not a PKCS#7 SignedData"
+    );
+}
+
+//===========================================================================//
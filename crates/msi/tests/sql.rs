@@ -0,0 +1,137 @@
+#[macro_use]
+mod testutil;
+
+use msi::{Package, PackageType, Statement, Value};
+use std::io::{Cursor, ErrorKind};
+
+//===========================================================================//
+
+fn execute<F>(package: &mut Package<F>, sql: &str) -> Vec<Vec<Value>>
+where
+    F: std::io::Read + std::io::Write + std::io::Seek,
+{
+    let statement = Statement::parse(sql).unwrap();
+    match package.execute(statement).unwrap() {
+        Some(rows) => rows
+            .map(|row| {
+                (0..row.len()).map(|index| row[index].clone()).collect()
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[test]
+fn create_and_query_tables() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    execute(
+        &mut package,
+        "CREATE TABLE `Component` (`Component` CHAR(72) NOT NULL, \
+         `Directory_` CHAR(72) NOT NULL PRIMARY KEY `Component`)",
+    );
+    execute(
+        &mut package,
+        "CREATE TABLE `File` (`File` CHAR(72) NOT NULL, \
+         `Component_` CHAR(72) NOT NULL, `FileSize` LONG NOT NULL, \
+         `Sequence` SHORT NOT NULL PRIMARY KEY `File`)",
+    );
+    execute(
+        &mut package,
+        "INSERT INTO `Component` (`Component`, `Directory_`) \
+         VALUES ('Main', 'INSTALLDIR'), ('Docs', 'DOCDIR')",
+    );
+    execute(
+        &mut package,
+        "INSERT INTO `File` (`File`, `Component_`, `FileSize`, `Sequence`) \
+         VALUES ('app.exe', 'Main', 4096, 2), ('readme.txt', 'Docs', 12, 3), \
+         ('app.dll', 'Main', 2048, 1)",
+    );
+
+    let rows = execute(
+        &mut package,
+        "SELECT `File`, `Directory_` FROM `File`, `Component` \
+         WHERE `Component_` = `Component` AND `FileSize` > 100 \
+         ORDER BY `Sequence`",
+    );
+    assert_eq!(
+        rows,
+        vec![
+            vec![Value::from("app.dll"), Value::from("INSTALLDIR")],
+            vec![Value::from("app.exe"), Value::from("INSTALLDIR")],
+        ]
+    );
+
+    execute(
+        &mut package,
+        "UPDATE `File` SET `Sequence` = 10 WHERE `File` = 'app.dll'",
+    );
+    execute(&mut package, "DELETE FROM `File` WHERE `Component_` = 'Docs'");
+    let rows = execute(
+        &mut package,
+        "SELECT `File`, `Sequence` FROM `File` ORDER BY `Sequence`",
+    );
+    assert_eq!(
+        rows,
+        vec![
+            vec![Value::from("app.exe"), Value::Int(2)],
+            vec![Value::from("app.dll"), Value::Int(10)],
+        ]
+    );
+
+    execute(&mut package, "DROP TABLE `File`");
+    assert!(!package.has_table("File"));
+}
+
+#[test]
+fn alter_table_add_column() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    execute(
+        &mut package,
+        "CREATE TABLE `Numbers` (`Number` SHORT NOT NULL \
+         PRIMARY KEY `Number`)",
+    );
+    execute(&mut package, "INSERT INTO `Numbers` VALUES (1), (2)");
+    assert_error!(
+        package.execute(
+            "ALTER TABLE `Numbers` ADD `Word` CHAR(16) NOT NULL"
+                .parse()
+                .unwrap()
+        ),
+        ErrorKind::InvalidInput,
+        "Cannot add non-nullable column \"Word\" to non-empty table \"Numbers\""
+    );
+    execute(&mut package, "ALTER TABLE `Numbers` ADD `Word` CHAR(16)");
+    execute(
+        &mut package,
+        "UPDATE `Numbers` SET `Word` = 'Two' WHERE `Number` = 2",
+    );
+    execute(&mut package, "INSERT INTO `Numbers` VALUES (3, 'Three')");
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let columns = package.get_table("Numbers").unwrap().columns();
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[1].name(), "Word");
+    assert!(columns[1].is_nullable());
+    let rows =
+        execute(&mut package, "SELECT * FROM `Numbers` ORDER BY `Number`");
+    assert_eq!(
+        rows,
+        vec![
+            vec![Value::Int(1), Value::Null],
+            vec![Value::Int(2), Value::from("Two")],
+            vec![Value::Int(3), Value::from("Three")],
+        ]
+    );
+    assert_error!(
+        package.execute(
+            "ALTER TABLE `Numbers` ADD `Word` SHORT".parse().unwrap()
+        ),
+        ErrorKind::AlreadyExists,
+        "Table \"Numbers\" already has a column named \"Word\""
+    );
+}
+
+//===========================================================================//
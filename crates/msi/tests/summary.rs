@@ -40,6 +40,38 @@ fn set_summary_info_properties() {
     assert_eq!(summary_info.uuid(), Some(uuid));
 }
 
+#[test]
+fn set_patch_summary_info_properties() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Patch, cursor).unwrap();
+    {
+        let summary_info = package.summary_info_mut();
+        summary_info.set_template("{0E1D4A5C-2B3F-4A6E-8D7C-9F0A1B2C3D4E}");
+        summary_info.set_last_author(":T1;:#T1");
+        summary_info
+            .set_revision_number("{5A9B3C1D-7E2F-4B6A-8C0D-1E2F3A4B5C6D}");
+        summary_info.set_character_count(0x0922_0000);
+    }
+
+    let cursor = package.into_inner().unwrap();
+    let package = Package::open(cursor).unwrap();
+    let summary_info = package.summary_info();
+    assert_eq!(
+        summary_info.template(),
+        Some("{0E1D4A5C-2B3F-4A6E-8D7C-9F0A1B2C3D4E}")
+    );
+    assert_eq!(summary_info.last_author(), Some(":T1;:#T1"));
+    assert_eq!(
+        summary_info.revision_number(),
+        Some("{5A9B3C1D-7E2F-4B6A-8C0D-1E2F3A4B5C6D}")
+    );
+    assert_eq!(
+        summary_info.uuid(),
+        Some(Uuid::parse_str("5a9b3c1d-7e2f-4b6a-8c0d-1e2f3a4b5c6d").unwrap())
+    );
+    assert_eq!(summary_info.character_count(), Some(0x0922_0000));
+}
+
 #[test]
 fn set_summary_info_codepage() {
     let cursor = Cursor::new(Vec::new());
@@ -0,0 +1,431 @@
+#[macro_use]
+mod testutil;
+
+use msi::{
+    Column, Delete, Expr, Insert, Language, Package, PackageType, Select,
+    Transform, Update, Value,
+};
+use std::collections::BTreeMap;
+use std::io::{Cursor, ErrorKind, Read, Write};
+
+//===========================================================================//
+
+type Contents = BTreeMap<String, (Vec<String>, Vec<Vec<Value>>)>;
+
+fn create_base() -> Package<Cursor<Vec<u8>>> {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Property").primary_key().id_string(72),
+        Column::build("Value").text_string(0),
+    ];
+    package.create_table("Property", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Property")
+                .row(vec![Value::from("ProductName"), Value::from("Base")])
+                .row(vec![Value::from("Manufacturer"), Value::from("Acme")])
+                .row(vec![Value::from("ARPNOREPAIR"), Value::from("1")]),
+        )
+        .unwrap();
+    let columns = vec![
+        Column::build("Component").primary_key().id_string(72),
+        Column::build("Directory_").id_string(72),
+        Column::build("Attributes").int16(),
+    ];
+    package.create_table("Component", columns).unwrap();
+    package
+        .insert_rows(
+            Insert::into("Component")
+                .row(vec![
+                    Value::from("Main"),
+                    Value::from("INSTALLDIR"),
+                    Value::Int(0),
+                ])
+                .row(vec![
+                    Value::from("Docs"),
+                    Value::from("DOCDIR"),
+                    Value::Int(0),
+                ]),
+        )
+        .unwrap();
+    let columns = vec![Column::build("Name").primary_key().id_string(72)];
+    package.create_table("Obsolete", columns).unwrap();
+    package
+        .insert_rows(Insert::into("Obsolete").row(vec![Value::from("Old")]))
+        .unwrap();
+    package.write_stream("Icon").unwrap().write_all(b"old icon").unwrap();
+    package.write_stream("Banner").unwrap().write_all(b"banner").unwrap();
+    package
+}
+
+fn customize(package: &mut Package<Cursor<Vec<u8>>>) {
+    package
+        .update_rows(
+            Update::table("Property")
+                .set("Value", Value::from("Custom"))
+                .with(Expr::col("Property").eq(Expr::string("ProductName"))),
+        )
+        .unwrap();
+    package
+        .delete_rows(
+            Delete::from("Property")
+                .with(Expr::col("Property").eq(Expr::string("ARPNOREPAIR"))),
+        )
+        .unwrap();
+    package
+        .insert_rows(
+            Insert::into("Property")
+                .row(vec![Value::from("CUSTOMER"), Value::from("Initech")]),
+        )
+        .unwrap();
+    let column = Column::build("Condition").nullable().formatted_string(255);
+    package.add_column("Component", column).unwrap();
+    package
+        .update_rows(
+            Update::table("Component")
+                .set("Condition", Value::from("INSTALLDOCS"))
+                .set("Attributes", Value::Int(8))
+                .with(Expr::col("Component").eq(Expr::string("Docs"))),
+        )
+        .unwrap();
+    package.drop_table("Obsolete").unwrap();
+    let columns = vec![
+        Column::build("Feature").primary_key().id_string(38),
+        Column::build("Title").nullable().text_string(64),
+        Column::build("Level").range(0, 32767).int16(),
+    ];
+    package.create_table("Feature", columns).unwrap();
+    package
+        .insert_rows(Insert::into("Feature").row(vec![
+            Value::from("Complete"),
+            Value::from("Everything"),
+            Value::Int(1),
+        ]))
+        .unwrap();
+    package.write_stream("Icon").unwrap().write_all(b"new icon").unwrap();
+    package.write_stream("Logo").unwrap().write_all(b"logo").unwrap();
+}
+
+fn contents(package: &mut Package<Cursor<Vec<u8>>>) -> Contents {
+    let tables: Vec<(String, Vec<String>)> = package
+        .tables()
+        .map(|table| {
+            let columns = table
+                .columns()
+                .iter()
+                .map(|column| {
+                    format!(
+                        "{} {} nullable={} key={} category={:?}",
+                        column.name(),
+                        column.coltype(),
+                        column.is_nullable(),
+                        column.is_primary_key(),
+                        column.category()
+                    )
+                })
+                .collect();
+            (table.name().to_string(), columns)
+        })
+        .collect();
+    let mut contents = Contents::new();
+    for (table_name, columns) in tables {
+        let mut rows: Vec<Vec<Value>> = package
+            .select_rows(Select::table(&table_name))
+            .unwrap()
+            .map(|row| {
+                (0..row.len()).map(|index| row[index].clone()).collect()
+            })
+            .collect();
+        rows.sort();
+        contents.insert(table_name, (columns, rows));
+    }
+    contents
+}
+
+fn read_stream(package: &mut Package<Cursor<Vec<u8>>>, name: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    package.read_stream(name).unwrap().read_to_end(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn diff_and_apply_transform() {
+    let mut base = create_base();
+    let mut target = create_base();
+    customize(&mut target);
+
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+    assert_eq!(
+        transform.table_names().collect::<Vec<_>>(),
+        vec![
+            "Component",
+            "Feature",
+            "Property",
+            "_Columns",
+            "_Tables",
+            "_Validation"
+        ]
+    );
+    assert_eq!(
+        transform.stream_names().collect::<Vec<_>>(),
+        vec!["Icon", "Logo"]
+    );
+    let cursor = transform.write(Cursor::new(Vec::new())).unwrap();
+    let transform = Transform::read(cursor).unwrap();
+    assert_eq!(transform.summary_info().title(), Some("Transform"));
+
+    base.apply_transform(&transform).unwrap();
+    let cursor = base.into_inner().unwrap();
+    let mut base = Package::open(cursor).unwrap();
+    assert_eq!(contents(&mut base), contents(&mut target));
+    assert!(!base.has_table("Obsolete"));
+    assert_eq!(read_stream(&mut base, "Icon"), b"new icon");
+    assert_eq!(read_stream(&mut base, "Logo"), b"logo");
+    assert_eq!(read_stream(&mut base, "Banner"), b"banner");
+
+    // Diffing identical packages gives an empty transform.
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+    assert_eq!(transform.table_names().count(), 0);
+    assert_eq!(transform.stream_names().count(), 0);
+}
+
+#[test]
+fn diff_summary_info() {
+    let set_product = |package: &mut Package<Cursor<Vec<u8>>>,
+                       code: &str,
+                       version: &str,
+                       language: u16| {
+        package
+            .insert_rows(
+                Insert::into("Property")
+                    .row(vec![Value::from("ProductCode"), Value::from(code)])
+                    .row(vec![
+                        Value::from("ProductVersion"),
+                        Value::from(version),
+                    ])
+                    .row(vec![
+                        Value::from("UpgradeCode"),
+                        Value::from("{A2B3C4D5-E6F7-4A8B-9C0D-1E2F3A4B5C6D}"),
+                    ]),
+            )
+            .unwrap();
+        let summary_info = package.summary_info_mut();
+        summary_info.set_arch("x64");
+        summary_info.set_languages(&[Language::from_code(language)]);
+    };
+    let mut base = create_base();
+    set_product(
+        &mut base,
+        "{0A1B2C3D-4E5F-4A6B-8C7D-9E0F1A2B3C4D}",
+        "1.0.0",
+        1033,
+    );
+    let mut target = create_base();
+    customize(&mut target);
+    set_product(
+        &mut target,
+        "{1B2C3D4E-5F6A-4B7C-9D8E-0F1A2B3C4D5E}",
+        "1.1.0",
+        1041,
+    );
+
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+    let cursor = transform.write(Cursor::new(Vec::new())).unwrap();
+    let transform = Transform::read(cursor).unwrap();
+    let summary_info = transform.summary_info();
+    assert_eq!(summary_info.arch(), Some("x64"));
+    assert_eq!(summary_info.languages(), vec![Language::from_code(1033)]);
+    assert_eq!(summary_info.last_author(), Some("x64;1041"));
+    assert_eq!(
+        summary_info.revision_number(),
+        Some(
+            "{0A1B2C3D-4E5F-4A6B-8C7D-9E0F1A2B3C4D}1.0.0;\
+             {1B2C3D4E-5F6A-4B7C-9D8E-0F1A2B3C4D5E}1.1.0;\
+             {A2B3C4D5-E6F7-4A8B-9C0D-1E2F3A4B5C6D}"
+        )
+    );
+    // Validate the language, product code and upgrade code.
+    assert_eq!(summary_info.character_count(), Some(0x0803_0000));
+
+    // Without product properties, only the languages are validated.
+    let transform =
+        Transform::diff(&mut create_base(), &mut create_base()).unwrap();
+    let summary_info = transform.summary_info();
+    assert_eq!(summary_info.last_author(), Some(";"));
+    assert_eq!(summary_info.revision_number(), None);
+    assert_eq!(summary_info.character_count(), Some(0));
+}
+
+#[test]
+fn open_transform_as_package() {
+    let mut base = create_base();
+    let mut target = create_base();
+    target
+        .update_rows(
+            Update::table("Property")
+                .set("Value", Value::from("Custom"))
+                .with(Expr::col("Property").eq(Expr::string("ProductName"))),
+        )
+        .unwrap();
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+    let cursor = transform.write(Cursor::new(Vec::new())).unwrap();
+    let package = Package::open(cursor).unwrap();
+    assert_eq!(package.package_type(), PackageType::Transform);
+    assert_eq!(package.summary_info().title(), Some("Transform"));
+
+    // The rows that a transform adds to _Tables and _Columns are not a
+    // schema, so they can't be read as one.
+    customize(&mut target);
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+    let cursor = transform.write(Cursor::new(Vec::new())).unwrap();
+    assert!(Package::open(cursor).is_err());
+}
+
+#[test]
+fn read_non_transform() {
+    let cursor = create_base().into_inner().unwrap();
+    assert_error!(
+        Transform::read(cursor),
+        ErrorKind::InvalidData,
+        "Not a transform (package CLSID is \
+         000c1084-0000-0000-c000-000000000046)"
+    );
+}
+
+#[test]
+fn diff_changed_columns() {
+    let mut base = create_base();
+    let mut target = create_base();
+    target.drop_table("Obsolete").unwrap();
+    let columns = vec![Column::build("Name").primary_key().id_string(255)];
+    target.create_table("Obsolete", columns).unwrap();
+    assert_error!(
+        Transform::diff(&mut base, &mut target),
+        ErrorKind::InvalidInput,
+        "Cannot express the column changes to table \"Obsolete\" in a \
+         transform"
+    );
+}
+
+#[test]
+fn apply_to_missing_table() {
+    let mut base = create_base();
+    let mut target = create_base();
+    customize(&mut target);
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    assert_error!(
+        package.apply_transform(&transform),
+        ErrorKind::NotFound,
+        "Transform adds columns to table \"Component\", which does not exist"
+    );
+}
+
+#[test]
+fn apply_to_package_without_validation_table() {
+    let mut base = create_base();
+    let mut target = create_base();
+    customize(&mut target);
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+
+    let mut package = create_base();
+    package
+        .delete_rows(
+            Delete::from("_Columns")
+                .with(Expr::col("Table").eq(Expr::string("_Validation"))),
+        )
+        .unwrap();
+    package
+        .delete_rows(
+            Delete::from("_Tables")
+                .with(Expr::col("Name").eq(Expr::string("_Validation"))),
+        )
+        .unwrap();
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    assert!(!package.has_table("_Validation"));
+
+    package.apply_transform(&transform).unwrap();
+    assert!(!package.has_table("_Validation"));
+    assert!(!package.has_table("Obsolete"));
+    let package_contents = contents(&mut package);
+    let target_contents = contents(&mut target);
+    // Without validation rows, the added columns lose their categories, so
+    // only the data is compared.
+    for table_name in ["Component", "Feature", "Property"] {
+        assert_eq!(
+            package_contents[table_name].1,
+            target_contents[table_name].1
+        );
+    }
+}
+
+#[test]
+fn update_missing_row() {
+    let mut base = create_base();
+    let mut target = create_base();
+    target
+        .update_rows(
+            Update::table("Property")
+                .set("Value", Value::from("Custom"))
+                .with(Expr::col("Property").eq(Expr::string("ProductName"))),
+        )
+        .unwrap();
+    let transform = Transform::diff(&mut base, &mut target).unwrap();
+
+    base.delete_rows(
+        Delete::from("Property")
+            .with(Expr::col("Property").eq(Expr::string("ProductName"))),
+    )
+    .unwrap();
+    assert_error!(
+        base.apply_transform(&transform),
+        ErrorKind::InvalidData,
+        "Transform updates a missing row of table \"Property\" (key: \
+         [Str(\"ProductName\")])"
+    );
+}
+
+#[test]
+fn embed_transform_in_patch() {
+    let mut base = create_base();
+    let mut target = create_base();
+    customize(&mut target);
+    let mut transform = Transform::diff(&mut base, &mut target).unwrap();
+    transform.summary_info_mut().set_character_count(0x0922_0000);
+
+    let cursor = Cursor::new(Vec::new());
+    let mut patch = Package::create(PackageType::Patch, cursor).unwrap();
+    patch.summary_info_mut().set_last_author(":T1");
+    patch.write_embedded_transform("T1", &transform).unwrap();
+    patch.write_stream("Cabinet").unwrap().write_all(b"cab").unwrap();
+    assert!(patch.has_embedded_transform("T1"));
+    assert_error!(
+        patch.write_embedded_transform("Not/Valid", &transform),
+        ErrorKind::InvalidInput,
+        "\"Not/Valid\" is not a valid embedded transform name"
+    );
+
+    let cursor = patch.into_inner().unwrap();
+    let mut patch = Package::open(cursor).unwrap();
+    assert_eq!(patch.package_type(), PackageType::Patch);
+    assert_eq!(patch.embedded_transforms(), vec!["T1"]);
+    assert_eq!(patch.streams().collect::<Vec<_>>(), vec!["Cabinet"]);
+    let transform = patch.read_embedded_transform("T1").unwrap();
+    assert_eq!(transform.summary_info().character_count(), Some(0x0922_0000));
+    base.apply_transform(&transform).unwrap();
+    assert_eq!(contents(&mut base), contents(&mut target));
+
+    patch.remove_embedded_transform("T1").unwrap();
+    assert!(!patch.has_embedded_transform("T1"));
+    assert_error!(
+        patch.read_embedded_transform("T1"),
+        ErrorKind::NotFound,
+        "Embedded transform \"T1\" does not exist"
+    );
+}
+
+//===========================================================================//
@@ -1,7 +1,10 @@
 #[macro_use]
 mod testutil;
 
-use msi::{Category, Column, Insert, Package, PackageType, Value};
+use msi::{
+    Category, Column, ColumnType, Delete, Expr, Insert, Package, PackageType,
+    Select, Update, ValidationWarning, Value,
+};
 use std::io::{Cursor, ErrorKind};
 
 // ========================================================================= //
@@ -66,6 +69,69 @@ fn string_column_category() {
     package.insert_rows(query).unwrap();
 }
 
+#[test]
+fn string_column_categories_checked_on_insert() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Key").primary_key().id_string(72),
+        Column::build("Guid").nullable().category(Category::Guid).string(38),
+        Column::build("Version")
+            .nullable()
+            .category(Category::Version)
+            .string(72),
+        Column::build("Language")
+            .nullable()
+            .category(Category::Language)
+            .string(20),
+        Column::build("Condition")
+            .nullable()
+            .category(Category::Condition)
+            .string(255),
+    ];
+    package.create_table("Things", columns).unwrap();
+
+    let row = |guid: &str, version: &str, language: &str, condition: &str| {
+        Insert::into("Things").row(vec![
+            Value::from("Thing"),
+            Value::from(guid),
+            Value::from(version),
+            Value::from(language),
+            Value::from(condition),
+        ])
+    };
+    let guid = "{34AB5C53-9B30-4E14-AEF0-2C1C7BA826C0}";
+    assert_error!(
+        package.insert_rows(row(
+            "{34AB5C53-9B30-4E14-AEF0-2C1C7BA826C}",
+            "1.2.3",
+            "1033",
+            "NOT Installed"
+        )),
+        ErrorKind::InvalidInput,
+        "\"{34AB5C53-9B30-4E14-AEF0-2C1C7BA826C}\" is not a valid value for \
+         column \"Guid\""
+    );
+    assert_error!(
+        package.insert_rows(row(guid, "1.2.3.4.5", "1033", "NOT Installed")),
+        ErrorKind::InvalidInput,
+        "\"1.2.3.4.5\" is not a valid value for column \"Version\""
+    );
+    assert_error!(
+        package.insert_rows(row(guid, "1.2.3", "1033;1036", "NOT Installed")),
+        ErrorKind::InvalidInput,
+        "\"1033;1036\" is not a valid value for column \"Language\""
+    );
+    assert_error!(
+        package.insert_rows(row(guid, "1.2.3", "1033", "NOT (Installed")),
+        ErrorKind::InvalidInput,
+        "\"NOT (Installed\" is not a valid value for column \"Condition\""
+    );
+    package
+        .insert_rows(row(guid, "1.2.3", "1033,1036", "NOT (Installed)"))
+        .unwrap();
+}
+
 #[test]
 fn string_column_enum_values() {
     let cursor = Cursor::new(Vec::new());
@@ -109,4 +175,180 @@ fn string_column_enum_values() {
     package.insert_rows(query).unwrap();
 }
 
+#[test]
+fn created_package_has_no_validation_warnings() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Key").primary_key().id_string(72),
+        Column::build("Number").nullable().range(0, 100).int16(),
+        Column::build("Data").nullable().binary(),
+    ];
+    package.create_table("Things", columns).unwrap();
+    assert!(package.validation_warnings().is_empty());
+
+    let cursor = package.into_inner().unwrap();
+    let package = Package::open(cursor).unwrap();
+    assert_eq!(package.validation_warnings(), &[]);
+}
+
+#[test]
+fn validation_drift_is_reported_and_repaired() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![
+        Column::build("Key").primary_key().id_string(72),
+        Column::build("Number").int16(),
+        Column::build("Name").nullable().text_string(32),
+        Column::build("Flag").nullable().int16(),
+    ];
+    package.create_table("Things", columns).unwrap();
+    let validation_row = |table: &str, column: &str| {
+        Expr::col("Table")
+            .eq(Expr::string(table))
+            .and(Expr::col("Column").eq(Expr::string(column)))
+    };
+    package
+        .update_rows(
+            Update::table("_Validation")
+                .set("Description", Value::from("The key of the thing"))
+                .with(validation_row("Things", "Key")),
+        )
+        .unwrap();
+    package
+        .update_rows(
+            Update::table("_Validation")
+                .set("Nullable", Value::from("Y"))
+                .set("Category", Value::from("Text"))
+                .with(validation_row("Things", "Number")),
+        )
+        .unwrap();
+    package
+        .update_rows(
+            Update::table("_Validation")
+                .set("MinValue", Value::Int(0))
+                .set("MaxValue", Value::Int(9))
+                .with(validation_row("Things", "Name")),
+        )
+        .unwrap();
+    package
+        .delete_rows(
+            Delete::from("_Validation").with(validation_row("Things", "Flag")),
+        )
+        .unwrap();
+    package
+        .insert_rows(Insert::into("_Validation").row(vec![
+            Value::from("Things"),
+            Value::from("Color"),
+            Value::from("N"),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+        ]))
+        .unwrap();
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    let table = |name: &str| name.to_string();
+    assert_eq!(
+        package.validation_warnings(),
+        &[
+            ValidationWarning::NullableMismatch {
+                table: table("Things"),
+                column: table("Number"),
+                nullable: true,
+            },
+            ValidationWarning::CategoryMismatch {
+                table: table("Things"),
+                column: table("Number"),
+                coltype: ColumnType::Int16,
+                category: Category::Text,
+            },
+            ValidationWarning::RangeMismatch {
+                table: table("Things"),
+                column: table("Name"),
+                coltype: ColumnType::Str(32),
+            },
+            ValidationWarning::MissingRow {
+                table: table("Things"),
+                column: table("Flag"),
+            },
+            ValidationWarning::UnknownColumn {
+                table: table("Things"),
+                column: table("Color"),
+            },
+        ]
+    );
+    assert_eq!(
+        package.validation_warnings()[0].to_string(),
+        "_Validation says column \"Things\".\"Number\" is nullable, but its \
+         type says otherwise"
+    );
+
+    package.repair_validation().unwrap();
+    assert!(package.validation_warnings().is_empty());
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    assert_eq!(package.validation_warnings(), &[]);
+    let rows: Vec<_> = package
+        .select_rows(
+            Select::table("_Validation")
+                .with(Expr::col("Table").eq(Expr::string("Things"))),
+        )
+        .unwrap()
+        .collect();
+    assert_eq!(rows.len(), 4);
+    let row = |column: &str| {
+        rows.iter().find(|row| row["Column"].as_str() == Some(column)).unwrap()
+    };
+    assert_eq!(row("Key")["Description"], Value::from("The key of the thing"));
+    assert_eq!(row("Number")["Nullable"], Value::from("Y"));
+    assert_eq!(row("Number")["Category"], Value::Null);
+    assert_eq!(row("Name")["MinValue"], Value::Null);
+    assert_eq!(row("Flag")["Nullable"], Value::from("Y"));
+    let table = package.get_table("Things").unwrap();
+    assert!(table.get_column("Number").unwrap().is_nullable());
+}
+
+#[test]
+fn missing_validation_table_is_recreated() {
+    let cursor = Cursor::new(Vec::new());
+    let mut package = Package::create(PackageType::Installer, cursor).unwrap();
+    let columns = vec![Column::build("Key").primary_key().id_string(72)];
+    package.create_table("Things", columns).unwrap();
+    package
+        .delete_rows(
+            Delete::from("_Columns")
+                .with(Expr::col("Table").eq(Expr::string("_Validation"))),
+        )
+        .unwrap();
+    package
+        .delete_rows(
+            Delete::from("_Tables")
+                .with(Expr::col("Name").eq(Expr::string("_Validation"))),
+        )
+        .unwrap();
+
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    assert_eq!(
+        package.validation_warnings(),
+        &[ValidationWarning::MissingTable]
+    );
+    assert!(!package.has_table("_Validation"));
+
+    package.repair_validation().unwrap();
+    let columns = vec![Column::build("Key").primary_key().id_string(72)];
+    package.create_table("Others", columns).unwrap();
+    let cursor = package.into_inner().unwrap();
+    let mut package = Package::open(cursor).unwrap();
+    assert_eq!(package.validation_warnings(), &[]);
+    let rows = package.select_rows(Select::table("_Validation")).unwrap();
+    assert_eq!(rows.len(), 12);
+}
+
 // ========================================================================= //
@@ -0,0 +1,334 @@
+// Build the common custom actions and schedule them in the sequence tables
+
+use std::io::{self, Read, Seek, Write};
+
+use crate::tables::{
+    CustomAction, CustomActionType, Entity, InstallExecuteSequence, InstallUISequence,
+};
+
+/// Run a file installed by the package, e.g. to configure the product after it is copied.
+/// `file` is the key of the File table, the formatted `arguments` are passed on the command line.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/custom-action-type-18
+pub fn run_installed_exe(id: &str, file: &str, arguments: &str) -> CustomActionBuilder {
+    CustomActionBuilder::new(
+        id,
+        CustomActionType::Exe | CustomActionType::SourceFile,
+        Some(file),
+        Some(arguments),
+    )
+}
+
+/// Run a script stored in a stream of the Binary table.
+/// The `function` of the script is called when given, otherwise the script is only evaluated.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/custom-action-type-5
+/// https://learn.microsoft.com/en-us/windows/win32/msi/custom-action-type-6
+pub fn run_script(
+    id: &str,
+    binary: &str,
+    language: ScriptLanguage,
+    function: Option<&str>,
+) -> CustomActionBuilder {
+    let language = match language {
+        ScriptLanguage::JScript => CustomActionType::JScript,
+        ScriptLanguage::VBScript => CustomActionType::VBScript,
+    };
+    CustomActionBuilder::new(
+        id,
+        language | CustomActionType::BinaryData,
+        Some(binary),
+        function,
+    )
+}
+
+/// Set a property to a formatted value, e.g. `[INSTALLDIR]bin`
+/// https://learn.microsoft.com/en-us/windows/win32/msi/custom-action-type-51
+pub fn set_property(id: &str, property: &str, value: &str) -> CustomActionBuilder {
+    CustomActionBuilder::new(
+        id,
+        CustomActionType::TextData | CustomActionType::Property,
+        Some(property),
+        Some(value),
+    )
+}
+
+/// Set a key of the Directory table to a formatted path, e.g. `[LocalAppDataFolder]Foo`
+/// https://learn.microsoft.com/en-us/windows/win32/msi/custom-action-type-35
+pub fn set_directory(id: &str, directory: &str, value: &str) -> CustomActionBuilder {
+    CustomActionBuilder::new(
+        id,
+        CustomActionType::TextData | CustomActionType::Directory,
+        Some(directory),
+        Some(value),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptLanguage {
+    JScript,
+    VBScript,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomActionBuilder {
+    action: CustomAction,
+    ui_sequence: Option<InstallUISequence>,
+    execute_sequence: Option<InstallExecuteSequence>,
+}
+
+impl CustomActionBuilder {
+    fn new(
+        id: &str,
+        action_type: CustomActionType,
+        source: Option<&str>,
+        target: Option<&str>,
+    ) -> Self {
+        CustomActionBuilder {
+            action: CustomAction {
+                action: id.to_string(),
+                action_type,
+                source: source.map(str::to_string),
+                target: target.map(str::to_string),
+            },
+            ui_sequence: None,
+            execute_sequence: None,
+        }
+    }
+
+    /// Run the action in the installation script, after the changes of the previous actions are done.
+    /// Deferred actions can't read properties, their formatted target is evaluated when the script is written.
+    pub fn deferred(mut self) -> Self {
+        self.action.action_type |= CustomActionType::InScript;
+        self
+    }
+
+    /// Run the deferred action as LocalSystem, e.g. to write in protected locations of per machine installs.
+    pub fn no_impersonate(mut self) -> Self {
+        self.action.action_type |= CustomActionType::InScript | CustomActionType::NoImpersonate;
+        self
+    }
+
+    /// Only run the deferred action when the installation fails, to undo another deferred action.
+    pub fn rollback(mut self) -> Self {
+        self.action.action_type |= CustomActionType::InScript | CustomActionType::Rollback;
+        self
+    }
+
+    /// Only run the deferred action once the installation succeeded.
+    pub fn commit(mut self) -> Self {
+        self.action.action_type |= CustomActionType::InScript | CustomActionType::Commit;
+        self
+    }
+
+    /// Ignore the exit code of the executable or the script instead of failing the installation.
+    pub fn continue_on_error(mut self) -> Self {
+        self.action.action_type |= CustomActionType::Continue;
+        self
+    }
+
+    /// Keep the target out of the log, e.g. when it contains a password.
+    pub fn hide_target(mut self) -> Self {
+        self.action.action_type |= CustomActionType::HideTarget;
+        self
+    }
+
+    /// Schedule the action in the InstallUISequence table.
+    /// Only immediate actions can run in the UI sequence.
+    pub fn in_ui_sequence(mut self, condition: Option<&str>, sequence: i32) -> Self {
+        self.ui_sequence = Some(InstallUISequence::new(
+            &self.action.action,
+            condition,
+            sequence,
+        ));
+        self
+    }
+
+    /// Schedule the action in the InstallExecuteSequence table.
+    /// Deferred actions must be after InstallInitialize (1500) and before InstallFinalize (6600).
+    pub fn in_execute_sequence(mut self, condition: Option<&str>, sequence: i32) -> Self {
+        self.execute_sequence = Some(InstallExecuteSequence::new(
+            &self.action.action,
+            condition,
+            sequence,
+        ));
+        self
+    }
+
+    pub fn custom_action(&self) -> CustomAction {
+        self.action.clone()
+    }
+
+    /// Add the action to the CustomAction table and to the sequence tables it is scheduled in, the tables are created
+    /// if missing.
+    pub fn insert<F: Read + Write + Seek>(&self, package: &mut msi::Package<F>) -> io::Result<()> {
+        let deferred = self.action.action_type.contains(CustomActionType::InScript);
+        if deferred && self.ui_sequence.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Deferred custom action {} can't run in the UI sequence",
+                    self.action.action
+                ),
+            ));
+        }
+        if let Some(sequence) = &self.execute_sequence
+            && deferred
+            && !(1501..6600).contains(&sequence.order)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Deferred custom action {} must run between InstallInitialize and InstallFinalize",
+                    self.action.action
+                ),
+            ));
+        }
+
+        if !package.has_table(CustomAction::table_name()) {
+            CustomAction::create_table(package)?;
+        }
+        CustomAction::insert(package, &[self.custom_action()])?;
+        if let Some(sequence) = &self.ui_sequence {
+            if !package.has_table(InstallUISequence::table_name()) {
+                InstallUISequence::create_table(package)?;
+            }
+            InstallUISequence::insert(package, std::slice::from_ref(sequence))?;
+        }
+        if let Some(sequence) = &self.execute_sequence {
+            if !package.has_table(InstallExecuteSequence::table_name()) {
+                InstallExecuteSequence::create_table(package)?;
+            }
+            InstallExecuteSequence::insert(package, std::slice::from_ref(sequence))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor};
+
+    use msi::{Package, PackageType};
+
+    use super::{ScriptLanguage, run_installed_exe, run_script, set_directory, set_property};
+    use crate::tables::{CustomAction, Entity, InstallExecuteSequence, InstallUISequence};
+
+    fn package() -> Package<Cursor<Vec<u8>>> {
+        Package::create(PackageType::Installer, Cursor::new(Vec::new())).unwrap()
+    }
+
+    #[test]
+    fn type_bits() {
+        let cases = [
+            (run_installed_exe("Exe", "app.exe", "--configure"), 18),
+            (
+                run_script("JS", "script", ScriptLanguage::JScript, Some("main")),
+                5,
+            ),
+            (
+                run_script("VBS", "script", ScriptLanguage::VBScript, None),
+                6,
+            ),
+            (set_property("Prop", "FOO", "[INSTALLDIR]"), 51),
+            (
+                set_directory("Dir", "APPDIR", "[LocalAppDataFolder]Foo"),
+                35,
+            ),
+            (
+                set_property("Deferred", "FOO", "bar").deferred(),
+                51 | 0x400,
+            ),
+            (
+                run_installed_exe("System", "app.exe", "").no_impersonate(),
+                18 | 0x400 | 0x800,
+            ),
+            (
+                run_installed_exe("Undo", "app.exe", "").rollback(),
+                18 | 0x400 | 0x100,
+            ),
+            (
+                run_installed_exe("Done", "app.exe", "").commit(),
+                18 | 0x400 | 0x200,
+            ),
+            (
+                run_installed_exe("Quiet", "app.exe", "")
+                    .continue_on_error()
+                    .hide_target(),
+                18 | 0x40 | 0x2000,
+            ),
+        ];
+        for (builder, bits) in cases {
+            let action = builder.custom_action();
+            assert_eq!(action.action_type.bits(), bits, "{}", action.action);
+        }
+        let action =
+            run_script("JS", "script", ScriptLanguage::JScript, Some("main")).custom_action();
+        assert_eq!(action.source.as_deref(), Some("script"));
+        assert_eq!(action.target.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn insert_creates_tables() {
+        let mut package = package();
+        set_property("SetFoo", "FOO", "bar")
+            .in_ui_sequence(Some("NOT Installed"), 1000)
+            .in_execute_sequence(None, 1000)
+            .insert(&mut package)
+            .unwrap();
+        run_installed_exe("Configure", "app.exe", "")
+            .deferred()
+            .in_execute_sequence(None, 1501)
+            .insert(&mut package)
+            .unwrap();
+
+        let actions = CustomAction::list(&mut package).unwrap();
+        assert_eq!(actions.len(), 2);
+        let ui = InstallUISequence::list(&mut package).unwrap();
+        assert_eq!(ui.len(), 1);
+        assert_eq!(ui[0].condition.as_deref(), Some("NOT Installed"));
+        let execute = InstallExecuteSequence::list(&mut package).unwrap();
+        assert_eq!(execute.len(), 2);
+        assert!(
+            execute
+                .iter()
+                .any(|row| row.action == "Configure" && row.order == 1501)
+        );
+    }
+
+    #[test]
+    fn deferred_action_not_in_ui_sequence() {
+        let mut package = package();
+        let err = set_property("Deferred", "FOO", "bar")
+            .deferred()
+            .in_ui_sequence(None, 1000)
+            .insert(&mut package)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(!package.has_table("CustomAction"));
+    }
+
+    #[test]
+    fn deferred_action_between_initialize_and_finalize() {
+        let mut package = package();
+        for order in [1000, 1500, 6600, 7000] {
+            let err = run_installed_exe("Configure", "app.exe", "")
+                .deferred()
+                .in_execute_sequence(None, order)
+                .insert(&mut package)
+                .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{order}");
+        }
+        for order in [1501, 6599] {
+            let id = format!("Configure{order}");
+            run_installed_exe(&id, "app.exe", "")
+                .deferred()
+                .in_execute_sequence(None, order)
+                .insert(&mut package)
+                .unwrap();
+        }
+        // Immediate actions can run anywhere
+        run_installed_exe("Launch", "app.exe", "")
+            .in_execute_sequence(None, 6601)
+            .insert(&mut package)
+            .unwrap();
+    }
+}
@@ -0,0 +1,53 @@
+// Build valid identifiers for the key columns of the database tables
+
+use uuid::Uuid;
+
+/// Maximum length of the identifiers used as keys, e.g. in the Component, File or Registry tables.
+pub const MAX_IDENTIFIER_LENGTH: usize = 72;
+
+// Namespace to hash the values which don't fit in an identifier
+const IDENTIFIER_NAMESPACE: Uuid = uuid::uuid!("6f1c5b0e-3b4e-4d36-9a8f-1f0c7a1d2e55");
+
+/// Turn any value into an identifier: letters, digits, underscores and periods, starting with a letter or an underscore.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/identifier
+///
+/// Other characters are replaced with underscores. Values longer than 72 characters are truncated
+/// and suffixed with a hash of the whole value so they stay unique.
+pub fn identifier(value: &str) -> String {
+    let mut id: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        id.insert(0, '_');
+    }
+    if id.len() > MAX_IDENTIFIER_LENGTH {
+        let suffix = hashed_suffix(value);
+        id.truncate(MAX_IDENTIFIER_LENGTH - suffix.len());
+        id.push_str(&suffix);
+    }
+    id
+}
+
+/// Like `identifier` but the hash suffix is always added, to tell apart values with the same identifier.
+pub fn unique_identifier(value: &str) -> String {
+    let mut id = identifier(value);
+    let suffix = hashed_suffix(value);
+    if !id.ends_with(&suffix) {
+        id.truncate(MAX_IDENTIFIER_LENGTH - suffix.len());
+        id.push_str(&suffix);
+    }
+    id
+}
+
+/// Suffix made of an underscore and a short hash of the value, e.g. `_1a2b3c4d`.
+pub(crate) fn hashed_suffix(value: &str) -> String {
+    let hash = Uuid::new_v5(&IDENTIFIER_NAMESPACE, value.as_bytes()).simple();
+    format!("_{}", &hash.to_string()[..8])
+}
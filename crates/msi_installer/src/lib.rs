@@ -1,5 +1,16 @@
+// The Entity derive refers to the crate by name, also from its own tables
+extern crate self as msi_installer;
+
+pub mod custom_action;
+pub mod identifier;
+pub mod merge_module;
+pub mod patch;
 pub mod properties;
+pub mod sequence;
 pub mod tables;
 pub mod ui;
 
-pub use properties::{PropertiesBuilder, RequiredProperties};
+pub use identifier::identifier;
+pub use merge_module::MergeModule;
+pub use patch::PatchBuilder;
+pub use properties::{InstallScope, PropertiesBuilder, RequiredProperties};
@@ -0,0 +1,639 @@
+// Merge modules (.msm) are installer databases holding shared components, e.g. the VC++
+// runtime, which are merged into the packages that need them.
+// https://learn.microsoft.com/en-us/windows/win32/msi/merge-modules
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, Read, Seek, Write},
+};
+
+use crate::{
+    identifier::hashed_suffix,
+    tables::{Entity, FeatureComponents, File},
+};
+
+/// Stream of the merge module holding the cabinet of its files, keyed by File table key.
+pub const MODULE_CABINET_STREAM: &str = "MergeModule.CABinet";
+
+// Merge module tables referencing a feature use this placeholder, replaced by the feature the
+// module is merged into.
+const NULL_FEATURE: &str = "{00000000-0000-0000-0000-000000000000}";
+
+// The root directory of the module, redirected to the directory the module is merged into.
+const TARGET_DIR: &str = "TARGETDIR";
+
+// Column of the module tables holding the module ID.
+const MODULE_ID_COLUMN: &str = "ModuleID";
+
+// Standard folder properties, kept under TARGETDIR when merged as they are resolved by Windows
+// Installer.
+// https://learn.microsoft.com/en-us/windows/win32/msi/system-folder-properties
+const STANDARD_FOLDERS: &[&str] = &[
+    "AdminToolsFolder",
+    "AppDataFolder",
+    "CommonAppDataFolder",
+    "CommonFiles64Folder",
+    "CommonFilesFolder",
+    "DesktopFolder",
+    "FavoritesFolder",
+    "FontsFolder",
+    "LocalAppDataFolder",
+    "MyPicturesFolder",
+    "NetHoodFolder",
+    "PersonalFolder",
+    "PrintHoodFolder",
+    "ProgramFiles64Folder",
+    "ProgramFilesFolder",
+    "ProgramMenuFolder",
+    "RecentFolder",
+    "SendToFolder",
+    "StartMenuFolder",
+    "StartupFolder",
+    "System16Folder",
+    "System64Folder",
+    "SystemFolder",
+    "TempFolder",
+    "TemplateFolder",
+    "WindowsFolder",
+    "WindowsVolume",
+];
+
+// Module tables describing the module itself, which are not merged as is.
+const MODULE_TABLES: &[&str] = &[
+    "ModuleConfiguration",
+    "ModuleDependency",
+    "ModuleExclusion",
+    "ModuleIgnoreTable",
+    "ModuleSubstitution",
+];
+
+// Module sequence tables and the sequence tables of the package their actions are merged into.
+const MODULE_SEQUENCE_TABLES: &[(&str, &str)] = &[
+    ("ModuleAdminExecuteSequence", "AdminExecuteSequence"),
+    ("ModuleAdminUISequence", "AdminUISequence"),
+    ("ModuleAdvtExecuteSequence", "AdvtExecuteSequence"),
+    ("ModuleAdvtUISequence", "AdvtUISequence"),
+    ("ModuleInstallExecuteSequence", "InstallExecuteSequence"),
+    ("ModuleInstallUISequence", "InstallUISequence"),
+];
+
+/// A merge module opened for merging into an installer package.
+pub struct MergeModule<F> {
+    package: msi::Package<F>,
+    module_id: String,
+    language: i32,
+    version: String,
+    // The `.GUID` suffix of the module's identifiers and the suffix replacing it when merged.
+    rekey: Option<(String, String)>,
+}
+
+impl<F: Read + Seek> MergeModule<F> {
+    /// Open a merge module, configurable merge modules are not supported.
+    pub fn open(inner: F) -> io::Result<MergeModule<F>> {
+        let mut package = msi::Package::open(inner)?;
+        if !package.has_table("ModuleSignature") {
+            return Err(invalid_data(
+                "Not a merge module, the ModuleSignature table is missing".to_string(),
+            ));
+        }
+        let signatures = select_all(&mut package, "ModuleSignature")?;
+        let [signature] = signatures.as_slice() else {
+            return Err(invalid_data(format!(
+                "Merge module has {} ModuleSignature rows, expected one",
+                signatures.len()
+            )));
+        };
+        let (module_id, language, version) = match signature.as_slice() {
+            [
+                msi::Value::Str(id),
+                msi::Value::Int(language),
+                msi::Value::Str(version),
+            ] => (id.clone(), *language, version.clone()),
+            _ => {
+                return Err(invalid_data(
+                    "Merge module has an invalid ModuleSignature row".to_string(),
+                ));
+            }
+        };
+        for table in ["ModuleConfiguration", "ModuleSubstitution"] {
+            if package.has_table(table) && !select_all(&mut package, table)?.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("Merge module {module_id} is configurable, which is not supported"),
+                ));
+            }
+        }
+        let rekey = module_id
+            .rfind('.')
+            .map(|index| (module_id[index..].to_string(), hashed_suffix(&module_id)));
+        Ok(MergeModule {
+            package,
+            module_id,
+            language,
+            version,
+            rekey,
+        })
+    }
+
+    /// Identifier of the module, its name followed by its GUID, e.g. `Name.GUID`.
+    /// The module's own identifiers are suffixed by the same `.GUID`. When merged, they are
+    /// re-keyed with a short hash of the module ID instead, e.g. `Runtime.GUID` becomes
+    /// `Runtime_1a2b3c4d`, which keeps them apart from the keys of the package and of other
+    /// modules.
+    pub fn module_id(&self) -> &str {
+        &self.module_id
+    }
+
+    pub fn language(&self) -> i32 {
+        self.language
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Keys of the components of the module, re-keyed.
+    pub fn components(&mut self) -> io::Result<Vec<String>> {
+        if !self.package.has_table("ModuleComponents") {
+            return Ok(Vec::new());
+        }
+        let rows = self
+            .package
+            .select_rows(msi::Select::table("ModuleComponents"))?;
+        let components = rows
+            .filter_map(|row| row["Component"].as_str().map(str::to_string))
+            .collect::<Vec<_>>();
+        Ok(components
+            .iter()
+            .map(|component| self.rekey(component))
+            .collect())
+    }
+
+    /// Files of the module, re-keyed, in the order of their sequence numbers.
+    pub fn files(&mut self) -> io::Result<Vec<File>> {
+        let files = self.module_files()?;
+        Ok(files
+            .into_iter()
+            .map(|file| self.rekey_file(file))
+            .collect())
+    }
+
+    /// Extract the files of the module from its cabinet, re-keyed, in the order of their sequence
+    /// numbers. The cabinet file names are the keys of the files.
+    pub fn read_files(&mut self) -> io::Result<Vec<(File, Vec<u8>)>> {
+        let files = self.module_files()?;
+        if files.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut data = Vec::new();
+        self.package
+            .read_stream(MODULE_CABINET_STREAM)?
+            .read_to_end(&mut data)?;
+        let mut cabinet = cab::Cabinet::new(io::Cursor::new(data))?;
+        let mut contents = Vec::with_capacity(files.len());
+        for file in files {
+            if cabinet.get_file_entry(&file.file).is_none() {
+                return Err(invalid_data(format!(
+                    "File {} of merge module {} is missing from its cabinet",
+                    file.file, self.module_id
+                )));
+            }
+            let mut data = Vec::new();
+            cabinet.read_file(&file.file)?.read_to_end(&mut data)?;
+            contents.push((self.rekey_file(file), data));
+        }
+        Ok(contents)
+    }
+
+    // Files of the module with their keys in the module, in the order of their sequence numbers.
+    fn module_files(&mut self) -> io::Result<Vec<File>> {
+        if !self.package.has_table(File::table_name()) {
+            return Ok(Vec::new());
+        }
+        let mut files =
+            File::list(&mut self.package).map_err(|err| invalid_data(err.to_string()))?;
+        files.sort_by_key(|file| file.sequence);
+        Ok(files)
+    }
+
+    // Replaces the `.GUID` suffix of the module's identifiers, including the ones referenced in
+    // formatted values, e.g. `[#File.GUID]`.
+    fn rekey(&self, value: &str) -> String {
+        match &self.rekey {
+            Some((suffix, new_suffix)) => value.replace(suffix.as_str(), new_suffix),
+            None => value.to_string(),
+        }
+    }
+
+    fn rekey_value(&self, value: &mut msi::Value) {
+        if let msi::Value::Str(string) = value {
+            *string = self.rekey(string);
+        }
+    }
+
+    fn rekey_file(&self, file: File) -> File {
+        File {
+            file: self.rekey(&file.file),
+            component: self.rekey(&file.component),
+            ..file
+        }
+    }
+
+    /// Merge the tables of the module into the package:
+    /// - the identifiers of the module are re-keyed, see [`module_id`](Self::module_id),
+    /// - the components of the module are installed by `feature`, and the feature placeholders
+    ///   of the module tables are replaced by it,
+    /// - the directories of the module's TARGETDIR are created in `directory`, except the
+    ///   standard folders (e.g. `System64Folder`) which stay in TARGETDIR,
+    /// - the files of the module are numbered from `first_sequence`, in their module order,
+    ///   the caller is responsible for adding them to a cabinet covered by the Media table,
+    /// - the actions of the module sequence tables are scheduled in the package's sequence tables.
+    ///
+    /// Rows already in the package are skipped, a row with the same key but different values is
+    /// a conflict. Foreign keys are not checked while merging, whatever the referential integrity
+    /// of the package.
+    pub fn merge_into<G: Read + Write + Seek>(
+        &mut self,
+        package: &mut msi::Package<G>,
+        feature: &str,
+        directory: &str,
+        first_sequence: i32,
+    ) -> io::Result<()> {
+        let integrity = package.referential_integrity();
+        package.set_referential_integrity(msi::ReferentialIntegrity::Ignore);
+        let result = self.merge_tables(package, feature, directory, first_sequence);
+        package.set_referential_integrity(integrity);
+        result
+    }
+
+    fn merge_tables<G: Read + Write + Seek>(
+        &mut self,
+        package: &mut msi::Package<G>,
+        feature: &str,
+        directory: &str,
+        first_sequence: i32,
+    ) -> io::Result<()> {
+        for (table, key) in [("Feature", feature), ("Directory", directory)] {
+            if !has_row(package, table, key)? {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{table} {key} does not exist in the package"),
+                ));
+            }
+        }
+        let merged = merged_modules(package)?;
+        if merged.contains(&self.module_id) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("Merge module {} is already merged", self.module_id),
+            ));
+        }
+        self.check_dependencies(&merged)?;
+
+        let sequences = self
+            .files()?
+            .into_iter()
+            .zip(first_sequence..)
+            .map(|(file, sequence)| (file.file, sequence))
+            .collect::<HashMap<_, _>>();
+        let ignored = if self.package.has_table("ModuleIgnoreTable") {
+            select_all(&mut self.package, "ModuleIgnoreTable")?
+                .into_iter()
+                .filter_map(|row| row[0].as_str().map(str::to_string))
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let tables = self
+            .package
+            .tables()
+            .map(|table| table.name().to_string())
+            .filter(|name| {
+                !name.starts_with('_')
+                    && !ignored.contains(name)
+                    && !MODULE_TABLES.contains(&name.as_str())
+                    && !MODULE_SEQUENCE_TABLES
+                        .iter()
+                        .any(|(table, _)| table == name)
+            })
+            .collect::<Vec<_>>();
+        for table in tables {
+            let Some(columns) = self
+                .package
+                .get_table(&table)
+                .map(|table| table.columns().to_vec())
+            else {
+                return Err(invalid_data(format!(
+                    "Table {table} of merge module {} is missing",
+                    self.module_id
+                )));
+            };
+            let sequence_index = columns
+                .iter()
+                .position(|column| column.name() == "Sequence");
+            let mut rows = Vec::new();
+            for mut row in select_all(&mut self.package, &table)? {
+                if table == "Directory"
+                    && let Some(key) = row[0].as_str()
+                {
+                    if key == TARGET_DIR {
+                        continue;
+                    }
+                    if STANDARD_FOLDERS.contains(&key) {
+                        if !has_row(package, "Directory", key)? {
+                            rows.push(row);
+                        }
+                        continue;
+                    }
+                }
+                for (value, column) in row.iter_mut().zip(columns.iter()) {
+                    // The module ID of ModuleSignature and ModuleComponents is kept
+                    if column.name() != MODULE_ID_COLUMN {
+                        self.rekey_value(value);
+                    }
+                    match (referenced_table(column).as_deref(), value.as_str()) {
+                        (Some("Directory"), Some(TARGET_DIR)) => *value = directory.into(),
+                        (Some("Feature"), Some(NULL_FEATURE)) => *value = feature.into(),
+                        _ => {}
+                    }
+                }
+                if table == File::table_name()
+                    && let Some(index) = sequence_index
+                    && let Some(sequence) = row[0].as_str().and_then(|file| sequences.get(file))
+                {
+                    row[index] = msi::Value::Int(*sequence);
+                }
+                rows.push(row);
+            }
+            merge_rows(package, &self.module_id, &table, &columns, rows)?;
+        }
+
+        let components = self
+            .components()?
+            .into_iter()
+            .map(|component| {
+                FeatureComponents {
+                    feature: feature.to_string(),
+                    component,
+                }
+                .to_row()
+            })
+            .collect();
+        merge_rows(
+            package,
+            &self.module_id,
+            FeatureComponents::table_name(),
+            &FeatureComponents::definition(),
+            components,
+        )?;
+
+        for (module_table, table) in MODULE_SEQUENCE_TABLES {
+            if self.package.has_table(module_table) && package.has_table(table) {
+                self.merge_sequence(package, module_table, table)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Checks the modules required by the module are merged, and the modules it excludes are not.
+    fn check_dependencies(&mut self, merged: &HashSet<String>) -> io::Result<()> {
+        if self.package.has_table("ModuleDependency") {
+            for row in select_all(&mut self.package, "ModuleDependency")? {
+                if let Some(required) = row[2].as_str()
+                    && !merged.contains(required)
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "Merge module {} requires merge module {required}",
+                            self.module_id
+                        ),
+                    ));
+                }
+            }
+        }
+        if self.package.has_table("ModuleExclusion") {
+            for row in select_all(&mut self.package, "ModuleExclusion")? {
+                if let Some(excluded) = row[2].as_str()
+                    && merged.contains(excluded)
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "Merge module {} cannot be merged with merge module {excluded}",
+                            self.module_id
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Schedules the actions of a module sequence table which are not in the package's sequence
+    // table yet. Actions without a sequence number are placed right before or after their base
+    // action, at the first free sequence number.
+    fn merge_sequence<G: Read + Write + Seek>(
+        &mut self,
+        package: &mut msi::Package<G>,
+        module_table: &str,
+        table: &str,
+    ) -> io::Result<()> {
+        let mut scheduled = package
+            .select_rows(msi::Select::table(table))?
+            .filter_map(|row| {
+                Some((
+                    row["Action"].as_str()?.to_string(),
+                    row["Sequence"].as_int()?,
+                ))
+            })
+            .collect::<HashMap<String, i32>>();
+        let mut taken = scheduled.values().copied().collect::<HashSet<i32>>();
+        let module_rows = self
+            .package
+            .select_rows(msi::Select::table(module_table))?
+            .collect::<Vec<_>>();
+        let mut pending = module_rows
+            .iter()
+            .filter_map(|row| {
+                let action = self.rekey(row["Action"].as_str()?);
+                if scheduled.contains_key(&action) {
+                    return None;
+                }
+                let mut condition = row["Condition"].clone();
+                self.rekey_value(&mut condition);
+                Some((
+                    action,
+                    row["Sequence"].as_int(),
+                    row["BaseAction"].as_str().map(|base| self.rekey(base)),
+                    row["After"].as_int() == Some(1),
+                    condition,
+                ))
+            })
+            .collect::<Vec<_>>();
+        let mut rows = Vec::new();
+        while !pending.is_empty() {
+            let count = pending.len();
+            let mut unscheduled = Vec::new();
+            for (action, sequence, base_action, after, condition) in pending {
+                let sequence = match (sequence, &base_action) {
+                    (Some(sequence), _) => sequence,
+                    (None, Some(base)) => match scheduled.get(base) {
+                        Some(&base) => {
+                            let step = if after { 1 } else { -1 };
+                            let mut sequence = base + step;
+                            while taken.contains(&sequence) {
+                                sequence += step;
+                            }
+                            sequence
+                        }
+                        None => {
+                            unscheduled.push((action, None, base_action, after, condition));
+                            continue;
+                        }
+                    },
+                    (None, None) => {
+                        return Err(invalid_data(format!(
+                            "Action {action} of {module_table} has neither a sequence nor a base action"
+                        )));
+                    }
+                };
+                scheduled.insert(action.clone(), sequence);
+                taken.insert(sequence);
+                rows.push(vec![action.into(), condition, msi::Value::Int(sequence)]);
+            }
+            if unscheduled.len() == count {
+                let (action, _, base_action, _, _) = &unscheduled[0];
+                return Err(invalid_data(format!(
+                    "Action {action} of {module_table} is scheduled relative to {}, which is not scheduled",
+                    base_action.as_deref().unwrap_or_default()
+                )));
+            }
+            pending = unscheduled;
+        }
+        if !rows.is_empty() {
+            package.insert_rows(msi::Insert::into(table).rows(rows))?;
+        }
+        Ok(())
+    }
+}
+
+// Inserts the rows of a module table in the package, creating the table if missing.
+fn merge_rows<G: Read + Write + Seek>(
+    package: &mut msi::Package<G>,
+    module_id: &str,
+    table: &str,
+    columns: &[msi::Column],
+    rows: Vec<Vec<msi::Value>>,
+) -> io::Result<()> {
+    let existing = match package.get_table(table) {
+        Some(existing) => {
+            let same_columns = existing.columns().len() == columns.len()
+                && existing
+                    .columns()
+                    .iter()
+                    .zip(columns)
+                    .all(|(a, b)| a.name() == b.name() && a.coltype() == b.coltype());
+            if !same_columns {
+                return Err(invalid_data(format!(
+                    "Table {table} of merge module {module_id} does not match the package's table"
+                )));
+            }
+            select_all(package, table)?
+        }
+        None => {
+            package.create_table(table, columns.to_vec())?;
+            Vec::new()
+        }
+    };
+    let key_indices = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.is_primary_key())
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let key = |row: &[msi::Value]| {
+        key_indices
+            .iter()
+            .map(|&index| row[index].clone())
+            .collect::<Vec<_>>()
+    };
+    let existing = existing
+        .into_iter()
+        .map(|row| (key(&row), row))
+        .collect::<HashMap<_, _>>();
+    let mut new_rows = Vec::new();
+    for row in rows {
+        match existing.get(&key(&row)) {
+            Some(existing) if *existing == row => {}
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!(
+                        "Row {:?} of table {table} of merge module {module_id} conflicts with the package",
+                        key(&row)
+                    ),
+                ));
+            }
+            None => new_rows.push(row),
+        }
+    }
+    if !new_rows.is_empty() {
+        package.insert_rows(msi::Insert::into(table).rows(new_rows))?;
+    }
+    Ok(())
+}
+
+// Returns the table a column references, from its foreign key or, for modules without a
+// _Validation table, from the usual names of directory and feature columns.
+fn referenced_table(column: &msi::Column) -> Option<String> {
+    if let Some((table, _)) = column.get_foreign_key() {
+        return Some(table);
+    }
+    match column.name() {
+        "Directory_" | "Directory_Parent" => Some("Directory".to_string()),
+        "Feature_" => Some("Feature".to_string()),
+        _ => None,
+    }
+}
+
+// Returns the IDs of the modules merged in the package.
+fn merged_modules<G: Read + Seek>(package: &mut msi::Package<G>) -> io::Result<HashSet<String>> {
+    if !package.has_table("ModuleSignature") {
+        return Ok(HashSet::new());
+    }
+    Ok(select_all(package, "ModuleSignature")?
+        .into_iter()
+        .filter_map(|row| row[0].as_str().map(str::to_string))
+        .collect())
+}
+
+fn has_row<G: Read + Seek>(
+    package: &mut msi::Package<G>,
+    table: &str,
+    key: &str,
+) -> io::Result<bool> {
+    let Some(column) = package
+        .get_table(table)
+        .and_then(|table| table.columns().first())
+        .map(|column| column.name().to_string())
+    else {
+        return Ok(false);
+    };
+    let query = msi::Select::table(table).with(msi::Expr::col(column).eq(msi::Expr::string(key)));
+    Ok(package.select_rows(query)?.next().is_some())
+}
+
+fn select_all<G: Read + Seek>(
+    package: &mut msi::Package<G>,
+    table: &str,
+) -> io::Result<Vec<Vec<msi::Value>>> {
+    Ok(package
+        .select_rows(msi::Select::table(table))?
+        .map(|row| (0..row.len()).map(|index| row[index].clone()).collect())
+        .collect())
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,402 @@
+// Patches (.msp) update an installed product from the differences between the package it was
+// installed from and a newer package, without a full reinstall.
+// https://learn.microsoft.com/en-us/windows/win32/msi/patch-packages
+
+use std::{
+    collections::HashMap,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+};
+
+use uuid::Uuid;
+
+use crate::identifier::identifier;
+use crate::tables::{
+    Entity, File, FileAttributes, Media, MsiPatchMetadata, MsiPatchSequence, PatchPackage, Property,
+};
+
+// Patches with a MsiPatchSequence table require Windows Installer 3.0.
+const PATCH_WORD_COUNT: i32 = 4;
+
+// Name of the embedded transform from the target to the upgraded package. The patch transform,
+// adding the media of the patch files, has the same name prefixed by `#`.
+const TRANSFORM_NAME: &str = "Upgrade";
+
+// Validation of the upgrade transform: same language, product and upgrade code, and installed
+// version equal to the target version, the default of the Windows Installer patch tools.
+// https://learn.microsoft.com/en-us/windows/win32/msi/character-count-summary
+const VALIDATE_LANGUAGE: i32 = 0x0001;
+const VALIDATE_PRODUCT: i32 = 0x0002;
+const VALIDATE_UPDATE_VERSION: i32 = 0x0020;
+const VALIDATE_NEW_EQUAL_BASE_VERSION: i32 = 0x0100;
+const VALIDATE_UPGRADE_CODE: i32 = 0x0800;
+
+// The patch transform only adds rows and a table, it ignores the errors of a patch applied twice.
+const ERROR_ADD_EXISTING_ROW: i32 = 0x0001;
+const ERROR_ADD_EXISTING_TABLE: i32 = 0x0004;
+
+/// Builds a patch updating the products installed from a target package to an upgraded package.
+///
+/// Files are patched whole: the files which are added or whose contents changed are packed in a
+/// cabinet of the patch, on a new disk numbered after the disks of the target package.
+#[derive(Debug, Clone)]
+pub struct PatchBuilder {
+    patch_code: Uuid,
+    family: Option<String>,
+    display_name: Option<String>,
+    description: Option<String>,
+    classification: String,
+    allow_removal: bool,
+}
+
+impl PatchBuilder {
+    pub fn new(patch_code: Uuid) -> Self {
+        PatchBuilder {
+            patch_code,
+            family: None,
+            display_name: None,
+            description: None,
+            classification: "Update".to_string(),
+            allow_removal: true,
+        }
+    }
+
+    /// Patches of the same family supersede each other, in the order of their sequence, which is
+    /// the version of the upgraded package. Defaults to the product name, as an identifier.
+    pub fn family(mut self, family: &str) -> Self {
+        self.family = Some(family.to_string());
+        self
+    }
+
+    /// Name of the patch in Add or Remove Programs, defaults to the product name and version.
+    pub fn display_name(mut self, display_name: &str) -> Self {
+        self.display_name = Some(display_name.to_string());
+        self
+    }
+
+    /// Description of the patch, shown in Add or Remove Programs.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Kind of patch, e.g. `Hotfix`, `Security Rollup` or `Update`.
+    pub fn classification(mut self, classification: &str) -> Self {
+        self.classification = classification.to_string();
+        self
+    }
+
+    /// Let the patch be uninstalled on its own, without uninstalling the product.
+    pub fn allow_removal(mut self, allow_removal: bool) -> Self {
+        self.allow_removal = allow_removal;
+        self
+    }
+
+    /// Write the patch updating `target` to `upgraded` in `out`. Both packages must have the same
+    /// upgrade code, and their files must be in cabinets embedded in the packages.
+    pub fn build<F, G, W>(&self, target: F, mut upgraded: G, out: W) -> io::Result<msi::Package<W>>
+    where
+        F: Read + Seek,
+        G: Read + Seek,
+        W: Read + Write + Seek,
+    {
+        let mut target = msi::Package::open(target)?;
+        let mut data = Vec::new();
+        upgraded.seek(SeekFrom::Start(0))?;
+        upgraded.read_to_end(&mut data)?;
+        let mut upgraded = msi::Package::open(Cursor::new(data))?;
+        let target_product = ProductInfo::read(&mut target)?;
+        let upgraded_product = ProductInfo::read(&mut upgraded)?;
+        if target_product.upgrade_code != upgraded_product.upgrade_code {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "The upgrade code of the upgraded package {} differs from the target's {}",
+                    upgraded_product.upgrade_code, target_product.upgrade_code
+                ),
+            ));
+        }
+
+        // Files added or changed get sequence numbers after the files of the target, the others
+        // keep their target sequence so they are still found in the target's cabinets
+        let target_contents = file_contents(&mut target)?;
+        let upgraded_contents = file_contents(&mut upgraded)?;
+        let target_files = list::<File, _>(&mut target)?
+            .into_iter()
+            .map(|file| (file.file.clone(), file))
+            .collect::<HashMap<_, _>>();
+        let target_media = list::<Media, _>(&mut target)?;
+        let last_sequence = target_media
+            .iter()
+            .map(|media| media.last_sequence)
+            .chain(target_files.values().map(|file| file.sequence))
+            .max()
+            .unwrap_or(0);
+        let disk_id = target_media
+            .iter()
+            .map(|media| media.disk_id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let mut upgraded_files = list::<File, _>(&mut upgraded)?;
+        upgraded_files.sort_by_key(|file| file.sequence);
+        let mut patch_files = Vec::new();
+        for mut file in upgraded_files {
+            let target_file = target_files.get(&file.file);
+            let contents = &upgraded_contents[&file.file];
+            match target_file {
+                Some(target_file) if target_contents.get(&file.file) == Some(contents) => {
+                    file.sequence = target_file.sequence;
+                }
+                _ => {
+                    file.sequence = last_sequence + patch_files.len() as i32 + 1;
+                    if target_file.is_none() {
+                        file.attributes |= FileAttributes::PatchAdded;
+                    }
+                    patch_files.push((file.clone(), contents.clone()));
+                }
+            }
+            upgraded.update_rows(
+                msi::Update::table(File::table_name())
+                    .set("Sequence", msi::Value::Int(file.sequence))
+                    .set("Attributes", msi::Value::Int(file.attributes.bits()))
+                    .with(msi::Expr::col("File").eq(msi::Expr::string(&file.file))),
+            )?;
+        }
+        // The upgrade transform leaves the media of the target, and the cabinets of the upgraded
+        // package are replaced by the cabinet of the patch
+        for media in list::<Media, _>(&mut upgraded)? {
+            if let Some(cabinet) = media.cabinet.as_deref().and_then(|c| c.strip_prefix('#'))
+                && upgraded.has_stream(cabinet)
+            {
+                upgraded.remove_stream(cabinet)?;
+            }
+        }
+        upgraded.delete_rows(msi::Delete::from(Media::table_name()))?;
+        Media::insert(&mut upgraded, &target_media)?;
+
+        let mut upgrade = msi::Transform::diff(&mut target, &mut upgraded)?;
+        set_transform_summary_info(&mut upgrade, &target_product, &upgraded_product);
+        upgrade.summary_info_mut().set_character_count(
+            (VALIDATE_LANGUAGE
+                | VALIDATE_PRODUCT
+                | VALIDATE_UPDATE_VERSION
+                | VALIDATE_NEW_EQUAL_BASE_VERSION
+                | VALIDATE_UPGRADE_CODE)
+                << 16,
+        );
+
+        upgraded.flush()?;
+        let data = upgraded.into_inner()?.into_inner();
+        let mut base = msi::Package::open(Cursor::new(data.clone()))?;
+        let mut with_media = msi::Package::open(Cursor::new(data))?;
+        let cabinet = format!("Patch{}", self.patch_code.simple());
+        Media::insert(
+            &mut with_media,
+            &[Media {
+                disk_id,
+                last_sequence: last_sequence + patch_files.len() as i32,
+                cabinet: Some(format!("#{cabinet}")),
+                ..Default::default()
+            }],
+        )?;
+        if !with_media.has_table(PatchPackage::table_name()) {
+            PatchPackage::create_table(&mut with_media)?;
+        }
+        PatchPackage::insert(
+            &mut with_media,
+            &[PatchPackage {
+                patch_id: self.patch_code,
+                media: disk_id,
+            }],
+        )?;
+        let mut patch_transform = msi::Transform::diff(&mut base, &mut with_media)?;
+        set_transform_summary_info(&mut patch_transform, &target_product, &upgraded_product);
+        patch_transform
+            .summary_info_mut()
+            .set_character_count(ERROR_ADD_EXISTING_ROW | ERROR_ADD_EXISTING_TABLE);
+
+        let mut patch = msi::Package::create(msi::PackageType::Patch, out)?;
+        let summary_info = patch.summary_info_mut();
+        summary_info.set_subject(self.patch_display_name(&upgraded_product));
+        summary_info.set_author(upgraded_product.manufacturer.clone());
+        if let Some(description) = &self.description {
+            summary_info.set_comments(description.clone());
+        }
+        summary_info.set_template(guid_string(&target_product.product_code));
+        summary_info.set_revision_number(guid_string(&self.patch_code));
+        summary_info.set_last_author(format!(":{TRANSFORM_NAME};:#{TRANSFORM_NAME}"));
+        summary_info.set_word_count(PATCH_WORD_COUNT);
+        summary_info.set_creation_time_to_now();
+
+        MsiPatchMetadata::create_table(&mut patch)?;
+        let mut metadata = vec![
+            (
+                "AllowRemoval",
+                if self.allow_removal { "1" } else { "0" }.to_string(),
+            ),
+            ("Classification", self.classification.clone()),
+            ("DisplayName", self.patch_display_name(&upgraded_product)),
+            ("ManufacturerName", upgraded_product.manufacturer.clone()),
+            ("TargetProductName", target_product.name.clone()),
+        ];
+        if let Some(description) = &self.description {
+            metadata.push(("Description", description.clone()));
+        }
+        let metadata = metadata
+            .into_iter()
+            .map(|(property, value)| MsiPatchMetadata {
+                company: None,
+                property: property.to_string(),
+                value,
+            })
+            .collect::<Vec<_>>();
+        MsiPatchMetadata::insert(&mut patch, &metadata)?;
+        MsiPatchSequence::create_table(&mut patch)?;
+        MsiPatchSequence::insert(
+            &mut patch,
+            &[MsiPatchSequence {
+                patch_family: self
+                    .family
+                    .clone()
+                    .unwrap_or_else(|| identifier(&upgraded_product.name)),
+                product_code: None,
+                sequence: upgraded_product.version.clone(),
+                attributes: None,
+            }],
+        )?;
+
+        patch.write_embedded_transform(TRANSFORM_NAME, &upgrade)?;
+        patch.write_embedded_transform(&format!("#{TRANSFORM_NAME}"), &patch_transform)?;
+        let mut builder = cab::CabinetBuilder::new();
+        let folder = builder.add_folder(cab::CompressionType::MsZip);
+        for (file, _) in patch_files.iter() {
+            folder.add_file(file.file.as_str());
+        }
+        let mut cabinet_writer = builder.build(patch.write_stream(&cabinet)?)?;
+        let mut contents = patch_files.iter().map(|(_, data)| data);
+        while let Some(mut file_writer) = cabinet_writer.next_file()? {
+            file_writer.write_all(contents.next().unwrap())?;
+        }
+        cabinet_writer.finish()?;
+        patch.flush()?;
+        Ok(patch)
+    }
+
+    fn patch_display_name(&self, product: &ProductInfo) -> String {
+        match &self.display_name {
+            Some(display_name) => display_name.clone(),
+            None => format!("{} {}", product.name, product.version),
+        }
+    }
+}
+
+// Both transforms of a patch record the product codes of the target and upgraded packages, checked
+// against the installed product before applying the patch.
+fn set_transform_summary_info(
+    transform: &mut msi::Transform,
+    target_product: &ProductInfo,
+    upgraded_product: &ProductInfo,
+) {
+    transform.summary_info_mut().set_revision_number(format!(
+        "{}{};{}{};{}",
+        guid_string(&target_product.product_code),
+        target_product.version,
+        guid_string(&upgraded_product.product_code),
+        upgraded_product.version,
+        guid_string(&target_product.upgrade_code)
+    ));
+}
+
+// Properties of a package identifying its product.
+struct ProductInfo {
+    product_code: Uuid,
+    upgrade_code: Uuid,
+    version: String,
+    name: String,
+    manufacturer: String,
+}
+
+impl ProductInfo {
+    fn read<F: Read + Seek>(package: &mut msi::Package<F>) -> io::Result<ProductInfo> {
+        let properties = list::<Property, _>(package)?
+            .into_iter()
+            .map(|property| (property.property, property.value))
+            .collect::<HashMap<_, _>>();
+        let property = |name: &str| {
+            properties.get(name).cloned().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Package has no {name} property"),
+                )
+            })
+        };
+        let guid = |name: &str| {
+            let value = property(name)?;
+            Uuid::parse_str(value.trim_start_matches('{').trim_end_matches('}')).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Package has an invalid {name} {value}"),
+                )
+            })
+        };
+        Ok(ProductInfo {
+            product_code: guid("ProductCode")?,
+            upgrade_code: guid("UpgradeCode")?,
+            version: property("ProductVersion")?,
+            name: property("ProductName")?,
+            manufacturer: property("Manufacturer").unwrap_or_default(),
+        })
+    }
+}
+
+// Returns the contents of the files of the package, by File key, from its embedded cabinets.
+fn file_contents<F: Read + Seek>(
+    package: &mut msi::Package<F>,
+) -> io::Result<HashMap<String, Vec<u8>>> {
+    let mut contents = HashMap::new();
+    for media in list::<Media, _>(package)? {
+        let Some(cabinet) = media.cabinet else {
+            continue;
+        };
+        let Some(stream) = cabinet.strip_prefix('#') else {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Cabinet {cabinet} is not embedded in the package"),
+            ));
+        };
+        let mut data = Vec::new();
+        package.read_stream(stream)?.read_to_end(&mut data)?;
+        let mut cabinet = cab::Cabinet::new(Cursor::new(data))?;
+        let names = cabinet
+            .folder_entries()
+            .flat_map(|folder| folder.file_entries())
+            .map(|file| file.name().to_string())
+            .collect::<Vec<_>>();
+        for name in names {
+            let mut data = Vec::new();
+            cabinet.read_file(&name)?.read_to_end(&mut data)?;
+            contents.insert(name, data);
+        }
+    }
+    for file in list::<File, _>(package)? {
+        if !contents.contains_key(&file.file) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("File {} is not in an embedded cabinet", file.file),
+            ));
+        }
+    }
+    Ok(contents)
+}
+
+// Lists the rows of a table, none when the package doesn't have the table.
+fn list<E: Entity, F: Read + Seek>(package: &mut msi::Package<F>) -> io::Result<Vec<E>> {
+    if !package.has_table(E::table_name()) {
+        return Ok(Vec::new());
+    }
+    E::list(package).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+}
+
+fn guid_string(value: &Uuid) -> String {
+    format!("{{{}}}", value.hyphenated()).to_uppercase()
+}
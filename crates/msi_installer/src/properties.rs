@@ -16,6 +16,18 @@ pub struct RequiredProperties {
     pub product_name: String,
     pub product_version: String,
 }
+/// Context in which the package is installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InstallScope {
+    /// Install for the current user only, in $LOCALAPPDATA/Programs. Doesn't require elevation.
+    #[default]
+    PerUser,
+    /// Install for all users, in $PROGRAMFILES. Requires elevation.
+    PerMachine,
+    /// Default to a per user install but let the user choose to install for everyone.
+    DualPurpose,
+}
+
 #[derive(Debug)]
 pub struct PropertiesBuilder {
     properties: HashMap<PropertyType, String>,
@@ -45,20 +57,40 @@ impl PropertiesBuilder {
         self
     }
 
-    /// Install per user $LOCALAPPDATA/Programs
-    pub fn install_per_user(&mut self) -> &mut Self {
-        self.properties
-            .insert(PropertyType::AllUsers, "2".to_string());
-        self.properties
-            .insert(PropertyType::MsiInstallPerUser, "1".to_string());
+    /// Set the install context of the package.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/single-package-authoring
+    pub fn install_scope(&mut self, scope: InstallScope) -> &mut Self {
+        match scope {
+            InstallScope::PerUser => {
+                self.properties.remove(&PropertyType::AllUsers);
+                self.properties.remove(&PropertyType::MsiInstallPerUser);
+            }
+            InstallScope::PerMachine => {
+                self.properties
+                    .insert(PropertyType::AllUsers, "1".to_string());
+                self.properties.remove(&PropertyType::MsiInstallPerUser);
+            }
+            InstallScope::DualPurpose => {
+                self.properties
+                    .insert(PropertyType::AllUsers, "2".to_string());
+                self.properties
+                    .insert(PropertyType::MsiInstallPerUser, "1".to_string());
+            }
+        }
         self
     }
 
-    /// Install globally $PROGRAMFILES/Programs
+    /// Default to a per user install in $LOCALAPPDATA/Programs, letting the user choose to
+    /// install for everyone (ALLUSERS=2 and MSIINSTALLPERUSER=1).
+    #[deprecated(note = "use `install_scope(InstallScope::DualPurpose)` instead")]
+    pub fn install_per_user(&mut self) -> &mut Self {
+        self.install_scope(InstallScope::DualPurpose)
+    }
+
+    /// Install for all users in $PROGRAMFILES (ALLUSERS=1).
+    #[deprecated(note = "use `install_scope(InstallScope::PerMachine)` instead")]
     pub fn install_global(&mut self) -> &mut Self {
-        self.properties.remove(&PropertyType::AllUsers);
-        self.properties.remove(&PropertyType::MsiInstallPerUser);
-        self
+        self.install_scope(InstallScope::PerMachine)
     }
 
     pub fn default_ui_font(&mut self, value: &str) -> &mut Self {
@@ -79,6 +111,91 @@ impl PropertiesBuilder {
         self
     }
 
+    /// Setting property disables the Repair button in Add or Remove Programs.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpnorepair
+    pub fn arp_no_repair(&mut self, value: bool) -> &mut Self {
+        self.set_flag(PropertyType::ArpNoRepair, value)
+    }
+
+    /// Setting property disables the Remove button in Add or Remove Programs.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpnoremove
+    pub fn arp_no_remove(&mut self, value: bool) -> &mut Self {
+        self.set_flag(PropertyType::ArpNoRemove, value)
+    }
+
+    /// Key of the Icon table entry shown for the product in Add or Remove Programs.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpproducticon
+    pub fn arp_product_icon(&mut self, icon: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpProductIcon, icon.to_string());
+        self
+    }
+
+    /// URL of the technical support page.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arphelplink
+    pub fn arp_help_link(&mut self, url: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpHelpLink, url.to_string());
+        self
+    }
+
+    /// Technical support phone number.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arphelptelephone
+    pub fn arp_help_telephone(&mut self, telephone: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpHelpTelephone, telephone.to_string());
+        self
+    }
+
+    /// URL of the home page of the product.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpurlinfoabout
+    pub fn arp_url_info_about(&mut self, url: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpUrlInfoAbout, url.to_string());
+        self
+    }
+
+    /// URL of the page with product updates.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpurlupdateinfo
+    pub fn arp_url_update_info(&mut self, url: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpUrlUpdateInfo, url.to_string());
+        self
+    }
+
+    /// Contact of the technical support.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpcontact
+    pub fn arp_contact(&mut self, contact: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpContact, contact.to_string());
+        self
+    }
+
+    /// Comments shown in Add or Remove Programs.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpcomments
+    pub fn arp_comments(&mut self, comments: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpComments, comments.to_string());
+        self
+    }
+
+    /// Path or URL of the readme of the product.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/arpreadme
+    pub fn arp_readme(&mut self, readme: &str) -> &mut Self {
+        self.properties
+            .insert(PropertyType::ArpReadme, readme.to_string());
+        self
+    }
+
+    fn set_flag(&mut self, property: PropertyType, value: bool) -> &mut Self {
+        if value {
+            self.properties.insert(property, "1".to_string());
+        } else {
+            self.properties.remove(&property);
+        }
+        self
+    }
+
     pub fn insert(&mut self, property: &str, value: &str) -> &mut Self {
         self.properties.insert(
             PropertyType::Custom(property.to_string()),
@@ -108,7 +225,8 @@ impl PropertiesBuilder {
     }
 }
 
-// cspell:ignore MSIINSTALLPERUSER ARPNOMODIFY
+// cspell:ignore MSIINSTALLPERUSER ARPNOMODIFY ARPNOREPAIR ARPNOREMOVE ARPPRODUCTICON ARPHELPLINK
+// cspell:ignore ARPHELPTELEPHONE ARPURLINFOABOUT ARPURLUPDATEINFO ARPCONTACT ARPCOMMENTS ARPREADME
 #[derive(Debug, PartialEq, Eq, Hash, EnumString, strum_macros::Display)]
 // #[allow(dead_code)]
 enum PropertyType {
@@ -124,6 +242,26 @@ enum PropertyType {
     DefaultUIFont,
     #[strum(serialize = "ARPNOMODIFY")]
     ArpNoModify,
+    #[strum(serialize = "ARPNOREPAIR")]
+    ArpNoRepair,
+    #[strum(serialize = "ARPNOREMOVE")]
+    ArpNoRemove,
+    #[strum(serialize = "ARPPRODUCTICON")]
+    ArpProductIcon,
+    #[strum(serialize = "ARPHELPLINK")]
+    ArpHelpLink,
+    #[strum(serialize = "ARPHELPTELEPHONE")]
+    ArpHelpTelephone,
+    #[strum(serialize = "ARPURLINFOABOUT")]
+    ArpUrlInfoAbout,
+    #[strum(serialize = "ARPURLUPDATEINFO")]
+    ArpUrlUpdateInfo,
+    #[strum(serialize = "ARPCONTACT")]
+    ArpContact,
+    #[strum(serialize = "ARPCOMMENTS")]
+    ArpComments,
+    #[strum(serialize = "ARPREADME")]
+    ArpReadme,
     #[strum(serialize = "ALLUSERS")]
     AllUsers,
     #[strum(serialize = "MSIINSTALLPERUSER")]
@@ -131,3 +269,56 @@ enum PropertyType {
     #[strum(to_string = "{0}")]
     Custom(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use msi::Language;
+    use uuid::Uuid;
+
+    use super::{InstallScope, PropertiesBuilder, RequiredProperties};
+
+    fn builder() -> PropertiesBuilder {
+        PropertiesBuilder::new(RequiredProperties {
+            product_code: Uuid::nil(),
+            product_language: Language::from_code(1033),
+            manufacturer: "Acme".to_string(),
+            product_name: "Test".to_string(),
+            product_version: "1.0.0".to_string(),
+        })
+    }
+
+    fn scope_properties(builder: &PropertiesBuilder) -> Vec<(String, String)> {
+        let mut properties = builder
+            .build()
+            .into_iter()
+            .filter(|row| ["ALLUSERS", "MSIINSTALLPERUSER"].contains(&row.property.as_str()))
+            .map(|row| (row.property, row.value))
+            .collect::<Vec<_>>();
+        properties.sort();
+        properties
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_scope_aliases() {
+        let mut per_user = builder();
+        per_user.install_per_user();
+        assert_eq!(
+            scope_properties(&per_user),
+            vec![
+                ("ALLUSERS".to_string(), "2".to_string()),
+                ("MSIINSTALLPERUSER".to_string(), "1".to_string()),
+            ]
+        );
+        let mut dual_purpose = builder();
+        dual_purpose.install_scope(InstallScope::DualPurpose);
+        assert_eq!(scope_properties(&per_user), scope_properties(&dual_purpose));
+
+        let mut global = builder();
+        global.install_per_user().install_global();
+        assert_eq!(
+            scope_properties(&global),
+            vec![("ALLUSERS".to_string(), "1".to_string())]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// Fill the sequence tables with the standard actions in their suggested order
+
+use std::io::{Read, Seek, Write};
+
+use crate::tables::{
+    ADMIN_EXECUTE_SEQUENCE, ADVT_EXECUTE_SEQUENCE, AdminExecuteSequence, AdvtExecuteSequence,
+    Entity, INSTALL_EXECUTE_SEQUENCE, INSTALL_UI_SEQUENCE, InstallExecuteSequence,
+    InstallUISequence, StandardAction,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceTable {
+    InstallExecute,
+    InstallUI,
+    AdminExecute,
+    AdvtExecute,
+}
+
+impl SequenceTable {
+    pub fn table_name(self) -> &'static str {
+        match self {
+            SequenceTable::InstallExecute => InstallExecuteSequence::table_name(),
+            SequenceTable::InstallUI => InstallUISequence::table_name(),
+            SequenceTable::AdminExecute => AdminExecuteSequence::table_name(),
+            SequenceTable::AdvtExecute => AdvtExecuteSequence::table_name(),
+        }
+    }
+
+    pub fn standard_actions(self) -> &'static [StandardAction] {
+        match self {
+            SequenceTable::InstallExecute => INSTALL_EXECUTE_SEQUENCE,
+            SequenceTable::InstallUI => INSTALL_UI_SEQUENCE,
+            SequenceTable::AdminExecute => ADMIN_EXECUTE_SEQUENCE,
+            SequenceTable::AdvtExecute => ADVT_EXECUTE_SEQUENCE,
+        }
+    }
+}
+
+/// Create a sequence with the standard actions of the table.
+pub fn new(table: SequenceTable) -> SequenceBuilder {
+    SequenceBuilder {
+        table,
+        actions: Vec::new(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SequenceBuilder {
+    table: SequenceTable,
+    actions: Vec<(String, Option<String>, i32)>,
+}
+
+impl SequenceBuilder {
+    /// Schedule a dialog or a custom action, along with the standard actions.
+    pub fn action(mut self, action: &str, condition: Option<&str>, sequence: i32) -> Self {
+        self.actions
+            .push((action.to_string(), condition.map(str::to_string), sequence));
+        self
+    }
+
+    /// Insert the actions in the sequence table, created if missing.
+    /// Standard actions are skipped when none of the tables they process exists,
+    /// so the sequence must be inserted once the other tables are created.
+    pub fn insert<F: Read + Write + Seek>(
+        &self,
+        package: &mut msi::Package<F>,
+    ) -> Result<(), std::io::Error> {
+        let mut actions: Vec<(&str, Option<&str>, i32)> = self
+            .table
+            .standard_actions()
+            .iter()
+            .filter(|action| {
+                action.tables.is_empty()
+                    || action.tables.iter().any(|table| package.has_table(table))
+            })
+            .map(|action| (action.name, action.condition, action.sequence))
+            .collect();
+        actions.extend(self.actions.iter().map(|(action, condition, sequence)| {
+            (action.as_str(), condition.as_deref(), *sequence)
+        }));
+
+        match self.table {
+            SequenceTable::InstallExecute => {
+                insert_sequence(package, &actions, InstallExecuteSequence::new)
+            }
+            SequenceTable::InstallUI => insert_sequence(package, &actions, InstallUISequence::new),
+            SequenceTable::AdminExecute => {
+                insert_sequence(package, &actions, AdminExecuteSequence::new)
+            }
+            SequenceTable::AdvtExecute => {
+                insert_sequence(package, &actions, AdvtExecuteSequence::new)
+            }
+        }
+    }
+}
+
+fn insert_sequence<F: Read + Write + Seek, E: Entity>(
+    package: &mut msi::Package<F>,
+    actions: &[(&str, Option<&str>, i32)],
+    new: impl Fn(&str, Option<&str>, i32) -> E,
+) -> Result<(), std::io::Error> {
+    if !package.has_table(E::table_name()) {
+        E::create_table(package)?;
+    }
+    let rows = actions
+        .iter()
+        .map(|(action, condition, sequence)| new(action, *condition, *sequence))
+        .collect::<Vec<E>>();
+    E::insert(package, &rows)
+}
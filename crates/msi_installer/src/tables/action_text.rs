@@ -0,0 +1,265 @@
+use super::Entity;
+
+/// ActionText Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/actiontext-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "ActionText")]
+pub struct ActionText {
+    /// Name of the standard or custom action.
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub action: String,
+    /// Shown by the controls subscribed to the ActionText event while the action runs.
+    #[column(localizable, category = Text, length = 0)]
+    pub description: Option<String>,
+    /// Shown by the controls subscribed to the ActionData event for each item processed by the action.
+    #[column(localizable, category = Template, length = 0)]
+    pub template: Option<String>,
+}
+
+impl ActionText {
+    pub fn new(action: &str, description: &str, template: Option<&str>) -> ActionText {
+        ActionText {
+            action: action.to_string(),
+            description: Some(description.to_string()),
+            template: template.map(str::to_string),
+        }
+    }
+
+    /// The descriptions of the standard actions, in English.
+    pub fn standard() -> Vec<ActionText> {
+        STANDARD_ACTION_TEXTS
+            .iter()
+            .map(|(action, description, template)| ActionText::new(action, description, *template))
+            .collect()
+    }
+}
+
+/// Descriptions and templates of the standard actions reporting their progress.
+pub const STANDARD_ACTION_TEXTS: &[(&str, &str, Option<&str>)] = &[
+    (
+        "AllocateRegistrySpace",
+        "Allocating registry space",
+        Some("Free space: [1]"),
+    ),
+    (
+        "AppSearch",
+        "Searching for installed applications",
+        Some("Property: [1], Signature: [2]"),
+    ),
+    ("BindImage", "Binding executables", Some("File: [1]")),
+    ("CCPSearch", "Searching for qualifying products", None),
+    ("CostFinalize", "Computing space requirements", None),
+    ("CostInitialize", "Computing space requirements", None),
+    ("CreateFolders", "Creating folders", Some("Folder: [1]")),
+    (
+        "CreateShortcuts",
+        "Creating shortcuts",
+        Some("Shortcut: [1]"),
+    ),
+    ("DeleteServices", "Deleting services", Some("Service: [1]")),
+    (
+        "DuplicateFiles",
+        "Creating duplicate files",
+        Some("File: [1],  Directory: [9],  Size: [6]"),
+    ),
+    ("FileCost", "Computing space requirements", None),
+    (
+        "FindRelatedProducts",
+        "Searching for related applications",
+        Some("Found application: [1]"),
+    ),
+    (
+        "GenerateScript",
+        "Generating script operations for action:",
+        Some("[1]"),
+    ),
+    (
+        "InstallAdminPackage",
+        "Copying network install files",
+        Some("File: [1], Directory: [9], Size: [6]"),
+    ),
+    (
+        "InstallFiles",
+        "Copying new files",
+        Some("File: [1],  Directory: [9],  Size: [6]"),
+    ),
+    (
+        "InstallServices",
+        "Installing new services",
+        Some("Service: [2]"),
+    ),
+    ("InstallValidate", "Validating install", None),
+    ("LaunchConditions", "Evaluating launch conditions", None),
+    (
+        "MigrateFeatureStates",
+        "Migrating feature states from related applications",
+        Some("Application: [1]"),
+    ),
+    (
+        "MoveFiles",
+        "Moving files",
+        Some("File: [1],  Directory: [9],  Size: [6]"),
+    ),
+    (
+        "MsiConfigureServices",
+        "Configuring services",
+        Some("Service: [1]"),
+    ),
+    (
+        "PatchFiles",
+        "Patching files",
+        Some("File: [1],  Directory: [2],  Size: [3]"),
+    ),
+    ("ProcessComponents", "Updating component registration", None),
+    (
+        "PublishComponents",
+        "Publishing Qualified Components",
+        Some("Component ID: [1], Qualifier: [2]"),
+    ),
+    (
+        "PublishFeatures",
+        "Publishing Product Features",
+        Some("Feature: [1]"),
+    ),
+    ("PublishProduct", "Publishing product information", None),
+    (
+        "RegisterClassInfo",
+        "Registering class servers",
+        Some("Class Id: [1]"),
+    ),
+    (
+        "RegisterExtensionInfo",
+        "Registering extension servers",
+        Some("Extension: [1]"),
+    ),
+    ("RegisterFonts", "Registering fonts", Some("Font: [1]")),
+    (
+        "RegisterMIMEInfo",
+        "Registering MIME info",
+        Some("MIME Content Type: [1], Extension: [2]"),
+    ),
+    ("RegisterProduct", "Registering product", Some("[1]")),
+    (
+        "RegisterProgIdInfo",
+        "Registering program identifiers",
+        Some("ProgId: [1]"),
+    ),
+    (
+        "RegisterTypeLibraries",
+        "Registering type libraries",
+        Some("LibID: [1]"),
+    ),
+    ("RegisterUser", "Registering user", Some("[1]")),
+    (
+        "RemoveDuplicateFiles",
+        "Removing duplicated files",
+        Some("File: [1], Directory: [9]"),
+    ),
+    (
+        "RemoveEnvironmentStrings",
+        "Updating environment strings",
+        Some("Name: [1], Value: [2], Action [3]"),
+    ),
+    (
+        "RemoveExistingProducts",
+        "Removing applications",
+        Some("Application: [1], Command line: [2]"),
+    ),
+    (
+        "RemoveFiles",
+        "Removing files",
+        Some("File: [1], Directory: [9]"),
+    ),
+    ("RemoveFolders", "Removing folders", Some("Folder: [1]")),
+    (
+        "RemoveIniValues",
+        "Removing INI files entries",
+        Some("File: [1],  Section: [2],  Key: [3], Value: [4]"),
+    ),
+    ("RemoveODBC", "Removing ODBC components", None),
+    (
+        "RemoveRegistryValues",
+        "Removing system registry values",
+        Some("Key: [1], Name: [2]"),
+    ),
+    (
+        "RemoveShortcuts",
+        "Removing shortcuts",
+        Some("Shortcut: [1]"),
+    ),
+    ("Rollback", "Rolling back action:", Some("[1]")),
+    (
+        "RollbackCleanup",
+        "Removing backup files",
+        Some("File: [1]"),
+    ),
+    (
+        "SelfRegModules",
+        "Registering modules",
+        Some("File: [1], Folder: [2]"),
+    ),
+    // cspell:ignore Unreg
+    (
+        "SelfUnregModules",
+        "Unregistering modules",
+        Some("File: [1], Folder: [2]"),
+    ),
+    ("StartServices", "Starting services", Some("Service: [1]")),
+    ("StopServices", "Stopping services", Some("Service: [1]")),
+    (
+        "UnmoveFiles",
+        "Removing moved files",
+        Some("File: [1], Directory: [9]"),
+    ),
+    (
+        "UnpublishComponents",
+        "Unpublishing Qualified Components",
+        Some("Component ID: [1], Qualifier: [2]"),
+    ),
+    (
+        "UnpublishFeatures",
+        "Unpublishing Product Features",
+        Some("Feature: [1]"),
+    ),
+    (
+        "UnregisterClassInfo",
+        "Unregister class servers",
+        Some("Class Id: [1]"),
+    ),
+    (
+        "UnregisterExtensionInfo",
+        "Unregistering extension servers",
+        Some("Extension: [1]"),
+    ),
+    ("UnregisterFonts", "Unregistering fonts", Some("Font: [1]")),
+    (
+        "UnregisterMIMEInfo",
+        "Unregistering MIME info",
+        Some("MIME Content Type: [1], Extension: [2]"),
+    ),
+    (
+        "UnregisterProgIdInfo",
+        "Unregistering program identifiers",
+        Some("ProgId: [1]"),
+    ),
+    (
+        "UnregisterTypeLibraries",
+        "Unregistering type libraries",
+        Some("LibID: [1]"),
+    ),
+    (
+        "WriteEnvironmentStrings",
+        "Updating environment strings",
+        Some("Name: [1], Value: [2], Action [3]"),
+    ),
+    (
+        "WriteIniValues",
+        "Writing INI files values",
+        Some("File: [1],  Section: [2],  Key: [3], Value: [4]"),
+    ),
+    (
+        "WriteRegistryValues",
+        "Writing system registry values",
+        Some("Key: [1], Name: [2], Value: [3]"),
+    ),
+];
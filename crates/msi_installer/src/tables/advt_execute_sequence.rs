@@ -0,0 +1,24 @@
+use super::Entity;
+
+/// AdvtExecuteSequence Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/advtexecutesequence-table
+#[derive(Debug, Clone, Entity)]
+#[entity(table = "AdvtExecuteSequence")]
+pub struct AdvtExecuteSequence {
+    #[column(name = "Action", primary_key, category = Identifier, length = 72)]
+    pub action: String,
+    #[column(category = Condition)]
+    pub condition: Option<String>,
+    #[column(name = "Sequence", nullable, range(-4, 32767))]
+    pub order: i32,
+}
+
+impl AdvtExecuteSequence {
+    pub fn new(action: &str, condition: Option<&str>, order: i32) -> Self {
+        AdvtExecuteSequence {
+            action: action.to_string(),
+            condition: condition.map(|s| s.to_string()),
+            order,
+        }
+    }
+}
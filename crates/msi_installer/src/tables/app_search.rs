@@ -0,0 +1,14 @@
+use super::Entity;
+
+/// AppSearch Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/appsearch-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "AppSearch")]
+pub struct AppSearch {
+    /// Property set with the result of the search, it must be public to be passed to the execute sequence.
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub property: String,
+    /// Key of the Signature, RegLocator or DrLocator tables describing the search.
+    #[column(name = "Signature_", primary_key, category = Identifier, length = 72)]
+    pub signature: String,
+}
@@ -128,6 +128,15 @@ bitflags! {
 
         // Edit control attributes
         const MultiLine	= 65536;
+
+        // Volume and directory combo attributes
+        const RemovableVolume = 65536;
+        const FixedVolume = 131072;
+        const RemoteVolume = 262144;
+        const CDROMVolume = 524288;
+        const RAMDiskVolume = 1048576;
+        const FloppyVolume = 2097152;
+        const AllVolumes = 4128768;
     }
 }
 
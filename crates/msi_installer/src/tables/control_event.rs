@@ -69,7 +69,7 @@ impl Entity for ControlEvent {
     }
 }
 
-#[derive(Debug, Clone, Copy, EnumString, strum_macros::Display)]
+#[derive(Debug, Clone, EnumString, strum_macros::Display)]
 pub enum ControlEventType {
     /// Replace the current dialog with a new one
     NewDialog,
@@ -79,4 +79,20 @@ pub enum ControlEventType {
 
     /// Notifies the installer to remove a modal dialog box.
     EndDialog,
+
+    /// Set the selected path of a directory property.
+    SetTargetPath,
+
+    /// Select the parent of the current directory in a DirectoryList control.
+    DirectoryListUp,
+
+    /// Create a new folder in the current directory of a DirectoryList control.
+    DirectoryListNew,
+
+    /// Reset all the property values to the values they had when the dialog was created.
+    Reset,
+
+    /// Set the value of a property. Use `{}` as the argument to remove it.
+    #[strum(to_string = "[{0}]")]
+    SetProperty(String),
 }
@@ -0,0 +1,70 @@
+use bitflags::bitflags;
+
+use super::Entity;
+
+/// CustomAction Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/customaction-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "CustomAction")]
+pub struct CustomAction {
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub action: String,
+    #[column(name = "Type", flags, range(1, 32767))]
+    pub action_type: CustomActionType,
+    /// Key of the Binary, File, Directory or Property table depending on the source of the action.
+    #[column(category = CustomSource, length = 72)]
+    pub source: Option<String>,
+    /// Arguments, script function or value depending on the type of the action.
+    #[column(category = Formatted)]
+    pub target: Option<String>,
+}
+
+bitflags! {
+    /// Custom Action Types, made of a base type, a source and execution options.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/summary-list-of-all-custom-action-types
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct CustomActionType: i32 {
+        /// Call a function of a DLL.
+        const Dll = 0x01;
+        /// Run an executable.
+        const Exe = 0x02;
+        /// Set a property or a directory to the formatted target.
+        const TextData = 0x03;
+        const JScript = 0x05;
+        const VBScript = 0x06;
+        /// Install a nested package.
+        const Install = 0x07;
+
+        /// The source is a stream of the Binary table.
+        const BinaryData = 0x00;
+        /// The source is a file installed by the package.
+        const SourceFile = 0x10;
+        /// The source is a key of the Directory table.
+        const Directory = 0x20;
+        /// The source is a property.
+        const Property = 0x30;
+
+        /// Ignore the exit code of the action and continue the installation.
+        const Continue = 0x40;
+        /// Run the action asynchronously.
+        const Async = 0x80;
+        /// Only run the action in the first sequence, e.g. not again in the execute sequence after the UI sequence.
+        const FirstSequence = 0x100;
+        /// Only run the action once per process.
+        const OncePerProcess = 0x200;
+        /// Only run the action on the client when both sequences are run.
+        const ClientRepeat = 0x300;
+        /// Deferred execution, the action runs in the installation script between InstallInitialize and InstallFinalize.
+        const InScript = 0x400;
+        /// With `InScript`, only run the action when the installation is rolled back.
+        const Rollback = 0x100;
+        /// With `InScript`, only run the action when the installation script is committed.
+        const Commit = 0x200;
+        /// With `InScript`, run the action as LocalSystem instead of impersonating the user.
+        const NoImpersonate = 0x800;
+        /// Don't write the target and the CustomActionData of the action to the log.
+        const HideTarget = 0x2000;
+        /// With `InScript` and `NoImpersonate`, impersonate the user on terminal servers.
+        const TSAware = 0x4000;
+    }
+}
@@ -0,0 +1,20 @@
+use super::Entity;
+
+/// DrLocator Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/drlocator-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "DrLocator")]
+pub struct DrLocator {
+    /// The file of the Signature table with this key is searched, the directory itself when there is none.
+    #[column(name = "Signature_", primary_key, category = Identifier, length = 72)]
+    pub signature: String,
+    /// Signature of another search whose result is the parent directory of `path`.
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub parent: Option<String>,
+    /// Absolute path, or relative to `parent`, may contain properties, e.g. `[ProgramFiles64Folder]Foo`
+    #[column(primary_key, category = AnyPath)]
+    pub path: Option<String>,
+    /// Number of levels of subdirectories searched below `path`.
+    #[column(range(0, 32767))]
+    pub depth: Option<i32>,
+}
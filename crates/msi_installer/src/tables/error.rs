@@ -9,6 +9,8 @@ pub enum MsiDataBaseError {
     TableMissingError { table: String },
     #[error("Value '{0}' is not a valid registry root, only -1, 0, 1, 2, 3 are allowed")]
     InvalidRegistryRoot(i32),
+    #[error("Value '{value}' is not a valid {name}")]
+    InvalidEnumValue { name: &'static str, value: i32 },
     #[error(
         "Table '{table}' cell {row}:{column} is not of the expected type: {expected_type}, value: {value}"
     )]
@@ -0,0 +1,19 @@
+use super::Entity;
+
+/// Extension Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/extension-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "Extension")]
+pub struct Extension {
+    /// File name extension without the leading dot.
+    #[column(primary_key, category = Text)]
+    pub extension: String,
+    #[column(name = "Component_", primary_key, foreign_key("Component", 1), category = Identifier, length = 72)]
+    pub component: String,
+    #[column(name = "ProgId_", foreign_key("ProgId", 1), category = Text)]
+    pub prog_id: Option<String>,
+    #[column(name = "MIME_", foreign_key("MIME", 1), category = Text, length = 64)]
+    pub mime: Option<String>,
+    #[column(name = "Feature_", foreign_key("Feature", 1), category = Identifier, length = 38)]
+    pub feature: String,
+}
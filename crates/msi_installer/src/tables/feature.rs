@@ -0,0 +1,111 @@
+use bitflags::bitflags;
+
+use super::{Entity, RowView, error::MsiDataBaseError};
+
+/// Feature Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/feature-table
+#[derive(Debug, Clone, Default)]
+pub struct Feature {
+    pub feature: String,
+    pub parent: Option<String>,
+    /// Short text shown in the selection tree.
+    pub title: Option<String>,
+    /// Longer text shown in the selection tree when the feature is selected.
+    pub description: Option<String>,
+    /// Order of the feature in the selection tree, odd numbers are expanded, even numbers collapsed.
+    /// 0 or null hides the feature.
+    pub display: Option<i32>,
+    /// The feature is installed when its level is between 1 and the INSTALLLEVEL property (1 by default).
+    /// A level of 0 disables the feature.
+    pub level: i32,
+    /// Directory property which can be changed from the selection tree.
+    pub directory: Option<String>,
+    pub attributes: FeatureAttributes,
+}
+
+impl Entity for Feature {
+    fn table_name() -> &'static str {
+        "Feature"
+    }
+
+    fn definition() -> Vec<msi::Column> {
+        vec![
+            msi::Column::build("Feature")
+                .primary_key()
+                .category(msi::Category::Identifier)
+                .string(38),
+            msi::Column::build("Feature_Parent")
+                .nullable()
+                .foreign_key("Feature", 1)
+                .category(msi::Category::Identifier)
+                .string(38),
+            msi::Column::build("Title")
+                .nullable()
+                .localizable()
+                .text_string(64),
+            msi::Column::build("Description")
+                .nullable()
+                .localizable()
+                .text_string(255),
+            msi::Column::build("Display")
+                .nullable()
+                .range(0, 0x7fff)
+                .int16(),
+            msi::Column::build("Level").range(0, 0x7fff).int16(),
+            msi::Column::build("Directory_")
+                .nullable()
+                .foreign_key("Directory", 1)
+                .category(msi::Category::UpperCase)
+                .string(72),
+            msi::Column::build("Attributes").int16(),
+        ]
+    }
+
+    fn from_row(row: &RowView) -> Result<Feature, MsiDataBaseError> {
+        Ok(Feature {
+            feature: row.string(0)?,
+            parent: row.opt_string(1)?,
+            title: row.opt_string(2)?,
+            description: row.opt_string(3)?,
+            display: row.opt_i32(4)?,
+            level: row.i32(5)?,
+            directory: row.opt_string(6)?,
+            attributes: FeatureAttributes::from_bits_retain(row.i32(7)?),
+        })
+    }
+
+    fn to_row(&self) -> Vec<msi::Value> {
+        vec![
+            msi::Value::Str(self.feature.clone()),
+            msi::Value::from_opt_string(&self.parent),
+            msi::Value::from_opt_string(&self.title),
+            msi::Value::from_opt_string(&self.description),
+            msi::Value::from_opt_i32(&self.display),
+            msi::Value::Int(self.level),
+            msi::Value::from_opt_string(&self.directory),
+            msi::Value::Int(self.attributes.bits()),
+        ]
+    }
+}
+
+bitflags! {
+    /// Feature Attributes
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/feature-table#attributes
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct FeatureAttributes: i32 {
+        /// Components of this feature are installed locally.
+        const FavorLocal = 0;
+        /// Components of this feature not listed in the Component table are set to run from source.
+        const FavorSource = 1;
+        /// The feature follows the install state of its parent.
+        const FollowParent = 2;
+        /// The feature is advertised when first installed.
+        const FavorAdvertise = 4;
+        /// Prevent the feature from being advertised.
+        const DisallowAdvertise = 8;
+        /// The user can't set the feature to absent in the selection tree.
+        const UIDisallowAbsent = 16;
+        /// Prevent the feature from being advertised on platforms that don't support it.
+        const NoUnsupportedAdvertise = 32;
+    }
+}
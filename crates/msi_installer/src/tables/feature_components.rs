@@ -1,42 +1,12 @@
-use super::{Entity, RowView, error::MsiDataBaseError};
+use super::Entity;
 
 /// FeatureComponent Table
 /// https://learn.microsoft.com/en-us/windows/win32/msi/featurecomponent-table
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "FeatureComponents")]
 pub struct FeatureComponents {
+    #[column(name = "Feature_", primary_key, foreign_key("Feature", 1), category = Identifier, length = 38)]
     pub feature: String,
+    #[column(name = "Component_", primary_key, foreign_key("Component", 1), category = Identifier, length = 72)]
     pub component: String,
 }
-
-impl Entity for FeatureComponents {
-    fn table_name() -> &'static str {
-        "FeatureComponents"
-    }
-
-    fn definition() -> Vec<msi::Column> {
-        vec![
-            msi::Column::build("Feature_")
-                .primary_key()
-                .foreign_key("Feature", 1)
-                .id_string(38),
-            msi::Column::build("Component_")
-                .primary_key()
-                .foreign_key("Component", 1)
-                .id_string(72),
-        ]
-    }
-
-    fn from_row(row: &RowView) -> Result<FeatureComponents, MsiDataBaseError> {
-        Ok(FeatureComponents {
-            feature: row.string(0)?,
-            component: row.string(1)?,
-        })
-    }
-
-    fn to_row(&self) -> Vec<msi::Value> {
-        vec![
-            msi::Value::Str(self.feature.clone()),
-            msi::Value::from(self.component.clone()),
-        ]
-    }
-}
@@ -0,0 +1,39 @@
+use super::{Entity, RowView, error::MsiDataBaseError};
+
+/// Icon Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/icon-table
+#[derive(Debug, Clone, Default)]
+pub struct Icon {
+    /// Name of the icon, the extension must match the file type, e.g. `ProductIcon.ico`
+    pub name: String,
+}
+
+impl Icon {
+    pub fn stream_name(&self) -> String {
+        format!("{}.{}", Self::table_name(), self.name)
+    }
+}
+
+impl Entity for Icon {
+    fn table_name() -> &'static str {
+        "Icon"
+    }
+
+    fn definition() -> Vec<msi::Column> {
+        vec![
+            msi::Column::build("Name").primary_key().id_string(72),
+            // Like the Binary table, the data is stored in the `Icon.Name` stream
+            msi::Column::build("Data").binary(),
+        ]
+    }
+
+    fn from_row(row: &RowView) -> Result<Icon, MsiDataBaseError> {
+        Ok(Icon {
+            name: row.string(0)?,
+        })
+    }
+
+    fn to_row(&self) -> Vec<msi::Value> {
+        vec![msi::Value::Str(self.name.clone()), msi::Value::Int(-32767)]
+    }
+}
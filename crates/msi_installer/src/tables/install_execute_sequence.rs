@@ -0,0 +1,24 @@
+use super::Entity;
+
+/// InstallExecuteSequence Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/installexecutesequence-table
+#[derive(Debug, Clone, Entity)]
+#[entity(table = "InstallExecuteSequence")]
+pub struct InstallExecuteSequence {
+    #[column(name = "Action", primary_key, category = Identifier, length = 72)]
+    pub action: String,
+    #[column(category = Condition)]
+    pub condition: Option<String>,
+    #[column(name = "Sequence", nullable, range(-4, 32767))]
+    pub order: i32,
+}
+
+impl InstallExecuteSequence {
+    pub fn new(action: &str, condition: Option<&str>, order: i32) -> Self {
+        InstallExecuteSequence {
+            action: action.to_string(),
+            condition: condition.map(|s| s.to_string()),
+            order,
+        }
+    }
+}
@@ -1,11 +1,13 @@
-use super::{Entity, RowView, error::MsiDataBaseError};
+use super::Entity;
 
-const TABLE_NAME: &str = "InstallUISequence";
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Entity)]
+#[entity(table = "InstallUISequence")]
 pub struct InstallUISequence {
+    #[column(name = "Action", primary_key, category = Identifier, length = 72)]
     pub dialog: String,
+    #[column(category = Condition)]
     pub condition: Option<String>,
+    #[column(name = "Sequence", nullable, range(-4, 32767))]
     pub order: i32,
 }
 
@@ -18,39 +20,3 @@ impl InstallUISequence {
         }
     }
 }
-
-impl Entity for InstallUISequence {
-    fn table_name() -> &'static str {
-        TABLE_NAME
-    }
-
-    fn definition() -> Vec<msi::Column> {
-        vec![
-            msi::Column::build("Action").primary_key().id_string(72),
-            msi::Column::build("Condition")
-                .nullable()
-                .category(msi::Category::Condition)
-                .string(255),
-            msi::Column::build("Sequence")
-                .nullable()
-                .range(-4, 0x7fff)
-                .int16(),
-        ]
-    }
-
-    fn from_row(row: &RowView) -> Result<InstallUISequence, MsiDataBaseError> {
-        Ok(InstallUISequence {
-            dialog: row.string(0)?,
-            condition: row.opt_string(1)?,
-            order: row.i32(2)?,
-        })
-    }
-
-    fn to_row(&self) -> Vec<msi::Value> {
-        vec![
-            msi::Value::Str(self.dialog.clone()),
-            msi::Value::from_opt_string(&self.condition),
-            msi::Value::Int(self.order),
-        ]
-    }
-}
@@ -0,0 +1,14 @@
+use super::Entity;
+
+/// LaunchCondition Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/launchcondition-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "LaunchCondition")]
+pub struct LaunchCondition {
+    /// The installation stops when this condition evaluates to false.
+    #[column(primary_key, category = Condition)]
+    pub condition: String,
+    /// Message shown to the user when the condition is false.
+    #[column(localizable, category = Formatted)]
+    pub description: String,
+}
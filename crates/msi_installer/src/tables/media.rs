@@ -0,0 +1,22 @@
+use super::Entity;
+
+/// Media Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/media-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "Media")]
+pub struct Media {
+    #[column(primary_key, range(1, 32767))]
+    pub disk_id: i32,
+    /// Sequence number of the last file of the File table on this disk.
+    #[column(range(0, 32767))]
+    pub last_sequence: i32,
+    #[column(category = Text, length = 64)]
+    pub disk_prompt: Option<String>,
+    /// Name of the cabinet, prefixed with `#` when it is a stream of the package.
+    #[column(category = Cabinet)]
+    pub cabinet: Option<String>,
+    #[column(category = Text, length = 32)]
+    pub volume_label: Option<String>,
+    #[column(category = Property, length = 32)]
+    pub source: Option<String>,
+}
@@ -0,0 +1,16 @@
+use uuid::Uuid;
+
+use super::Entity;
+
+/// MIME Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/mime-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "MIME")]
+pub struct Mime {
+    #[column(primary_key, category = Text, length = 64)]
+    pub content_type: String,
+    #[column(name = "Extension_", foreign_key("Extension", 1), category = Text)]
+    pub extension: String,
+    #[column(name = "CLSID")]
+    pub clsid: Option<Uuid>,
+}
@@ -1,35 +1,87 @@
+mod action_text;
+mod admin_execute_sequence;
+mod advt_execute_sequence;
+mod app_search;
 mod binary;
 mod component;
 mod control;
 mod control_event;
+mod custom_action;
 mod dialog;
 mod directory;
+mod dr_locator;
 mod environment;
 mod error;
+mod error_message;
 mod event_mapping;
+mod extension;
+mod feature;
 mod feature_components;
 mod file;
+mod icon;
+mod install_execute_sequence;
 mod install_ui_sequence;
+mod launch_condition;
+mod media;
+mod mime;
+mod msi_patch_metadata;
+mod msi_patch_sequence;
+mod msi_service_config_failure_actions;
+mod patch_package;
+mod prog_id;
 mod property;
+mod reg_locator;
 mod registry;
+mod service_control;
+mod service_install;
+mod signature;
 mod standard_actions;
 mod table;
 mod text_style;
+mod verb;
 
+pub use action_text::*;
+pub use admin_execute_sequence::*;
+pub use advt_execute_sequence::*;
+pub use app_search::*;
 pub use binary::*;
 pub use component::*;
 pub use control::*;
 pub use control_event::*;
+pub use custom_action::*;
 pub use dialog::*;
 pub use directory::*;
+pub use dr_locator::*;
 pub use environment::*;
 pub use error::MsiDataBaseError;
+pub use error_message::*;
 pub use event_mapping::*;
+pub use extension::*;
+pub use feature::*;
 pub use feature_components::*;
 pub use file::*;
+pub use icon::*;
+pub use install_execute_sequence::*;
 pub use install_ui_sequence::*;
+pub use launch_condition::*;
+pub use media::*;
+pub use mime::*;
+pub use msi_macros::Entity;
+pub use msi_patch_metadata::*;
+pub use msi_patch_sequence::*;
+pub use msi_service_config_failure_actions::*;
+pub use patch_package::*;
+pub use prog_id::*;
 pub use property::*;
+pub use reg_locator::*;
 pub use registry::*;
-pub use standard_actions::is_standard_action;
+pub use service_control::*;
+pub use service_install::*;
+pub use signature::*;
+pub use standard_actions::{
+    ADMIN_EXECUTE_SEQUENCE, ADVT_EXECUTE_SEQUENCE, INSTALL_EXECUTE_SEQUENCE, INSTALL_UI_SEQUENCE,
+    StandardAction, is_standard_action,
+};
 pub use table::*;
 pub use text_style::*;
+pub use verb::*;
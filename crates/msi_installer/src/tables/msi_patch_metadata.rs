@@ -0,0 +1,15 @@
+use super::Entity;
+
+/// MsiPatchMetadata Table, in patch packages
+/// https://learn.microsoft.com/en-us/windows/win32/msi/msipatchmetadata-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "MsiPatchMetadata")]
+pub struct MsiPatchMetadata {
+    /// Company of a custom property, `None` for the standard properties.
+    #[column(primary_key, category = Text, length = 72)]
+    pub company: Option<String>,
+    #[column(primary_key, category = Text, length = 72)]
+    pub property: String,
+    #[column(category = Text, length = 0)]
+    pub value: String,
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use super::Entity;
+
+/// MsiPatchSequence Table, in patch packages
+/// https://learn.microsoft.com/en-us/windows/win32/msi/msipatchsequence-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "MsiPatchSequence")]
+pub struct MsiPatchSequence {
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub patch_family: String,
+    /// Product the sequence applies to, `None` for all the targets of the patch.
+    #[column(primary_key)]
+    pub product_code: Option<Uuid>,
+    /// Order of the patch in its family, a version string.
+    #[column(category = Version, length = 72)]
+    pub sequence: String,
+    /// 1 when the patch supersedes the earlier patches of the family.
+    #[column(range(0, 1))]
+    pub attributes: Option<i32>,
+}
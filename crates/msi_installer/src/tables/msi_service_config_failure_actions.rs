@@ -0,0 +1,114 @@
+use bitflags::bitflags;
+
+use super::{Entity, RowView, error::MsiDataBaseError};
+
+/// MsiServiceConfigFailureActions Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/msiserviceconfigfailureactions-table
+#[derive(Debug, Clone, Default)]
+pub struct MsiServiceConfigFailureActions {
+    pub msi_service_config_failure_actions: String,
+    pub name: String,
+    /// When the configuration is applied. Only install and reinstall are supported.
+    pub event: ServiceConfigEvent,
+    /// Time in seconds after which the failure count is reset to zero.
+    pub reset_period: Option<i32>,
+    pub reboot_message: Option<String>,
+    /// Command line of the process to run when the run command action is triggered.
+    pub command: Option<String>,
+    /// List of actions separated by `[~]`. See [`ServiceFailureActionType`].
+    pub actions: Option<String>,
+    /// Delays in milliseconds before each action, separated by `[~]`.
+    pub delay_actions: Option<String>,
+    pub component: String,
+}
+
+impl Entity for MsiServiceConfigFailureActions {
+    fn table_name() -> &'static str {
+        "MsiServiceConfigFailureActions"
+    }
+
+    fn definition() -> Vec<msi::Column> {
+        vec![
+            msi::Column::build("MsiServiceConfigFailureActions")
+                .primary_key()
+                .category(msi::Category::Identifier)
+                .string(72),
+            msi::Column::build("Name")
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Event").range(0, 7).int16(),
+            msi::Column::build("ResetPeriod").nullable().int32(),
+            msi::Column::build("RebootMessage")
+                .nullable()
+                .localizable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Command")
+                .nullable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Actions")
+                .nullable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("DelayActions")
+                .nullable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Component_")
+                .foreign_key("Component", 1)
+                .category(msi::Category::Identifier)
+                .string(72),
+        ]
+    }
+
+    fn from_row(row: &RowView) -> Result<MsiServiceConfigFailureActions, MsiDataBaseError> {
+        Ok(MsiServiceConfigFailureActions {
+            msi_service_config_failure_actions: row.string(0)?,
+            name: row.string(1)?,
+            event: ServiceConfigEvent::from_bits_retain(row.i32(2)?),
+            reset_period: row.opt_i32(3)?,
+            reboot_message: row.opt_string(4)?,
+            command: row.opt_string(5)?,
+            actions: row.opt_string(6)?,
+            delay_actions: row.opt_string(7)?,
+            component: row.string(8)?,
+        })
+    }
+
+    fn to_row(&self) -> Vec<msi::Value> {
+        vec![
+            msi::Value::Str(self.msi_service_config_failure_actions.clone()),
+            msi::Value::Str(self.name.clone()),
+            msi::Value::Int(self.event.bits()),
+            msi::Value::from_opt_i32(&self.reset_period),
+            msi::Value::from_opt_string(&self.reboot_message),
+            msi::Value::from_opt_string(&self.command),
+            msi::Value::from_opt_string(&self.actions),
+            msi::Value::from_opt_string(&self.delay_actions),
+            msi::Value::Str(self.component.clone()),
+        ]
+    }
+}
+
+bitflags! {
+    /// When the service configuration is applied.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/msiserviceconfigfailureactions-table#Event
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ServiceConfigEvent: i32 {
+        const Install = 0x1;
+        const Uninstall = 0x2;
+        const Reinstall = 0x4;
+    }
+}
+
+/// Action performed by the service control manager when the service fails.
+/// https://learn.microsoft.com/en-us/windows/win32/api/winsvc/ns-winsvc-sc_action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ServiceFailureActionType {
+    None = 0,
+    Restart = 1,
+    Reboot = 2,
+    RunCommand = 3,
+}
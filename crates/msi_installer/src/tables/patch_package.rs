@@ -0,0 +1,15 @@
+use uuid::Uuid;
+
+use super::Entity;
+
+/// PatchPackage Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/patchpackage-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "PatchPackage")]
+pub struct PatchPackage {
+    #[column(name = "PatchId", primary_key)]
+    pub patch_id: Uuid,
+    /// Disk of the Media table holding the files of the patch.
+    #[column(name = "Media_", foreign_key("Media", 1), range(0, 32767))]
+    pub media: i32,
+}
@@ -0,0 +1,77 @@
+use uuid::Uuid;
+
+use super::{Entity, RowView, error::MsiDataBaseError};
+
+/// ProgId Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/progid-table
+#[derive(Debug, Clone, Default)]
+pub struct ProgId {
+    pub prog_id: String,
+    pub prog_id_parent: Option<String>,
+    pub class: Option<Uuid>,
+    pub description: Option<String>,
+    /// Key of the icon in the Icon table.
+    pub icon: Option<String>,
+    /// Index of the icon in the icon file, negative values are resource ids.
+    pub icon_index: Option<i32>,
+}
+
+impl Entity for ProgId {
+    fn table_name() -> &'static str {
+        "ProgId"
+    }
+
+    fn definition() -> Vec<msi::Column> {
+        vec![
+            msi::Column::build("ProgId")
+                .primary_key()
+                .category(msi::Category::Text)
+                .string(255),
+            msi::Column::build("ProgId_Parent")
+                .nullable()
+                .foreign_key("ProgId", 1)
+                .category(msi::Category::Text)
+                .string(255),
+            msi::Column::build("Class_")
+                .nullable()
+                .category(msi::Category::Guid)
+                .string(38),
+            msi::Column::build("Description")
+                .nullable()
+                .localizable()
+                .category(msi::Category::Text)
+                .string(255),
+            msi::Column::build("Icon_")
+                .nullable()
+                .foreign_key("Icon", 1)
+                .category(msi::Category::Identifier)
+                .string(72),
+            msi::Column::build("IconIndex")
+                .nullable()
+                .range(-32767, 32767)
+                .int16(),
+        ]
+    }
+
+    fn from_row(row: &RowView) -> Result<ProgId, MsiDataBaseError> {
+        Ok(ProgId {
+            prog_id: row.string(0)?,
+            prog_id_parent: row.opt_string(1)?,
+            class: row.opt_uuid(2)?,
+            description: row.opt_string(3)?,
+            icon: row.opt_string(4)?,
+            icon_index: row.opt_i32(5)?,
+        })
+    }
+
+    fn to_row(&self) -> Vec<msi::Value> {
+        vec![
+            msi::Value::Str(self.prog_id.clone()),
+            msi::Value::from_opt_string(&self.prog_id_parent),
+            msi::Value::from(self.class),
+            msi::Value::from_opt_string(&self.description),
+            msi::Value::from_opt_string(&self.icon),
+            msi::Value::from_opt_i32(&self.icon_index),
+        ]
+    }
+}
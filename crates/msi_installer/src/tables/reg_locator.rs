@@ -0,0 +1,79 @@
+use bitflags::bitflags;
+
+use super::{Entity, RegistryRoot, RowView, error::MsiDataBaseError};
+
+/// RegLocator Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/reglocator-table
+#[derive(Debug, Clone, Default)]
+pub struct RegLocator {
+    pub signature: String,
+    /// `RegistryRoot::Auto` isn't allowed in searches.
+    pub root: RegistryRoot,
+    pub key: String,
+    /// Name of the value, `None` for the default value of the key.
+    pub name: Option<String>,
+    pub locator_type: RegLocatorType,
+}
+
+impl Entity for RegLocator {
+    fn table_name() -> &'static str {
+        "RegLocator"
+    }
+
+    fn definition() -> Vec<msi::Column> {
+        vec![
+            msi::Column::build("Signature_")
+                .primary_key()
+                .category(msi::Category::Identifier)
+                .string(72),
+            msi::Column::build("Root").range(0, 3).int16(),
+            msi::Column::build("Key")
+                .category(msi::Category::RegPath)
+                .string(255),
+            msi::Column::build("Name")
+                .nullable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Type").nullable().range(0, 18).int16(),
+        ]
+    }
+
+    fn from_row(row: &RowView) -> Result<RegLocator, MsiDataBaseError> {
+        Ok(RegLocator {
+            signature: row.string(0)?,
+            root: match RegistryRoot::from_repr(row.i32(1)?) {
+                Some(root) => root,
+                None => return Err(MsiDataBaseError::InvalidRegistryRoot(row.i32(1)?)),
+            },
+            key: row.string(2)?,
+            name: row.opt_string(3)?,
+            locator_type: RegLocatorType::from_bits_retain(row.opt_i32(4)?.unwrap_or(0)),
+        })
+    }
+
+    fn to_row(&self) -> Vec<msi::Value> {
+        vec![
+            msi::Value::Str(self.signature.clone()),
+            msi::Value::Int(self.root as i32),
+            msi::Value::Str(self.key.clone()),
+            msi::Value::from_opt_string(&self.name),
+            msi::Value::Int(self.locator_type.bits()),
+        ]
+    }
+}
+
+bitflags! {
+    /// How the registry value is interpreted.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/reglocator-table#Type
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct RegLocatorType: i32 {
+        /// The value is a directory path, checked against the Signature table when it has a row.
+        const Directory = 0;
+        /// The value is a file path, checked against the Signature table.
+        const FileName = 1;
+        /// The value is copied to the property, prefixed like the Value column of the Registry table.
+        const Raw = 2;
+        /// Search the 64-bit portion of the registry instead of the 32-bit view.
+        const Win64 = 0x10;
+    }
+}
@@ -9,8 +9,10 @@ pub struct Registry {
     pub registry: String,
     pub root: RegistryRoot,
     pub key: String,
-    pub name: String,
-    pub value: String,
+    /// Name of the value, `None` for the default value of the key.
+    /// Special names `+`, `-` and `*` create and/or delete the whole key when `value` is `None`.
+    pub name: Option<String>,
+    pub value: Option<String>,
     pub component: String,
 }
 
@@ -55,8 +57,8 @@ impl Entity for Registry {
                 None => return Err(MsiDataBaseError::InvalidRegistryRoot(row.i32(1)?)),
             },
             key: row.string(2)?,
-            name: row.string(3)?,
-            value: row.string(4)?,
+            name: row.opt_string(3)?,
+            value: row.opt_string(4)?,
             component: row.string(5)?,
         })
     }
@@ -64,10 +66,10 @@ impl Entity for Registry {
     fn to_row(&self) -> Vec<msi::Value> {
         vec![
             msi::Value::Str(self.registry.clone()),
-            msi::Value::Int(self.root.clone() as i32),
+            msi::Value::Int(self.root as i32),
             msi::Value::Str(self.key.clone()),
-            msi::Value::Str(self.name.clone()),
-            msi::Value::Str(self.value.clone()),
+            msi::Value::from_opt_string(&self.name),
+            msi::Value::from_opt_string(&self.value),
             msi::Value::Str(self.component.clone()),
         ]
     }
@@ -76,7 +78,7 @@ impl Entity for Registry {
 /// The predefined root key for the registry value.
 /// Enter a value of -1 in this field to make the root key dependent on the type of installation.
 /// Enter one of the other values in the following table to force the registry value to be written under a particular root key.
-#[derive(Debug, Clone, FromRepr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRepr)]
 #[repr(i32)]
 #[derive(Default)]
 pub enum RegistryRoot {
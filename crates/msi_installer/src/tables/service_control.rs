@@ -0,0 +1,89 @@
+use bitflags::bitflags;
+
+use super::{Entity, RowView, error::MsiDataBaseError};
+
+/// ServiceControl Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/servicecontrol-table
+#[derive(Debug, Clone, Default)]
+pub struct ServiceControl {
+    pub service_control: String,
+    pub name: String,
+    pub event: ServiceControlEvent,
+    /// Arguments passed when starting the service, separated by `[~]`.
+    pub arguments: Option<String>,
+    /// Wait for the service to complete the operation before continuing.
+    pub wait: Option<bool>,
+    pub component: String,
+}
+
+impl Entity for ServiceControl {
+    fn table_name() -> &'static str {
+        "ServiceControl"
+    }
+
+    fn definition() -> Vec<msi::Column> {
+        vec![
+            msi::Column::build("ServiceControl")
+                .primary_key()
+                .category(msi::Category::Identifier)
+                .string(72),
+            msi::Column::build("Name")
+                .localizable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Event").range(0, 187).int16(),
+            msi::Column::build("Arguments")
+                .nullable()
+                .localizable()
+                .category(msi::Category::Formatted)
+                .string(255),
+            msi::Column::build("Wait").nullable().range(0, 1).int16(),
+            msi::Column::build("Component_")
+                .foreign_key("Component", 1)
+                .category(msi::Category::Identifier)
+                .string(72),
+        ]
+    }
+
+    fn from_row(row: &RowView) -> Result<ServiceControl, MsiDataBaseError> {
+        Ok(ServiceControl {
+            service_control: row.string(0)?,
+            name: row.string(1)?,
+            event: ServiceControlEvent::from_bits_retain(row.i32(2)?),
+            arguments: row.opt_string(3)?,
+            wait: row.opt_i32(4)?.map(|wait| wait != 0),
+            component: row.string(5)?,
+        })
+    }
+
+    fn to_row(&self) -> Vec<msi::Value> {
+        vec![
+            msi::Value::Str(self.service_control.clone()),
+            msi::Value::Str(self.name.clone()),
+            msi::Value::Int(self.event.bits()),
+            msi::Value::from_opt_string(&self.arguments),
+            msi::Value::from_opt_i32(&self.wait.map(i32::from)),
+            msi::Value::Str(self.component.clone()),
+        ]
+    }
+}
+
+bitflags! {
+    /// Operations to perform on the service during install and uninstall.
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/servicecontrol-table#Event
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ServiceControlEvent: i32 {
+        /// Starts the service during the StartServices action.
+        const Start = 0x1;
+        /// Stops the service during the StopServices action.
+        const Stop = 0x2;
+        /// Deletes the service during the DeleteServices action.
+        const Delete = 0x8;
+        /// Starts the service during the StartServices action at uninstall.
+        const UninstallStart = 0x10;
+        /// Stops the service during the StopServices action at uninstall.
+        const UninstallStop = 0x20;
+        /// Deletes the service during the DeleteServices action at uninstall.
+        const UninstallDelete = 0x80;
+    }
+}
@@ -0,0 +1,123 @@
+use bitflags::bitflags;
+use strum_macros::FromRepr;
+
+use super::Entity;
+
+/// ServiceInstall Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/serviceinstall-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "ServiceInstall")]
+pub struct ServiceInstall {
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub service_install: String,
+    #[column(category = Formatted)]
+    pub name: String,
+    #[column(localizable, category = Formatted)]
+    pub display_name: Option<String>,
+    #[column(flags, int32)]
+    pub service_type: ServiceType,
+    #[column(enumeration, int32, range(0, 4))]
+    pub start_type: ServiceStartType,
+    #[column(flags, int32)]
+    pub error_control: ServiceErrorControl,
+    #[column(category = Formatted)]
+    pub load_order_group: Option<String>,
+    /// List of services or load order groups the service depends on, separated by `[~]`.
+    #[column(category = Formatted)]
+    pub dependencies: Option<String>,
+    /// Account running the service. LocalSystem is used when not set.
+    #[column(category = Formatted)]
+    pub start_name: Option<String>,
+    #[column(category = Formatted)]
+    pub password: Option<String>,
+    #[column(category = Formatted)]
+    pub arguments: Option<String>,
+    /// Component whose key file is the service executable.
+    #[column(name = "Component_", foreign_key("Component", 1), category = Identifier, length = 72)]
+    pub component: String,
+    #[column(localizable, category = Formatted)]
+    pub description: Option<String>,
+}
+
+bitflags! {
+    /// Service Type
+    /// https://learn.microsoft.com/en-us/windows/win32/msi/serviceinstall-table#ServiceType
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ServiceType: i32 {
+        /// A Microsoft Win32 service that runs its own process.
+        const OwnProcess = 0x10;
+        /// A Win32 service that shares a process.
+        const ShareProcess = 0x20;
+        /// A Win32 service that interacts with the desktop. Only valid with LocalSystem.
+        const Interactive = 0x100;
+    }
+}
+
+impl Default for ServiceType {
+    fn default() -> Self {
+        ServiceType::OwnProcess
+    }
+}
+
+/// When the service is started.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/serviceinstall-table#StartType
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, FromRepr)]
+#[repr(i32)]
+pub enum ServiceStartType {
+    /// Started by the system loader. Only valid for driver services.
+    Boot = 0,
+    /// Started by IoInitSystem. Only valid for driver services.
+    System = 1,
+    /// Started automatically by the service control manager during system startup.
+    Auto = 2,
+    /// Started by the service control manager when a process calls StartService.
+    #[default]
+    Demand = 3,
+    /// Cannot be started.
+    Disabled = 4,
+}
+
+/// Error Control, how the system reacts when the service fails to start, and whether the
+/// installation fails when the service fails to install.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/serviceinstall-table#ErrorControl
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ServiceErrorControl {
+    pub level: ServiceErrorLevel,
+    /// The installation fails if the service fails to install.
+    pub vital: bool,
+}
+
+/// How the system reacts when the service fails to start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, FromRepr)]
+#[repr(i32)]
+pub enum ServiceErrorLevel {
+    /// Logs the error and continues with the startup operation.
+    #[default]
+    Ignore = 0,
+    /// Logs the error, displays a message box and continues the startup operation.
+    Normal = 1,
+    /// Logs the error and the system is restarted with the last configuration known to be good.
+    /// If the last-known-good configuration is being started, the startup continues.
+    Severe = 2,
+    /// Logs the error if it is possible and the system is restarted with the last configuration known to be good.
+    /// If the last-known-good configuration is being started, the startup operation fails.
+    Critical = 3,
+}
+
+impl ServiceErrorControl {
+    const LEVEL_MASK: i32 = 0x3;
+    const VITAL: i32 = 0x8000;
+
+    /// Read the column value, the bits other than the level and the vital flag are ignored.
+    pub fn from_bits_retain(bits: i32) -> Self {
+        Self {
+            level: ServiceErrorLevel::from_repr(bits & Self::LEVEL_MASK).unwrap_or_default(),
+            vital: bits & Self::VITAL != 0,
+        }
+    }
+
+    pub fn bits(&self) -> i32 {
+        let vital = if self.vital { Self::VITAL } else { 0 };
+        self.level as i32 | vital
+    }
+}
@@ -0,0 +1,29 @@
+use super::Entity;
+
+/// Signature Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/signature-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "Signature")]
+pub struct Signature {
+    #[column(primary_key, category = Identifier, length = 72)]
+    pub signature: String,
+    /// Name of the searched file, in the `short|long` format of the Filename category.
+    #[column(name = "FileName", category = Text)]
+    pub file_name: String,
+    #[column(category = Text, length = 20)]
+    pub min_version: Option<String>,
+    #[column(category = Text, length = 20)]
+    pub max_version: Option<String>,
+    /// Size in bytes.
+    #[column(int32, range(0, 2147483647))]
+    pub min_size: Option<i32>,
+    #[column(int32, range(0, 2147483647))]
+    pub max_size: Option<i32>,
+    #[column(int32, range(0, 2147483647))]
+    pub min_date: Option<i32>,
+    #[column(int32, range(0, 2147483647))]
+    pub max_date: Option<i32>,
+    /// Comma separated language ids, e.g. `1033,1036`.
+    #[column(category = Language)]
+    pub languages: Option<String>,
+}
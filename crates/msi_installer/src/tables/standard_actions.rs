@@ -22,10 +22,12 @@ const STANDARD_ACTIONS: &[&str] = &[
     "InstallInitialize", // https://learn.microsoft.com/en-us/windows/win32/msi/installinitialize-action
     "InstallODBC",       // https://learn.microsoft.com/en-us/windows/win32/msi/installodbc-action
     "InstallServices", // https://learn.microsoft.com/en-us/windows/win32/msi/installservices-action
+    "InstallValidate", // https://learn.microsoft.com/en-us/windows/win32/msi/installvalidate-action
     "IsolateComponents", // https://learn.microsoft.com/en-us/windows/win32/msi/isolatecomponents-action
     "LaunchConditions", // https://learn.microsoft.com/en-us/windows/win32/msi/launchconditions-action
     "MigrateFeatureStates", // https://learn.microsoft.com/en-us/windows/win32/msi/migratefeaturestates-action
     "MoveFiles",            // https://learn.microsoft.com/en-us/windows/win32/msi/movefiles-action
+    "MsiConfigureServices", // https://learn.microsoft.com/en-us/windows/win32/msi/msiconfigureservices-action
     "PatchFiles",           // https://learn.microsoft.com/en-us/windows/win32/msi/patchfiles-action
     "ProcessComponents", // https://learn.microsoft.com/en-us/windows/win32/msi/processcomponents-action
     "PublishComponents", // https://learn.microsoft.com/en-us/windows/win32/msi/publishcomponents-action
@@ -33,8 +35,10 @@ const STANDARD_ACTIONS: &[&str] = &[
     "PublishProduct",  // https://learn.microsoft.com/en-us/windows/win32/msi/publishproduct-action
     "RegisterClassInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/registerclassinfo-action
     "RegisterComPlus", // https://learn.microsoft.com/en-us/windows/win32/msi/registercomplus-action
-    "RegisterFonts",   // https://learn.microsoft.com/en-us/windows/win32/msi/registerfonts-action
+    "RegisterExtensionInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/registerextensioninfo-action
+    "RegisterFonts", // https://learn.microsoft.com/en-us/windows/win32/msi/registerfonts-action
     "RegisterMIMEInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/registermimeinfo-action
+    "RegisterProgIdInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/registerprogidinfo-action
     "RegisterProduct", // https://learn.microsoft.com/en-us/windows/win32/msi/registerproduct-action
     "RegisterTypeLibraries", // https://learn.microsoft.com/en-us/windows/win32/msi/registertypelibraries-action
     "RegisterUser", // https://learn.microsoft.com/en-us/windows/win32/msi/registeruser-action
@@ -48,6 +52,7 @@ const STANDARD_ACTIONS: &[&str] = &[
     "RemoveRegistryValues", // https://learn.microsoft.com/en-us/windows/win32/msi/removeregistryvalues-action
     "RemoveShortcuts", // https://learn.microsoft.com/en-us/windows/win32/msi/removeshortcuts-action
     "ResolveSource",   // https://learn.microsoft.com/en-us/windows/win32/msi/resolvesource-action
+    "RMCCPSearch",     // https://learn.microsoft.com/en-us/windows/win32/msi/rmccpsearch-action
     "SelfRegModules",  // https://learn.microsoft.com/en-us/windows/win32/msi/selfregmodules-action
     // cspell:ignore Unreg
     "SelfUnregModules", // https://learn.microsoft.com/en-us/windows/win32/msi/selfunregmodules-action
@@ -60,8 +65,10 @@ const STANDARD_ACTIONS: &[&str] = &[
     "UnpublishFeatures", // https://learn.microsoft.com/en-us/windows/win32/msi/unpublishfeatures-action
     "UnregisterClassInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/unregisterclassinfo-action
     "UnregisterComPlus", // https://learn.microsoft.com/en-us/windows/win32/msi/unregistercomplus-action
+    "UnregisterExtensionInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/unregisterextensioninfo-action
     "UnregisterFonts", // https://learn.microsoft.com/en-us/windows/win32/msi/unregisterfonts-action
     "UnregisterMIMEInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/unregistermimeinfo-action
+    "UnregisterProgIdInfo", // https://learn.microsoft.com/en-us/windows/win32/msi/unregisterprogidinfo-action
     "UnregisterTypeLibraries", // https://learn.microsoft.com/en-us/windows/win32/msi/unregistertypelibraries-action
     "ValidateProductID", // https://learn.microsoft.com/en-us/windows/win32/msi/validateproductid-action
     "WriteEnvironmentStrings", // https://learn.microsoft.com/en-us/windows/win32/msi/writeenvironmentstrings-action
@@ -72,3 +79,180 @@ const STANDARD_ACTIONS: &[&str] = &[
 pub fn is_standard_action(action: &str) -> bool {
     STANDARD_ACTIONS.contains(&action)
 }
+
+/// Position of a standard action in a sequence table.
+#[derive(Debug, Clone, Copy)]
+pub struct StandardAction {
+    pub name: &'static str,
+    pub condition: Option<&'static str>,
+    pub sequence: i32,
+    /// The action is only scheduled when one of these tables exists, always when empty.
+    pub tables: &'static [&'static str],
+}
+
+const fn action(
+    name: &'static str,
+    condition: Option<&'static str>,
+    sequence: i32,
+    tables: &'static [&'static str],
+) -> StandardAction {
+    StandardAction {
+        name,
+        condition,
+        sequence,
+        tables,
+    }
+}
+
+/// https://learn.microsoft.com/en-us/windows/win32/msi/suggested-installexecutesequence
+/// The searches run before LaunchConditions so the conditions can check their results.
+pub const INSTALL_EXECUTE_SEQUENCE: &[StandardAction] = &[
+    action("FindRelatedProducts", None, 25, &["Upgrade"]),
+    action("AppSearch", None, 50, &["AppSearch"]),
+    action("LaunchConditions", None, 100, &["LaunchCondition"]),
+    action("CCPSearch", Some("NOT Installed"), 500, &["CCPSearch"]),
+    action("RMCCPSearch", Some("NOT Installed"), 600, &["CCPSearch"]),
+    action("ValidateProductID", None, 700, &[]),
+    action("CostInitialize", None, 800, &[]),
+    action("FileCost", None, 900, &[]),
+    action("CostFinalize", None, 1000, &[]),
+    action("SetODBCFolders", None, 1100, &[]),
+    action("MigrateFeatureStates", None, 1200, &["Upgrade"]),
+    action("InstallValidate", None, 1400, &[]),
+    action("InstallInitialize", None, 1500, &[]),
+    action("AllocateRegistrySpace", Some("NOT Installed"), 1550, &[]),
+    action("ProcessComponents", None, 1600, &[]),
+    action("UnpublishComponents", None, 1700, &[]),
+    action("UnpublishFeatures", None, 1800, &[]),
+    action("StopServices", Some("VersionNT"), 1900, &["ServiceControl"]),
+    action(
+        "DeleteServices",
+        Some("VersionNT"),
+        2000,
+        &["ServiceControl"],
+    ),
+    action("UnregisterComPlus", None, 2100, &[]),
+    action("SelfUnregModules", None, 2200, &["SelfReg"]),
+    action("UnregisterTypeLibraries", None, 2300, &["TypeLib"]),
+    action(
+        "RemoveODBC",
+        None,
+        2400,
+        &["ODBCDataSource", "ODBCDriver", "ODBCTranslator"],
+    ),
+    action("UnregisterFonts", None, 2500, &["Font"]),
+    action(
+        "RemoveRegistryValues",
+        None,
+        2600,
+        &["Registry", "RemoveRegistry"],
+    ),
+    action("UnregisterClassInfo", None, 2700, &["Class"]),
+    action("UnregisterExtensionInfo", None, 2800, &["Extension"]),
+    action(
+        "UnregisterProgIdInfo",
+        None,
+        2900,
+        &["ProgId", "Extension", "Class"],
+    ),
+    action("UnregisterMIMEInfo", None, 3000, &["MIME"]),
+    action("RemoveIniValues", None, 3100, &["IniFile", "RemoveIniFile"]),
+    action("RemoveShortcuts", None, 3200, &["Shortcut"]),
+    action("RemoveEnvironmentStrings", None, 3300, &["Environment"]),
+    action("RemoveDuplicateFiles", None, 3400, &["DuplicateFile"]),
+    action("RemoveFiles", None, 3500, &[]),
+    action("RemoveFolders", None, 3600, &[]),
+    action("CreateFolders", None, 3700, &[]),
+    action("MoveFiles", None, 3800, &[]),
+    action("InstallFiles", None, 4000, &[]),
+    action("PatchFiles", None, 4090, &["Patch"]),
+    action("DuplicateFiles", None, 4210, &["DuplicateFile"]),
+    action("BindImage", None, 4300, &["BindImage"]),
+    action("CreateShortcuts", None, 4500, &["Shortcut"]),
+    action("RegisterClassInfo", None, 4600, &["Class"]),
+    action("RegisterExtensionInfo", None, 4700, &["Extension"]),
+    action("RegisterProgIdInfo", None, 4800, &["ProgId"]),
+    action("RegisterMIMEInfo", None, 4900, &["MIME"]),
+    action("WriteRegistryValues", None, 5000, &["Registry"]),
+    action("WriteIniValues", None, 5100, &["IniFile"]),
+    action("WriteEnvironmentStrings", None, 5200, &["Environment"]),
+    action("RegisterFonts", None, 5300, &["Font"]),
+    action(
+        "InstallODBC",
+        None,
+        5400,
+        &["ODBCDataSource", "ODBCDriver", "ODBCTranslator"],
+    ),
+    action("RegisterTypeLibraries", None, 5500, &["TypeLib"]),
+    action("SelfRegModules", None, 5600, &["SelfReg"]),
+    action("RegisterComPlus", None, 5700, &[]),
+    action(
+        "InstallServices",
+        Some("VersionNT"),
+        5800,
+        &["ServiceInstall"],
+    ),
+    action(
+        "MsiConfigureServices",
+        Some("VersionNT >= 600"),
+        5850,
+        &["MsiServiceConfig", "MsiServiceConfigFailureActions"],
+    ),
+    action(
+        "StartServices",
+        Some("VersionNT"),
+        5900,
+        &["ServiceControl"],
+    ),
+    action("RegisterUser", None, 6000, &[]),
+    action("RegisterProduct", None, 6100, &[]),
+    action("PublishComponents", None, 6200, &[]),
+    action("PublishFeatures", None, 6300, &[]),
+    action("PublishProduct", None, 6400, &[]),
+    action("InstallFinalize", None, 6600, &[]),
+    action("RemoveExistingProducts", None, 6700, &["Upgrade"]),
+];
+
+/// https://learn.microsoft.com/en-us/windows/win32/msi/suggested-installuisequence
+/// The dialogs are scheduled by the UI builder.
+pub const INSTALL_UI_SEQUENCE: &[StandardAction] = &[
+    action("FindRelatedProducts", None, 25, &["Upgrade"]),
+    action("AppSearch", None, 50, &["AppSearch"]),
+    action("LaunchConditions", None, 100, &["LaunchCondition"]),
+    action("CCPSearch", Some("NOT Installed"), 500, &["CCPSearch"]),
+    action("RMCCPSearch", Some("NOT Installed"), 600, &["CCPSearch"]),
+    action("CostInitialize", None, 800, &[]),
+    action("FileCost", None, 900, &[]),
+    action("CostFinalize", None, 1000, &[]),
+    action("MigrateFeatureStates", None, 1200, &["Upgrade"]),
+    action("ExecuteAction", None, 1300, &[]),
+];
+
+/// https://learn.microsoft.com/en-us/windows/win32/msi/suggested-adminexecutesequence
+pub const ADMIN_EXECUTE_SEQUENCE: &[StandardAction] = &[
+    action("CostInitialize", None, 800, &[]),
+    action("FileCost", None, 900, &[]),
+    action("CostFinalize", None, 1000, &[]),
+    action("InstallValidate", None, 1400, &[]),
+    action("InstallInitialize", None, 1500, &[]),
+    action("InstallAdminPackage", None, 3900, &[]),
+    action("InstallFiles", None, 4000, &[]),
+    action("InstallFinalize", None, 6600, &[]),
+];
+
+/// https://learn.microsoft.com/en-us/windows/win32/msi/suggested-advtexecutesequence
+pub const ADVT_EXECUTE_SEQUENCE: &[StandardAction] = &[
+    action("CostInitialize", None, 800, &[]),
+    action("CostFinalize", None, 1000, &[]),
+    action("InstallValidate", None, 1400, &[]),
+    action("InstallInitialize", None, 1500, &[]),
+    action("CreateShortcuts", None, 4500, &["Shortcut"]),
+    action("RegisterClassInfo", None, 4600, &["Class"]),
+    action("RegisterExtensionInfo", None, 4700, &["Extension"]),
+    action("RegisterProgIdInfo", None, 4800, &["ProgId"]),
+    action("RegisterMIMEInfo", None, 4900, &["MIME"]),
+    action("PublishComponents", None, 6200, &[]),
+    action("PublishFeatures", None, 6300, &[]),
+    action("PublishProduct", None, 6400, &[]),
+    action("InstallFinalize", None, 6600, &[]),
+];
@@ -0,0 +1,22 @@
+use super::Entity;
+
+/// Verb Table
+/// https://learn.microsoft.com/en-us/windows/win32/msi/verb-table
+#[derive(Debug, Clone, Default, Entity)]
+#[entity(table = "Verb")]
+pub struct Verb {
+    #[column(name = "Extension_", primary_key, foreign_key("Extension", 1), category = Text)]
+    pub extension: String,
+    /// Name of the verb, e.g. `open` or `edit`.
+    #[column(primary_key, category = Text, length = 32)]
+    pub verb: String,
+    /// Order of the verb in the context menu, the lowest one is the default verb.
+    #[column(range(0, 32767))]
+    pub sequence: Option<i32>,
+    /// Text of the verb in the context menu.
+    #[column(localizable, category = Formatted)]
+    pub command: Option<String>,
+    /// Arguments of the command, e.g. `"%1"` for the path of the file.
+    #[column(localizable, category = Formatted)]
+    pub argument: Option<String>,
+}
@@ -3,9 +3,9 @@ use std::{
     io::{Read, Seek, Write},
 };
 
-use crate::tables::{
-    Control, ControlEvent, Dialog, Entity, EventMapping, InstallUISequence, StyleAttributes,
-    TextStyle,
+use crate::{
+    sequence::{self, SequenceTable},
+    tables::{Control, ControlEvent, Dialog, Entity, EventMapping, StyleAttributes, TextStyle},
 };
 
 use super::dialog::{self, DialogBuilder};
@@ -13,12 +13,14 @@ use super::dialog::{self, DialogBuilder};
 pub fn new() -> UiBuilder {
     UiBuilder {
         title: "[ProductName] Setup".to_string(),
+        font: "Segoe UI".to_string(),
         dialogs: HashMap::new(),
     }
 }
 
 pub struct UiBuilder {
     title: String,
+    font: String,
     dialogs: HashMap<String, DialogBuilder>,
 }
 
@@ -28,6 +30,12 @@ impl UiBuilder {
         self
     }
 
+    /// Face name of the text styles, e.g. a font with the glyphs of the language of the dialogs.
+    pub fn font(mut self, face_name: &str) -> Self {
+        self.font = face_name.to_string();
+        self
+    }
+
     pub fn new_dialog<F: Fn(DialogBuilder) -> DialogBuilder>(mut self, id: &str, cb: F) -> Self {
         let dialog = dialog::new(id, &self.title);
         self.dialogs.insert(id.to_string(), cb(dialog));
@@ -91,21 +99,21 @@ impl UiBuilder {
             &[
                 TextStyle {
                     text_style: "DefaultFont".to_string(),
-                    face_name: "Segoe UI".to_string(),
+                    face_name: self.font.clone(),
                     size: 8,
                     color: 0,
                     attributes: None,
                 },
                 TextStyle {
                     text_style: "BoldFont".to_string(),
-                    face_name: "Segoe UI".to_string(),
+                    face_name: self.font.clone(),
                     size: 12,
                     color: 0,
                     attributes: Some(StyleAttributes::Bold),
                 },
                 TextStyle {
                     text_style: "TitleFont".to_string(),
-                    face_name: "Segoe UI".to_string(),
+                    face_name: self.font.clone(),
                     size: 9,
                     color: 0,
                     attributes: Some(StyleAttributes::Bold),
@@ -119,55 +127,20 @@ impl UiBuilder {
         &self,
         package: &mut msi::Package<impl Read + Write + Seek>,
     ) -> Result<(), std::io::Error> {
-        InstallUISequence::create_table(package)?;
-        let mut seq = Vec::new();
-
-        fn add_ui_dlg(
-            id: &str,
-            condition: Option<String>,
-            order: i32,
-            seq: &mut Vec<InstallUISequence>,
-            self_dialogs: &HashMap<String, DialogBuilder>,
-        ) {
-            if self_dialogs.contains_key(id) {
-                seq.push(InstallUISequence {
-                    dialog: id.to_string(),
-                    condition,
-                    order,
-                });
+        let dialogs = [
+            ("FatalErrorDlg", None, -3),
+            ("UserErrorDlg", None, -2),
+            ("ExitDlg", None, -1),
+            ("WelcomeDlg", Some("NOT Installed"), 1230),
+            ("RemoveDlg", Some("Installed"), 1240),
+            ("ProgressDlg", None, 1280),
+        ];
+        let mut seq = sequence::new(SequenceTable::InstallUI);
+        for (id, condition, order) in dialogs {
+            if self.dialogs.contains_key(id) {
+                seq = seq.action(id, condition, order);
             }
         }
-
-        add_ui_dlg("FatalErrorDlg", None, -3, &mut seq, &self.dialogs);
-        add_ui_dlg("UserErrorDlg", None, -2, &mut seq, &self.dialogs);
-        add_ui_dlg("ExitDlg", None, -1, &mut seq, &self.dialogs);
-        // add_known_dlg("LaunchConditions", None, 100);
-        // add_known_dlg("FindRelatedProducts", None, 200);
-        // add_known_dlg("AppSearch", None, 400);
-        // add_known_dlg("CCPSearch", Some("NOT Installed".to_string()), 500);
-        // add_known_dlg("RMCCPSearch", Some("NOT Installed".to_string()), 600);
-        seq.push(InstallUISequence::new("CostInitialize", None, 800));
-        seq.push(InstallUISequence::new("FileCost", None, 900));
-        seq.push(InstallUISequence::new("CostFinalize", None, 1000));
-        // add_known_dlg("MigrateFeatureStates", None, 1200);
-        add_ui_dlg(
-            "WelcomeDlg",
-            Some("NOT Installed".to_string()),
-            1230,
-            &mut seq,
-            &self.dialogs,
-        );
-        add_ui_dlg(
-            "RemoveDlg",
-            Some("Installed".to_string()),
-            1240,
-            &mut seq,
-            &self.dialogs,
-        );
-        add_ui_dlg("ProgressDlg", None, 1280, &mut seq, &self.dialogs);
-        seq.push(InstallUISequence::new("ExecuteAction", None, 1300));
-
-        InstallUISequence::insert(package, &seq)?;
-        Ok(())
+        seq.insert(package)
     }
 }
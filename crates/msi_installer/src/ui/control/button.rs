@@ -48,6 +48,12 @@ impl Button {
         self.events.push(size);
         self
     }
+
+    /// Trigger all the events in order.
+    pub fn triggers(mut self, events: impl IntoIterator<Item = Event>) -> Self {
+        self.events.extend(events);
+        self
+    }
 }
 
 impl ControlBuilder for Button {
@@ -0,0 +1,81 @@
+use crate::{
+    tables::{Control, ControlAttributes, ControlType},
+    ui::{position::Position, size::Size},
+};
+
+use super::ControlBuilder;
+
+/// Create a directory combo box bound to the given directory property.
+/// Only fixed and remote volumes are listed by default.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/directorycombo-control
+pub fn directory_combo(id: &str, property: &str) -> DirectoryCombo {
+    DirectoryCombo {
+        id: id.to_string(),
+        property: property.to_string(),
+        pos: Position::ZERO,
+        size: Size::new(0, 80),
+        attributes: ControlAttributes::Visible
+            | ControlAttributes::Enabled
+            | ControlAttributes::FixedVolume
+            | ControlAttributes::RemoteVolume,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DirectoryCombo {
+    id: String,
+    property: String,
+    pos: Position,
+    size: Size,
+    attributes: ControlAttributes,
+}
+
+impl DirectoryCombo {
+    pub fn pos(mut self, pos: impl Into<Position>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// The property holds the name of the directory property to edit instead of the path itself.
+    pub fn indirect(mut self) -> Self {
+        self.attributes |= ControlAttributes::Indirect;
+        self
+    }
+
+    /// Set which kind of volumes are listed in the combo box.
+    pub fn volumes(mut self, volumes: ControlAttributes) -> Self {
+        self.attributes.remove(ControlAttributes::AllVolumes);
+        self.attributes |= volumes & ControlAttributes::AllVolumes;
+        self
+    }
+}
+
+impl ControlBuilder for DirectoryCombo {
+    fn interactive(&self) -> bool {
+        true
+    }
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+    fn build(&self, dialog_id: &str) -> Control {
+        Control {
+            type_: ControlType::DirectoryCombo,
+            dialog: dialog_id.to_string(),
+            control: self.id.clone(),
+            x: self.pos.x,
+            y: self.pos.y,
+            width: self.size.width,
+            height: self.size.height,
+            attributes: self.attributes.clone(),
+            text: None,
+            property: Some(self.property.clone()),
+            control_next: None,
+            help: None,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    tables::{Control, ControlAttributes, ControlType},
+    ui::{position::Position, size::Size},
+};
+
+use super::ControlBuilder;
+
+/// Create a directory list bound to the given directory property.
+/// Pair it with the `DirectoryListUp`/`DirectoryListNew` events to navigate and create folders.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/directorylist-control
+pub fn directory_list(id: &str, property: &str) -> DirectoryList {
+    DirectoryList {
+        id: id.to_string(),
+        property: property.to_string(),
+        pos: Position::ZERO,
+        size: Size::ZERO,
+        attributes: ControlAttributes::Visible
+            | ControlAttributes::Enabled
+            | ControlAttributes::Sunken,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DirectoryList {
+    id: String,
+    property: String,
+    pos: Position,
+    size: Size,
+    attributes: ControlAttributes,
+}
+
+impl DirectoryList {
+    pub fn pos(mut self, pos: impl Into<Position>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// The property holds the name of the directory property to edit instead of the path itself.
+    pub fn indirect(mut self) -> Self {
+        self.attributes |= ControlAttributes::Indirect;
+        self
+    }
+}
+
+impl ControlBuilder for DirectoryList {
+    fn interactive(&self) -> bool {
+        true
+    }
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+    fn build(&self, dialog_id: &str) -> Control {
+        Control {
+            type_: ControlType::DirectoryList,
+            dialog: dialog_id.to_string(),
+            control: self.id.clone(),
+            x: self.pos.x,
+            y: self.pos.y,
+            width: self.size.width,
+            height: self.size.height,
+            attributes: self.attributes.clone(),
+            text: None,
+            property: Some(self.property.clone()),
+            control_next: None,
+            help: None,
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    tables::{Control, ControlAttributes, ControlType},
+    ui::{position::Position, size::Size},
+};
+
+use super::ControlBuilder;
+
+/// Create a text edit control bound to the given property.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/edit-control
+pub fn edit(id: &str, property: &str) -> Edit {
+    Edit {
+        id: id.to_string(),
+        property: property.to_string(),
+        max_length: None,
+        pos: Position::ZERO,
+        size: Size::new(0, 18),
+        attributes: ControlAttributes::Visible
+            | ControlAttributes::Enabled
+            | ControlAttributes::Sunken,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Edit {
+    id: String,
+    property: String,
+    max_length: Option<i32>,
+    pos: Position,
+    size: Size,
+    attributes: ControlAttributes,
+}
+
+impl Edit {
+    pub fn pos(mut self, pos: impl Into<Position>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    pub fn width(mut self, size: i32) -> Self {
+        self.size = Size {
+            width: size,
+            height: self.size.height,
+        };
+        self
+    }
+
+    /// Limit the number of characters that can be entered.
+    pub fn max_length(mut self, max_length: i32) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn multiline(mut self) -> Self {
+        self.attributes |= ControlAttributes::MultiLine;
+        self
+    }
+
+    /// Hide the characters typed by the user.
+    pub fn password(mut self) -> Self {
+        self.attributes |= ControlAttributes::Password;
+        self
+    }
+}
+
+impl ControlBuilder for Edit {
+    fn interactive(&self) -> bool {
+        true
+    }
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+    fn build(&self, dialog_id: &str) -> Control {
+        Control {
+            type_: ControlType::Edit,
+            dialog: dialog_id.to_string(),
+            control: self.id.clone(),
+            x: self.pos.x,
+            y: self.pos.y,
+            width: self.size.width,
+            height: self.size.height,
+            attributes: self.attributes.clone(),
+            text: self.max_length.map(|x| format!("{{{x}}}")),
+            property: Some(self.property.clone()),
+            control_next: None,
+            help: None,
+        }
+    }
+}
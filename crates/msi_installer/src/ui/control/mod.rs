@@ -1,13 +1,23 @@
 mod bitmap;
 mod builder;
 mod button;
+mod directory_combo;
+mod directory_list;
+mod edit;
 mod line;
+mod path_edit;
 mod progress_bar;
+mod selection_tree;
 mod text;
 
 pub use bitmap::*;
 pub(crate) use builder::ControlBuilder;
 pub use button::*;
+pub use directory_combo::*;
+pub use directory_list::*;
+pub use edit::*;
 pub use line::*;
+pub use path_edit::*;
 pub use progress_bar::*;
+pub use selection_tree::*;
 pub use text::*;
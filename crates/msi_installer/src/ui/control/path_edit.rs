@@ -0,0 +1,85 @@
+use crate::{
+    tables::{Control, ControlAttributes, ControlType},
+    ui::{position::Position, size::Size},
+};
+
+use super::ControlBuilder;
+
+/// Create a path edit control bound to the given directory property.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/pathedit-control
+pub fn path_edit(id: &str, property: &str) -> PathEdit {
+    PathEdit {
+        id: id.to_string(),
+        property: property.to_string(),
+        pos: Position::ZERO,
+        size: Size::new(0, 17),
+        attributes: ControlAttributes::Visible
+            | ControlAttributes::Enabled
+            | ControlAttributes::Sunken,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PathEdit {
+    id: String,
+    property: String,
+    pos: Position,
+    size: Size,
+    attributes: ControlAttributes,
+}
+
+impl PathEdit {
+    pub fn pos(mut self, pos: impl Into<Position>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    pub fn width(mut self, size: i32) -> Self {
+        self.size = Size {
+            width: size,
+            height: self.size.height,
+        };
+        self
+    }
+
+    /// The property holds the name of the directory property to edit instead of the path itself.
+    pub fn indirect(mut self) -> Self {
+        self.attributes |= ControlAttributes::Indirect;
+        self
+    }
+
+    pub fn disable(mut self) -> Self {
+        self.attributes.remove(ControlAttributes::Enabled);
+        self
+    }
+}
+
+impl ControlBuilder for PathEdit {
+    fn interactive(&self) -> bool {
+        true
+    }
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+    fn build(&self, dialog_id: &str) -> Control {
+        Control {
+            type_: ControlType::PathEdit,
+            dialog: dialog_id.to_string(),
+            control: self.id.clone(),
+            x: self.pos.x,
+            y: self.pos.y,
+            width: self.size.width,
+            height: self.size.height,
+            attributes: self.attributes.clone(),
+            text: None,
+            property: Some(self.property.clone()),
+            control_next: None,
+            help: None,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    tables::{Control, ControlAttributes, ControlType},
+    ui::{position::Position, size::Size},
+};
+
+use super::ControlBuilder;
+
+/// Create a selection tree listing the features so the user can choose which ones to install.
+/// The property is the directory property changed by the `SelectionBrowse` event.
+/// The tree publishes the `SelectionDescription`, `SelectionSize` and `SelectionPath` events.
+/// https://learn.microsoft.com/en-us/windows/win32/msi/selectiontree-control
+pub fn selection_tree(id: &str, property: &str) -> SelectionTree {
+    SelectionTree {
+        id: id.to_string(),
+        property: property.to_string(),
+        pos: Position::ZERO,
+        size: Size::ZERO,
+        attributes: ControlAttributes::Visible
+            | ControlAttributes::Enabled
+            | ControlAttributes::Sunken,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SelectionTree {
+    id: String,
+    property: String,
+    pos: Position,
+    size: Size,
+    attributes: ControlAttributes,
+}
+
+impl SelectionTree {
+    pub fn pos(mut self, pos: impl Into<Position>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl ControlBuilder for SelectionTree {
+    fn interactive(&self) -> bool {
+        true
+    }
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+    fn build(&self, dialog_id: &str) -> Control {
+        Control {
+            type_: ControlType::SelectionTree,
+            dialog: dialog_id.to_string(),
+            control: self.id.clone(),
+            x: self.pos.x,
+            y: self.pos.y,
+            width: self.size.width,
+            height: self.size.height,
+            attributes: self.attributes.clone(),
+            text: None,
+            property: Some(self.property.clone()),
+            control_next: None,
+            help: None,
+        }
+    }
+}
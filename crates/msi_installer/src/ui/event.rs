@@ -2,18 +2,12 @@ use crate::tables::{ControlEvent, ControlEventType};
 
 /// Create a new dialog event. Replace the current dialog with a new one.
 pub fn new_dialog(name: &str) -> Event {
-    Event {
-        event: ControlEventType::NewDialog,
-        argument: name.to_string(),
-    }
+    Event::new(ControlEventType::NewDialog, name)
 }
 
 // Spawn a new child dialog
 pub fn spawn_dialog(name: &str) -> Event {
-    Event {
-        event: ControlEventType::SpawnDialog,
-        argument: name.to_string(),
-    }
+    Event::new(ControlEventType::SpawnDialog, name)
 }
 
 #[derive(Debug, strum_macros::Display, strum_macros::EnumString)]
@@ -26,26 +20,63 @@ pub enum EndDialogAction {
 
 // Notifies the installer to remove a modal dialog box.
 pub fn end_dialog(name: EndDialogAction) -> Event {
-    Event {
-        event: ControlEventType::EndDialog,
-        argument: name.to_string(),
-    }
+    Event::new(ControlEventType::EndDialog, &name.to_string())
+}
+
+/// Commit the path selected for the given directory property.
+pub fn set_target_path(property: &str) -> Event {
+    Event::new(ControlEventType::SetTargetPath, property)
+}
+
+/// Navigate to the parent folder in the DirectoryList control of the dialog.
+pub fn directory_list_up() -> Event {
+    Event::new(ControlEventType::DirectoryListUp, "0")
+}
+
+/// Create a new folder in the DirectoryList control of the dialog.
+pub fn directory_list_new() -> Event {
+    Event::new(ControlEventType::DirectoryListNew, "0")
+}
+
+/// Discard the property changes made in the dialog.
+pub fn reset() -> Event {
+    Event::new(ControlEventType::Reset, "0")
+}
+
+/// Set the value of a property. Pass `{}` as the value to remove the property.
+pub fn set_property(property: &str, value: &str) -> Event {
+    Event::new(ControlEventType::SetProperty(property.to_string()), value)
 }
 
 #[derive(Debug)]
 pub struct Event {
     event: ControlEventType,
     argument: String,
+    condition: Option<String>,
 }
 
 impl Event {
+    fn new(event: ControlEventType, argument: &str) -> Self {
+        Event {
+            event,
+            argument: argument.to_string(),
+            condition: None,
+        }
+    }
+
+    /// Only trigger the event when the condition is met.
+    pub fn when(mut self, condition: &str) -> Self {
+        self.condition = Some(condition.to_string());
+        self
+    }
+
     pub fn as_control_event(&self, dialog: &str, control: &str, ordering: i32) -> ControlEvent {
         ControlEvent {
             dialog: dialog.to_string(),
             control: control.to_string(),
             event: self.event.to_string(),
             argument: self.argument.clone(),
-            condition: Some("1".to_string()),
+            condition: Some(self.condition.clone().unwrap_or("1".to_string())),
             ordering: Some(ordering),
         }
     }
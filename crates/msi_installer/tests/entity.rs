@@ -0,0 +1,571 @@
+use std::io::Cursor;
+
+use msi::{Category, Column, Value};
+use msi_installer::tables::{
+    AdminExecuteSequence, AdvtExecuteSequence, AppSearch, CustomAction, CustomActionType,
+    DrLocator, Entity, Extension, FeatureComponents, InstallExecuteSequence, InstallUISequence,
+    LaunchCondition, Media, Mime, ServiceErrorControl, ServiceErrorLevel, ServiceInstall,
+    ServiceStartType, ServiceType, Signature, Verb,
+};
+use uuid::Uuid;
+
+// The expected definitions and rows are the hand-written `Entity` implementations that the derive
+// replaced, the generated ones must describe the same tables.
+
+fn describe(columns: &[Column]) -> Vec<String> {
+    columns
+        .iter()
+        .map(|column| {
+            format!(
+                "{} {} nullable={} key={} localizable={} category={:?} range={:?} \
+                 foreign_key={:?}",
+                column.name(),
+                column.coltype(),
+                column.is_nullable(),
+                column.is_primary_key(),
+                column.is_localizable(),
+                column.category(),
+                column.value_range(),
+                column.get_foreign_key()
+            )
+        })
+        .collect()
+}
+
+fn check_entity<E: Entity>(table_name: &str, definition: Vec<Column>, item: E, row: Vec<Value>) {
+    assert_eq!(E::table_name(), table_name);
+    assert_eq!(describe(&E::definition()), describe(&definition));
+    assert_eq!(item.to_row(), row);
+
+    let cursor = Cursor::new(Vec::new());
+    let mut package = msi::Package::create(msi::PackageType::Installer, cursor).unwrap();
+    E::create_table(&mut package).unwrap();
+    E::insert(&mut package, &[item]).unwrap();
+    let items = E::list(&mut package).unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].to_row(), row);
+}
+
+fn sequence_definition() -> Vec<Column> {
+    vec![
+        Column::build("Action").primary_key().id_string(72),
+        Column::build("Condition")
+            .nullable()
+            .category(Category::Condition)
+            .string(255),
+        Column::build("Sequence")
+            .nullable()
+            .range(-4, 0x7fff)
+            .int16(),
+    ]
+}
+
+fn sequence_row() -> Vec<Value> {
+    vec![
+        Value::from("InstallFiles"),
+        Value::from("NOT Installed"),
+        Value::Int(4000),
+    ]
+}
+
+#[test]
+fn sequence_tables() {
+    check_entity(
+        "AdminExecuteSequence",
+        sequence_definition(),
+        AdminExecuteSequence {
+            action: "InstallFiles".to_string(),
+            condition: Some("NOT Installed".to_string()),
+            order: 4000,
+        },
+        sequence_row(),
+    );
+    check_entity(
+        "AdvtExecuteSequence",
+        sequence_definition(),
+        AdvtExecuteSequence {
+            action: "InstallFiles".to_string(),
+            condition: Some("NOT Installed".to_string()),
+            order: 4000,
+        },
+        sequence_row(),
+    );
+    check_entity(
+        "InstallExecuteSequence",
+        sequence_definition(),
+        InstallExecuteSequence {
+            action: "InstallFiles".to_string(),
+            condition: Some("NOT Installed".to_string()),
+            order: 4000,
+        },
+        sequence_row(),
+    );
+    check_entity(
+        "InstallUISequence",
+        sequence_definition(),
+        InstallUISequence {
+            dialog: "InstallFiles".to_string(),
+            condition: Some("NOT Installed".to_string()),
+            order: 4000,
+        },
+        sequence_row(),
+    );
+}
+
+#[test]
+fn app_search() {
+    check_entity(
+        "AppSearch",
+        vec![
+            Column::build("Property")
+                .primary_key()
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("Signature_")
+                .primary_key()
+                .category(Category::Identifier)
+                .string(72),
+        ],
+        AppSearch {
+            property: "EDITORPATH".to_string(),
+            signature: "EditorSearch".to_string(),
+        },
+        vec![Value::from("EDITORPATH"), Value::from("EditorSearch")],
+    );
+}
+
+#[test]
+fn custom_action() {
+    check_entity(
+        "CustomAction",
+        vec![
+            Column::build("Action")
+                .primary_key()
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("Type").range(1, 32767).int16(),
+            Column::build("Source")
+                .nullable()
+                .category(Category::CustomSource)
+                .string(72),
+            Column::build("Target")
+                .nullable()
+                .category(Category::Formatted)
+                .string(255),
+        ],
+        CustomAction {
+            action: "SetInstallDir".to_string(),
+            action_type: CustomActionType::Property | CustomActionType::TextData,
+            source: Some("INSTALLDIR".to_string()),
+            target: Some("[ProgramFilesFolder]Editor".to_string()),
+        },
+        vec![
+            Value::from("SetInstallDir"),
+            Value::Int(0x33),
+            Value::from("INSTALLDIR"),
+            Value::from("[ProgramFilesFolder]Editor"),
+        ],
+    );
+}
+
+#[test]
+fn dr_locator() {
+    check_entity(
+        "DrLocator",
+        vec![
+            Column::build("Signature_")
+                .primary_key()
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("Parent")
+                .primary_key()
+                .nullable()
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("Path")
+                .primary_key()
+                .nullable()
+                .category(Category::AnyPath)
+                .string(255),
+            Column::build("Depth").nullable().range(0, 0x7fff).int16(),
+        ],
+        DrLocator {
+            signature: "EditorSearch".to_string(),
+            parent: None,
+            path: Some("[ProgramFilesFolder]Editor".to_string()),
+            depth: Some(2),
+        },
+        vec![
+            Value::from("EditorSearch"),
+            Value::Null,
+            Value::from("[ProgramFilesFolder]Editor"),
+            Value::Int(2),
+        ],
+    );
+}
+
+#[test]
+fn extension() {
+    check_entity(
+        "Extension",
+        vec![
+            Column::build("Extension")
+                .primary_key()
+                .category(Category::Text)
+                .string(255),
+            Column::build("Component_")
+                .primary_key()
+                .foreign_key("Component", 1)
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("ProgId_")
+                .nullable()
+                .foreign_key("ProgId", 1)
+                .category(Category::Text)
+                .string(255),
+            Column::build("MIME_")
+                .nullable()
+                .foreign_key("MIME", 1)
+                .category(Category::Text)
+                .string(64),
+            Column::build("Feature_")
+                .foreign_key("Feature", 1)
+                .category(Category::Identifier)
+                .string(38),
+        ],
+        Extension {
+            extension: "txt".to_string(),
+            component: "Editor".to_string(),
+            prog_id: Some("Editor.Text".to_string()),
+            mime: None,
+            feature: "Complete".to_string(),
+        },
+        vec![
+            Value::from("txt"),
+            Value::from("Editor"),
+            Value::from("Editor.Text"),
+            Value::Null,
+            Value::from("Complete"),
+        ],
+    );
+}
+
+#[test]
+fn feature_components() {
+    check_entity(
+        "FeatureComponents",
+        vec![
+            Column::build("Feature_")
+                .primary_key()
+                .foreign_key("Feature", 1)
+                .id_string(38),
+            Column::build("Component_")
+                .primary_key()
+                .foreign_key("Component", 1)
+                .id_string(72),
+        ],
+        FeatureComponents {
+            feature: "Complete".to_string(),
+            component: "Editor".to_string(),
+        },
+        vec![Value::from("Complete"), Value::from("Editor")],
+    );
+}
+
+#[test]
+fn launch_condition() {
+    check_entity(
+        "LaunchCondition",
+        vec![
+            Column::build("Condition")
+                .primary_key()
+                .category(Category::Condition)
+                .string(255),
+            Column::build("Description")
+                .localizable()
+                .category(Category::Formatted)
+                .string(255),
+        ],
+        LaunchCondition {
+            condition: "VersionNT >= 601".to_string(),
+            description: "[ProductName] requires Windows 7 or later.".to_string(),
+        },
+        vec![
+            Value::from("VersionNT >= 601"),
+            Value::from("[ProductName] requires Windows 7 or later."),
+        ],
+    );
+}
+
+#[test]
+fn media() {
+    check_entity(
+        "Media",
+        vec![
+            Column::build("DiskId")
+                .primary_key()
+                .range(1, 0x7fff)
+                .int16(),
+            Column::build("LastSequence").range(0, 0x7fff).int16(),
+            Column::build("DiskPrompt").nullable().text_string(64),
+            Column::build("Cabinet")
+                .nullable()
+                .category(Category::Cabinet)
+                .string(255),
+            Column::build("VolumeLabel").nullable().text_string(32),
+            Column::build("Source")
+                .nullable()
+                .category(Category::Property)
+                .string(32),
+        ],
+        Media {
+            disk_id: 1,
+            last_sequence: 12,
+            disk_prompt: None,
+            cabinet: Some("#product.cab".to_string()),
+            volume_label: Some("DISK1".to_string()),
+            source: None,
+        },
+        vec![
+            Value::Int(1),
+            Value::Int(12),
+            Value::Null,
+            Value::from("#product.cab"),
+            Value::from("DISK1"),
+            Value::Null,
+        ],
+    );
+}
+
+#[test]
+fn mime() {
+    let clsid = Uuid::parse_str("6d2c6d9e-1c1f-4b8e-9a3d-2f7e5c4b3a21").unwrap();
+    check_entity(
+        "MIME",
+        vec![
+            Column::build("ContentType")
+                .primary_key()
+                .category(Category::Text)
+                .string(64),
+            Column::build("Extension_")
+                .foreign_key("Extension", 1)
+                .category(Category::Text)
+                .string(255),
+            Column::build("CLSID")
+                .nullable()
+                .category(Category::Guid)
+                .string(38),
+        ],
+        Mime {
+            content_type: "text/plain".to_string(),
+            extension: "txt".to_string(),
+            clsid: Some(clsid),
+        },
+        vec![
+            Value::from("text/plain"),
+            Value::from("txt"),
+            Value::from(Some(clsid)),
+        ],
+    );
+}
+
+#[test]
+fn service_install() {
+    check_entity(
+        "ServiceInstall",
+        vec![
+            Column::build("ServiceInstall")
+                .primary_key()
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("Name")
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("DisplayName")
+                .nullable()
+                .localizable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("ServiceType").int32(),
+            Column::build("StartType").range(0, 4).int32(),
+            Column::build("ErrorControl").int32(),
+            Column::build("LoadOrderGroup")
+                .nullable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("Dependencies")
+                .nullable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("StartName")
+                .nullable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("Password")
+                .nullable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("Arguments")
+                .nullable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("Component_")
+                .foreign_key("Component", 1)
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("Description")
+                .nullable()
+                .localizable()
+                .category(Category::Formatted)
+                .string(255),
+        ],
+        ServiceInstall {
+            service_install: "EditorService".to_string(),
+            name: "editor".to_string(),
+            display_name: Some("Editor".to_string()),
+            service_type: ServiceType::OwnProcess,
+            start_type: ServiceStartType::Auto,
+            error_control: ServiceErrorControl {
+                level: ServiceErrorLevel::Normal,
+                vital: true,
+            },
+            load_order_group: None,
+            dependencies: None,
+            start_name: Some("LocalSystem".to_string()),
+            password: None,
+            arguments: Some("--service".to_string()),
+            component: "Editor".to_string(),
+            description: Some("Runs the editor in the background".to_string()),
+        },
+        vec![
+            Value::from("EditorService"),
+            Value::from("editor"),
+            Value::from("Editor"),
+            Value::Int(0x10),
+            Value::Int(2),
+            Value::Int(0x8001),
+            Value::Null,
+            Value::Null,
+            Value::from("LocalSystem"),
+            Value::Null,
+            Value::from("--service"),
+            Value::from("Editor"),
+            Value::from("Runs the editor in the background"),
+        ],
+    );
+}
+
+#[test]
+fn signature() {
+    check_entity(
+        "Signature",
+        vec![
+            Column::build("Signature")
+                .primary_key()
+                .category(Category::Identifier)
+                .string(72),
+            Column::build("FileName")
+                .category(Category::Text)
+                .string(255),
+            Column::build("MinVersion")
+                .nullable()
+                .category(Category::Text)
+                .string(20),
+            Column::build("MaxVersion")
+                .nullable()
+                .category(Category::Text)
+                .string(20),
+            Column::build("MinSize")
+                .nullable()
+                .range(0, i32::MAX)
+                .int32(),
+            Column::build("MaxSize")
+                .nullable()
+                .range(0, i32::MAX)
+                .int32(),
+            Column::build("MinDate")
+                .nullable()
+                .range(0, i32::MAX)
+                .int32(),
+            Column::build("MaxDate")
+                .nullable()
+                .range(0, i32::MAX)
+                .int32(),
+            Column::build("Languages")
+                .nullable()
+                .category(Category::Language)
+                .string(255),
+        ],
+        Signature {
+            signature: "EditorSearch".to_string(),
+            file_name: "editor.exe".to_string(),
+            min_version: Some("1.0.0".to_string()),
+            max_version: None,
+            min_size: Some(1024),
+            max_size: None,
+            min_date: None,
+            max_date: None,
+            languages: Some("1033".to_string()),
+        },
+        vec![
+            Value::from("EditorSearch"),
+            Value::from("editor.exe"),
+            Value::from("1.0.0"),
+            Value::Null,
+            Value::Int(1024),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::from("1033"),
+        ],
+    );
+}
+
+#[test]
+fn verb() {
+    check_entity(
+        "Verb",
+        vec![
+            Column::build("Extension_")
+                .primary_key()
+                .foreign_key("Extension", 1)
+                .category(Category::Text)
+                .string(255),
+            Column::build("Verb")
+                .primary_key()
+                .category(Category::Text)
+                .string(32),
+            Column::build("Sequence").nullable().range(0, 32767).int16(),
+            Column::build("Command")
+                .nullable()
+                .localizable()
+                .category(Category::Formatted)
+                .string(255),
+            Column::build("Argument")
+                .nullable()
+                .localizable()
+                .category(Category::Formatted)
+                .string(255),
+        ],
+        Verb {
+            extension: "txt".to_string(),
+            verb: "open".to_string(),
+            sequence: Some(1),
+            command: Some("&Open".to_string()),
+            argument: Some("\"%1\"".to_string()),
+        },
+        vec![
+            Value::from("txt"),
+            Value::from("open"),
+            Value::Int(1),
+            Value::from("&Open"),
+            Value::from("\"%1\""),
+        ],
+    );
+}
+
+#[test]
+fn unsupported_field_type() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,11 @@
+use msi_installer::tables::Entity;
+
+#[derive(Entity)]
+#[entity(table = "Counter")]
+struct Counter {
+    #[column(primary_key)]
+    name: String,
+    count: f64,
+}
+
+fn main() {}
@@ -0,0 +1,369 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Field, Fields, GenericArgument, Ident, LitInt, LitStr, PathArguments, Type,
+    parse_macro_input,
+};
+
+/// Implement `msi_installer::tables::Entity` for a struct whose fields are the columns of a table.
+///
+/// ```ignore
+/// #[derive(Entity)]
+/// #[entity(table = "Verb")]
+/// pub struct Verb {
+///     #[column(name = "Extension_", primary_key, foreign_key("Extension", 1), category = Text)]
+///     pub extension: String,
+///     #[column(primary_key, category = Text, length = 32)]
+///     pub verb: String,
+///     #[column(range(0, 32767))]
+///     pub sequence: Option<i32>,
+/// }
+/// ```
+///
+/// The type of the column is deduced from the type of the field:
+/// - `String` columns are 255 characters long unless `length` is given, 0 for unlimited.
+/// - `i32` columns are 16 bits integers unless `int32` is given.
+/// - `Uuid` columns are GUID strings.
+/// - bitflags need the `flags` attribute and `FromRepr` enums the `enumeration` attribute,
+///   both are stored in 16 bits integer columns.
+///
+/// `Option` fields are nullable. The column name defaults to the field name in PascalCase.
+#[proc_macro_derive(Entity, attributes(entity, column))]
+pub fn derive_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.into_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut table: Option<LitStr> = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("entity"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                table = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `table = \"...\"`"))
+            }
+        })?;
+    }
+    let table = table.ok_or_else(|| {
+        syn::Error::new_spanned(name, "missing `#[entity(table = \"...\")]` attribute")
+    })?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Entity can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Entity can only be derived for structs",
+            ));
+        }
+    };
+
+    let columns = fields
+        .iter()
+        .map(Column::parse)
+        .collect::<syn::Result<Vec<Column>>>()?;
+    let definitions = columns.iter().map(Column::definition);
+    let reads = columns.iter().enumerate().map(|(index, column)| {
+        let ident = &column.ident;
+        let read = column.read(index);
+        quote! { #ident: #read }
+    });
+    let writes = columns.iter().map(Column::write);
+
+    Ok(quote! {
+        impl ::msi_installer::tables::Entity for #name {
+            fn table_name() -> &'static str {
+                #table
+            }
+
+            fn definition() -> Vec<::msi::Column> {
+                vec![#(#definitions),*]
+            }
+
+            fn from_row(
+                row: &::msi_installer::tables::RowView,
+            ) -> Result<#name, ::msi_installer::tables::MsiDataBaseError> {
+                Ok(#name {
+                    #(#reads),*
+                })
+            }
+
+            fn to_row(&self) -> Vec<::msi::Value> {
+                vec![#(#writes),*]
+            }
+        }
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    String,
+    Integer,
+    Uuid,
+    Flags,
+    Enumeration,
+}
+
+struct Column {
+    ident: Ident,
+    name: String,
+    kind: Kind,
+    optional: bool,
+    /// Type of the field, without `Option`
+    inner_type: Type,
+    primary_key: bool,
+    nullable: bool,
+    localizable: bool,
+    int32: bool,
+    length: Option<usize>,
+    category: Option<Ident>,
+    range: Option<(i32, i32)>,
+    foreign_key: Option<(LitStr, i32)>,
+}
+
+impl Column {
+    fn parse(field: &Field) -> syn::Result<Column> {
+        let ident = field.ident.clone().expect("named field");
+        let (optional, inner_type) = match option_inner_type(&field.ty) {
+            Some(inner_type) => (true, inner_type.clone()),
+            None => (false, field.ty.clone()),
+        };
+        let mut column = Column {
+            name: pascal_case(&ident.to_string()),
+            ident,
+            kind: Kind::String,
+            optional,
+            inner_type,
+            primary_key: false,
+            nullable: false,
+            localizable: false,
+            int32: false,
+            length: None,
+            category: None,
+            range: None,
+            foreign_key: None,
+        };
+        let mut kind: Option<Kind> = None;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("column"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    column.name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("primary_key") {
+                    column.primary_key = true;
+                } else if meta.path.is_ident("nullable") {
+                    column.nullable = true;
+                } else if meta.path.is_ident("localizable") {
+                    column.localizable = true;
+                } else if meta.path.is_ident("int32") {
+                    column.int32 = true;
+                } else if meta.path.is_ident("flags") {
+                    kind = Some(Kind::Flags);
+                } else if meta.path.is_ident("enumeration") {
+                    kind = Some(Kind::Enumeration);
+                } else if meta.path.is_ident("length") {
+                    column.length = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("category") {
+                    column.category = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("range") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let min = parse_i32(&content)?;
+                    content.parse::<syn::Token![,]>()?;
+                    let max = parse_i32(&content)?;
+                    column.range = Some((min, max));
+                } else if meta.path.is_ident("foreign_key") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let table: LitStr = content.parse()?;
+                    content.parse::<syn::Token![,]>()?;
+                    let index: LitInt = content.parse()?;
+                    column.foreign_key = Some((table, index.base10_parse()?));
+                } else {
+                    return Err(meta.error("unknown column attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        column.kind = match kind {
+            Some(kind) => kind,
+            None => match type_name(&column.inner_type).as_deref() {
+                Some("String") => Kind::String,
+                Some("i32") => Kind::Integer,
+                Some("Uuid") => Kind::Uuid,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &field.ty,
+                        "unsupported column type, use `flags` or `enumeration` for bitflags and enums",
+                    ));
+                }
+            },
+        };
+        Ok(column)
+    }
+
+    fn definition(&self) -> TokenStream2 {
+        let name = &self.name;
+        let mut builder = quote! { ::msi::Column::build(#name) };
+        if self.primary_key {
+            builder = quote! { #builder.primary_key() };
+        }
+        if self.nullable || self.optional {
+            builder = quote! { #builder.nullable() };
+        }
+        if self.localizable {
+            builder = quote! { #builder.localizable() };
+        }
+        if let Some((min, max)) = self.range {
+            builder = quote! { #builder.range(#min, #max) };
+        }
+        if let Some((table, index)) = &self.foreign_key {
+            builder = quote! { #builder.foreign_key(#table, #index) };
+        }
+        let category = match (&self.category, self.kind) {
+            (Some(category), _) => Some(quote! { #category }),
+            (None, Kind::Uuid) => Some(quote! { Guid }),
+            (None, _) => None,
+        };
+        if let Some(category) = category {
+            builder = quote! { #builder.category(::msi::Category::#category) };
+        }
+        match self.kind {
+            Kind::String => {
+                let length = self.length.unwrap_or(255);
+                quote! { #builder.string(#length) }
+            }
+            Kind::Uuid => quote! { #builder.string(38) },
+            Kind::Integer | Kind::Flags | Kind::Enumeration if self.int32 => {
+                quote! { #builder.int32() }
+            }
+            Kind::Integer | Kind::Flags | Kind::Enumeration => quote! { #builder.int16() },
+        }
+    }
+
+    fn read(&self, index: usize) -> TokenStream2 {
+        let ty = &self.inner_type;
+        let name = &self.name;
+        match (self.kind, self.optional) {
+            (Kind::String, false) => quote! { row.string(#index)? },
+            (Kind::String, true) => quote! { row.opt_string(#index)? },
+            (Kind::Integer, false) => quote! { row.i32(#index)? },
+            (Kind::Integer, true) => quote! { row.opt_i32(#index)? },
+            (Kind::Uuid, false) => quote! { row.uuid(#index)? },
+            (Kind::Uuid, true) => quote! { row.opt_uuid(#index)? },
+            (Kind::Flags, false) => quote! { <#ty>::from_bits_retain(row.i32(#index)?) },
+            (Kind::Flags, true) => quote! { row.opt_i32(#index)?.map(<#ty>::from_bits_retain) },
+            (Kind::Enumeration, optional) => {
+                let parse = quote! {
+                    |value: i32| <#ty>::from_repr(value).ok_or(
+                        ::msi_installer::tables::MsiDataBaseError::InvalidEnumValue {
+                            name: #name,
+                            value,
+                        },
+                    )
+                };
+                if optional {
+                    quote! { row.opt_i32(#index)?.map(#parse).transpose()? }
+                } else {
+                    quote! { (#parse)(row.i32(#index)?)? }
+                }
+            }
+        }
+    }
+
+    fn write(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        match (self.kind, self.optional) {
+            (Kind::String, false) => quote! { ::msi::Value::Str(self.#ident.clone()) },
+            (Kind::String, true) => quote! { ::msi::Value::from_opt_string(&self.#ident) },
+            (Kind::Integer, false) => quote! { ::msi::Value::Int(self.#ident) },
+            (Kind::Integer, true) => quote! { ::msi::Value::from_opt_i32(&self.#ident) },
+            (Kind::Uuid, false) => quote! { ::msi::Value::from(Some(self.#ident)) },
+            (Kind::Uuid, true) => quote! { ::msi::Value::from(self.#ident) },
+            (Kind::Flags, false) => quote! { ::msi::Value::Int(self.#ident.bits()) },
+            (Kind::Flags, true) => {
+                quote! { ::msi::Value::from_opt_i32(&self.#ident.map(|value| value.bits())) }
+            }
+            (Kind::Enumeration, false) => quote! { ::msi::Value::Int(self.#ident as i32) },
+            (Kind::Enumeration, true) => {
+                quote! { ::msi::Value::from_opt_i32(&self.#ident.map(|value| value as i32)) }
+            }
+        }
+    }
+}
+
+// Returns `T` when the type is `Option<T>`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner_type) => Some(inner_type),
+        _ => None,
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_i32(input: syn::parse::ParseStream) -> syn::Result<i32> {
+    let negative = input.peek(syn::Token![-]);
+    if negative {
+        input.parse::<syn::Token![-]>()?;
+    }
+    let value: i32 = input.parse::<LitInt>()?.base10_parse()?;
+    Ok(if negative { -value } else { value })
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
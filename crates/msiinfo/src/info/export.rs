@@ -7,38 +7,35 @@ use crate::info::style::TableStyles;
 use color::{bold, cyan, green, magenta, yellow};
 
 pub fn print_table_contents<F: Read + Seek>(package: &mut msi::Package<F>, table_name: &str) {
-    let mut col_widths: Vec<usize> = package
+    let column_names = package
         .get_table(table_name)
         .unwrap()
         .columns()
         .iter()
-        .map(|column| column.name().len())
+        .map(|column| column.name().to_string())
         .collect();
     let mut rows: Vec<Vec<msi::Value>> = package
         .select_rows(msi::Select::table(table_name))
         .expect("select")
-        .map(|row| {
-            let mut strings = Vec::with_capacity(row.len());
-            for index in 0..row.len() {
-                let string = row[index].to_string();
-                col_widths[index] = cmp::max(col_widths[index], string.len());
-                strings.push(row[index].clone());
-            }
-            strings
-        })
+        .map(|row| (0..row.len()).map(|index| row[index].clone()).collect())
         .collect();
+    rows.sort();
+    print_rows(column_names, rows);
+}
+
+/// Prints rows as a table, in the given order.
+pub fn print_rows(column_names: Vec<String>, rows: Vec<Vec<msi::Value>>) {
+    let mut col_widths: Vec<usize> = column_names.iter().map(String::len).collect();
+    for row in rows.iter() {
+        for (index, value) in row.iter().enumerate() {
+            col_widths[index] = cmp::max(col_widths[index], value.to_string().len());
+        }
+    }
     print_separator(&col_widths);
     {
         let mut line = String::new();
-        for (index, column) in package
-            .get_table(table_name)
-            .unwrap()
-            .columns()
-            .iter()
-            .enumerate()
-        {
-            let name = column.name().to_string();
-            let string = bold(cyan(&name)) + &whitespaces(col_widths[index] - name.len());
+        for (index, name) in column_names.iter().enumerate() {
+            let string = bold(cyan(name)) + &whitespaces(col_widths[index] - name.len());
             line.push_str(&string);
             line.push_str("  ");
         }
@@ -46,7 +43,6 @@ pub fn print_table_contents<F: Read + Seek>(package: &mut msi::Package<F>, table
     }
     print_separator(&col_widths);
 
-    rows.sort();
     for row in rows.into_iter() {
         let mut line = String::new();
         for (index, value) in row.into_iter().enumerate() {
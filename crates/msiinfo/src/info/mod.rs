@@ -1,3 +1,4 @@
 pub mod describe;
 pub mod export;
+pub mod query;
 pub mod style;
@@ -0,0 +1,30 @@
+use std::io;
+
+use crate::info::export::print_rows;
+
+/// Runs a SQL statement against the package at `path`, the way `msidb` or
+/// `WiRunSQL.vbs` do. The package is only opened for writing when the
+/// statement modifies it, and the rows of a `SELECT` are printed in order.
+pub fn run_query(path: &str, sql: &str) -> io::Result<()> {
+    let statement = msi::Statement::parse(sql)?;
+    if let msi::Statement::Select(_) = statement {
+        let mut package = msi::open(path)?;
+        let Some(rows) = package.execute(statement)? else {
+            return Err(io::Error::other("The SELECT statement returned no rows"));
+        };
+        let column_names = rows
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        let rows = rows
+            .map(|row| (0..row.len()).map(|index| row[index].clone()).collect())
+            .collect();
+        print_rows(column_names, rows);
+    } else {
+        let mut package = msi::open_rw(path)?;
+        package.execute(statement)?;
+        package.flush()?;
+    }
+    Ok(())
+}
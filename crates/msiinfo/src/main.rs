@@ -1,9 +1,11 @@
 use color::{blue, cyan, yellow};
 use msiinfo::info::describe::print_table_description;
 use msiinfo::info::export::print_table_contents;
+use msiinfo::info::query::run_query;
 use std::fs::File;
 use std::io;
 use std::io::{Read, Seek};
+use std::process;
 use time::OffsetDateTime;
 
 use msiinfo::validate::validator::validate_msi_installer;
@@ -35,6 +37,11 @@ enum Command {
         path: String,
         table: String,
     },
+    /// Run a SQL statement, e.g. "SELECT `File`, `FileSize` FROM `File`"
+    Query {
+        path: String,
+        sql: String,
+    },
     Extract {
         path: String,
         stream: String,
@@ -115,6 +122,12 @@ fn main() {
             let mut package = msi::open(path).expect("open package");
             print_table_contents(&mut package, &table)
         }
+        Command::Query { path, sql } => {
+            if let Err(error) = run_query(&path, &sql) {
+                eprintln!("error: {error}");
+                process::exit(1);
+            }
+        }
         Command::Streams { path } => {
             let package = msi::open(path).expect("open package");
             for stream_name in package.streams() {
@@ -99,6 +99,7 @@ mod tests {
             &[File {
                 file: "File1".to_string(),
                 component: "Component1".to_string(),
+                filename: "file1.txt".to_string(),
                 sequence: 1,
                 ..Default::default()
             }],
@@ -118,6 +119,7 @@ mod tests {
             &[File {
                 file: "File1".to_string(),
                 component: "Component1".to_string(),
+                filename: "file1.txt".to_string(),
                 sequence: 1,
                 ..Default::default()
             }],
@@ -138,7 +140,7 @@ mod tests {
             &mut package,
             &[Directory {
                 directory: "Directory1".to_string(),
-                default_dir: "foo/bar".to_string(),
+                default_dir: "bar".to_string(),
                 parent: None,
             }],
         )